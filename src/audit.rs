@@ -0,0 +1,268 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Audit log file, relative to the tickets directory
+pub const LOG_FILE: &str = ".sync-log.jsonl";
+
+/// Kind of change made on GitHub
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Close,
+    Reopen,
+    Link,
+    Draft,
+    ProjectAdd,
+    ProjectRemove,
+    ProjectField,
+}
+
+impl AuditAction {
+    /// Output tag, as `ttr history` prints it
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::Create => "CREATE  ",
+            Self::Update => "UPDATE  ",
+            Self::Close => "CLOSE   ",
+            Self::Reopen => "REOPEN  ",
+            Self::Link => "LINK    ",
+            Self::Draft => "DRAFT   ",
+            Self::ProjectAdd | Self::ProjectRemove | Self::ProjectField => "PROJECT ",
+        }
+    }
+}
+
+/// One field's value before and after a change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A single line of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    #[serde(default)]
+    pub timestamp: String,
+    /// Identifies the ttr invocation that made the change
+    #[serde(default)]
+    pub run: String,
+    pub action: AuditAction,
+    pub ticket: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<u64>,
+    /// Node ID of the issue, draft, or project item that changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
+    /// Run reverted by this entry (set by `ttr undo`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(action: AuditAction, ticket: &str) -> Self {
+        Self {
+            timestamp: String::new(),
+            run: String::new(),
+            action,
+            ticket: ticket.to_string(),
+            issue: None,
+            node: None,
+            changes: Vec::new(),
+            undoes: None,
+        }
+    }
+
+    pub fn issue(mut self, number: u64) -> Self {
+        self.issue = Some(number);
+        self
+    }
+
+    pub fn node(mut self, id: &str) -> Self {
+        self.node = Some(id.to_string());
+        self
+    }
+
+    pub fn change(mut self, field: &str, old: Option<&str>, new: Option<&str>) -> Self {
+        self.changes.push(FieldChange {
+            field: field.to_string(),
+            old: old.map(|s| s.to_string()),
+            new: new.map(|s| s.to_string()),
+        });
+        self
+    }
+
+    pub fn undoes(mut self, run: &str) -> Self {
+        self.undoes = Some(run.to_string());
+        self
+    }
+
+    /// Old and new values of a changed field
+    pub fn changed(&self, field: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|c| c.field == field)
+    }
+}
+
+/// Appends sync operations to `.tickets/.sync-log.jsonl`
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    run: String,
+}
+
+impl AuditLog {
+    /// Log to the audit file in a tickets directory
+    pub fn new(tickets_dir: &Path) -> Self {
+        let now = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+        Self {
+            path: Some(tickets_dir.join(LOG_FILE)),
+            run: format!("{}-{}", now, std::process::id()),
+        }
+    }
+
+    /// A log that records nothing
+    pub fn disabled() -> Self {
+        Self { path: None, run: String::new() }
+    }
+
+    /// ID shared by every entry recorded through this log
+    pub fn run(&self) -> &str {
+        &self.run
+    }
+
+    /// Append an entry, warning (but not failing) if the log can't be written
+    pub fn record(&self, mut entry: AuditEntry) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        entry.timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        entry.run = self.run.clone();
+
+        if let Err(e) = append(path, &entry) {
+            eprintln!("WARN    Failed to write {}: {}", path.display(), e);
+        }
+    }
+
+    /// Read all entries from a tickets directory's audit log, oldest first
+    pub fn read(tickets_dir: &Path) -> Result<Vec<AuditEntry>> {
+        let path = tickets_dir.join(LOG_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line)
+                    .with_context(|| format!("{}:{}: invalid audit entry", path.display(), i + 1))
+            })
+            .collect()
+    }
+}
+
+/// Entries recorded for a ticket under its current or a previous ID, oldest
+/// first
+pub fn history<'a>(entries: &'a [AuditEntry], ids: &[String]) -> Vec<&'a AuditEntry> {
+    entries.iter().filter(|e| ids.contains(&e.ticket)).collect()
+}
+
+/// A changed value on one line: quoted, or its first line and line count
+/// when it spans several
+pub fn summarize(value: Option<&str>) -> String {
+    const MAX: usize = 60;
+    let Some(value) = value else {
+        return "(none)".to_string();
+    };
+    let lines = value.lines().count();
+    let first = value.lines().next().unwrap_or("");
+    if lines <= 1 && first.chars().count() <= MAX {
+        return format!("{:?}", first);
+    }
+    let mut shown: String = first.chars().take(MAX).collect();
+    if shown.len() < first.len() {
+        shown.push('…');
+    }
+    if lines > 1 {
+        format!("{:?} ({} lines)", shown, lines)
+    } else {
+        format!("{:?}", shown)
+    }
+}
+
+fn append(path: &Path, entry: &AuditEntry) -> Result<()> {
+    let line = serde_json::to_string(entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_read() {
+        let dir = TempDir::new().unwrap();
+        let log = AuditLog::new(dir.path());
+
+        log.record(
+            AuditEntry::new(AuditAction::Create, "ttr-0001")
+                .issue(12)
+                .node("I_1")
+                .change("external-ref", None, Some("gh-12")),
+        );
+        log.record(
+            AuditEntry::new(AuditAction::Close, "ttr-0001")
+                .issue(12)
+                .change("state", Some("OPEN"), Some("CLOSED")),
+        );
+
+        let entries = AuditLog::read(dir.path()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, AuditAction::Create);
+        assert_eq!(entries[0].issue, Some(12));
+        assert_eq!(entries[0].run, log.run());
+        assert!(!entries[0].timestamp.is_empty());
+        assert_eq!(entries[1].changes[0].old.as_deref(), Some("OPEN"));
+
+        let raw = fs::read_to_string(dir.path().join(LOG_FILE)).unwrap();
+        assert!(raw.lines().next().unwrap().contains(r#""action":"create""#));
+    }
+
+    #[test]
+    fn test_history_follows_previous_ids() {
+        let entries = vec![
+            AuditEntry::new(AuditAction::Create, "old").issue(3).change("title", None, Some("T")),
+            AuditEntry::new(AuditAction::Close, "other").issue(4),
+            AuditEntry::new(AuditAction::Update, "new").issue(3).change("body", Some("a\nb"), Some("a")),
+        ];
+        let ids = vec!["new".to_string(), "old".to_string()];
+        let actions: Vec<_> = history(&entries, &ids).iter().map(|e| e.action).collect();
+        assert_eq!(actions, vec![AuditAction::Create, AuditAction::Update]);
+
+        assert_eq!(summarize(None), "(none)");
+        assert_eq!(summarize(Some("Add retry logic")), "\"Add retry logic\"");
+        assert_eq!(summarize(Some("## Goal\n\nRetry\n")), "\"## Goal\" (3 lines)");
+        assert_eq!(summarize(Some(&"x".repeat(70))), format!("\"{}…\"", "x".repeat(60)));
+    }
+
+    #[test]
+    fn test_disabled_log_writes_nothing() {
+        let dir = TempDir::new().unwrap();
+        AuditLog::disabled().record(AuditEntry::new(AuditAction::Update, "ttr-0001"));
+        assert!(AuditLog::read(dir.path()).unwrap().is_empty());
+    }
+}
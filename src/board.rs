@@ -0,0 +1,417 @@
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::path::{Path, PathBuf};
+
+use crate::ticket::{LoadOptions, Ticket};
+
+/// Status columns always shown, in order; other statuses are appended
+const DEFAULT_COLUMNS: [&str; 3] = ["open", "in_progress", "closed"];
+
+const HELP: &str =
+    "←/→ column  ↑/↓ ticket  H/L move  e edit title  p push all  P push ticket  r reload  q quit";
+
+/// Why the board event loop returned control
+#[derive(Debug, PartialEq, Eq)]
+pub enum BoardExit {
+    Quit,
+    /// Push the given ticket IDs (all tickets if empty)
+    Push(Vec<String>),
+}
+
+/// Input mode of the board
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    /// Editing the selected ticket's title
+    EditTitle(String),
+}
+
+/// Kanban-style view of the local tickets
+pub struct Board {
+    tickets_dir: PathBuf,
+    load_options: LoadOptions,
+    tickets: Vec<Ticket>,
+    columns: Vec<String>,
+    column: usize,
+    row: usize,
+    mode: Mode,
+    message: Option<String>,
+}
+
+impl Board {
+    /// Load a board from the tickets directory
+    pub fn load(tickets_dir: &Path, load_options: LoadOptions) -> Result<Self> {
+        let mut board = Board {
+            tickets_dir: tickets_dir.to_path_buf(),
+            load_options,
+            tickets: Vec::new(),
+            columns: Vec::new(),
+            column: 0,
+            row: 0,
+            mode: Mode::Normal,
+            message: None,
+        };
+        board.reload()?;
+        Ok(board)
+    }
+
+    /// Re-read tickets from disk, keeping the selection where possible
+    pub fn reload(&mut self) -> Result<()> {
+        let tickets = Ticket::load_all(&self.tickets_dir, self.load_options)?;
+        self.set_tickets(tickets);
+        Ok(())
+    }
+
+    /// Show a message in the footer until the next key press
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+    }
+
+    fn set_tickets(&mut self, tickets: Vec<Ticket>) {
+        let selected_id = self.selected().map(|t| t.id.clone());
+
+        self.columns = DEFAULT_COLUMNS.iter().map(|s| s.to_string()).collect();
+        for ticket in &tickets {
+            if !self.columns.contains(&ticket.status) {
+                self.columns.push(ticket.status.clone());
+            }
+        }
+        self.tickets = tickets;
+
+        match selected_id {
+            Some(id) => self.select_id(&id),
+            None => self.clamp_row(),
+        }
+    }
+
+    /// Indices into `tickets` for the given column
+    fn column_tickets(&self, column: usize) -> Vec<usize> {
+        let status = &self.columns[column];
+        self.tickets
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.status == *status)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        if self.column >= self.columns.len() {
+            return None;
+        }
+        self.column_tickets(self.column).get(self.row).copied()
+    }
+
+    /// The currently selected ticket
+    pub fn selected(&self) -> Option<&Ticket> {
+        self.selected_index().map(|i| &self.tickets[i])
+    }
+
+    fn select_id(&mut self, id: &str) {
+        for column in 0..self.columns.len() {
+            let position = self
+                .column_tickets(column)
+                .iter()
+                .position(|&i| self.tickets[i].id == id);
+            if let Some(row) = position {
+                self.column = column;
+                self.row = row;
+                return;
+            }
+        }
+        self.clamp_row();
+    }
+
+    fn clamp_row(&mut self) {
+        self.column = self.column.min(self.columns.len().saturating_sub(1));
+        let len = self.column_tickets(self.column).len();
+        self.row = self.row.min(len.saturating_sub(1));
+    }
+
+    fn focus_column(&mut self, delta: isize) {
+        let last = self.columns.len() as isize - 1;
+        self.column = (self.column as isize + delta).clamp(0, last) as usize;
+        self.clamp_row();
+    }
+
+    fn focus_row(&mut self, delta: isize) {
+        let len = self.column_tickets(self.column).len() as isize;
+        if len > 0 {
+            self.row = (self.row as isize + delta).clamp(0, len - 1) as usize;
+        }
+    }
+
+    /// Move the selected ticket to a neighbouring status column
+    fn move_selected(&mut self, delta: isize) -> Result<()> {
+        let Some(idx) = self.selected_index() else {
+            return Ok(());
+        };
+        let target = self.column as isize + delta;
+        if target < 0 || target >= self.columns.len() as isize {
+            return Ok(());
+        }
+
+        let status = self.columns[target as usize].clone();
+        let ticket = &mut self.tickets[idx];
+        if status == "closed" {
+            ticket.write_closed(None)?;
+        } else {
+            ticket.write_status(&status)?;
+        }
+
+        let id = ticket.id.clone();
+        self.set_message(format!("{} → {}", id, status));
+        self.select_id(&id);
+        Ok(())
+    }
+
+    fn save_title(&mut self, title: &str) -> Result<()> {
+        let title = title.trim();
+        let Some(idx) = self.selected_index() else {
+            return Ok(());
+        };
+        if title.is_empty() {
+            self.set_message("Title cannot be empty");
+            return Ok(());
+        }
+
+        let ticket = &mut self.tickets[idx];
+        if ticket.title != title {
+            ticket.write_title(title)?;
+            let message = format!("{} renamed", ticket.id);
+            self.set_message(message);
+        }
+        Ok(())
+    }
+
+    /// Apply a key press, returning an exit request if any
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<Option<BoardExit>> {
+        if let Mode::EditTitle(buffer) = &mut self.mode {
+            match key.code {
+                KeyCode::Esc => self.mode = Mode::Normal,
+                KeyCode::Enter => {
+                    let title = std::mem::take(buffer);
+                    self.mode = Mode::Normal;
+                    self.save_title(&title)?;
+                }
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            }
+            return Ok(None);
+        }
+
+        self.message = None;
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(Some(BoardExit::Quit));
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(BoardExit::Quit)),
+            KeyCode::Char('p') => return Ok(Some(BoardExit::Push(Vec::new()))),
+            KeyCode::Char('P') => {
+                if let Some(ticket) = self.selected() {
+                    return Ok(Some(BoardExit::Push(vec![ticket.id.clone()])));
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') => self.focus_column(-1),
+            KeyCode::Right | KeyCode::Char('l') => self.focus_column(1),
+            KeyCode::Up | KeyCode::Char('k') => self.focus_row(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.focus_row(1),
+            KeyCode::Char('H') | KeyCode::Char('<') => self.move_selected(-1)?,
+            KeyCode::Char('L') | KeyCode::Char('>') => self.move_selected(1)?,
+            KeyCode::Char('e') | KeyCode::Enter => {
+                if let Some(ticket) = self.selected() {
+                    self.mode = Mode::EditTitle(ticket.title.clone());
+                }
+            }
+            KeyCode::Char('r') => {
+                self.reload()?;
+                self.set_message("Reloaded");
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Draw the board
+    pub fn render(&self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+        let n = self.columns.len() as u32;
+        let areas = Layout::horizontal(self.columns.iter().map(|_| Constraint::Ratio(1, n)))
+            .split(main);
+
+        for (column, area) in areas.iter().enumerate() {
+            let indices = self.column_tickets(column);
+            let focused = column == self.column;
+
+            let items: Vec<ListItem> = indices
+                .iter()
+                .map(|&i| ticket_item(&self.tickets[i]))
+                .collect();
+
+            let border_style = if focused {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(format!(" {} ({}) ", self.columns[column], indices.len()));
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+            let mut state = ListState::default();
+            if focused && !indices.is_empty() {
+                state.select(Some(self.row));
+            }
+            frame.render_stateful_widget(list, *area, &mut state);
+        }
+
+        let footer_text = match &self.mode {
+            Mode::EditTitle(buffer) => format!("Title: {}▏  (Enter save, Esc cancel)", buffer),
+            Mode::Normal => self.message.clone().unwrap_or_else(|| HELP.to_string()),
+        };
+        frame.render_widget(Paragraph::new(footer_text), footer);
+    }
+}
+
+/// Short sync indicator for a ticket: issue number, draft, or local-only
+pub fn sync_indicator(ticket: &Ticket) -> String {
+    if let Some(number) = ticket.github_issue_number() {
+        format!("#{}", number)
+    } else if ticket.draft_item_id().is_some() {
+        "draft".to_string()
+    } else {
+        "local".to_string()
+    }
+}
+
+fn ticket_item(ticket: &Ticket) -> ListItem<'static> {
+    let color = if ticket.is_synced() {
+        Color::Green
+    } else if ticket.draft_item_id().is_some() {
+        Color::Yellow
+    } else {
+        Color::DarkGray
+    };
+
+    ListItem::new(vec![
+        Line::from(vec![
+            Span::styled(format!("{:<6}", sync_indicator(ticket)), Style::default().fg(color)),
+            Span::styled(
+                format!(" {} P{}", ticket.id, ticket.priority),
+                Style::default().add_modifier(Modifier::DIM),
+            ),
+        ]),
+        Line::from(format!("  {}", ticket.title)),
+    ])
+}
+
+/// Run the board until the user quits or asks for a push
+pub fn run(terminal: &mut DefaultTerminal, board: &mut Board) -> Result<BoardExit> {
+    loop {
+        terminal.draw(|frame| board.render(frame))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match board.handle_key(key) {
+                Ok(Some(exit)) => return Ok(exit),
+                Ok(None) => {}
+                Err(e) => board.set_message(format!("Error: {}", e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::TicketDir;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn board_with_tickets() -> (TicketDir, Board) {
+        let dir = TicketDir::new();
+        dir.ticket("t-1", "status: open\nexternal-ref: gh-7\n");
+        dir.ticket("t-2", "status: open\n");
+        dir.ticket("t-3", "status: blocked\n");
+        let board = Board::load(dir.path(), LoadOptions::default()).unwrap();
+        (dir, board)
+    }
+
+    #[test]
+    fn test_columns_include_extra_statuses() {
+        let (_dir, board) = board_with_tickets();
+        assert_eq!(board.columns, vec!["open", "in_progress", "closed", "blocked"]);
+        assert_eq!(board.selected().unwrap().id, "t-1");
+    }
+
+    #[test]
+    fn test_move_ticket_writes_status() {
+        let (dir, mut board) = board_with_tickets();
+
+        board.handle_key(key(KeyCode::Down)).unwrap();
+        board.handle_key(key(KeyCode::Char('L'))).unwrap();
+
+        // Selection follows the ticket into its new column
+        assert_eq!(board.column, 1);
+        assert_eq!(board.selected().unwrap().id, "t-2");
+
+        let ticket = Ticket::parse(&dir.path().join("t-2.md")).unwrap();
+        assert_eq!(ticket.status, "in_progress");
+    }
+
+    #[test]
+    fn test_edit_title() {
+        let (dir, mut board) = board_with_tickets();
+
+        board.handle_key(key(KeyCode::Char('e'))).unwrap();
+        for _ in 0.."t-1".len() {
+            board.handle_key(key(KeyCode::Backspace)).unwrap();
+        }
+        for c in "Renamed".chars() {
+            board.handle_key(key(KeyCode::Char(c))).unwrap();
+        }
+        board.handle_key(key(KeyCode::Enter)).unwrap();
+
+        let ticket = Ticket::parse(&dir.path().join("t-1.md")).unwrap();
+        assert_eq!(ticket.title, "Renamed");
+    }
+
+    #[test]
+    fn test_push_keys() {
+        let (_dir, mut board) = board_with_tickets();
+
+        assert_eq!(
+            board.handle_key(key(KeyCode::Char('p'))).unwrap(),
+            Some(BoardExit::Push(Vec::new()))
+        );
+        assert_eq!(
+            board.handle_key(key(KeyCode::Char('P'))).unwrap(),
+            Some(BoardExit::Push(vec!["t-1".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_sync_indicator() {
+        let (_dir, board) = board_with_tickets();
+        assert_eq!(sync_indicator(&board.tickets[0]), "#7");
+        assert_eq!(sync_indicator(&board.tickets[1]), "local");
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::ticket::Ticket;
+
+/// Longest slug generated from a ticket title
+const MAX_SLUG_LEN: usize = 40;
+
+/// Expand a branch pattern for a ticket
+///
+/// Supports `{id}`, `{type}`, `{slug}`, and `{issue}` (the GitHub issue
+/// number, empty if the ticket isn't synced).
+pub fn branch_name(pattern: &str, ticket: &Ticket) -> String {
+    let issue = ticket
+        .github_issue_number()
+        .map(|n| n.to_string())
+        .unwrap_or_default();
+
+    let name = pattern
+        .replace("{id}", &ticket.id)
+        .replace("{type}", &ticket.ticket_type)
+        .replace("{slug}", &slugify(&ticket.title))
+        .replace("{issue}", &issue);
+
+    // Placeholders that expand to nothing can leave stray separators
+    let mut cleaned = String::with_capacity(name.len());
+    for c in name.chars() {
+        let prev = cleaned.chars().last();
+        if (c == '-' || c == '/') && (prev.is_none() || prev == Some('-') || prev == Some('/')) {
+            continue;
+        }
+        cleaned.push(c);
+    }
+    cleaned.trim_end_matches(['-', '/']).to_string()
+}
+
+/// Lowercase, hyphen-separated form of a title, cut at a word boundary
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let word = word.to_ascii_lowercase();
+        let extra = if slug.is_empty() { word.len() } else { word.len() + 1 };
+        if slug.len() + extra > MAX_SLUG_LEN {
+            if slug.is_empty() {
+                slug = word[..MAX_SLUG_LEN].to_string();
+            }
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word);
+    }
+    slug
+}
+
+/// Check out a branch, creating it from HEAD if it doesn't exist
+///
+/// Returns true if the branch was created.
+pub fn checkout(name: &str) -> Result<bool> {
+    let exists = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", name)])
+        .output()
+        .context("Failed to run git")?
+        .status
+        .success();
+
+    let args: Vec<&str> = if exists {
+        vec!["checkout", name]
+    } else {
+        vec!["checkout", "-b", name]
+    };
+
+    let output = Command::new("git")
+        .args(&args)
+        .output()
+        .context("Failed to run git checkout")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git checkout {} failed: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(!exists)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::TicketDir;
+
+    fn ticket(title: &str, external_ref: Option<&str>) -> Ticket {
+        let external_ref = external_ref.map(|r| format!("external-ref: {}\n", r)).unwrap_or_default();
+        let content = format!("---\nid: ttr-0042\ntype: bug\n{}---\n# {}\n", external_ref, title);
+        Ticket::parse(&TicketDir::new().write("ttr-0042.md", &content)).unwrap()
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Fix login: crash on empty password!"), "fix-login-crash-on-empty-password");
+        assert_eq!(
+            slugify("Support very long titles that exceed the slug length limit"),
+            "support-very-long-titles-that-exceed-the"
+        );
+    }
+
+    #[test]
+    fn test_branch_name() {
+        let t = ticket("Fix login crash", Some("gh-12"));
+        assert_eq!(branch_name("{type}/{id}-{slug}", &t), "bug/ttr-0042-fix-login-crash");
+        assert_eq!(branch_name("{issue}-{slug}", &t), "12-fix-login-crash");
+    }
+
+    #[test]
+    fn test_branch_name_unsynced_issue() {
+        let t = ticket("Fix login crash", None);
+        assert_eq!(branch_name("{issue}-{id}", &t), "ttr-0042");
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::github::projects::{IterationOption, ProjectItemIteration};
+
+/// Completed and remaining work for one iteration
+#[derive(Debug, Clone)]
+pub struct IterationBurndown {
+    pub title: String,
+    pub start: NaiveDate,
+    /// Last day of the iteration (inclusive)
+    pub end: NaiveDate,
+    pub total: usize,
+    /// Items closed by the end of the iteration (or by today, if current)
+    pub completed: usize,
+    pub remaining: usize,
+    /// Whether today falls within the iteration
+    pub current: bool,
+    /// Whether the iteration ended before today
+    pub finished: bool,
+    /// Remaining items at the end of each day, up to today
+    pub daily_remaining: Vec<(NaiveDate, usize)>,
+}
+
+fn parse_date(timestamp: &str) -> Option<NaiveDate> {
+    timestamp
+        .get(..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+/// Compute per-iteration burndown from project items, oldest iteration first
+///
+/// Iterations that haven't started yet are skipped.
+pub fn compute(
+    iterations: &[IterationOption],
+    items: &[ProjectItemIteration],
+    today: NaiveDate,
+) -> Vec<IterationBurndown> {
+    let mut result: Vec<IterationBurndown> = iterations
+        .iter()
+        .filter_map(|iteration| {
+            let start = parse_date(&iteration.start_date)?;
+            if start > today {
+                return None;
+            }
+            let end = start + Duration::days(iteration.duration.max(1) as i64 - 1);
+
+            let closed_dates: Vec<Option<NaiveDate>> = items
+                .iter()
+                .filter(|item| item.iteration_id.as_deref() == Some(iteration.id.as_str()))
+                .map(|item| item.closed_at.as_deref().and_then(parse_date))
+                .collect();
+
+            let total = closed_dates.len();
+            let cutoff = end.min(today);
+            let remaining_on = |day: NaiveDate| {
+                closed_dates
+                    .iter()
+                    .filter(|closed| closed.is_none_or(|d| d > day))
+                    .count()
+            };
+
+            let mut daily_remaining = Vec::new();
+            let mut day = start;
+            while day <= cutoff {
+                daily_remaining.push((day, remaining_on(day)));
+                day += Duration::days(1);
+            }
+
+            let remaining = remaining_on(cutoff);
+            Some(IterationBurndown {
+                title: iteration.title.clone(),
+                start,
+                end,
+                total,
+                completed: total - remaining,
+                remaining,
+                current: start <= today && today <= end,
+                finished: end < today,
+                daily_remaining,
+            })
+        })
+        .collect();
+
+    result.sort_by_key(|b| b.start);
+    result
+}
+
+/// Average items completed per finished iteration
+pub fn average_velocity(burndowns: &[IterationBurndown]) -> Option<f64> {
+    let finished: Vec<&IterationBurndown> = burndowns.iter().filter(|b| b.finished).collect();
+    if finished.is_empty() {
+        return None;
+    }
+    let completed: usize = finished.iter().map(|b| b.completed).sum();
+    Some(completed as f64 / finished.len() as f64)
+}
+
+/// Render an ASCII burndown chart: one bar per day, with the ideal line as `|`
+pub fn render_chart(burndown: &IterationBurndown, width: usize) -> String {
+    let mut out = String::new();
+    if burndown.total == 0 {
+        return out;
+    }
+
+    let days = (burndown.end - burndown.start).num_days().max(1) as f64;
+    let scale = |n: f64| ((n / burndown.total as f64) * width as f64).round() as usize;
+
+    for (day, remaining) in &burndown.daily_remaining {
+        let elapsed = (*day - burndown.start).num_days() as f64;
+        let ideal = scale(burndown.total as f64 * (1.0 - elapsed / days));
+        let bar = scale(*remaining as f64);
+
+        let mut line: Vec<char> = (0..width.max(bar).max(ideal + 1))
+            .map(|i| if i < bar { '#' } else { ' ' })
+            .collect();
+        let marker = ideal.min(line.len() - 1);
+        line[marker] = '|';
+
+        out.push_str(&format!(
+            "  {} {} {}\n",
+            day.format("%m-%d"),
+            line.iter().collect::<String>().trim_end(),
+            remaining
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iteration(id: &str, start: &str, duration: u64) -> IterationOption {
+        IterationOption {
+            id: id.to_string(),
+            title: format!("Sprint {}", id),
+            start_date: start.to_string(),
+            duration,
+        }
+    }
+
+    fn item(iteration: &str, closed_at: Option<&str>) -> ProjectItemIteration {
+        ProjectItemIteration {
+            item_id: "PVTI".to_string(),
+            iteration_id: Some(iteration.to_string()),
+            issue_number: Some(1),
+            closed_at: closed_at.map(|s| s.to_string()),
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_compute_burndown() {
+        let iterations = vec![
+            iteration("2", "2026-03-16", 14),
+            iteration("1", "2026-03-02", 14),
+            iteration("3", "2026-03-30", 14),
+        ];
+        let items = vec![
+            item("1", Some("2026-03-05T10:00:00Z")),
+            // Closed after the iteration ended: carried over
+            item("1", Some("2026-03-20T10:00:00Z")),
+            item("2", Some("2026-03-17T10:00:00Z")),
+            item("2", None),
+        ];
+
+        let burndowns = compute(&iterations, &items, date("2026-03-18"));
+
+        // Future iteration skipped; sorted by start date
+        assert_eq!(burndowns.len(), 2);
+        assert_eq!(burndowns[0].title, "Sprint 1");
+        assert!(burndowns[0].finished);
+        assert_eq!(burndowns[0].completed, 1);
+        assert_eq!(burndowns[0].remaining, 1);
+
+        assert!(burndowns[1].current);
+        assert_eq!(burndowns[1].total, 2);
+        assert_eq!(burndowns[1].completed, 1);
+        assert_eq!(
+            burndowns[1].daily_remaining,
+            vec![(date("2026-03-16"), 2), (date("2026-03-17"), 1), (date("2026-03-18"), 1)]
+        );
+
+        assert_eq!(average_velocity(&burndowns), Some(1.0));
+    }
+
+    #[test]
+    fn test_render_chart() {
+        let iterations = vec![iteration("1", "2026-03-02", 3)];
+        let items = vec![item("1", Some("2026-03-03")), item("1", None)];
+        let burndowns = compute(&iterations, &items, date("2026-03-04"));
+
+        let chart = render_chart(&burndowns[0], 4);
+        assert_eq!(chart, "  03-02 ####| 2\n  03-03 ##| 1\n  03-04 |# 1\n");
+    }
+}
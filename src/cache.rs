@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::github::client::GitHubClient;
+use crate::github::issues::ExistingIssue;
+
+/// Cache directory, relative to the tickets directory
+pub const CACHE_DIR: &str = ".cache";
+
+/// Issue snapshots, keyed by issue number
+const ISSUES_FILE: &str = "issues.json";
+
+/// Labels and issue types of the configured repo
+const REPO_FILE: &str = "repo.json";
+
+/// Create the cache directory, keeping it out of version control
+pub fn ensure_dir(tickets_dir: &Path) -> Result<PathBuf> {
+    let dir = tickets_dir.join(CACHE_DIR);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(&gitignore, "*\n").with_context(|| format!("Failed to write {}", gitignore.display()))?;
+    }
+    Ok(dir)
+}
+
+/// Issues as last fetched from GitHub
+///
+/// Entries are only trusted while their `updated_at` matches GitHub's, so a
+/// stale or corrupt cache costs a refetch, never a wrong answer.
+#[derive(Debug, Default)]
+pub struct IssueCache {
+    issues: HashMap<u64, ExistingIssue>,
+}
+
+impl IssueCache {
+    /// Load the cache, starting empty if it's missing or unreadable
+    pub fn load(tickets_dir: &Path) -> Self {
+        let path = tickets_dir.join(CACHE_DIR).join(ISSUES_FILE);
+        let issues = fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Self { issues }
+    }
+
+    pub fn save(&self, tickets_dir: &Path) -> Result<()> {
+        let path = ensure_dir(tickets_dir)?.join(ISSUES_FILE);
+        let raw = serde_json::to_string(&self.issues)?;
+        fs::write(&path, raw).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Fetch issues, reusing cached entries GitHub reports as unchanged
+    pub async fn get_issues(
+        &mut self,
+        client: &GitHubClient,
+        owner: &str,
+        name: &str,
+        numbers: &[u64],
+    ) -> Result<HashMap<u64, ExistingIssue>> {
+        let updated = client.get_issues_updated_at(owner, name, numbers).await?;
+
+        let stale: Vec<u64> = numbers
+            .iter()
+            .copied()
+            .filter(|n| match (self.issues.get(n), updated.get(n)) {
+                (Some(cached), Some(updated_at)) => cached.updated_at != *updated_at,
+                (_, None) => false,
+                (None, Some(_)) => true,
+            })
+            .collect();
+
+        for (number, issue) in client.get_issues_batch(owner, name, &stale).await? {
+            self.issues.insert(number, issue);
+        }
+        // Issues that no longer exist shouldn't linger
+        self.issues.retain(|n, _| !numbers.contains(n) || updated.contains_key(n));
+
+        Ok(numbers
+            .iter()
+            .filter(|n| updated.contains_key(n))
+            .filter_map(|n| self.issues.get(n).map(|issue| (*n, issue.clone())))
+            .collect())
+    }
+}
+
+/// Repo labels and issue types, which rarely change between runs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepoMetadata {
+    /// "owner/repo" the metadata belongs to
+    pub repo: String,
+    /// Unix timestamp of the fetch
+    pub fetched_at: i64,
+    /// Label name (lowercase) -> label ID
+    pub labels: HashMap<String, String>,
+    /// Issue type name (lowercase) -> ID
+    pub issue_types: HashMap<String, String>,
+}
+
+impl RepoMetadata {
+    pub fn new(repo: &str, labels: HashMap<String, String>, issue_types: HashMap<String, String>) -> Self {
+        Self {
+            repo: repo.to_string(),
+            fetched_at: chrono::Utc::now().timestamp(),
+            labels,
+            issue_types,
+        }
+    }
+
+    /// Cached metadata for `repo`, unless refreshing or older than `ttl` seconds
+    pub fn load(tickets_dir: &Path, repo: &str, ttl: u64, refresh: bool) -> Option<Self> {
+        if refresh || ttl == 0 {
+            return None;
+        }
+        let raw = fs::read_to_string(tickets_dir.join(CACHE_DIR).join(REPO_FILE)).ok()?;
+        let cached: Self = serde_json::from_str(&raw).ok()?;
+        let age = chrono::Utc::now().timestamp() - cached.fetched_at;
+        (cached.repo == repo && (0..ttl as i64).contains(&age)).then_some(cached)
+    }
+
+    pub fn save(&self, tickets_dir: &Path) -> Result<()> {
+        let path = ensure_dir(tickets_dir)?.join(REPO_FILE);
+        let raw = serde_json::to_string(self)?;
+        fs::write(&path, raw).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn issue(number: u64, updated_at: &str) -> ExistingIssue {
+        ExistingIssue {
+            id: format!("I_{}", number),
+            number,
+            title: "Title".to_string(),
+            body: "Body".to_string(),
+            state: "OPEN".to_string(),
+            url: format!("https://github.com/o/r/issues/{}", number),
+            updated_at: updated_at.to_string(),
+            created_at: String::new(),
+            closed_at: None,
+            assignees: Vec::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_issue_cache_round_trip() {
+        let dir = TempDir::new().unwrap();
+        assert!(IssueCache::load(dir.path()).issues.is_empty());
+
+        let mut cache = IssueCache::default();
+        cache.issues.insert(3, issue(3, "2026-01-01T00:00:00Z"));
+        cache.save(dir.path()).unwrap();
+
+        let loaded = IssueCache::load(dir.path());
+        assert_eq!(loaded.issues[&3].updated_at, "2026-01-01T00:00:00Z");
+        assert!(dir.path().join(CACHE_DIR).join(".gitignore").exists());
+
+        fs::write(dir.path().join(CACHE_DIR).join(ISSUES_FILE), "not json").unwrap();
+        assert!(IssueCache::load(dir.path()).issues.is_empty());
+    }
+
+    #[test]
+    fn test_repo_metadata_expiry() {
+        let dir = TempDir::new().unwrap();
+        let labels = HashMap::from([("bug".to_string(), "LA_1".to_string())]);
+        let mut metadata = RepoMetadata::new("o/r", labels, HashMap::new());
+        metadata.save(dir.path()).unwrap();
+
+        let cached = RepoMetadata::load(dir.path(), "o/r", 3600, false).unwrap();
+        assert_eq!(cached.labels["bug"], "LA_1");
+        assert!(RepoMetadata::load(dir.path(), "o/other", 3600, false).is_none());
+        assert!(RepoMetadata::load(dir.path(), "o/r", 0, false).is_none());
+        assert!(RepoMetadata::load(dir.path(), "o/r", 3600, true).is_none());
+
+        metadata.fetched_at -= 7200;
+        metadata.save(dir.path()).unwrap();
+        assert!(RepoMetadata::load(dir.path(), "o/r", 3600, false).is_none());
+    }
+}
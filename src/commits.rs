@@ -0,0 +1,109 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::ticket::Ticket;
+
+/// Keywords that close a referenced ticket, as on GitHub
+pub const CLOSING_KEYWORDS: &[&str] = &[
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
+
+/// A commit whose message closes one or more references
+#[derive(Debug, Clone)]
+pub struct ClosingCommit {
+    /// Abbreviated commit hash
+    pub sha: String,
+    /// First line of the commit message
+    pub subject: String,
+    /// Referenced ticket IDs or `#N` issue numbers
+    pub references: Vec<String>,
+}
+
+/// Find commits in a revision range whose messages close references
+pub fn scan_range(range: &str) -> Result<Vec<ClosingCommit>> {
+    let output = Command::new("git")
+        .args(["log", "--format=%h%x1f%B%x1e", range])
+        .output()
+        .context("Failed to run git log")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log {} failed: {}",
+            range,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let log = String::from_utf8_lossy(&output.stdout);
+    let commits = log
+        .split('\x1e')
+        .filter_map(|record| {
+            let (sha, message) = record.trim_start().split_once('\x1f')?;
+            let references = closing_references(message);
+            if references.is_empty() {
+                return None;
+            }
+            Some(ClosingCommit {
+                sha: sha.to_string(),
+                subject: message.lines().next().unwrap_or("").to_string(),
+                references,
+            })
+        })
+        .collect();
+
+    Ok(commits)
+}
+
+/// Extract references following a closing keyword
+///
+/// Matches "fixes ttr-0042", "Closes: #12" and the like, case-insensitively.
+pub fn closing_references(message: &str) -> Vec<String> {
+    let words: Vec<&str> = message.split_whitespace().collect();
+    let mut refs: Vec<String> = Vec::new();
+
+    for pair in words.windows(2) {
+        let keyword = pair[0].trim_end_matches(':').to_lowercase();
+        if !CLOSING_KEYWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+
+        let reference = pair[1].trim_matches(|c: char| ",.;:()[]".contains(c));
+        if !reference.is_empty() && !refs.iter().any(|r| r == reference) {
+            refs.push(reference.to_string());
+        }
+    }
+
+    refs
+}
+
+/// Resolve a reference to a ticket by ID or by synced issue number
+pub fn resolve_reference<'a>(tickets: &'a [Ticket], reference: &str) -> Option<&'a Ticket> {
+    match reference.strip_prefix('#').and_then(|n| n.parse::<u64>().ok()) {
+        Some(number) => tickets
+            .iter()
+            .find(|t| t.github_issue_number() == Some(number)),
+        None => tickets.iter().find(|t| t.id.eq_ignore_ascii_case(reference)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closing_references() {
+        let message = "Handle empty config\n\nFixes ttr-0042, closes: #12.\nSee ttr-0001";
+        assert_eq!(closing_references(message), vec!["ttr-0042", "#12"]);
+    }
+
+    #[test]
+    fn test_closing_references_case_and_dedupe() {
+        let message = "RESOLVED ttr-7 (also Resolves ttr-7)";
+        assert_eq!(closing_references(message), vec!["ttr-7"]);
+    }
+
+    #[test]
+    fn test_no_keyword() {
+        assert!(closing_references("Refactor ttr-0042 parsing").is_empty());
+    }
+}
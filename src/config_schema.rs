@@ -0,0 +1,498 @@
+use serde_json::{json, Map, Value};
+use toml_edit::{ImDocument, Item, TableLike};
+
+/// What a sync.toml key holds
+pub enum Kind {
+    String,
+    Bool,
+    /// A non-negative integer
+    Integer,
+    /// One of a fixed set of strings
+    OneOf(&'static [&'static str]),
+    StringList,
+    /// A table with free-form keys, each holding the given kind
+    Map(&'static Kind),
+    /// A table with known keys, given in groups
+    Table(&'static [&'static [Field]]),
+    /// A table with known keys, plus free-form keys each holding the given kind
+    OpenTable(&'static [&'static [Field]], &'static Kind),
+    /// An array of tables with known keys (`[[projects]]`)
+    TableList(&'static [&'static [Field]]),
+}
+
+/// A known sync.toml key
+pub struct Field {
+    pub name: &'static str,
+    pub kind: Kind,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+const fn field(name: &'static str, kind: Kind, description: &'static str) -> Field {
+    Field {
+        name,
+        kind,
+        required: false,
+        description,
+    }
+}
+
+const fn required(name: &'static str, kind: Kind, description: &'static str) -> Field {
+    Field {
+        name,
+        kind,
+        required: true,
+        description,
+    }
+}
+
+const GITHUB: &[Field] = &[
+    required("repo", Kind::String, "Repository in \"owner/repo\" format"),
+    field("project", Kind::String, "GitHub Project name or number"),
+    field("assignee", Kind::String, "Assignee for all created issues"),
+    field("assignees", Kind::StringList, "Assignees new issues are spread across"),
+    field(
+        "assignee_strategy",
+        Kind::OneOf(&["round-robin", "least-open"]),
+        "How new issues are spread across `assignees`",
+    ),
+    field("api", Kind::OneOf(&["graphql", "rest"]), "API for core issue operations"),
+    field("managed_label", Kind::String, "Label put on every synced issue; pulls only consider issues carrying it"),
+];
+
+const MAPPING: &[Field] = &[
+    field("type_field", Kind::String, "Project field name for ticket type"),
+    field("type", Kind::Map(&Kind::String), "Ticket type to issue type or project field value"),
+    field("mentions", Kind::Map(&Kind::String), "Local `@handle` to GitHub login"),
+    field("escape_unmapped_mentions", Kind::Bool, "Put unmapped `@handle` mentions in code"),
+    field("emoji", Kind::Map(&Kind::String), "Ticket type to emoji put in front of issue titles"),
+];
+
+const LABELS: &[Field] = &[
+    field("sync_tags", Kind::Bool, "Sync ticket tags as GitHub labels"),
+    field("create_missing", Kind::Bool, "Create labels that don't exist"),
+    field("type_label", Kind::String, "Type label for repos without issue types, e.g. \"type:{type}\""),
+];
+
+const PROJECT_FIELDS: &[Field] = &[
+    field("status_field", Kind::String, "Name of the project's Status field"),
+    field("iteration_field", Kind::String, "Name of the project's Iteration field"),
+    field("status", Kind::Map(&Kind::String), "Ticket status to Status option"),
+    field("iteration", Kind::String, "\"@current\", \"@next\", \"@current+N\", \"@none\", or an iteration name"),
+    field("draft_types", Kind::StringList, "Ticket types added as draft project items"),
+    field("custom_fields", Kind::Map(&Kind::String), "Frontmatter key to project field name"),
+    field("tag_fields", Kind::Map(&Kind::String), "Tag prefix (`area` for `area:*` tags) to project field name"),
+    field("estimate_field", Kind::String, "Number field set from `estimate-hours`"),
+    field("spent_field", Kind::String, "Number field set from `spent-hours`, summed over an epic's children"),
+];
+
+const PROJECT_ROUTE: &[Field] = &[
+    required("name", Kind::String, "Project name or number"),
+    field("types", Kind::StringList, "Ticket types routed to this project (empty matches any)"),
+    field("tags", Kind::StringList, "Ticket tags routed to this project (empty matches any)"),
+];
+
+const PULL_REQUESTS: &[Field] = &[field(
+    "link",
+    Kind::OneOf(&["none", "closes", "comment"]),
+    "How to link pull requests to synced issues",
+)];
+
+const DISCUSSIONS: &[Field] = &[
+    field("category", Kind::String, "Discussion category for tickets synced as discussions"),
+    field("tags", Kind::StringList, "Tags that make a ticket a discussion (default: idea, rfc)"),
+];
+
+const GIT: &[Field] = &[field(
+    "branch_pattern",
+    Kind::String,
+    "Pattern for `ttr branch` names: {id}, {type}, {slug}, {issue}",
+)];
+
+const SYNC: &[Field] = &[
+    field("write_url", Kind::Bool, "Write `url:` into tickets after creating their issue"),
+    field("title_format", Kind::String, "Issue title template with {id} and {title}"),
+    field("private_sections", Kind::StringList, "`## ` sections kept out of issue bodies"),
+    field("deps_format", Kind::OneOf(&["inline", "tasklist"]), "How dependencies are rendered"),
+    field("mention_links", Kind::OneOf(&["issue", "url", "none"]), "How ticket IDs in bodies are linked"),
+    field("absolute_links", Kind::Bool, "Rewrite relative file links to blob URLs"),
+    field(
+        "unchecked_criteria",
+        Kind::OneOf(&["allow", "warn", "fail"]),
+        "Closing with unchecked acceptance criteria",
+    ),
+    field("footer", Kind::Bool, "Add the footer line to issue bodies"),
+    field("footer_text", Kind::String, "Footer text, with {id} for the ticket ID"),
+    field("footer_link", Kind::Bool, "Link the footer to the ticket file"),
+    field("footer_branch", Kind::String, "Branch for the footer link"),
+    field("marker_position", Kind::OneOf(&["top", "bottom"]), "Where the hidden ticket marker goes"),
+    field("strict", Kind::Bool, "Abort when a ticket file fails to load"),
+    field("manage_body", Kind::Bool, "Push bodies to existing issues (tickets can override with `manage-body`)"),
+    field("manage_assignees", Kind::Bool, "Reassign existing issues when the ticket's or configured assignee changes"),
+    field("issue_template", Kind::String, "Issue template (Markdown or issue form) to lay issue bodies out by"),
+    field("parent_dirs", Kind::Bool, "Load tickets from `.tickets/<epic-id>/` too, with that epic as their parent"),
+];
+
+const NETWORK: &[Field] = &[
+    field("proxy", Kind::String, "Proxy URL for all requests"),
+    field("ca_bundle", Kind::String, "PEM file of extra root certificates"),
+    field("danger_accept_invalid_certs", Kind::Bool, "Skip TLS certificate verification"),
+];
+
+const NOTIFY: &[Field] = &[
+    field("slack_webhook", Kind::String, "Slack incoming webhook URL for push and sync summaries"),
+    field("matrix_webhook", Kind::String, "Matrix webhook URL for push and sync summaries"),
+];
+
+const LINT_EXTERNAL: &[Field] = &[
+    required("cmd", Kind::String, "Shell command given each ticket's markdown on stdin, e.g. \"vale --output=line\""),
+    field("severity", Kind::OneOf(&["off", "warn", "error"]), "How its findings are reported"),
+];
+
+const LINT: &[Field] = &[field("external", Kind::Table(&[LINT_EXTERNAL]), "External check run on each ticket")];
+
+const CACHE: &[Field] = &[field("ttl", Kind::Integer, "Seconds before cached metadata is refetched (0 disables)")];
+
+/// Every top-level sync.toml key
+pub const CONFIG: &[Field] = &[
+    field("version", Kind::Integer, "Config layout version, updated by `ttr upgrade-config`"),
+    required("github", Kind::Table(&[GITHUB]), "Target repository"),
+    field("mapping", Kind::Table(&[MAPPING]), "Ticket type mapping"),
+    field("labels", Kind::Table(&[LABELS]), "Label syncing"),
+    field("project", Kind::Table(&[PROJECT_FIELDS]), "Field settings for `[github] project`"),
+    field("projects", Kind::TableList(&[PROJECT_ROUTE, PROJECT_FIELDS]), "Additional projects with routing rules"),
+    field("pull_requests", Kind::Table(&[PULL_REQUESTS]), "Pull request linking"),
+    field("discussions", Kind::Table(&[DISCUSSIONS]), "Syncing tagged tickets to GitHub Discussions"),
+    field("git", Kind::Table(&[GIT]), "Local git integration"),
+    field("sync", Kind::Table(&[SYNC]), "How tickets are written to issues"),
+    field(
+        "lint",
+        Kind::OpenTable(&[LINT], &Kind::OneOf(&["off", "warn", "error"])),
+        "Severity per `ttr lint` rule, and external checks",
+    ),
+    field("network", Kind::Table(&[NETWORK]), "How ttr reaches the GitHub API"),
+    field("cache", Kind::Table(&[CACHE]), "Local caching of GitHub metadata"),
+    field("notify", Kind::Table(&[NOTIFY]), "Chat messages after a push or sync"),
+];
+
+/// The JSON Schema for sync.toml, for editors with TOML schema support
+pub fn json_schema() -> Value {
+    let mut schema = table_schema(&[CONFIG]);
+    schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+    schema["title"] = json!("ttr sync.toml");
+    schema
+}
+
+fn kind_schema(kind: &Kind) -> Value {
+    match kind {
+        Kind::String => json!({ "type": "string" }),
+        Kind::Bool => json!({ "type": "boolean" }),
+        Kind::Integer => json!({ "type": "integer", "minimum": 0 }),
+        Kind::OneOf(values) => json!({ "enum": values }),
+        Kind::StringList => json!({ "type": "array", "items": { "type": "string" } }),
+        Kind::Map(value) => json!({ "type": "object", "additionalProperties": kind_schema(value) }),
+        Kind::Table(groups) => table_schema(groups),
+        Kind::OpenTable(groups, value) => {
+            let mut schema = table_schema(groups);
+            schema["additionalProperties"] = kind_schema(value);
+            schema
+        }
+        Kind::TableList(groups) => json!({ "type": "array", "items": table_schema(groups) }),
+    }
+}
+
+fn table_schema(groups: &[&[Field]]) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for field in groups.iter().flat_map(|g| g.iter()) {
+        let mut property = kind_schema(&field.kind);
+        property["description"] = json!(field.description);
+        properties.insert(field.name.to_string(), property);
+        if field.required {
+            required.push(field.name);
+        }
+    }
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+    schema
+}
+
+/// A problem with a sync.toml key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// Dotted key path, e.g. "projects[1].status_field"
+    pub path: String,
+    /// 1-based line, when the problem is tied to a place in the file
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "`{}`: {}", self.path, self.message)
+    }
+}
+
+/// Check sync.toml content against the schema
+///
+/// Problems carry the key path and line, and misspelled keys and values
+/// come with a suggestion. Content that isn't valid TOML is left for the
+/// TOML parser to report.
+pub fn validate(content: &str) -> Vec<Problem> {
+    let Ok(doc) = ImDocument::parse(content) else {
+        return Vec::new();
+    };
+    let mut checker = Checker {
+        content,
+        problems: Vec::new(),
+    };
+    checker.table(doc.as_table(), &[CONFIG], None, "", None);
+    checker.problems
+}
+
+struct Checker<'a> {
+    content: &'a str,
+    problems: Vec<Problem>,
+}
+
+impl Checker<'_> {
+    fn line(&self, span: Option<std::ops::Range<usize>>) -> Option<usize> {
+        span.map(|s| self.content[..s.start.min(self.content.len())].matches('\n').count() + 1)
+    }
+
+    fn report(&mut self, path: &str, span: Option<std::ops::Range<usize>>, message: String) {
+        self.problems.push(Problem {
+            path: path.to_string(),
+            line: self.line(span),
+            message,
+        });
+    }
+
+    fn table(
+        &mut self,
+        table: &dyn TableLike,
+        groups: &[&[Field]],
+        rest: Option<&Kind>,
+        prefix: &str,
+        span: Option<std::ops::Range<usize>>,
+    ) {
+        let fields: Vec<&Field> = groups.iter().flat_map(|g| g.iter()).collect();
+        for (key, item) in table.iter() {
+            let path = join(prefix, key);
+            let key_span = table.get_key_value(key).and_then(|(k, _)| k.span());
+            match (fields.iter().find(|f| f.name == key), rest) {
+                (Some(field), _) => self.item(item, &field.kind, &path, key_span),
+                (None, Some(kind)) => self.item(item, kind, &path, key_span),
+                (None, None) => {
+                    let message = match closest(key, fields.iter().map(|f| f.name)) {
+                        Some(name) => format!("unknown key (did you mean `{}`?)", name),
+                        None => "unknown key".to_string(),
+                    };
+                    self.report(&path, key_span, message);
+                }
+            }
+        }
+        for field in fields.iter().filter(|f| f.required && !table.contains_key(f.name)) {
+            let message = match field.kind {
+                Kind::Table(_) => format!("missing required table [{}]", field.name),
+                _ => "missing required key".to_string(),
+            };
+            self.report(&join(prefix, field.name), span.clone(), message);
+        }
+    }
+
+    fn item(&mut self, item: &Item, kind: &Kind, path: &str, key_span: Option<std::ops::Range<usize>>) {
+        let span = key_span.or_else(|| item.span());
+        match kind {
+            Kind::Table(groups) => match item.as_table_like() {
+                Some(table) => self.table(table, groups, None, path, span),
+                None => self.report(path, span, format!("expected a table, found {}", describe(item))),
+            },
+            Kind::OpenTable(groups, value_kind) => match item.as_table_like() {
+                Some(table) => self.table(table, groups, Some(value_kind), path, span),
+                None => self.report(path, span, format!("expected a table, found {}", describe(item))),
+            },
+            Kind::Map(value_kind) => match item.as_table_like() {
+                Some(table) => {
+                    for (key, value) in table.iter() {
+                        let value_span = table.get_key_value(key).and_then(|(k, _)| k.span());
+                        self.item(value, value_kind, &join(path, key), value_span);
+                    }
+                }
+                None => self.report(path, span, format!("expected a table, found {}", describe(item))),
+            },
+            Kind::TableList(groups) => {
+                if let Some(tables) = item.as_array_of_tables() {
+                    for (i, table) in tables.iter().enumerate() {
+                        self.table(table, groups, None, &format!("{}[{}]", path, i), table.span().or(span.clone()));
+                    }
+                } else if let Some(array) = item.as_array() {
+                    for (i, value) in array.iter().enumerate() {
+                        let entry = format!("{}[{}]", path, i);
+                        match value.as_inline_table() {
+                            Some(table) => self.table(table, groups, None, &entry, value.span()),
+                            None => self.report(&entry, value.span(), "expected a table".to_string()),
+                        }
+                    }
+                } else {
+                    self.report(path, span, format!("expected an array of tables, found {}", describe(item)));
+                }
+            }
+            Kind::String => {
+                if !item.is_str() {
+                    self.report(path, span, format!("expected a string, found {}", describe(item)));
+                }
+            }
+            Kind::Bool => {
+                if !item.is_bool() {
+                    self.report(path, span, format!("expected true or false, found {}", describe(item)));
+                }
+            }
+            Kind::Integer => match item.as_integer() {
+                Some(n) if n < 0 => self.report(path, span, "must not be negative".to_string()),
+                Some(_) => {}
+                None => self.report(path, span, format!("expected an integer, found {}", describe(item))),
+            },
+            Kind::StringList => {
+                let all_strings = item.as_array().map(|a| a.iter().all(|v| v.is_str()));
+                if all_strings != Some(true) {
+                    self.report(path, span, "expected an array of strings".to_string());
+                }
+            }
+            Kind::OneOf(values) => {
+                let allowed = values.iter().map(|v| format!("\"{}\"", v)).collect::<Vec<_>>().join(", ");
+                match item.as_str() {
+                    Some(value) if values.contains(&value) => {}
+                    Some(value) => {
+                        let mut message = format!("\"{}\" is not one of {}", value, allowed);
+                        if let Some(close) = closest(value, values.iter().copied()) {
+                            message.push_str(&format!(" (did you mean \"{}\"?)", close));
+                        }
+                        self.report(path, span, message);
+                    }
+                    None => self.report(path, span, format!("expected one of {}, found {}", allowed, describe(item))),
+                }
+            }
+        }
+    }
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// What kind of TOML value an item is, for messages
+fn describe(item: &Item) -> &'static str {
+    match item {
+        Item::None => "nothing",
+        Item::Table(_) | Item::Value(toml_edit::Value::InlineTable(_)) => "a table",
+        Item::ArrayOfTables(_) => "an array of tables",
+        Item::Value(value) => match value {
+            toml_edit::Value::String(_) => "a string",
+            toml_edit::Value::Integer(_) => "an integer",
+            toml_edit::Value::Float(_) => "a float",
+            toml_edit::Value::Boolean(_) => "a boolean",
+            toml_edit::Value::Datetime(_) => "a datetime",
+            toml_edit::Value::Array(_) => "an array",
+            toml_edit::Value::InlineTable(_) => "a table",
+        },
+    }
+}
+
+/// The candidate closest to a misspelling, if any is close enough
+fn closest<'a>(word: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (word.chars().count() / 3).clamp(1, 3);
+    candidates
+        .map(|c| (edit_distance(&word.to_lowercase(), c), c))
+        .filter(|(d, _)| *d <= limit)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+/// Edit distance counting a swap of adjacent characters as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reports_paths_lines_and_suggestions() {
+        let content = r#"[github]
+repo = "owner/repo"
+api = "rests"
+
+[lables]
+sync_tags = true
+
+[sync]
+write_url = "yes"
+
+[[projects]]
+name = "Triage"
+status_feild = "State"
+
+[cache]
+ttl = -1
+"#;
+        let problems: Vec<String> = validate(content).iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            vec![
+                "line 3: `github.api`: \"rests\" is not one of \"graphql\", \"rest\" (did you mean \"rest\"?)",
+                "line 5: `lables`: unknown key (did you mean `labels`?)",
+                "line 9: `sync.write_url`: expected true or false, found a string",
+                "line 13: `projects[0].status_feild`: unknown key (did you mean `status_field`?)",
+                "line 16: `cache.ttl`: must not be negative",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_config_and_reports_missing_keys() {
+        let content = "[github]\nrepo = \"o/r\"\n[mapping.type]\nbug = \"Bug\"\n[lint]\nmy-rule = \"warn\"\n";
+        assert!(validate(content).is_empty());
+
+        let problems = validate("[github]\nproject = \"Roadmap\"\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].to_string(), "line 1: `github.repo`: missing required key");
+        assert_eq!(validate("")[0].to_string(), "`github`: missing required table [github]");
+    }
+
+    #[test]
+    fn test_shipped_schema_is_current() {
+        let shipped: Value = serde_json::from_str(include_str!("../sync.schema.json")).unwrap();
+        assert_eq!(shipped, json_schema(), "regenerate with `ttr config schema > sync.schema.json`");
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Conflict quarantine directory, relative to the tickets directory
+pub const CONFLICTS_DIR: &str = ".conflicts";
+
+/// Where a conflicted ticket's remote issue body is kept
+pub fn remote_path(tickets_dir: &Path, ticket_id: &str) -> PathBuf {
+    tickets_dir.join(CONFLICTS_DIR).join(format!("{}.remote.md", ticket_id))
+}
+
+/// Save the issue body a push wouldn't overwrite, for merging by hand
+///
+/// The directory is kept out of version control like the cache.
+pub fn quarantine(tickets_dir: &Path, ticket_id: &str, remote_body: &str) -> Result<PathBuf> {
+    let dir = tickets_dir.join(CONFLICTS_DIR);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(&gitignore, "*\n").with_context(|| format!("Failed to write {}", gitignore.display()))?;
+    }
+    let path = remote_path(tickets_dir, ticket_id);
+    let mut body = remote_body.replace("\r\n", "\n");
+    if !body.ends_with('\n') {
+        body.push('\n');
+    }
+    fs::write(&path, body).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Remove a ticket's quarantined body once it no longer conflicts
+///
+/// Returns whether there was one.
+pub fn clear(tickets_dir: &Path, ticket_id: &str) -> Result<bool> {
+    let path = remote_path(tickets_dir, ticket_id);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_quarantine_and_clear() {
+        let dir = TempDir::new().unwrap();
+        let path = quarantine(dir.path(), "t-1", "Edited on GitHub\r\nsecond line").unwrap();
+        assert_eq!(path, dir.path().join(".conflicts/t-1.remote.md"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Edited on GitHub\nsecond line\n");
+        assert_eq!(fs::read_to_string(dir.path().join(".conflicts/.gitignore")).unwrap(), "*\n");
+
+        assert!(clear(dir.path(), "t-1").unwrap());
+        assert!(!path.exists());
+        assert!(!clear(dir.path(), "t-1").unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ticket::Ticket;
+
+/// Dependency state of the backlog, for `ttr status --blocked`
+///
+/// Each list is in dependency order: tickets come after what they depend on.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DepsReport {
+    /// Open tickets waiting on open dependencies: (ticket ID, open dependency IDs)
+    pub blocked: Vec<(String, Vec<String>)>,
+    /// Open, not yet started tickets whose dependencies are all closed
+    pub ready: Vec<String>,
+    /// Closed tickets that open tickets depend on: (ticket ID, open dependent IDs)
+    pub closed_with_open_dependents: Vec<(String, Vec<String>)>,
+}
+
+/// Classify tickets by the state of their dependencies
+///
+/// `closed` holds the IDs of closed tickets, locally or on GitHub.
+/// Dependencies on unknown tickets are ignored (`ttr lint` reports them).
+pub fn analyze(tickets: &[Ticket], closed: &HashSet<String>) -> DepsReport {
+    let by_id: HashMap<&str, &Ticket> = tickets.iter().map(|t| (t.id.as_str(), t)).collect();
+    let mut depths = HashMap::new();
+    let mut order: Vec<&Ticket> = tickets.iter().collect();
+    order.sort_by_cached_key(|t| (depth(&t.id, &by_id, &mut depths, &mut HashSet::new()), t.id.clone()));
+
+    let mut report = DepsReport::default();
+    for ticket in order {
+        if closed.contains(&ticket.id) {
+            let dependents = open_dependents(tickets, closed, &ticket.id);
+            if !dependents.is_empty() {
+                report.closed_with_open_dependents.push((ticket.id.clone(), dependents));
+            }
+            continue;
+        }
+
+        let open_deps: Vec<String> = ticket
+            .deps
+            .iter()
+            .filter(|d| by_id.contains_key(d.as_str()) && !closed.contains(*d))
+            .cloned()
+            .collect();
+        if !open_deps.is_empty() {
+            report.blocked.push((ticket.id.clone(), open_deps));
+        } else if ticket.status != "in_progress" {
+            report.ready.push(ticket.id.clone());
+        }
+    }
+    report
+}
+
+/// IDs of open tickets that depend on `id`
+pub fn open_dependents(tickets: &[Ticket], closed: &HashSet<String>, id: &str) -> Vec<String> {
+    tickets
+        .iter()
+        .filter(|t| !closed.contains(&t.id) && t.deps.iter().any(|d| d == id))
+        .map(|t| t.id.clone())
+        .collect()
+}
+
+/// Length of the longest dependency chain below a ticket (cycles are cut)
+fn depth<'a>(
+    id: &'a str,
+    by_id: &HashMap<&'a str, &'a Ticket>,
+    depths: &mut HashMap<&'a str, usize>,
+    visiting: &mut HashSet<&'a str>,
+) -> usize {
+    if let Some(&d) = depths.get(id) {
+        return d;
+    }
+    let Some(ticket) = by_id.get(id) else {
+        return 0;
+    };
+    if !visiting.insert(id) {
+        return 0;
+    }
+    let d = ticket
+        .deps
+        .iter()
+        .filter(|dep| by_id.contains_key(dep.as_str()))
+        .map(|dep| depth(dep, by_id, depths, visiting) + 1)
+        .max()
+        .unwrap_or(0);
+    visiting.remove(id);
+    depths.insert(id, d);
+    d
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::TicketDir;
+
+    #[test]
+    fn test_analyze_dependencies() {
+        let dir = TicketDir::new();
+        dir.ticket("d", "deps: [c, a]\n");
+        dir.ticket("c", "deps: [b]\n");
+        dir.ticket("b", "deps: [a]\nstatus: in_progress\n");
+        dir.ticket("a", "status: closed\n");
+        dir.ticket("e", "deps: [missing]\n");
+        let tickets = dir.load();
+        let closed: HashSet<String> = HashSet::from(["a".to_string()]);
+
+        let report = analyze(&tickets, &closed);
+
+        assert_eq!(
+            report.blocked,
+            vec![("c".to_string(), vec!["b".to_string()]), ("d".to_string(), vec!["c".to_string()])]
+        );
+        assert_eq!(report.ready, vec!["e"]);
+        assert_eq!(report.closed_with_open_dependents, vec![("a".to_string(), vec!["b".to_string(), "d".to_string()])]);
+    }
+}
@@ -0,0 +1,351 @@
+/// A top-level key and the lines its value spans
+struct Entry {
+    key: String,
+    /// Line of `key:`
+    line: usize,
+    /// One past the last line of the value (trailing blank lines and
+    /// column-0 comments excluded)
+    end: usize,
+}
+
+/// Lines of a document, remembering its line ending
+struct Document {
+    lines: Vec<String>,
+    newline: &'static str,
+    /// Index of the closing `---`
+    close: usize,
+}
+
+impl Document {
+    fn parse(content: &str) -> Option<Self> {
+        let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+        let lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+        if lines.first().map(|l| l.as_str()) != Some("---") {
+            return None;
+        }
+        let close = lines.iter().skip(1).position(|l| l == "---")? + 1;
+        Some(Self {
+            lines,
+            newline,
+            close,
+        })
+    }
+
+    fn entries(&self) -> Vec<Entry> {
+        let mut entries: Vec<Entry> = Vec::new();
+        for i in 1..self.close {
+            let line = &self.lines[i];
+            if let Some(key) = top_level_key(line) {
+                entries.push(Entry { key, line: i, end: i + 1 });
+            } else if is_continuation(line) {
+                if let Some(entry) = entries.last_mut() {
+                    // Blank lines only count once followed by more of the value
+                    if !line.trim().is_empty() {
+                        entry.end = i + 1;
+                    }
+                }
+            }
+        }
+        entries
+    }
+
+    /// Join lines back together, ending with a newline
+    fn render(&self) -> String {
+        let mut out = self.lines.join(self.newline);
+        out.push_str(self.newline);
+        out
+    }
+}
+
+/// Key of a top-level `key: value` line
+fn top_level_key(line: &str) -> Option<String> {
+    if line.starts_with([' ', '\t', '#', '-']) || line.trim().is_empty() {
+        return None;
+    }
+    let (key, rest) = split_key(line)?;
+    if !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    Some(key.trim_matches(['"', '\'']).to_string())
+}
+
+/// Split `key: rest` at the first colon outside quotes
+fn split_key(line: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') if i == 0 => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, ':') => return Some((&line[..i], &line[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Lines that belong to the value of the key above them
+fn is_continuation(line: &str) -> bool {
+    line.trim().is_empty() || line.starts_with([' ', '\t']) || line.starts_with('-')
+}
+
+/// Split a value into the scalar and an inline ` # comment` (with its spacing)
+fn split_comment(value: &str) -> (&str, &str) {
+    let mut quote = None;
+    let bytes = value.as_bytes();
+    for (i, c) in value.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') if value[..i].trim().is_empty() => quote = Some(c),
+            (Some(q), c) if c == q && !(q == '"' && i > 0 && bytes[i - 1] == b'\\') => quote = None,
+            (None, '#') if i > 0 && value[..i].ends_with([' ', '\t']) => {
+                let start = value[..i].trim_end().len();
+                return (&value[..start], &value[start..]);
+            }
+            _ => {}
+        }
+    }
+    (value.trim_end(), &value[value.trim_end().len()..])
+}
+
+/// Whether a string can be written as a plain (unquoted) YAML scalar
+fn is_plain_safe(value: &str) -> bool {
+    !value.is_empty()
+        && value.trim() == value
+        && !value.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
+        ])
+        && !value.contains(": ")
+        && !value.contains(" #")
+        && !value.ends_with(':')
+        && !value.contains(['\n', '\r', '\t'])
+}
+
+/// Format a scalar, keeping the quote style of the value it replaces
+fn format_scalar(value: &str, old: &str) -> String {
+    let double = |v: &str| format!("\"{}\"", v.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"));
+    if old.starts_with('"') {
+        double(value)
+    } else if old.starts_with('\'') && !value.contains('\n') {
+        format!("'{}'", value.replace('\'', "''"))
+    } else if is_plain_safe(value) {
+        value.to_string()
+    } else {
+        double(value)
+    }
+}
+
+/// Set a top-level key, editing only that key's lines
+///
+/// Other keys keep their order, quoting, comments, and multi-line values,
+/// and the file keeps its line endings. Returns None if the content has no
+/// frontmatter.
+pub fn set_field(content: &str, key: &str, value: &str) -> Option<String> {
+    replace_value(content, key, |old| format_scalar(value, old))
+}
+
+/// Set a top-level key to a flow-style list, e.g. `[gh-42, jira-7]`
+pub fn set_list(content: &str, key: &str, values: &[String]) -> Option<String> {
+    let items: Vec<String> = values
+        .iter()
+        .map(|v| {
+            if v.contains([',', '[', ']', '{', '}']) {
+                format_scalar(v, "\"")
+            } else {
+                format_scalar(v, "")
+            }
+        })
+        .collect();
+    let list = format!("[{}]", items.join(", "));
+    replace_value(content, key, |_| list.clone())
+}
+
+/// Replace a key's value with `format(old_value)`, or append the key
+fn replace_value(content: &str, key: &str, format: impl Fn(&str) -> String) -> Option<String> {
+    let mut doc = Document::parse(content)?;
+
+    match doc.entries().into_iter().find(|e| e.key == key) {
+        Some(entry) => {
+            let line = &doc.lines[entry.line];
+            let (key_part, rest) = split_key(line)?;
+            let spacing = &rest[..rest.len() - rest.trim_start().len()];
+            let (old, comment) = split_comment(rest.trim_start());
+
+            // Drop the old multi-line value, keeping any comments within it
+            let continuation: Vec<String> = doc.lines[entry.line + 1..entry.end]
+                .iter()
+                .filter(|l| l.trim_start().starts_with('#'))
+                .cloned()
+                .collect();
+
+            let spacing = if spacing.is_empty() { " " } else { spacing };
+            let new_line = format!("{}:{}{}{}", key_part, spacing, format(old), comment);
+            let mut replacement = vec![new_line];
+            replacement.extend(continuation);
+            doc.lines.splice(entry.line..entry.end, replacement);
+        }
+        None => {
+            let new_line = format!("{}: {}", key, format(""));
+            doc.lines.insert(doc.close, new_line);
+        }
+    }
+
+    Some(doc.render())
+}
+
+/// Replace `old` with `new` where it appears as a whole value of a key
+///
+/// Works on scalars and on flow or block lists, matching whole words only so
+/// `ttr-1` doesn't touch `ttr-10`. Returns None if the content has no
+/// frontmatter.
+pub fn rename_value(content: &str, key: &str, old: &str, new: &str) -> Option<String> {
+    let mut doc = Document::parse(content)?;
+    if let Some(entry) = doc.entries().into_iter().find(|e| e.key == key) {
+        for i in entry.line..entry.end {
+            let line = &doc.lines[i];
+            let split = if i == entry.line { split_key(line)?.0.len() + 1 } else { 0 };
+            let (prefix, value) = line.split_at(split);
+            let (scalar, comment) = split_comment(value);
+            let trailing = &value[scalar.len()..value.len() - comment.len()];
+            doc.lines[i] = format!("{}{}{}{}", prefix, replace_word(scalar, old, new), trailing, comment);
+        }
+    }
+    Some(doc.render())
+}
+
+/// Replace whole-word occurrences, where a word is a run of ID characters
+fn replace_word(text: &str, old: &str, new: &str) -> String {
+    let is_id_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.');
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(old) {
+        let before = rest[..pos].chars().last().or_else(|| out.chars().last());
+        let after = rest[pos + old.len()..].chars().next();
+        out.push_str(&rest[..pos]);
+        if before.is_some_and(is_id_char) || after.is_some_and(is_id_char) {
+            out.push_str(old);
+        } else {
+            out.push_str(new);
+        }
+        rest = &rest[pos + old.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Remove a top-level key and its value, if present
+///
+/// Returns None if the content has no frontmatter.
+pub fn remove_field(content: &str, key: &str) -> Option<String> {
+    let mut doc = Document::parse(content)?;
+    if let Some(entry) = doc.entries().into_iter().find(|e| e.key == key) {
+        doc.lines.drain(entry.line..entry.end);
+    }
+    Some(doc.render())
+}
+
+/// The YAML between the `---` delimiters, which starts on line 2 of the file
+pub fn yaml_source(content: &str) -> Option<String> {
+    let doc = Document::parse(content)?;
+    Some(doc.lines[1..doc.close].join("\n"))
+}
+
+/// File position (1-based line and column) of a top-level key's value
+///
+/// Values on the lines below their key point just past the colon.
+pub fn value_position(content: &str, key: &str) -> Option<(usize, usize)> {
+    let doc = Document::parse(content)?;
+    let entry = doc.entries().into_iter().find(|e| e.key == key)?;
+    let line = &doc.lines[entry.line];
+    let (key_part, rest) = split_key(line)?;
+    let spacing = rest.len() - rest.trim_start().len();
+    let col = key_part.chars().count() + 1 + if rest.trim().is_empty() { 0 } else { spacing };
+    Some((entry.line + 1, col + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_preserves_other_keys_and_comments() {
+        let content = "---\n# ticket metadata\nid: t-1\nstatus: open  # workflow state\ndescription: |\n  status: not a key\n\n  more text\ntags:\n- a\n- b\n---\n# Title\n";
+
+        let updated = set_field(content, "status", "closed").unwrap();
+
+        assert_eq!(
+            updated,
+            "---\n# ticket metadata\nid: t-1\nstatus: closed  # workflow state\ndescription: |\n  status: not a key\n\n  more text\ntags:\n- a\n- b\n---\n# Title\n"
+        );
+    }
+
+    #[test]
+    fn test_set_replaces_multiline_value() {
+        let content = "---\nid: t-1\nexternal-ref:\n  gh-1\n# keep me\nstatus: open\n---\n";
+
+        let updated = set_field(content, "external-ref", "gh-2").unwrap();
+
+        assert_eq!(updated, "---\nid: t-1\nexternal-ref: gh-2\n# keep me\nstatus: open\n---\n");
+    }
+
+    #[test]
+    fn test_set_keeps_quote_style() {
+        let content = "---\nexternal-ref: \"gh-1\"\nbranch: 'feat/a'\n---\n";
+
+        let updated = set_field(content, "external-ref", "gh-2").unwrap();
+        let updated = set_field(&updated, "branch", "it's").unwrap();
+
+        assert_eq!(updated, "---\nexternal-ref: \"gh-2\"\nbranch: 'it''s'\n---\n");
+    }
+
+    #[test]
+    fn test_set_quotes_unsafe_plain_values() {
+        let content = "---\nid: t-1\n---\n";
+        let updated = set_field(content, "branch", "fix: thing #1").unwrap();
+        assert_eq!(updated, "---\nid: t-1\nbranch: \"fix: thing #1\"\n---\n");
+    }
+
+    #[test]
+    fn test_set_preserves_crlf() {
+        let content = "---\r\nid: t-1\r\nstatus: open\r\n---\r\nBody\r\n";
+        let updated = set_field(content, "status", "closed").unwrap();
+        assert_eq!(updated, "---\r\nid: t-1\r\nstatus: closed\r\n---\r\nBody\r\n");
+    }
+
+    #[test]
+    fn test_set_list() {
+        let content = "---\nid: t-1\nexternal-ref: jira-7  # tracker\n---\n";
+        let refs = vec!["jira-7".to_string(), "gh-42".to_string(), "a,b".to_string()];
+        let updated = set_list(content, "external-ref", &refs).unwrap();
+        assert_eq!(updated, "---\nid: t-1\nexternal-ref: [jira-7, gh-42, \"a,b\"]  # tracker\n---\n");
+    }
+
+    #[test]
+    fn test_rename_value() {
+        let content = "---\nid: t-2\ndeps: [t-1, t-10]\nlinks:\n  - \"t-1\"  # see t-1\n  - t-11\nparent: t-1\n---\nt-1\n";
+
+        let updated = rename_value(content, "deps", "t-1", "x-1").unwrap();
+        let updated = rename_value(&updated, "links", "t-1", "x-1").unwrap();
+        let updated = rename_value(&updated, "parent", "t-1", "x-1").unwrap();
+
+        assert_eq!(
+            updated,
+            "---\nid: t-2\ndeps: [x-1, t-10]\nlinks:\n  - \"x-1\"  # see t-1\n  - t-11\nparent: x-1\n---\nt-1\n"
+        );
+    }
+
+    #[test]
+    fn test_remove_field() {
+        let content = "---\nid: t-1\ndeps:\n- a\n- b\nstatus: open\n---\n";
+        assert_eq!(remove_field(content, "deps").unwrap(), "---\nid: t-1\nstatus: open\n---\n");
+        assert_eq!(remove_field(content, "missing").unwrap(), content);
+        assert!(remove_field("no frontmatter", "id").is_none());
+    }
+
+    #[test]
+    fn test_value_position() {
+        let content = "---\nid: t-1\npriority:   high\ntags:\n- a\n---\n";
+        assert_eq!(yaml_source(content).unwrap(), "id: t-1\npriority:   high\ntags:\n- a");
+        assert_eq!(value_position(content, "priority"), Some((3, 13)));
+        assert_eq!(value_position(content, "tags"), Some((4, 6)));
+        assert_eq!(value_position(content, "missing"), None);
+    }
+}
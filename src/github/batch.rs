@@ -0,0 +1,364 @@
+use anyhow::Result;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use super::client::{GitHubClient, GraphQLError};
+
+/// Most aliased fields sent in one request; bigger batches are split
+pub const MAX_BATCH_FIELDS: usize = 50;
+
+/// Whether a batch reads or writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Query,
+    Mutation,
+}
+
+/// A GraphQL request made of many aliased copies of one field
+///
+/// Each item becomes `{prefix}_{key}: {field}({args}) {selection}`. Items may
+/// have their own variable (`$input_3`), and the batch may share variables
+/// (`$owner`) and wrap its fields in a scope like `repository(...)`.
+#[derive(Debug, Clone)]
+pub struct Batch {
+    operation: Operation,
+    prefix: &'static str,
+    field: &'static str,
+    selection: &'static str,
+    /// Input type for mutation items' `$input_N`
+    input_type: Option<&'static str>,
+    scope: Option<&'static str>,
+    shared: Vec<(&'static str, &'static str, Value)>,
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    key: String,
+    args: String,
+    var: Option<(String, &'static str, Value)>,
+}
+
+impl Batch {
+    /// A query batch of `field` with the given selection set
+    pub fn query(prefix: &'static str, field: &'static str, selection: &'static str) -> Self {
+        Self::new(Operation::Query, prefix, field, selection, None)
+    }
+
+    /// A mutation batch whose items each take an `input: $input_N`
+    pub fn mutation(
+        prefix: &'static str,
+        field: &'static str,
+        input_type: &'static str,
+        selection: &'static str,
+    ) -> Self {
+        Self::new(Operation::Mutation, prefix, field, selection, Some(input_type))
+    }
+
+    fn new(
+        operation: Operation,
+        prefix: &'static str,
+        field: &'static str,
+        selection: &'static str,
+        input_type: Option<&'static str>,
+    ) -> Self {
+        assert!(is_name(prefix) && !prefix.starts_with("__"), "invalid batch alias prefix: {}", prefix);
+        assert!(is_name(field), "invalid batch field: {}", field);
+        Self {
+            operation,
+            prefix,
+            field,
+            selection,
+            input_type,
+            scope: None,
+            shared: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Nest the aliased fields inside a field, e.g.
+    /// `repository(owner: $owner, name: $name)`
+    pub fn scoped(mut self, scope: &'static str) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Declare a variable every item can use, sent with each request
+    pub fn shared(mut self, name: &'static str, var_type: &'static str, value: Value) -> Self {
+        assert!(is_name(name), "invalid variable name: {}", name);
+        self.shared.push((name, var_type, value));
+        self
+    }
+
+    /// Add a mutation item, aliased by its position
+    pub fn input(&mut self, input: Value) {
+        let input_type = self.input_type.expect("input items need a mutation batch");
+        let key = self.items.len().to_string();
+        self.items.push(Item {
+            args: format!("input: $input_{}", key),
+            var: Some((format!("input_{}", key), input_type, input)),
+            key,
+        });
+    }
+
+    /// Add an item with its own variable, aliased by its position
+    ///
+    /// `{}` in `args` stands for the variable, which is named `var` plus the
+    /// position (`$q_2`).
+    pub fn with_var(&mut self, var: &'static str, var_type: &'static str, value: Value, args: &str) {
+        assert!(is_name(var), "invalid variable name: {}", var);
+        let key = self.items.len().to_string();
+        let name = format!("{}_{}", var, key);
+        self.items.push(Item {
+            args: args.replace("{}", &format!("${}", name)),
+            var: Some((name, var_type, value)),
+            key,
+        });
+    }
+
+    /// Add an item with literal arguments, aliased by `key`
+    ///
+    /// A key that's already in the batch is skipped.
+    pub fn keyed(&mut self, key: impl Display, args: impl Into<String>) {
+        let key = key.to_string();
+        assert!(key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_'), "invalid batch key: {}", key);
+        if self.items.iter().any(|item| item.key == key) {
+            return;
+        }
+        self.items.push(Item {
+            key,
+            args: args.into(),
+            var: None,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The alias an item's result comes back under
+    pub fn alias(&self, key: impl Display) -> String {
+        format!("{}_{}", self.prefix, key)
+    }
+
+    /// The requests to send: documents and their variables, at most
+    /// `MAX_BATCH_FIELDS` items each
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        self.requests_of(MAX_BATCH_FIELDS)
+    }
+
+    /// The requests to send, at most `size` items each
+    fn requests_of(&self, size: usize) -> Vec<(String, Value)> {
+        self.items.chunks(size).map(|chunk| self.request(chunk)).collect()
+    }
+
+    fn request(&self, items: &[Item]) -> (String, Value) {
+        let mut var_defs: Vec<String> = Vec::new();
+        let mut variables = Map::new();
+        for (name, var_type, value) in &self.shared {
+            var_defs.push(format!("${}: {}", name, var_type));
+            variables.insert(name.to_string(), value.clone());
+        }
+        for (name, var_type, value) in items.iter().filter_map(|item| item.var.as_ref()) {
+            var_defs.push(format!("${}: {}", name, var_type));
+            variables.insert(name.clone(), value.clone());
+        }
+
+        let indent = if self.scope.is_some() { "    " } else { "  " };
+        let fields: Vec<String> = items
+            .iter()
+            .map(|item| {
+                let args = if item.args.is_empty() { String::new() } else { format!("({})", item.args) };
+                format!("{}{}: {}{} {}", indent, self.alias(&item.key), self.field, args, self.selection)
+            })
+            .collect();
+        let mut body = fields.join("\n");
+        if let Some(scope) = self.scope {
+            body = format!("  {} {{\n{}\n  }}", scope, body);
+        }
+
+        let operation = match self.operation {
+            Operation::Query => "query",
+            Operation::Mutation => "mutation",
+        };
+        let header = if var_defs.is_empty() {
+            operation.to_string()
+        } else {
+            format!("{}({})", operation, var_defs.join(", "))
+        };
+        (format!("{} {{\n{}\n}}", header, body), Value::Object(variables))
+    }
+
+    /// The field name of the scope, where results are nested
+    fn scope_field(&self) -> Option<&str> {
+        self.scope.map(|scope| scope.split(['(', ' ']).next().unwrap_or(scope))
+    }
+}
+
+/// Results of a batch, looked up by item key
+#[derive(Debug, Default)]
+pub struct BatchResponse {
+    prefix: &'static str,
+    data: Map<String, Value>,
+    /// GitHub's error messages for items that failed, by alias
+    errors: HashMap<String, String>,
+}
+
+impl BatchResponse {
+    /// An item's result as returned, which may be null
+    pub fn get(&self, key: impl Display) -> Option<&Value> {
+        self.data.get(&format!("{}_{}", self.prefix, key))
+    }
+
+    /// GitHub's error for an item, if it failed
+    pub fn error(&self, key: impl Display) -> Option<&str> {
+        self.errors.get(&format!("{}_{}", self.prefix, key)).map(String::as_str)
+    }
+
+    /// Why an item failed: `what`, followed by GitHub's error if there is one
+    pub fn failure(&self, key: impl Display, what: &str) -> String {
+        match self.error(key) {
+            Some(message) => format!("{}: {}", what, message),
+            None => what.to_string(),
+        }
+    }
+}
+
+impl GitHubClient {
+    /// Send a batch, one request per chunk, and gather the results
+    ///
+    /// With `with_pace`, mutation batches are sent an item at a time.
+    ///
+    /// Errors GitHub reports against a single item are kept for that item
+    /// (see `BatchResponse::error`) so the rest of the batch still counts;
+    /// any other error fails the whole batch.
+    pub async fn run_batch(&self, batch: &Batch) -> Result<BatchResponse> {
+        let mut response = BatchResponse {
+            prefix: batch.prefix,
+            ..Default::default()
+        };
+        // Paced mutations go one at a time, so each waits its turn
+        let size = if self.paced() && batch.operation == Operation::Mutation { 1 } else { MAX_BATCH_FIELDS };
+        for (chunk, (document, variables)) in batch.items.chunks(size).zip(batch.requests_of(size)) {
+            let (mut data, errors) = self.query_partial(&document, Some(variables)).await?;
+            let aliases: HashSet<String> = chunk.iter().map(|item| batch.alias(&item.key)).collect();
+            let (item_errors, other): (Vec<_>, Vec<_>) = errors
+                .into_iter()
+                .map(|error| (error_alias(&error, &aliases), error))
+                .partition(|(alias, _)| alias.is_some());
+            if !other.is_empty() {
+                let messages: Vec<String> = other.iter().map(|(_, e)| e.to_string()).collect();
+                anyhow::bail!("GitHub GraphQL errors:\n  {}", messages.join("\n  "));
+            }
+            for (alias, error) in item_errors {
+                let message = response.errors.entry(alias.unwrap_or_default()).or_default();
+                if !message.is_empty() {
+                    message.push_str("; ");
+                }
+                message.push_str(&error.message);
+            }
+
+            if let Some(scope) = batch.scope_field() {
+                data = data.get_mut(scope).map(Value::take).unwrap_or(Value::Null);
+            }
+            if let Value::Object(fields) = data {
+                response.data.extend(fields);
+            }
+        }
+        Ok(response)
+    }
+}
+
+/// The item alias an error's path points into, e.g. `create_3` for
+/// `["create_3", "issue"]` or `["repository", "issue_7"]`
+fn error_alias(error: &GraphQLError, aliases: &HashSet<String>) -> Option<String> {
+    error
+        .path
+        .iter()
+        .filter_map(Value::as_str)
+        .find(|segment| aliases.contains(*segment))
+        .map(str::to_string)
+}
+
+/// A valid GraphQL name: `[_A-Za-z][_0-9A-Za-z]*`
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_mutation_batch_document() {
+        let mut batch = Batch::mutation("close", "closeIssue", "CloseIssueInput!", "{ issue { id } }");
+        batch.input(json!({ "issueId": "I_1" }));
+        batch.input(json!({ "issueId": "I_2" }));
+
+        let requests = batch.requests();
+        assert_eq!(requests.len(), 1);
+        let (document, variables) = &requests[0];
+        assert_eq!(
+            document,
+            "mutation($input_0: CloseIssueInput!, $input_1: CloseIssueInput!) {\n\
+             \x20 close_0: closeIssue(input: $input_0) { issue { id } }\n\
+             \x20 close_1: closeIssue(input: $input_1) { issue { id } }\n}"
+        );
+        assert_eq!(variables, &json!({ "input_0": { "issueId": "I_1" }, "input_1": { "issueId": "I_2" } }));
+        assert_eq!(batch.alias(1), "close_1");
+    }
+
+    #[test]
+    fn test_scoped_query_with_shared_and_item_variables() {
+        let mut batch = Batch::query("issue", "issue", "{ updatedAt }")
+            .scoped("repository(owner: $owner, name: $name)")
+            .shared("owner", "String!", json!("o"))
+            .shared("name", "String!", json!("r"));
+        batch.keyed(7, "number: 7");
+        batch.keyed(7, "number: 7");
+        assert_eq!(batch.len(), 1);
+        assert_eq!(
+            batch.requests()[0].0,
+            "query($owner: String!, $name: String!) {\n\
+             \x20 repository(owner: $owner, name: $name) {\n\
+             \x20   issue_7: issue(number: 7) { updatedAt }\n\
+             \x20 }\n}"
+        );
+        assert_eq!(batch.scope_field(), Some("repository"));
+
+        let mut search = Batch::query("count", "search", "{ issueCount }");
+        search.with_var("q", "String!", json!("is:open"), "query: {}, type: ISSUE, first: 0");
+        assert_eq!(
+            search.requests()[0].0,
+            "query($q_0: String!) {\n  count_0: search(query: $q_0, type: ISSUE, first: 0) { issueCount }\n}"
+        );
+    }
+
+    #[test]
+    fn test_large_batches_are_split_with_stable_aliases() {
+        let mut batch = Batch::mutation("add", "addProjectV2ItemById", "AddProjectV2ItemByIdInput!", "{ item { id } }");
+        for i in 0..MAX_BATCH_FIELDS + 3 {
+            batch.input(json!({ "contentId": format!("I_{}", i) }));
+        }
+        let requests = batch.requests();
+        assert_eq!(requests.len(), 2);
+        let (document, variables) = &requests[1];
+        assert!(document.starts_with(&format!("mutation($input_{}: ", MAX_BATCH_FIELDS)));
+        assert!(document.contains(&format!("add_{}: ", MAX_BATCH_FIELDS + 2)));
+        assert_eq!(variables.as_object().unwrap().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid batch alias prefix")]
+    fn test_rejects_reserved_prefix() {
+        Batch::query("__type", "issue", "{ id }");
+    }
+}
@@ -1,16 +1,77 @@
 use anyhow::{Context, Result};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::rest::RestApi;
+use crate::config::{ApiTransport, Config, NetworkConfig};
+use crate::timings::Recorder;
 
 const GITHUB_GRAPHQL_URL: &str = "https://api.github.com/graphql";
 
 /// GraphQL client for GitHub API
 #[derive(Clone)]
 pub struct GitHubClient {
-    client: reqwest::Client,
+    pub(super) client: reqwest::Client,
     #[allow(dead_code)]
     token: String,
     base_url: String,
+    /// Set when core issue operations go through REST (`[github] api = "rest"`)
+    pub(super) rest: Option<Arc<RestApi>>,
+    /// Set when mutations are spread out (`ttr push --pace`)
+    pacer: Option<Arc<Pacer>>,
+    /// Set when request latencies are recorded (`ttr push --timings`)
+    timings: Option<Recorder>,
+}
+
+/// Spaces mutations evenly, one per interval
+struct Pacer {
+    interval: Duration,
+    next: tokio::sync::Mutex<Instant>,
+}
+
+impl Pacer {
+    fn new(per_minute: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(60) / per_minute.max(1),
+            next: tokio::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait for the next free slot and take it
+    async fn wait(&self) {
+        let mut next = self.next.lock().await;
+        let now = Instant::now();
+        if *next > now {
+            tokio::time::sleep(*next - now).await;
+        }
+        *next = Instant::now().max(*next) + self.interval;
+    }
+}
+
+/// Optional features, which not every plan or GitHub Enterprise version has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub sub_issues: bool,
+    pub issue_types: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            sub_issues: true,
+            issue_types: true,
+        }
+    }
+}
+
+/// Whether an error says the schema lacks a field or type, as when a
+/// feature isn't available on the instance
+pub fn is_schema_error(err: &anyhow::Error) -> bool {
+    let message = err.to_string();
+    message.contains("doesn't exist on type") || message.contains("isn't a defined input type")
 }
 
 #[derive(Serialize)]
@@ -57,8 +118,72 @@ impl GitHubClient {
         Self::with_base_url(token, GITHUB_GRAPHQL_URL.to_string())
     }
 
+    /// Create a new GitHub client using the `[network]` settings from sync.toml
+    pub fn with_network(token: String, network: &NetworkConfig) -> Result<Self> {
+        Self::build(token, GITHUB_GRAPHQL_URL.to_string(), network)
+    }
+
+    /// Create a GitHub client for the `[network]` and `[github] api` settings
+    pub fn for_config(token: String, config: &Config) -> Result<Self> {
+        let client = Self::with_network(token, &config.network)?;
+        match config.github.api {
+            ApiTransport::Graphql => Ok(client),
+            ApiTransport::Rest => {
+                let (owner, name) = config.github.repo_parts()?;
+                Ok(client.with_rest(owner, name))
+            }
+        }
+    }
+
+    /// Send core issue operations for `owner/name` through the REST API
+    ///
+    /// The REST root is the GraphQL URL without its `/graphql` suffix.
+    pub fn with_rest(mut self, owner: &str, name: &str) -> Self {
+        let base = self.base_url.strip_suffix("/graphql").unwrap_or(&self.base_url);
+        self.rest = Some(Arc::new(RestApi::new(base, owner, name)));
+        self
+    }
+
+    /// Send at most `per_minute` mutations a minute, one per request
+    ///
+    /// Keeps huge pushes under GitHub's secondary rate limits on content
+    /// creation; queries aren't slowed down.
+    pub fn with_pace(mut self, per_minute: u32) -> Self {
+        self.pacer = Some(Arc::new(Pacer::new(per_minute)));
+        self
+    }
+
+    /// Record each request's latency in `recorder`
+    pub fn with_timings(mut self, recorder: Recorder) -> Self {
+        self.timings = Some(recorder);
+        self
+    }
+
+    /// Record a request's latency, if timing
+    pub(super) fn record_request(&self, started: Instant) {
+        if let Some(timings) = &self.timings {
+            timings.request(started.elapsed());
+        }
+    }
+
+    /// Whether mutations are paced, and so sent one per request
+    pub(super) fn paced(&self) -> bool {
+        self.pacer.is_some()
+    }
+
+    /// Wait for the pacer, if there is one, before a mutation
+    pub(super) async fn pace(&self) {
+        if let Some(pacer) = &self.pacer {
+            pacer.wait().await;
+        }
+    }
+
     /// Create a new GitHub client with a custom base URL (for testing)
     pub fn with_base_url(token: String, base_url: String) -> Result<Self> {
+        Self::build(token, base_url, &NetworkConfig::default())
+    }
+
+    fn build(token: String, base_url: String, network: &NetworkConfig) -> Result<Self> {
         let mut headers = HeaderMap::new();
         headers.insert(USER_AGENT, HeaderValue::from_static("ttr"));
         headers.insert(
@@ -67,12 +192,19 @@ impl GitHubClient {
                 .context("Invalid token format")?,
         );
 
-        let client = reqwest::Client::builder()
+        let client = http_client_builder(network)?
             .default_headers(headers)
             .build()
             .context("Failed to create HTTP client")?;
 
-        Ok(Self { client, token, base_url })
+        Ok(Self {
+            client,
+            token,
+            base_url,
+            rest: None,
+            pacer: None,
+            timings: None,
+        })
     }
 
     /// Execute a GraphQL query
@@ -81,47 +213,63 @@ impl GitHubClient {
         query: &str,
         variables: Option<serde_json::Value>,
     ) -> Result<T> {
-        let request = GraphQLRequest { query, variables };
+        let graphql_response: GraphQLResponse<T> = self.post_graphql(query, variables).await?;
 
-        let response = self
-            .client
-            .post(&self.base_url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send request to GitHub API")?;
-
-        let status = response.status();
-        if status == reqwest::StatusCode::UNAUTHORIZED {
-            anyhow::bail!("GitHub API authentication failed. Check your token.");
+        if let Some(errors) = graphql_response.errors {
+            let error_messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            anyhow::bail!("GitHub GraphQL errors:\n  {}", error_messages.join("\n  "));
         }
 
-        if status == reqwest::StatusCode::FORBIDDEN {
-            let text = response.text().await.unwrap_or_default();
-            if text.contains("rate limit") {
-                anyhow::bail!("GitHub API rate limit exceeded. Please wait and try again.");
+        graphql_response
+            .data
+            .ok_or_else(|| anyhow::anyhow!("No data in GitHub API response"))
+    }
+
+    /// Execute a GraphQL query that may partly fail, returning the data
+    /// alongside any errors
+    ///
+    /// Fails like `query` only when there's no data at all.
+    pub(super) async fn query_partial(
+        &self,
+        query: &str,
+        variables: Option<serde_json::Value>,
+    ) -> Result<(serde_json::Value, Vec<GraphQLError>)> {
+        let graphql_response: GraphQLResponse<serde_json::Value> = self.post_graphql(query, variables).await?;
+        let errors = graphql_response.errors.unwrap_or_default();
+        match graphql_response.data {
+            Some(data) if !data.is_null() => Ok((data, errors)),
+            _ if !errors.is_empty() => {
+                let error_messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                anyhow::bail!("GitHub GraphQL errors:\n  {}", error_messages.join("\n  "));
             }
-            anyhow::bail!("GitHub API forbidden: {}", text);
+            _ => anyhow::bail!("No data in GitHub API response"),
         }
+    }
 
-        if !status.is_success() {
-            let text = response.text().await.unwrap_or_default();
-            anyhow::bail!("GitHub API error ({}): {}", status, text);
+    async fn post_graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Option<serde_json::Value>,
+    ) -> Result<GraphQLResponse<T>> {
+        let request = GraphQLRequest { query, variables };
+        if query.trim_start().starts_with("mutation") {
+            self.pace().await;
         }
 
-        let graphql_response: GraphQLResponse<T> = response
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(&self.base_url)
+            .json(&request)
+            .send()
+            .await;
+        self.record_request(started);
+        let response = check_response(response).await?;
+
+        response
             .json()
             .await
-            .context("Failed to parse GitHub API response")?;
-
-        if let Some(errors) = graphql_response.errors {
-            let error_messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
-            anyhow::bail!("GitHub GraphQL errors:\n  {}", error_messages.join("\n  "));
-        }
-
-        graphql_response
-            .data
-            .ok_or_else(|| anyhow::anyhow!("No data in GitHub API response"))
+            .context("Failed to parse GitHub API response")
     }
 
     /// Execute a GraphQL mutation (same as query, just for semantic clarity)
@@ -133,6 +281,26 @@ impl GitHubClient {
         self.query(mutation, variables).await
     }
 
+    /// Which optional GitHub features this instance's schema has
+    ///
+    /// If the probe itself fails, everything is assumed available and errors
+    /// surface where the features are used.
+    pub async fn get_capabilities(&self) -> Capabilities {
+        let query = r#"
+            query {
+                subIssues: __type(name: "AddSubIssueInput") { name }
+                issueTypes: __type(name: "IssueType") { name }
+            }
+        "#;
+        match self.query::<serde_json::Value>(query, None).await {
+            Ok(schema) => Capabilities {
+                sub_issues: !schema["subIssues"].is_null(),
+                issue_types: !schema["issueTypes"].is_null(),
+            },
+            Err(_) => Capabilities::default(),
+        }
+    }
+
     /// Get the token (for debugging/testing)
     #[allow(dead_code)]
     pub fn token(&self) -> &str {
@@ -140,6 +308,88 @@ impl GitHubClient {
     }
 }
 
+/// Turn a failed request or an error status into an error
+pub(super) async fn check_response(
+    response: reqwest::Result<reqwest::Response>,
+) -> Result<reqwest::Response> {
+    let response = response.map_err(|e| match proxy_error(&e) {
+        Some(reason) => anyhow::anyhow!(
+            "Proxy refused to connect to GitHub ({}). Check [network] proxy or \
+             HTTPS_PROXY, including credentials, or exempt the host via NO_PROXY.",
+            reason
+        ),
+        None => anyhow::Error::new(e).context("Failed to send request to GitHub API"),
+    })?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        anyhow::bail!("GitHub API authentication failed. Check your token.");
+    }
+
+    if status == reqwest::StatusCode::FORBIDDEN {
+        let text = response.text().await.unwrap_or_default();
+        if text.contains("rate limit") {
+            anyhow::bail!("GitHub API rate limit exceeded. Please wait and try again.");
+        }
+        anyhow::bail!("GitHub API forbidden: {}", text);
+    }
+
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        anyhow::bail!("GitHub API error ({}): {}", status, text);
+    }
+
+    Ok(response)
+}
+
+/// An HTTP client builder with the `[network]` proxy and TLS settings
+///
+/// Also used for requests that don't go to GitHub, like chat webhooks.
+pub fn http_client_builder(network: &NetworkConfig) -> Result<reqwest::ClientBuilder> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(ref url) = network.proxy {
+        let proxy = reqwest::Proxy::all(url)
+            .with_context(|| format!("Invalid [network] proxy URL: {}", url))?
+            .no_proxy(reqwest::NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+    if let Some(ref path) = network.ca_bundle {
+        for cert in load_ca_bundle(path)? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    if network.danger_accept_invalid_certs {
+        eprintln!("WARN    TLS certificate verification is disabled ([network] danger_accept_invalid_certs)");
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    Ok(builder)
+}
+
+/// Read the root certificates from a PEM bundle
+fn load_ca_bundle(path: &Path) -> Result<Vec<reqwest::Certificate>> {
+    let pem = std::fs::read(path)
+        .with_context(|| format!("Failed to read [network] ca_bundle: {}", path.display()))?;
+    let certs = reqwest::Certificate::from_pem_bundle(&pem)
+        .with_context(|| format!("Invalid certificate in {}", path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("No certificates found in {}", path.display());
+    }
+    Ok(certs)
+}
+
+/// The proxy's reason, if a request failed because the proxy rejected the CONNECT
+fn proxy_error(err: &reqwest::Error) -> Option<String> {
+    let mut source = std::error::Error::source(err);
+    while let Some(e) = source {
+        let message = e.to_string();
+        if message.starts_with("tunnel error") {
+            return Some(message);
+        }
+        source = e.source();
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +400,36 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_invalid_proxy_url() {
+        let network = NetworkConfig {
+            proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        let err = GitHubClient::with_network("test_token".to_string(), &network).err().unwrap();
+        assert!(err.to_string().contains("Invalid [network] proxy URL"));
+    }
+
+    #[test]
+    fn test_ca_bundle_without_certificates() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("ca.pem");
+        std::fs::write(&path, "not a certificate\n").unwrap();
+
+        let network = NetworkConfig {
+            ca_bundle: Some(path.clone()),
+            ..Default::default()
+        };
+        assert!(GitHubClient::with_network("test_token".to_string(), &network).is_err());
+
+        let network = NetworkConfig {
+            ca_bundle: Some(dir.path().join("missing.pem")),
+            ..Default::default()
+        };
+        let err = GitHubClient::with_network("test_token".to_string(), &network).err().unwrap();
+        assert!(err.to_string().contains("Failed to read [network] ca_bundle"));
+    }
+
     #[test]
     fn test_graphql_error_display() {
         let error = GraphQLError {
@@ -0,0 +1,331 @@
+use super::batch::Batch;
+use super::client::GitHubClient;
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// A repository's discussion category
+#[derive(Debug, Clone)]
+pub struct DiscussionCategory {
+    pub id: String,
+    pub name: String,
+}
+
+/// A discussion as it is on GitHub
+#[derive(Debug, Clone)]
+pub struct ExistingDiscussion {
+    pub id: String,
+    pub number: u64,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+}
+
+/// A created or updated discussion
+#[derive(Debug, Clone)]
+pub struct DiscussionInfo {
+    pub id: String,
+    pub number: u64,
+    pub url: String,
+}
+
+/// A top-level comment on a discussion
+#[derive(Debug, Clone)]
+pub struct DiscussionComment {
+    /// Login of the author, or "ghost" for deleted accounts
+    pub author: String,
+    pub body: String,
+    /// When it was posted (ISO 8601)
+    pub created_at: String,
+}
+
+// Response types for GraphQL queries
+
+#[derive(Deserialize)]
+struct CategoriesResponse {
+    repository: Option<CategoriesRepository>,
+}
+
+#[derive(Deserialize)]
+struct CategoriesRepository {
+    #[serde(rename = "discussionCategories")]
+    discussion_categories: CategoryConnection,
+}
+
+#[derive(Deserialize)]
+struct CategoryConnection {
+    nodes: Vec<CategoryNode>,
+}
+
+#[derive(Deserialize)]
+struct CategoryNode {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct DiscussionNode {
+    id: String,
+    number: u64,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    body: String,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct CreateDiscussionResponse {
+    #[serde(rename = "createDiscussion")]
+    create_discussion: Option<DiscussionPayload>,
+}
+
+#[derive(Deserialize)]
+struct UpdateDiscussionResponse {
+    #[serde(rename = "updateDiscussion")]
+    update_discussion: Option<DiscussionPayload>,
+}
+
+#[derive(Deserialize)]
+struct DiscussionPayload {
+    discussion: Option<DiscussionNode>,
+}
+
+#[derive(Deserialize)]
+struct CommentsResponse {
+    repository: Option<CommentsRepository>,
+}
+
+#[derive(Deserialize)]
+struct CommentsRepository {
+    discussion: Option<CommentsDiscussion>,
+}
+
+#[derive(Deserialize)]
+struct CommentsDiscussion {
+    comments: CommentConnection,
+}
+
+#[derive(Deserialize)]
+struct CommentConnection {
+    nodes: Vec<CommentNode>,
+}
+
+#[derive(Deserialize)]
+struct CommentNode {
+    author: Option<CommentAuthor>,
+    body: String,
+    #[serde(rename = "createdAt")]
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+struct CommentAuthor {
+    login: String,
+}
+
+impl DiscussionNode {
+    fn into_info(self) -> DiscussionInfo {
+        DiscussionInfo {
+            id: self.id,
+            number: self.number,
+            url: self.url,
+        }
+    }
+}
+
+impl GitHubClient {
+    /// Get a repository's discussion categories
+    pub async fn get_discussion_categories(&self, owner: &str, name: &str) -> Result<Vec<DiscussionCategory>> {
+        let query = r#"
+            query($owner: String!, $name: String!) {
+                repository(owner: $owner, name: $name) {
+                    discussionCategories(first: 25) {
+                        nodes {
+                            id
+                            name
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "owner": owner,
+            "name": name
+        });
+
+        let response: CategoriesResponse = self.query(query, Some(variables)).await?;
+        let repository = response
+            .repository
+            .ok_or_else(|| anyhow::anyhow!("Repository {}/{} not found", owner, name))?;
+
+        Ok(repository
+            .discussion_categories
+            .nodes
+            .into_iter()
+            .map(|c| DiscussionCategory { id: c.id, name: c.name })
+            .collect())
+    }
+
+    /// Get multiple discussions by number in a single request
+    ///
+    /// Missing discussions are left out of the map.
+    pub async fn get_discussions_batch(
+        &self,
+        owner: &str,
+        name: &str,
+        numbers: &[u64],
+    ) -> Result<HashMap<u64, ExistingDiscussion>> {
+        if numbers.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut batch = Batch::query("discussion", "discussion", "{ id number title body url }")
+            .scoped("repository(owner: $owner, name: $name)")
+            .shared("owner", "String!", json!(owner))
+            .shared("name", "String!", json!(name));
+        for n in numbers {
+            batch.keyed(n, format!("number: {}", n));
+        }
+
+        let response = self.run_batch(&batch).await?;
+
+        let mut results = HashMap::new();
+        for number in numbers {
+            let Some(node) = response.get(number) else {
+                continue;
+            };
+            if let Ok(d) = DiscussionNode::deserialize(node) {
+                results.insert(
+                    *number,
+                    ExistingDiscussion {
+                        id: d.id,
+                        number: d.number,
+                        title: d.title,
+                        body: d.body,
+                        url: d.url,
+                    },
+                );
+            }
+        }
+        Ok(results)
+    }
+
+    /// Start a discussion in a category
+    pub async fn create_discussion(
+        &self,
+        repository_id: &str,
+        category_id: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<DiscussionInfo> {
+        let mutation = r#"
+            mutation($input: CreateDiscussionInput!) {
+                createDiscussion(input: $input) {
+                    discussion {
+                        id
+                        number
+                        url
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "input": {
+                "repositoryId": repository_id,
+                "categoryId": category_id,
+                "title": title,
+                "body": body
+            }
+        });
+
+        let response: CreateDiscussionResponse = self.mutate(mutation, Some(variables)).await?;
+        response
+            .create_discussion
+            .and_then(|p| p.discussion)
+            .map(DiscussionNode::into_info)
+            .ok_or_else(|| anyhow::anyhow!("Failed to create discussion"))
+    }
+
+    /// Update a discussion's title and body
+    pub async fn update_discussion(&self, discussion_id: &str, title: &str, body: &str) -> Result<DiscussionInfo> {
+        let mutation = r#"
+            mutation($input: UpdateDiscussionInput!) {
+                updateDiscussion(input: $input) {
+                    discussion {
+                        id
+                        number
+                        url
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "input": {
+                "discussionId": discussion_id,
+                "title": title,
+                "body": body
+            }
+        });
+
+        let response: UpdateDiscussionResponse = self.mutate(mutation, Some(variables)).await?;
+        response
+            .update_discussion
+            .and_then(|p| p.discussion)
+            .map(DiscussionNode::into_info)
+            .ok_or_else(|| anyhow::anyhow!("Failed to update discussion {}", discussion_id))
+    }
+
+    /// Get a discussion's top-level comments, oldest first
+    pub async fn get_discussion_comments(
+        &self,
+        owner: &str,
+        name: &str,
+        number: u64,
+    ) -> Result<Vec<DiscussionComment>> {
+        let query = r#"
+            query($owner: String!, $name: String!, $number: Int!) {
+                repository(owner: $owner, name: $name) {
+                    discussion(number: $number) {
+                        comments(first: 100) {
+                            nodes {
+                                author {
+                                    login
+                                }
+                                body
+                                createdAt
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "owner": owner,
+            "name": name,
+            "number": number
+        });
+
+        let response: CommentsResponse = self.query(query, Some(variables)).await?;
+        let discussion = response
+            .repository
+            .and_then(|r| r.discussion)
+            .ok_or_else(|| anyhow::anyhow!("Discussion #{} not found", number))?;
+
+        Ok(discussion
+            .comments
+            .nodes
+            .into_iter()
+            .map(|c| DiscussionComment {
+                author: c.author.map_or_else(|| "ghost".to_string(), |a| a.login),
+                body: c.body,
+                created_at: c.created_at,
+            })
+            .collect())
+    }
+}
@@ -1,6 +1,7 @@
+use super::batch::Batch;
 use super::client::GitHubClient;
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 
@@ -13,7 +14,7 @@ pub struct IssueInfo {
 }
 
 /// Information about an existing issue
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExistingIssue {
     pub id: String,
     pub number: u64,
@@ -21,6 +22,37 @@ pub struct ExistingIssue {
     pub body: String,
     pub state: String,  // OPEN or CLOSED
     pub url: String,
+    /// ISO 8601 timestamp of the last change on GitHub
+    #[serde(default)]
+    pub updated_at: String,
+    /// ISO 8601 timestamp the issue was opened
+    #[serde(default)]
+    pub created_at: String,
+    /// ISO 8601 timestamp the issue was last closed, if it has been
+    #[serde(default)]
+    pub closed_at: Option<String>,
+    /// Who the issue is assigned to; only fetched by `get_issues_batch`
+    #[serde(default)]
+    pub assignees: Vec<IssueAssignee>,
+    /// Names of the issue's labels; only fetched by `get_issues_batch`
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// A user an issue is assigned to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueAssignee {
+    /// User node ID; the login over REST
+    pub id: String,
+    pub login: String,
+}
+
+/// Assignees to add to and remove from an issue, as user IDs
+#[derive(Debug, Clone)]
+pub struct AssigneeChange {
+    pub issue_id: String,
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
 }
 
 /// Request to update an issue
@@ -39,6 +71,8 @@ pub struct IssueCreate {
     pub body: String,
     pub label_ids: Vec<String>,
     pub issue_type_id: Option<String>,
+    /// Assignees for this issue, in place of the batch-wide ones
+    pub assignee_ids: Vec<String>,
 }
 
 /// Label information
@@ -67,6 +101,22 @@ struct RepositoryNode {
     id: String,
 }
 
+#[derive(Deserialize)]
+struct DefaultBranchResponse {
+    repository: Option<DefaultBranchRepository>,
+}
+
+#[derive(Deserialize)]
+struct DefaultBranchRepository {
+    #[serde(rename = "defaultBranchRef")]
+    default_branch_ref: Option<BranchRef>,
+}
+
+#[derive(Deserialize)]
+struct BranchRef {
+    name: String,
+}
+
 #[derive(Deserialize)]
 struct CreateIssueResponse {
     #[serde(rename = "createIssue")]
@@ -89,6 +139,31 @@ struct IssueNode {
     body: String,
     #[serde(default)]
     state: String,
+    #[serde(default, rename = "updatedAt")]
+    updated_at: String,
+    #[serde(default, rename = "createdAt")]
+    created_at: String,
+    #[serde(default, rename = "closedAt")]
+    closed_at: Option<String>,
+    /// Only selected by queries that filter on labels
+    #[serde(default)]
+    labels: LabelNames,
+}
+
+#[derive(Deserialize, Default)]
+struct LabelNames {
+    nodes: Vec<LabelName>,
+}
+
+#[derive(Deserialize)]
+struct LabelName {
+    name: String,
+}
+
+impl LabelNames {
+    fn names(self) -> Vec<String> {
+        self.nodes.into_iter().map(|n| n.name).collect()
+    }
 }
 
 #[derive(Deserialize)]
@@ -215,9 +290,15 @@ struct IssueTypeNode {
     name: String,
 }
 
+/// Most issues GitHub lets a repository pin
+pub const MAX_PINNED_ISSUES: usize = 3;
+
 impl GitHubClient {
     /// Get repository node ID
     pub async fn get_repository_id(&self, owner: &str, name: &str) -> Result<String> {
+        if let Some(rest) = &self.rest {
+            return self.rest_repository_id(rest).await;
+        }
         let query = r#"
             query($owner: String!, $name: String!) {
                 repository(owner: $owner, name: $name) {
@@ -239,8 +320,34 @@ impl GitHubClient {
             .ok_or_else(|| anyhow::anyhow!("Repository {}/{} not found", owner, name))
     }
 
+    /// Get the name of the repository's default branch (None if the repo is empty)
+    pub async fn get_default_branch(&self, owner: &str, name: &str) -> Result<Option<String>> {
+        let query = r#"
+            query($owner: String!, $name: String!) {
+                repository(owner: $owner, name: $name) {
+                    defaultBranchRef { name }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "owner": owner,
+            "name": name
+        });
+
+        let response: DefaultBranchResponse = self.query(query, Some(variables)).await?;
+
+        let repository = response
+            .repository
+            .ok_or_else(|| anyhow::anyhow!("Repository {}/{} not found", owner, name))?;
+        Ok(repository.default_branch_ref.map(|r| r.name))
+    }
+
     /// Get user node ID by username
     pub async fn get_user_id(&self, username: &str) -> Result<String> {
+        if let Some(rest) = &self.rest {
+            return self.rest_user_id(rest, username).await;
+        }
         let query = r#"
             query($login: String!) {
                 user(login: $login) {
@@ -262,6 +369,10 @@ impl GitHubClient {
     /// Get issue types for a repository (org-level feature)
     /// Returns empty vec for personal repos (which don't support issue types)
     pub async fn get_issue_types(&self, owner: &str, name: &str) -> Result<Vec<IssueTypeInfo>> {
+        // Issue types have no REST listing; REST creates go without them
+        if self.rest.is_some() {
+            return Ok(Vec::new());
+        }
         let query = r#"
             query($owner: String!, $name: String!) {
                 repository(owner: $owner, name: $name) {
@@ -280,7 +391,12 @@ impl GitHubClient {
             "name": name
         });
 
-        let response: GetIssueTypesResponse = self.query(query, Some(variables)).await?;
+        let response: GetIssueTypesResponse = match self.query(query, Some(variables)).await {
+            Ok(response) => response,
+            // Instances without issue types have no `issueTypes` field
+            Err(e) if super::client::is_schema_error(&e) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
 
         let types = response
             .repository
@@ -355,44 +471,24 @@ impl GitHubClient {
         creates: &[IssueCreate],
         assignee_ids: Option<&[String]>,
     ) -> Result<Vec<Result<IssueInfo, String>>> {
+        if let Some(rest) = &self.rest {
+            return Ok(self.rest_create_issues(rest, creates, assignee_ids).await);
+        }
         if creates.is_empty() {
             return Ok(Vec::new());
         }
 
-        // Build dynamic mutation with aliases
-        let mutations: Vec<String> = creates
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
-                format!(
-                    "create_{i}: createIssue(input: $input_{i}) {{ issue {{ id number url }} }}"
-                )
-            })
-            .collect();
-
-        // Build variable definitions
-        let var_defs: Vec<String> = creates
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$input_{}: CreateIssueInput!", i))
-            .collect();
-
-        let mutation = format!(
-            "mutation({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            mutations.join("\n  ")
-        );
-
-        // Build variables object
-        let mut variables = serde_json::Map::new();
-        for (i, create) in creates.iter().enumerate() {
+        let mut batch = Batch::mutation("create", "createIssue", "CreateIssueInput!", "{ issue { id number url } }");
+        for create in creates {
             let mut input = json!({
                 "repositoryId": repo_id,
                 "title": create.title,
                 "body": create.body
             });
 
-            if let Some(ids) = assignee_ids {
+            if !create.assignee_ids.is_empty() {
+                input["assigneeIds"] = json!(create.assignee_ids);
+            } else if let Some(ids) = assignee_ids {
                 if !ids.is_empty() {
                     input["assigneeIds"] = json!(ids);
                 }
@@ -406,17 +502,14 @@ impl GitHubClient {
                 input["issueTypeId"] = json!(type_id);
             }
 
-            variables.insert(format!("input_{}", i), input);
+            batch.input(input);
         }
 
-        let response: serde_json::Value = self
-            .mutate(&mutation, Some(serde_json::Value::Object(variables)))
-            .await?;
+        let response = self.run_batch(&batch).await?;
 
         let mut results = Vec::with_capacity(creates.len());
         for i in 0..creates.len() {
-            let key = format!("create_{}", i);
-            if let Some(data) = response.get(&key) {
+            if let Some(data) = response.get(i) {
                 if let Some(issue) = data.get("issue") {
                     if let (Some(id), Some(number), Some(url)) = (
                         issue.get("id").and_then(|v| v.as_str()),
@@ -432,7 +525,7 @@ impl GitHubClient {
                     }
                 }
             }
-            results.push(Err(format!("Failed to create issue {}", i)));
+            results.push(Err(response.failure(i, "Failed to create issue")));
         }
 
         Ok(results)
@@ -455,6 +548,9 @@ impl GitHubClient {
                         body
                         state
                         url
+                        updatedAt
+                        createdAt
+                        closedAt
                     }
                 }
             }
@@ -480,6 +576,11 @@ impl GitHubClient {
             body: issue.body,
             state: issue.state,
             url: issue.url,
+            updated_at: issue.updated_at,
+            created_at: issue.created_at,
+            closed_at: issue.closed_at,
+            assignees: Vec::new(),
+            labels: Vec::new(),
         })
     }
 
@@ -491,68 +592,361 @@ impl GitHubClient {
         name: &str,
         numbers: &[u64],
     ) -> Result<std::collections::HashMap<u64, ExistingIssue>> {
+        if let Some(rest) = &self.rest {
+            return self.rest_issues(rest, numbers).await;
+        }
         if numbers.is_empty() {
             return Ok(std::collections::HashMap::new());
         }
 
-        // Build a dynamic query with aliases for each issue
-        // e.g., issue_1: issue(number: 1) { ... }
-        let issue_fields = "id number title body state url";
-        let issue_queries: Vec<String> = numbers
-            .iter()
-            .map(|n| format!("issue_{}: issue(number: {}) {{ {} }}", n, n, issue_fields))
-            .collect();
+        // One aliased field per issue, e.g. issue_1: issue(number: 1) { ... }
+        let mut batch = Batch::query(
+            "issue",
+            "issue",
+            "{ id number title body state url updatedAt createdAt closedAt assignees(first: 20) { nodes { id login } } labels(first: 50) { nodes { name } } }",
+        )
+            .scoped("repository(owner: $owner, name: $name)")
+            .shared("owner", "String!", json!(owner))
+            .shared("name", "String!", json!(name));
+        for n in numbers {
+            batch.keyed(n, format!("number: {}", n));
+        }
 
-        let query = format!(
-            r#"query($owner: String!, $name: String!) {{
-                repository(owner: $owner, name: $name) {{
-                    {}
-                }}
-            }}"#,
-            issue_queries.join("\n                    ")
-        );
+        let response = self.run_batch(&batch).await?;
 
-        let variables = json!({
-            "owner": owner,
-            "name": name
-        });
+        let mut results = std::collections::HashMap::new();
 
-        let response: serde_json::Value = self.query(&query, Some(variables)).await?;
+        for num in numbers {
+            if let Some(issue_data) = response.get(num) {
+                if !issue_data.is_null() {
+                    if let (Some(id), Some(title), Some(body), Some(state), Some(url)) = (
+                        issue_data.get("id").and_then(|v| v.as_str()),
+                        issue_data.get("title").and_then(|v| v.as_str()),
+                        issue_data.get("body").and_then(|v| v.as_str()),
+                        issue_data.get("state").and_then(|v| v.as_str()),
+                        issue_data.get("url").and_then(|v| v.as_str()),
+                    ) {
+                        results.insert(
+                            *num,
+                            ExistingIssue {
+                                id: id.to_string(),
+                                number: *num,
+                                title: title.to_string(),
+                                body: body.to_string(),
+                                state: state.to_string(),
+                                url: url.to_string(),
+                                updated_at: issue_data
+                                    .get("updatedAt")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                created_at: issue_data
+                                    .get("createdAt")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                closed_at: issue_data
+                                    .get("closedAt")
+                                    .and_then(|v| v.as_str())
+                                    .map(str::to_string),
+                                assignees: issue_data
+                                    .pointer("/assignees/nodes")
+                                    .cloned()
+                                    .and_then(|nodes| serde_json::from_value(nodes).ok())
+                                    .unwrap_or_default(),
+                                labels: issue_data
+                                    .pointer("/labels/nodes")
+                                    .and_then(|nodes| nodes.as_array())
+                                    .map(|nodes| {
+                                        nodes
+                                            .iter()
+                                            .filter_map(|n| n.get("name").and_then(|v| v.as_str()))
+                                            .map(str::to_string)
+                                            .collect()
+                                    })
+                                    .unwrap_or_default(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
 
-        let mut results = std::collections::HashMap::new();
+        Ok(results)
+    }
+
+    /// Every open issue in a repository, optionally only those with a label
+    ///
+    /// Fetched 100 at a time; assignees are left out.
+    pub async fn list_open_issues(&self, owner: &str, name: &str, label: Option<&str>) -> Result<Vec<ExistingIssue>> {
+        if let Some(rest) = &self.rest {
+            return self.rest_open_issues(rest, label).await;
+        }
 
-        if let Some(repo) = response.get("repository") {
-            for num in numbers {
-                let key = format!("issue_{}", num);
-                if let Some(issue_data) = repo.get(&key) {
-                    if !issue_data.is_null() {
-                        if let (Some(id), Some(title), Some(body), Some(state), Some(url)) = (
-                            issue_data.get("id").and_then(|v| v.as_str()),
-                            issue_data.get("title").and_then(|v| v.as_str()),
-                            issue_data.get("body").and_then(|v| v.as_str()),
-                            issue_data.get("state").and_then(|v| v.as_str()),
-                            issue_data.get("url").and_then(|v| v.as_str()),
-                        ) {
-                            results.insert(
-                                *num,
-                                ExistingIssue {
-                                    id: id.to_string(),
-                                    number: *num,
-                                    title: title.to_string(),
-                                    body: body.to_string(),
-                                    state: state.to_string(),
-                                    url: url.to_string(),
-                                },
-                            );
+        let query = r#"
+            query($owner: String!, $name: String!, $labels: [String!], $after: String) {
+                repository(owner: $owner, name: $name) {
+                    issues(first: 100, after: $after, states: [OPEN], labels: $labels) {
+                        pageInfo {
+                            hasNextPage
+                            endCursor
+                        }
+                        nodes {
+                            id number title body state url updatedAt createdAt closedAt
                         }
                     }
                 }
             }
+        "#;
+
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let variables = json!({
+                "owner": owner,
+                "name": name,
+                "labels": label.map(|l| vec![l]),
+                "after": after
+            });
+
+            let response: serde_json::Value = self.query(query, Some(variables)).await?;
+            let page = response
+                .get("repository")
+                .and_then(|r| r.get("issues"))
+                .ok_or_else(|| anyhow::anyhow!("Repository {}/{} not found", owner, name))?;
+
+            if let Some(nodes) = page.get("nodes").and_then(|n| n.as_array()) {
+                for node in nodes {
+                    let Ok(node) = serde_json::from_value::<IssueNode>(node.clone()) else {
+                        continue;
+                    };
+                    issues.push(ExistingIssue {
+                        id: node.id,
+                        number: node.number,
+                        title: node.title,
+                        body: node.body,
+                        state: node.state,
+                        url: node.url,
+                        updated_at: node.updated_at,
+                        created_at: node.created_at,
+                        closed_at: node.closed_at,
+                        assignees: Vec::new(),
+                        labels: Vec::new(),
+                    });
+                }
+            }
+
+            let page_info = page.get("pageInfo");
+            let has_next = page_info
+                .and_then(|p| p.get("hasNextPage"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            after = page_info
+                .and_then(|p| p.get("endCursor"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+
+            if !has_next || after.is_none() {
+                break;
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Get the last-updated timestamp of multiple issues in a single request
+    ///
+    /// Much lighter than `get_issues_batch`; used to decide which cached
+    /// issues need refetching. Missing issues are left out of the map.
+    pub async fn get_issues_updated_at(
+        &self,
+        owner: &str,
+        name: &str,
+        numbers: &[u64],
+    ) -> Result<HashMap<u64, String>> {
+        // REST has no lighter lookup than the issue itself
+        if let Some(rest) = &self.rest {
+            let issues = self.rest_issues(rest, numbers).await?;
+            return Ok(issues.into_iter().map(|(n, issue)| (n, issue.updated_at)).collect());
+        }
+        if numbers.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut batch = Batch::query("issue", "issue", "{ updatedAt }")
+            .scoped("repository(owner: $owner, name: $name)")
+            .shared("owner", "String!", json!(owner))
+            .shared("name", "String!", json!(name));
+        for n in numbers {
+            batch.keyed(n, format!("number: {}", n));
+        }
+
+        let response = self.run_batch(&batch).await?;
+
+        let mut results = HashMap::new();
+        for num in numbers {
+            let updated_at = response
+                .get(num)
+                .and_then(|issue| issue.get("updatedAt"))
+                .and_then(|v| v.as_str());
+            if let Some(updated_at) = updated_at {
+                results.insert(*num, updated_at.to_string());
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Count 👍 reactions on multiple issues in a single request
+    ///
+    /// Reactions don't change an issue's `updatedAt`, so they aren't cached
+    /// with it. Missing issues are left out of the map.
+    pub async fn get_thumbs_up_counts(&self, owner: &str, name: &str, numbers: &[u64]) -> Result<HashMap<u64, u64>> {
+        if let Some(rest) = &self.rest {
+            return self.rest_thumbs_up(rest, numbers).await;
+        }
+        if numbers.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut batch = Batch::query("issue", "issue", "{ reactions(content: THUMBS_UP) { totalCount } }")
+            .scoped("repository(owner: $owner, name: $name)")
+            .shared("owner", "String!", json!(owner))
+            .shared("name", "String!", json!(name));
+        for n in numbers {
+            batch.keyed(n, format!("number: {}", n));
+        }
+
+        let response = self.run_batch(&batch).await?;
+
+        let mut results = HashMap::new();
+        for num in numbers {
+            let count = response
+                .get(num)
+                .and_then(|issue| issue.pointer("/reactions/totalCount"))
+                .and_then(|v| v.as_u64());
+            if let Some(count) = count {
+                results.insert(*num, count);
+            }
         }
 
         Ok(results)
     }
 
+    /// Issues created at or after `since` (RFC 3339), newest first
+    ///
+    /// Reads the repository's issue list rather than search, whose index lags
+    /// behind writes; pages stop once they reach older issues.
+    pub async fn list_issues_created_since(&self, owner: &str, name: &str, since: &str) -> Result<Vec<ExistingIssue>> {
+        let since = chrono::DateTime::parse_from_rfc3339(since)
+            .map_err(|e| anyhow::anyhow!("Invalid timestamp '{}': {}", since, e))?;
+        if let Some(rest) = &self.rest {
+            return self.rest_issues_created_since(rest, since).await;
+        }
+
+        let query = r#"
+            query($owner: String!, $name: String!, $after: String) {
+                repository(owner: $owner, name: $name) {
+                    issues(first: 50, after: $after, orderBy: {field: CREATED_AT, direction: DESC}) {
+                        pageInfo {
+                            hasNextPage
+                            endCursor
+                        }
+                        nodes {
+                            id number title body state url updatedAt createdAt closedAt
+                            labels(first: 50) { nodes { name } }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let variables = json!({ "owner": owner, "name": name, "after": after });
+            let response: serde_json::Value = self.query(query, Some(variables)).await?;
+            let page = response
+                .get("repository")
+                .and_then(|r| r.get("issues"))
+                .ok_or_else(|| anyhow::anyhow!("Repository {}/{} not found", owner, name))?;
+
+            let mut reached_older = false;
+            if let Some(nodes) = page.get("nodes").and_then(|n| n.as_array()) {
+                for node in nodes {
+                    let Ok(node) = serde_json::from_value::<IssueNode>(node.clone()) else {
+                        continue;
+                    };
+                    if created_before(&node.created_at, since) {
+                        reached_older = true;
+                        break;
+                    }
+                    issues.push(ExistingIssue {
+                        id: node.id,
+                        number: node.number,
+                        title: node.title,
+                        body: node.body,
+                        state: node.state,
+                        url: node.url,
+                        updated_at: node.updated_at,
+                        created_at: node.created_at,
+                        closed_at: node.closed_at,
+                        assignees: Vec::new(),
+                        labels: node.labels.names(),
+                    });
+                }
+            }
+
+            let page_info = page.get("pageInfo");
+            let has_next = page_info
+                .and_then(|p| p.get("hasNextPage"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            after = page_info
+                .and_then(|p| p.get("endCursor"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+
+            if reached_older || !has_next || after.is_none() {
+                break;
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Count each user's open issues in a repository
+    ///
+    /// Returns counts in the same order as `logins`.
+    pub async fn count_open_issues_assigned(&self, owner: &str, name: &str, logins: &[String]) -> Result<Vec<u64>> {
+        if let Some(rest) = &self.rest {
+            return self.rest_count_open_assigned(rest, logins).await;
+        }
+        if logins.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut batch = Batch::query("count", "search", "{ issueCount }");
+        for login in logins {
+            let q = format!("repo:{}/{} is:issue is:open assignee:{}", owner, name, login);
+            batch.with_var("q", "String!", json!(q), "query: {}, type: ISSUE, first: 0");
+        }
+
+        let response = self.run_batch(&batch).await?;
+
+        Ok((0..logins.len())
+            .map(|i| {
+                response
+                    .get(i)
+                    .and_then(|s| s.get("issueCount"))
+                    .and_then(|n| n.as_u64())
+                    .unwrap_or(0)
+            })
+            .collect())
+    }
+
     /// Update an existing issue
     pub async fn update_issue(
         &self,
@@ -600,37 +994,15 @@ impl GitHubClient {
         &self,
         updates: &[IssueUpdate],
     ) -> Result<HashMap<String, Result<IssueInfo, String>>> {
+        if let Some(rest) = &self.rest {
+            return Ok(self.rest_update_issues(rest, updates).await);
+        }
         if updates.is_empty() {
             return Ok(HashMap::new());
         }
 
-        // Build dynamic mutation with aliases
-        let mutations: Vec<String> = updates
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
-                format!(
-                    "update_{i}: updateIssue(input: $input_{i}) {{ issue {{ id number url }} }}"
-                )
-            })
-            .collect();
-
-        // Build variable definitions
-        let var_defs: Vec<String> = updates
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$input_{}: UpdateIssueInput!", i))
-            .collect();
-
-        let mutation = format!(
-            "mutation({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            mutations.join("\n  ")
-        );
-
-        // Build variables object
-        let mut variables = serde_json::Map::new();
-        for (i, update) in updates.iter().enumerate() {
+        let mut batch = Batch::mutation("update", "updateIssue", "UpdateIssueInput!", "{ issue { id number url } }");
+        for update in updates {
             let mut input = json!({
                 "id": update.issue_id,
                 "title": update.title,
@@ -641,17 +1013,14 @@ impl GitHubClient {
                 input["issueTypeId"] = json!(type_id);
             }
 
-            variables.insert(format!("input_{}", i), input);
+            batch.input(input);
         }
 
-        let response: serde_json::Value = self
-            .mutate(&mutation, Some(serde_json::Value::Object(variables)))
-            .await?;
+        let response = self.run_batch(&batch).await?;
 
         let mut results = HashMap::new();
         for (i, update) in updates.iter().enumerate() {
-            let key = format!("update_{}", i);
-            if let Some(data) = response.get(&key) {
+            if let Some(data) = response.get(i) {
                 if let Some(issue) = data.get("issue") {
                     if let (Some(id), Some(number), Some(url)) = (
                         issue.get("id").and_then(|v| v.as_str()),
@@ -672,87 +1041,145 @@ impl GitHubClient {
             }
             results.insert(
                 update.issue_id.clone(),
-                Err(format!("Failed to update issue {}", update.issue_id)),
+                Err(response.failure(i, &format!("Failed to update issue {}", update.issue_id))),
             );
         }
 
         Ok(results)
     }
 
-    /// Batch close multiple issues in a single request
-    pub async fn close_issues_batch(&self, issue_ids: &[String]) -> Result<()> {
-        if issue_ids.is_empty() {
-            return Ok(());
+    /// Move an issue to another repository, returning where it ended up
+    ///
+    /// Transfers are GraphQL-only, whichever API is configured.
+    pub async fn transfer_issue(&self, issue_id: &str, repository_id: &str) -> Result<IssueInfo> {
+        let mutation = r#"
+            mutation($issueId: ID!, $repositoryId: ID!) {
+                transferIssue(input: { issueId: $issueId, repositoryId: $repositoryId }) {
+                    issue {
+                        id
+                        number
+                        url
+                    }
+                }
+            }
+        "#;
+        let variables = json!({ "issueId": issue_id, "repositoryId": repository_id });
+        let response: serde_json::Value = self.mutate(mutation, Some(variables)).await?;
+        let issue = response
+            .get("transferIssue")
+            .and_then(|t| t.get("issue"))
+            .filter(|i| !i.is_null())
+            .ok_or_else(|| anyhow::anyhow!("Failed to transfer issue"))?;
+        Ok(IssueInfo {
+            id: issue["id"].as_str().unwrap_or_default().to_string(),
+            number: issue["number"].as_u64().unwrap_or_default(),
+            url: issue["url"].as_str().unwrap_or_default().to_string(),
+        })
+    }
+
+    /// Add and remove assignees on multiple issues, one request for adds and
+    /// one for removals
+    ///
+    /// Returns a map of issue_id -> result, with an entry for every change.
+    pub async fn update_assignees_batch(&self, changes: &[AssigneeChange]) -> Result<HashMap<String, Result<(), String>>> {
+        if let Some(rest) = &self.rest {
+            return Ok(self.rest_update_assignees(rest, changes).await);
         }
 
-        let mutations: Vec<String> = issue_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("close_{i}: closeIssue(input: $input_{i}) {{ issue {{ id }} }}"))
-            .collect();
+        let mut results: HashMap<String, Result<(), String>> =
+            changes.iter().map(|c| (c.issue_id.clone(), Ok(()))).collect();
+        let kinds = [
+            ("add", "addAssigneesToAssignable", "AddAssigneesToAssignableInput!", true),
+            ("remove", "removeAssigneesFromAssignable", "RemoveAssigneesFromAssignableInput!", false),
+        ];
+        for (prefix, field, input_type, adding) in kinds {
+            let todo: Vec<(&AssigneeChange, &Vec<String>)> = changes
+                .iter()
+                .map(|c| (c, if adding { &c.add } else { &c.remove }))
+                .filter(|(_, ids)| !ids.is_empty())
+                .collect();
+            if todo.is_empty() {
+                continue;
+            }
 
-        let var_defs: Vec<String> = issue_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$input_{}: CloseIssueInput!", i))
-            .collect();
+            let mut batch = Batch::mutation(prefix, field, input_type, "{ clientMutationId }");
+            for (change, ids) in &todo {
+                batch.input(json!({ "assignableId": change.issue_id, "assigneeIds": ids }));
+            }
+            let response = self.run_batch(&batch).await?;
+            for (i, (change, _)) in todo.iter().enumerate() {
+                if response.get(i).is_none_or(|v| v.is_null()) {
+                    let what = format!("Failed to {} assignees on issue {}", prefix, change.issue_id);
+                    results.insert(change.issue_id.clone(), Err(response.failure(i, &what)));
+                }
+            }
+        }
 
-        let mutation = format!(
-            "mutation({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            mutations.join("\n  ")
-        );
+        Ok(results)
+    }
 
-        let mut variables = serde_json::Map::new();
-        for (i, issue_id) in issue_ids.iter().enumerate() {
-            variables.insert(
-                format!("input_{}", i),
-                json!({ "issueId": issue_id }),
-            );
+    /// Add labels to multiple issues in a single request, as
+    /// (issue_id, label_ids) pairs
+    ///
+    /// Returns a map of issue_id -> result, with an entry for every issue.
+    pub async fn add_labels_batch(&self, additions: &[(String, Vec<String>)]) -> Result<HashMap<String, Result<(), String>>> {
+        if let Some(rest) = &self.rest {
+            return Ok(self.rest_add_labels(rest, additions).await);
+        }
+
+        let mut results: HashMap<String, Result<(), String>> =
+            additions.iter().map(|(issue_id, _)| (issue_id.clone(), Ok(()))).collect();
+        if additions.is_empty() {
+            return Ok(results);
         }
 
-        let _: serde_json::Value = self
-            .mutate(&mutation, Some(serde_json::Value::Object(variables)))
-            .await?;
+        let mut batch = Batch::mutation("label", "addLabelsToLabelable", "AddLabelsToLabelableInput!", "{ clientMutationId }");
+        for (issue_id, label_ids) in additions {
+            batch.input(json!({ "labelableId": issue_id, "labelIds": label_ids }));
+        }
+        let response = self.run_batch(&batch).await?;
+        for (i, (issue_id, _)) in additions.iter().enumerate() {
+            if response.get(i).is_none_or(|v| v.is_null()) {
+                let what = format!("Failed to add labels to issue {}", issue_id);
+                results.insert(issue_id.clone(), Err(response.failure(i, &what)));
+            }
+        }
 
-        Ok(())
+        Ok(results)
     }
 
-    /// Batch reopen multiple issues in a single request
-    pub async fn reopen_issues_batch(&self, issue_ids: &[String]) -> Result<()> {
+    /// Batch close multiple issues in a single request
+    pub async fn close_issues_batch(&self, issue_ids: &[String]) -> Result<()> {
+        if let Some(rest) = &self.rest {
+            return self.rest_set_state(rest, issue_ids, "closed").await;
+        }
         if issue_ids.is_empty() {
             return Ok(());
         }
 
-        let mutations: Vec<String> = issue_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("reopen_{i}: reopenIssue(input: $input_{i}) {{ issue {{ id }} }}"))
-            .collect();
-
-        let var_defs: Vec<String> = issue_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$input_{}: ReopenIssueInput!", i))
-            .collect();
+        let mut batch = Batch::mutation("close", "closeIssue", "CloseIssueInput!", "{ issue { id } }");
+        for issue_id in issue_ids {
+            batch.input(json!({ "issueId": issue_id }));
+        }
+        self.run_batch(&batch).await?;
 
-        let mutation = format!(
-            "mutation({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            mutations.join("\n  ")
-        );
+        Ok(())
+    }
 
-        let mut variables = serde_json::Map::new();
-        for (i, issue_id) in issue_ids.iter().enumerate() {
-            variables.insert(
-                format!("input_{}", i),
-                json!({ "issueId": issue_id }),
-            );
+    /// Batch reopen multiple issues in a single request
+    pub async fn reopen_issues_batch(&self, issue_ids: &[String]) -> Result<()> {
+        if let Some(rest) = &self.rest {
+            return self.rest_set_state(rest, issue_ids, "open").await;
+        }
+        if issue_ids.is_empty() {
+            return Ok(());
         }
 
-        let _: serde_json::Value = self
-            .mutate(&mutation, Some(serde_json::Value::Object(variables)))
-            .await?;
+        let mut batch = Batch::mutation("reopen", "reopenIssue", "ReopenIssueInput!", "{ issue { id } }");
+        for issue_id in issue_ids {
+            batch.input(json!({ "issueId": issue_id }));
+        }
+        self.run_batch(&batch).await?;
 
         Ok(())
     }
@@ -801,8 +1228,99 @@ impl GitHubClient {
         Ok(())
     }
 
+    /// Issues pinned to a repository, in pinned order
+    pub async fn get_pinned_issues(&self, owner: &str, name: &str) -> Result<Vec<IssueInfo>> {
+        let query = format!(
+            r#"query($owner: String!, $name: String!) {{
+                repository(owner: $owner, name: $name) {{
+                    pinnedIssues(first: {}) {{
+                        nodes {{ issue {{ id number url }} }}
+                    }}
+                }}
+            }}"#,
+            MAX_PINNED_ISSUES
+        );
+        let variables = json!({ "owner": owner, "name": name });
+        let response: serde_json::Value = self.query(&query, Some(variables)).await?;
+        let nodes = response["repository"]["pinnedIssues"]["nodes"].as_array().cloned().unwrap_or_default();
+        Ok(nodes
+            .iter()
+            .filter_map(|node| {
+                let issue = &node["issue"];
+                Some(IssueInfo {
+                    id: issue["id"].as_str()?.to_string(),
+                    number: issue["number"].as_u64()?,
+                    url: issue["url"].as_str().unwrap_or_default().to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Pin an issue to its repository
+    pub async fn pin_issue(&self, issue_id: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($issueId: ID!) {
+                pinIssue(input: { issueId: $issueId }) {
+                    issue {
+                        id
+                    }
+                }
+            }
+        "#;
+        let _response: serde_json::Value = self.mutate(mutation, Some(json!({ "issueId": issue_id }))).await?;
+        Ok(())
+    }
+
+    /// Unpin an issue from its repository
+    pub async fn unpin_issue(&self, issue_id: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($issueId: ID!) {
+                unpinIssue(input: { issueId: $issueId }) {
+                    issue {
+                        id
+                    }
+                }
+            }
+        "#;
+        let _response: serde_json::Value = self.mutate(mutation, Some(json!({ "issueId": issue_id }))).await?;
+        Ok(())
+    }
+
+    /// Lock an issue's conversation as resolved
+    pub async fn lock_issue(&self, issue_id: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($lockableId: ID!) {
+                lockLockable(input: { lockableId: $lockableId, lockReason: RESOLVED }) {
+                    lockedRecord {
+                        locked
+                    }
+                }
+            }
+        "#;
+        let _response: serde_json::Value = self.mutate(mutation, Some(json!({ "lockableId": issue_id }))).await?;
+        Ok(())
+    }
+
+    /// Unlock an issue's conversation
+    pub async fn unlock_issue(&self, issue_id: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($lockableId: ID!) {
+                unlockLockable(input: { lockableId: $lockableId }) {
+                    unlockedRecord {
+                        locked
+                    }
+                }
+            }
+        "#;
+        let _response: serde_json::Value = self.mutate(mutation, Some(json!({ "lockableId": issue_id }))).await?;
+        Ok(())
+    }
+
     /// Get all labels in a repository
     pub async fn get_labels(&self, owner: &str, name: &str) -> Result<Vec<LabelInfo>> {
+        if let Some(rest) = &self.rest {
+            return self.rest_labels(rest).await;
+        }
         let query = r#"
             query($owner: String!, $name: String!) {
                 repository(owner: $owner, name: $name) {
@@ -847,6 +1365,9 @@ impl GitHubClient {
         name: &str,
         color: &str,
     ) -> Result<LabelInfo> {
+        if let Some(rest) = &self.rest {
+            return self.rest_create_label(rest, name, color).await;
+        }
         let mutation = r#"
             mutation($input: CreateLabelInput!) {
                 createLabel(input: $input) {
@@ -910,34 +1431,63 @@ impl GitHubClient {
         Ok(())
     }
 
-    /// Get or create a label, returning its ID
-    pub async fn get_or_create_label(
+    /// Create labels in one request, each with a color derived from its name
+    ///
+    /// Returns results in input order. Over REST, labels are created one at
+    /// a time.
+    pub async fn create_labels_batch(
         &self,
-        owner: &str,
-        name: &str,
         repo_id: &str,
-        label_name: &str,
-        create_if_missing: bool,
-    ) -> Result<Option<String>> {
-        // First try to find existing label
-        let labels = self.get_labels(owner, name).await?;
-
-        if let Some(label) = labels.iter().find(|l| l.name.eq_ignore_ascii_case(label_name)) {
-            return Ok(Some(label.id.clone()));
+        names: &[String],
+    ) -> Result<Vec<Result<LabelInfo, String>>> {
+        if let Some(rest) = &self.rest {
+            let mut results = Vec::with_capacity(names.len());
+            for name in names {
+                let color = generate_label_color(name);
+                results.push(self.rest_create_label(rest, name, &color).await.map_err(|e| e.to_string()));
+            }
+            return Ok(results);
+        }
+        if names.is_empty() {
+            return Ok(Vec::new());
         }
 
-        // Label doesn't exist
-        if !create_if_missing {
-            return Ok(None);
+        let mut batch = Batch::mutation("label", "createLabel", "CreateLabelInput!", "{ label { id name } }");
+        for name in names {
+            batch.input(json!({
+                "repositoryId": repo_id,
+                "name": name,
+                "color": generate_label_color(name)
+            }));
         }
 
-        // Create it with a default color
-        let color = generate_label_color(label_name);
-        let label = self.create_label(repo_id, label_name, &color).await?;
-        Ok(Some(label.id))
+        let response = self.run_batch(&batch).await?;
+
+        Ok(names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let label = response.get(i).and_then(|d| d.get("label"));
+                match (
+                    label.and_then(|l| l.get("id")).and_then(|v| v.as_str()),
+                    label.and_then(|l| l.get("name")).and_then(|v| v.as_str()),
+                ) {
+                    (Some(id), Some(name)) => Ok(LabelInfo {
+                        id: id.to_string(),
+                        name: name.to_string(),
+                    }),
+                    _ => Err(response.failure(i, &format!("Failed to create label '{}'", name))),
+                }
+            })
+            .collect())
     }
 }
 
+/// Whether an issue's `createdAt` is before `since`; unparseable times aren't
+pub(super) fn created_before(created_at: &str, since: chrono::DateTime<chrono::FixedOffset>) -> bool {
+    chrono::DateTime::parse_from_rfc3339(created_at).is_ok_and(|at| at < since)
+}
+
 /// Generate a consistent color for a label based on its name
 fn generate_label_color(name: &str) -> String {
     // Simple hash-based color generation
@@ -1,4 +1,8 @@
+pub mod batch;
 pub mod client;
+pub mod discussions;
 pub mod issues;
 pub mod projects;
+pub mod pulls;
+pub mod rest;
 pub mod subissues;
@@ -1,7 +1,10 @@
 // GitHub Projects integration
 
+use super::batch::Batch;
 use super::client::GitHubClient;
+use super::issues::IssueInfo;
 use anyhow::Result;
+use chrono::NaiveDate;
 use serde::Deserialize;
 use serde_json::json;
 use std::collections::HashMap;
@@ -18,6 +21,39 @@ pub struct ProjectInfo {
 #[derive(Debug, Clone)]
 pub struct ProjectItemInfo {
     pub item_id: String,
+    /// The issue was in the project before this add
+    pub existing: bool,
+}
+
+impl ProjectItemInfo {
+    /// An issue already in the project, before its item ID is looked up
+    fn existing() -> Self {
+        Self {
+            item_id: String::new(),
+            existing: true,
+        }
+    }
+}
+
+/// A draft issue living only in a project
+#[derive(Debug, Clone)]
+pub struct DraftIssueInfo {
+    /// DraftIssue node ID (not the project item ID)
+    pub id: String,
+    pub title: String,
+    pub body: String,
+}
+
+/// A project item with its iteration assignment and issue state
+#[derive(Debug, Clone)]
+pub struct ProjectItemIteration {
+    pub item_id: String,
+    /// Assigned iteration ID, if any
+    pub iteration_id: Option<String>,
+    /// Issue number (None for draft items)
+    pub issue_number: Option<u64>,
+    /// When the issue was closed (ISO 8601), if closed
+    pub closed_at: Option<String>,
 }
 
 /// Information about a project field (Status or Iteration)
@@ -36,13 +72,41 @@ pub enum ProjectFieldType {
     /// Iteration field with active and completed iterations
     Iteration {
         active: Vec<IterationOption>,
-        #[allow(dead_code)]
         completed: Vec<IterationOption>,
     },
+    /// Free text field
+    Text,
+    /// Number field
+    Number,
+    /// Date field (YYYY-MM-DD values)
+    Date,
     /// Unknown/unsupported field type
     Other,
 }
 
+/// A value to set on a project item field
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectFieldValue {
+    Text(String),
+    Number(f64),
+    /// Date in YYYY-MM-DD format
+    Date(String),
+    /// Single-select option ID
+    SingleSelect(String),
+}
+
+impl ProjectFieldValue {
+    /// The `ProjectV2FieldValue` input object
+    fn to_input(&self) -> serde_json::Value {
+        match self {
+            Self::Text(text) => json!({ "text": text }),
+            Self::Number(number) => json!({ "number": number }),
+            Self::Date(date) => json!({ "date": date }),
+            Self::SingleSelect(option_id) => json!({ "singleSelectOptionId": option_id }),
+        }
+    }
+}
+
 /// Option for a single select field
 #[derive(Debug, Clone)]
 pub struct FieldOption {
@@ -55,6 +119,22 @@ pub struct FieldOption {
 pub struct IterationOption {
     pub id: String,
     pub title: String,
+    /// Start date in YYYY-MM-DD format
+    pub start_date: String,
+    /// Length of the iteration in days
+    pub duration: u64,
+}
+
+impl IterationOption {
+    /// Check if a date falls within this iteration's range
+    pub fn contains_date(&self, date: NaiveDate) -> bool {
+        let start = match NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d") {
+            Ok(d) => d,
+            Err(_) => return false,
+        };
+        let end = start + chrono::Duration::days(self.duration as i64);
+        date >= start && date < end
+    }
 }
 
 // Response types for GraphQL queries
@@ -155,6 +235,9 @@ struct FieldNode {
     name: String,
     #[serde(rename = "__typename")]
     typename: String,
+    // For plain fields (TEXT, NUMBER, DATE, ...)
+    #[serde(rename = "dataType")]
+    data_type: Option<String>,
     // For SingleSelectField
     options: Option<Vec<SelectOptionNode>>,
     // For IterationField
@@ -178,8 +261,60 @@ struct IterationConfiguration {
 struct IterationNode {
     id: String,
     title: String,
+    #[serde(rename = "startDate", default)]
+    start_date: String,
+    #[serde(default)]
+    duration: u64,
+}
+
+// Response types for project item iteration queries
+
+#[derive(Deserialize)]
+struct IterationItemsResponse {
+    node: Option<IterationItemsNode>,
+}
+
+#[derive(Deserialize)]
+struct IterationItemsNode {
+    items: IterationItemConnection,
+}
+
+#[derive(Deserialize)]
+struct IterationItemConnection {
+    #[serde(rename = "pageInfo")]
+    page_info: ItemsPageInfo,
+    nodes: Vec<IterationItemNode>,
+}
+
+#[derive(Deserialize)]
+struct ItemsPageInfo {
+    #[serde(rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IterationItemNode {
+    id: String,
+    #[serde(rename = "fieldValueByName")]
+    field_value: Option<IterationValueNode>,
+    content: Option<IterationItemContent>,
+}
+
+#[derive(Deserialize)]
+struct IterationValueNode {
+    #[serde(rename = "iterationId")]
+    iteration_id: Option<String>,
 }
 
+/// Issue fields; empty for drafts and pull requests
+#[derive(Deserialize)]
+struct IterationItemContent {
+    number: Option<u64>,
+    #[serde(rename = "closedAt")]
+    closed_at: Option<String>,
+}
 
 
 // Response types for field value updates
@@ -235,6 +370,34 @@ impl GitHubClient {
         }
     }
 
+    /// List all projects accessible from a repository
+    ///
+    /// Includes repo-level projects followed by owner-level (org or user)
+    /// projects, without duplicates.
+    pub async fn list_projects(&self, owner: &str, repo: &str) -> Result<Vec<ProjectInfo>> {
+        let mut projects = self.fetch_repo_projects(owner, repo).await?;
+
+        let owner_projects = if self.is_organization(owner, repo).await? {
+            self.fetch_org_projects(owner).await?
+        } else {
+            self.fetch_user_projects(owner).await?
+        };
+        for p in owner_projects {
+            if !projects.iter().any(|existing| existing.id == p.id) {
+                projects.push(p);
+            }
+        }
+
+        Ok(projects
+            .into_iter()
+            .map(|p| ProjectInfo {
+                id: p.id,
+                title: p.title,
+                number: p.number,
+            })
+            .collect())
+    }
+
     /// Find a project at the repo level
     async fn find_repo_project(
         &self,
@@ -243,6 +406,34 @@ impl GitHubClient {
         name: &str,
         number: Option<u64>,
     ) -> Result<Option<ProjectInfo>> {
+        let projects = self.fetch_repo_projects(owner, repo).await?;
+        Ok(find_matching_project(&projects, name, number))
+    }
+
+    /// Find a project at the organization level
+    async fn find_org_project(
+        &self,
+        org: &str,
+        name: &str,
+        number: Option<u64>,
+    ) -> Result<Option<ProjectInfo>> {
+        let projects = self.fetch_org_projects(org).await?;
+        Ok(find_matching_project(&projects, name, number))
+    }
+
+    /// Find a project at the user level
+    async fn find_user_project(
+        &self,
+        user: &str,
+        name: &str,
+        number: Option<u64>,
+    ) -> Result<Option<ProjectInfo>> {
+        let projects = self.fetch_user_projects(user).await?;
+        Ok(find_matching_project(&projects, name, number))
+    }
+
+    /// Fetch repo-level projects
+    async fn fetch_repo_projects(&self, owner: &str, repo: &str) -> Result<Vec<ProjectNode>> {
         let query = r#"
             query($owner: String!, $repo: String!) {
                 repository(owner: $owner, name: $repo) {
@@ -264,22 +455,15 @@ impl GitHubClient {
 
         let response: RepoProjectsResponse = self.query(query, Some(variables)).await?;
 
-        let projects = response
+        Ok(response
             .repository
             .and_then(|r| r.projects_v2)
             .map(|p| p.nodes)
-            .unwrap_or_default();
-
-        Ok(find_matching_project(&projects, name, number))
+            .unwrap_or_default())
     }
 
-    /// Find a project at the organization level
-    async fn find_org_project(
-        &self,
-        org: &str,
-        name: &str,
-        number: Option<u64>,
-    ) -> Result<Option<ProjectInfo>> {
+    /// Fetch organization-level projects
+    async fn fetch_org_projects(&self, org: &str) -> Result<Vec<ProjectNode>> {
         let query = r#"
             query($org: String!) {
                 organization(login: $org) {
@@ -298,22 +482,15 @@ impl GitHubClient {
 
         let response: OrgProjectsResponse = self.query(query, Some(variables)).await?;
 
-        let projects = response
+        Ok(response
             .organization
             .and_then(|o| o.projects_v2)
             .map(|p| p.nodes)
-            .unwrap_or_default();
-
-        Ok(find_matching_project(&projects, name, number))
+            .unwrap_or_default())
     }
 
-    /// Find a project at the user level
-    async fn find_user_project(
-        &self,
-        user: &str,
-        name: &str,
-        number: Option<u64>,
-    ) -> Result<Option<ProjectInfo>> {
+    /// Fetch user-level projects
+    async fn fetch_user_projects(&self, user: &str) -> Result<Vec<ProjectNode>> {
         let query = r#"
             query($user: String!) {
                 user(login: $user) {
@@ -332,13 +509,11 @@ impl GitHubClient {
 
         let response: UserProjectsResponse = self.query(query, Some(variables)).await?;
 
-        let projects = response
+        Ok(response
             .user
             .and_then(|u| u.projects_v2)
             .map(|p| p.nodes)
-            .unwrap_or_default();
-
-        Ok(find_matching_project(&projects, name, number))
+            .unwrap_or_default())
     }
 
     /// Check if the repo owner is an organization
@@ -400,17 +575,15 @@ impl GitHubClient {
                     .map(|i| i.id)
                     .ok_or_else(|| anyhow::anyhow!("Failed to add issue to project"))?;
 
-                Ok(ProjectItemInfo { item_id })
+                Ok(ProjectItemInfo { item_id, existing: false })
             }
             Err(e) => {
-                let err_str = e.to_string().to_lowercase();
-                // Handle "already in project" - need to fetch existing item ID
-                if err_str.contains("already in the project") || err_str.contains("already added") {
-                    // For now, return a placeholder - we'd need another query to get the real item ID
-                    // This is fine for ttr-0019; ttr-0020 will need to handle this properly
-                    Ok(ProjectItemInfo {
-                        item_id: String::new(),
-                    })
+                // Handle "already in project" - look up the existing item ID
+                if is_already_in_project(&e.to_string()) {
+                    let ids = [issue_id.to_string()];
+                    let mut results = vec![Ok(ProjectItemInfo::existing())];
+                    self.adopt_existing_items(project_id, &ids, &mut results).await;
+                    results.remove(0).map_err(|e| anyhow::anyhow!(e))
                 } else {
                     Err(e)
                 }
@@ -431,54 +604,21 @@ impl GitHubClient {
             return Ok(HashMap::new());
         }
 
-        // Build dynamic query with aliases
-        let queries: Vec<String> = issue_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
-                format!(
-                    r#"issue_{i}: node(id: $id_{i}) {{
-                        ... on Issue {{
-                            projectItems(first: 10) {{
-                                nodes {{
-                                    id
-                                    project {{ id }}
-                                }}
-                            }}
-                        }}
-                    }}"#
-                )
-            })
-            .collect();
-
-        // Build variable definitions
-        let var_defs: Vec<String> = issue_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$id_{}: ID!", i))
-            .collect();
-
-        let query = format!(
-            "query({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            queries.join("\n  ")
+        let mut batch = Batch::query(
+            "issue",
+            "node",
+            "{ ... on Issue { projectItems(first: 10) { nodes { id project { id } } } } }",
         );
-
-        // Build variables object
-        let mut variables = serde_json::Map::new();
-        for (i, issue_id) in issue_ids.iter().enumerate() {
-            variables.insert(format!("id_{}", i), json!(issue_id));
+        for issue_id in issue_ids {
+            batch.with_var("id", "ID!", json!(issue_id), "id: {}");
         }
 
-        let response: serde_json::Value = self
-            .query(&query, Some(serde_json::Value::Object(variables)))
-            .await?;
+        let response = self.run_batch(&batch).await?;
 
         // Parse results
         let mut result = HashMap::new();
         for (i, issue_id) in issue_ids.iter().enumerate() {
-            let key = format!("issue_{}", i);
-            if let Some(issue_data) = response.get(&key) {
+            if let Some(issue_data) = response.get(i) {
                 if let Some(items) = issue_data
                     .get("projectItems")
                     .and_then(|pi| pi.get("nodes"))
@@ -505,6 +645,128 @@ impl GitHubClient {
         Ok(result)
     }
 
+    /// Get the single-select value of a field (e.g. Status) on the project
+    /// items of multiple issues
+    /// Returns a map of issue node ID -> option name, for issues in the project with the field set
+    pub async fn get_project_item_statuses_batch(
+        &self,
+        project_id: &str,
+        issue_ids: &[String],
+        field_name: &str,
+    ) -> Result<HashMap<String, String>> {
+        if issue_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut batch = Batch::query(
+            "issue",
+            "node",
+            "{ ... on Issue { projectItems(first: 10) { nodes { project { id } \
+             fieldValueByName(name: $field) { ... on ProjectV2ItemFieldSingleSelectValue { name } } } } } }",
+        )
+        .shared("field", "String!", json!(field_name));
+        for issue_id in issue_ids {
+            batch.with_var("id", "ID!", json!(issue_id), "id: {}");
+        }
+
+        let response = self.run_batch(&batch).await?;
+
+        let mut result = HashMap::new();
+        for (i, issue_id) in issue_ids.iter().enumerate() {
+            let items = response
+                .get(i)
+                .and_then(|issue| issue.get("projectItems"))
+                .and_then(|pi| pi.get("nodes"))
+                .and_then(|n| n.as_array());
+            let status = items.into_iter().flatten().find_map(|item| {
+                let in_project = item
+                    .get("project")
+                    .and_then(|p| p.get("id"))
+                    .and_then(|id| id.as_str())
+                    == Some(project_id);
+                let name = item
+                    .get("fieldValueByName")
+                    .and_then(|v| v.get("name"))
+                    .and_then(|v| v.as_str());
+                name.filter(|_| in_project)
+            });
+            if let Some(status) = status {
+                result.insert(issue_id.clone(), status.to_string());
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// List all items in a project with their iteration field values
+    pub async fn get_project_iteration_items(
+        &self,
+        project_id: &str,
+        iteration_field: &str,
+    ) -> Result<Vec<ProjectItemIteration>> {
+        let query = r#"
+            query($projectId: ID!, $field: String!, $after: String) {
+                node(id: $projectId) {
+                    ... on ProjectV2 {
+                        items(first: 100, after: $after) {
+                            pageInfo {
+                                hasNextPage
+                                endCursor
+                            }
+                            nodes {
+                                id
+                                fieldValueByName(name: $field) {
+                                    ... on ProjectV2ItemFieldIterationValue {
+                                        iterationId
+                                    }
+                                }
+                                content {
+                                    ... on Issue {
+                                        number
+                                        closedAt
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let mut items = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let variables = json!({
+                "projectId": project_id,
+                "field": iteration_field,
+                "after": after
+            });
+
+            let response: IterationItemsResponse = self.query(query, Some(variables)).await?;
+            let page = response
+                .node
+                .ok_or_else(|| anyhow::anyhow!("Project not found"))?
+                .items;
+
+            for node in page.nodes {
+                items.push(ProjectItemIteration {
+                    item_id: node.id,
+                    iteration_id: node.field_value.and_then(|v| v.iteration_id),
+                    issue_number: node.content.as_ref().and_then(|c| c.number),
+                    closed_at: node.content.and_then(|c| c.closed_at),
+                });
+            }
+
+            after = page.page_info.end_cursor;
+            if !page.page_info.has_next_page || after.is_none() {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
     /// Get all fields for a project
     pub async fn get_project_fields(&self, project_id: &str) -> Result<Vec<ProjectFieldInfo>> {
         let query = r#"
@@ -517,6 +779,7 @@ impl GitHubClient {
                                     id
                                     name
                                     __typename
+                                    dataType
                                 }
                                 ... on ProjectV2SingleSelectField {
                                     id
@@ -535,10 +798,14 @@ impl GitHubClient {
                                         iterations {
                                             id
                                             title
+                                            startDate
+                                            duration
                                         }
                                         completedIterations {
                                             id
                                             title
+                                            startDate
+                                            duration
                                         }
                                     }
                                 }
@@ -585,6 +852,8 @@ impl GitHubClient {
                                 .map(|i| IterationOption {
                                     id: i.id,
                                     title: i.title,
+                                    start_date: i.start_date,
+                                    duration: i.duration,
                                 })
                                 .collect(),
                             completed: config
@@ -593,10 +862,18 @@ impl GitHubClient {
                                 .map(|i| IterationOption {
                                     id: i.id,
                                     title: i.title,
+                                    start_date: i.start_date,
+                                    duration: i.duration,
                                 })
                                 .collect(),
                         }
                     }
+                    "ProjectV2Field" => match f.data_type.as_deref() {
+                        Some("TEXT") => ProjectFieldType::Text,
+                        Some("NUMBER") => ProjectFieldType::Number,
+                        Some("DATE") => ProjectFieldType::Date,
+                        _ => ProjectFieldType::Other,
+                    },
                     _ => ProjectFieldType::Other,
                 };
 
@@ -688,58 +965,31 @@ impl GitHubClient {
             return Ok(Vec::new());
         }
 
-        // Build dynamic mutation with aliases
-        let mutations: Vec<String> = items
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
-                format!(
-                    "update_{i}: updateProjectV2ItemFieldValue(input: $input_{i}) {{ projectV2Item {{ id }} }}"
-                )
-            })
-            .collect();
-
-        // Build variable definitions
-        let var_defs: Vec<String> = items
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$input_{}: UpdateProjectV2ItemFieldValueInput!", i))
-            .collect();
-
-        let mutation = format!(
-            "mutation({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            mutations.join("\n  ")
+        let mut batch = Batch::mutation(
+            "update",
+            "updateProjectV2ItemFieldValue",
+            "UpdateProjectV2ItemFieldValueInput!",
+            "{ projectV2Item { id } }",
         );
-
-        // Build variables object
-        let mut variables = serde_json::Map::new();
-        for (i, (item_id, option_id)) in items.iter().enumerate() {
-            variables.insert(
-                format!("input_{}", i),
-                json!({
-                    "projectId": project_id,
-                    "itemId": item_id,
-                    "fieldId": field_id,
-                    "value": {
-                        "singleSelectOptionId": option_id
-                    }
-                }),
-            );
+        for (item_id, option_id) in items {
+            batch.input(json!({
+                "projectId": project_id,
+                "itemId": item_id,
+                "fieldId": field_id,
+                "value": {
+                    "singleSelectOptionId": option_id
+                }
+            }));
         }
 
-        match self
-            .mutate::<serde_json::Value>(&mutation, Some(serde_json::Value::Object(variables)))
-            .await
-        {
+        match self.run_batch(&batch).await {
             Ok(response) => {
                 let mut results = Vec::with_capacity(items.len());
                 for i in 0..items.len() {
-                    let key = format!("update_{}", i);
-                    if response.get(&key).is_some() {
-                        results.push(Ok(()));
+                    if response.error(i).is_some() || response.get(i).is_none() {
+                        results.push(Err(response.failure(i, "Missing response for item")));
                     } else {
-                        results.push(Err("Missing response for item".to_string()));
+                        results.push(Ok(()));
                     }
                 }
                 Ok(results)
@@ -748,6 +998,43 @@ impl GitHubClient {
         }
     }
 
+    /// Batch set field values of any supported type on project items
+    ///
+    /// updates: Vec of (item_id, field_id, value). Returns results in input order.
+    pub async fn set_project_item_field_values_batch(
+        &self,
+        project_id: &str,
+        updates: &[(String, String, ProjectFieldValue)],
+    ) -> Result<Vec<Result<(), String>>> {
+        if updates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut batch = Batch::mutation(
+            "update",
+            "updateProjectV2ItemFieldValue",
+            "UpdateProjectV2ItemFieldValueInput!",
+            "{ projectV2Item { id } }",
+        );
+        for (item_id, field_id, value) in updates {
+            batch.input(json!({
+                "projectId": project_id,
+                "itemId": item_id,
+                "fieldId": field_id,
+                "value": value.to_input()
+            }));
+        }
+
+        let response = self.run_batch(&batch).await?;
+
+        Ok((0..updates.len())
+            .map(|i| match response.get(i) {
+                Some(v) if !v.is_null() => Ok(()),
+                _ => Err(response.failure(i, "Missing response for item")),
+            })
+            .collect())
+    }
+
     /// Batch set iteration field values on multiple project items
     /// 
     /// items: Vec of item_ids (all get same iteration)
@@ -762,58 +1049,31 @@ impl GitHubClient {
             return Ok(Vec::new());
         }
 
-        // Build dynamic mutation with aliases
-        let mutations: Vec<String> = item_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
-                format!(
-                    "update_{i}: updateProjectV2ItemFieldValue(input: $input_{i}) {{ projectV2Item {{ id }} }}"
-                )
-            })
-            .collect();
-
-        // Build variable definitions
-        let var_defs: Vec<String> = item_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$input_{}: UpdateProjectV2ItemFieldValueInput!", i))
-            .collect();
-
-        let mutation = format!(
-            "mutation({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            mutations.join("\n  ")
+        let mut batch = Batch::mutation(
+            "update",
+            "updateProjectV2ItemFieldValue",
+            "UpdateProjectV2ItemFieldValueInput!",
+            "{ projectV2Item { id } }",
         );
-
-        // Build variables object
-        let mut variables = serde_json::Map::new();
-        for (i, item_id) in item_ids.iter().enumerate() {
-            variables.insert(
-                format!("input_{}", i),
-                json!({
-                    "projectId": project_id,
-                    "itemId": item_id,
-                    "fieldId": field_id,
-                    "value": {
-                        "iterationId": iteration_id
-                    }
-                }),
-            );
+        for item_id in item_ids {
+            batch.input(json!({
+                "projectId": project_id,
+                "itemId": item_id,
+                "fieldId": field_id,
+                "value": {
+                    "iterationId": iteration_id
+                }
+            }));
         }
 
-        match self
-            .mutate::<serde_json::Value>(&mutation, Some(serde_json::Value::Object(variables)))
-            .await
-        {
+        match self.run_batch(&batch).await {
             Ok(response) => {
                 let mut results = Vec::with_capacity(item_ids.len());
                 for i in 0..item_ids.len() {
-                    let key = format!("update_{}", i);
-                    if response.get(&key).is_some() {
-                        results.push(Ok(()));
+                    if response.error(i).is_some() || response.get(i).is_none() {
+                        results.push(Err(response.failure(i, "Missing response for item")));
                     } else {
-                        results.push(Err("Missing response for item".to_string()));
+                        results.push(Ok(()));
                     }
                 }
                 Ok(results)
@@ -834,52 +1094,27 @@ impl GitHubClient {
             return Ok(Vec::new());
         }
 
-        // Build dynamic mutation with aliases
-        let mutations: Vec<String> = issue_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
-                format!(
-                    "add_{i}: addProjectV2ItemById(input: $input_{i}) {{ item {{ id }} }}"
-                )
-            })
-            .collect();
-
-        // Build variable definitions
-        let var_defs: Vec<String> = issue_ids
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$input_{}: AddProjectV2ItemByIdInput!", i))
-            .collect();
-
-        let mutation = format!(
-            "mutation({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            mutations.join("\n  ")
+        let mut batch = Batch::mutation(
+            "add",
+            "addProjectV2ItemById",
+            "AddProjectV2ItemByIdInput!",
+            "{ item { id } }",
         );
-
-        // Build variables object
-        let mut variables = serde_json::Map::new();
-        for (i, issue_id) in issue_ids.iter().enumerate() {
-            variables.insert(
-                format!("input_{}", i),
-                json!({
-                    "projectId": project_id,
-                    "contentId": issue_id
-                }),
-            );
+        for issue_id in issue_ids {
+            batch.input(json!({
+                "projectId": project_id,
+                "contentId": issue_id
+            }));
         }
 
         // Execute - handle partial failures
-        match self
-            .mutate::<serde_json::Value>(&mutation, Some(serde_json::Value::Object(variables)))
-            .await
-        {
+        match self.run_batch(&batch).await {
             Ok(response) => {
                 let mut results = Vec::with_capacity(issue_ids.len());
                 for i in 0..issue_ids.len() {
-                    let key = format!("add_{}", i);
-                    if let Some(data) = response.get(&key) {
+                    if response.error(i).is_some_and(is_already_in_project) {
+                        results.push(Ok(ProjectItemInfo::existing()));
+                    } else if let Some(data) = response.get(i) {
                         if let Some(item_id) = data
                             .get("item")
                             .and_then(|item| item.get("id"))
@@ -887,27 +1122,273 @@ impl GitHubClient {
                         {
                             results.push(Ok(ProjectItemInfo {
                                 item_id: item_id.to_string(),
+                                existing: false,
                             }));
                         } else {
-                            results.push(Err("No item ID in response".to_string()));
+                            results.push(Err(response.failure(i, "No item ID in response")));
                         }
                     } else {
-                        results.push(Err("Missing response for item".to_string()));
+                        results.push(Err(response.failure(i, "Missing response for item")));
                     }
                 }
+                self.adopt_existing_items(project_id, issue_ids, &mut results).await;
                 Ok(results)
             }
             Err(e) => {
-                let err_str = e.to_string().to_lowercase();
                 // If error is "already in project", treat all as success
-                if err_str.contains("already in the project") || err_str.contains("already added") {
-                    Ok(vec![Ok(ProjectItemInfo { item_id: String::new() }); issue_ids.len()])
+                if is_already_in_project(&e.to_string()) {
+                    let mut results = vec![Ok(ProjectItemInfo::existing()); issue_ids.len()];
+                    self.adopt_existing_items(project_id, issue_ids, &mut results).await;
+                    Ok(results)
                 } else {
                     Err(e)
                 }
             }
         }
     }
+
+    /// Look up the item IDs of issues GitHub said were already in the project
+    ///
+    /// Issues whose item can't be found become failures, so callers never
+    /// see an empty item ID.
+    async fn adopt_existing_items(
+        &self,
+        project_id: &str,
+        issue_ids: &[String],
+        results: &mut [Result<ProjectItemInfo, String>],
+    ) {
+        let pending: Vec<String> = issue_ids
+            .iter()
+            .zip(results.iter())
+            .filter(|(_, result)| matches!(result, Ok(info) if info.item_id.is_empty()))
+            .map(|(id, _)| id.clone())
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+
+        let found = self.get_project_item_ids_batch(project_id, &pending).await;
+        for (issue_id, result) in issue_ids.iter().zip(results.iter_mut()) {
+            let Ok(info) = result else {
+                continue;
+            };
+            if !info.item_id.is_empty() {
+                continue;
+            }
+            *result = match &found {
+                Ok(ids) => match ids.get(issue_id) {
+                    Some(item_id) => Ok(ProjectItemInfo {
+                        item_id: item_id.clone(),
+                        existing: true,
+                    }),
+                    None => Err("Already in the project, but its item wasn't found".to_string()),
+                },
+                Err(e) => Err(format!("Already in the project; looking up its item failed: {}", e)),
+            };
+        }
+    }
+
+    /// Batch add draft issues to a project
+    ///
+    /// drafts: Vec of (title, body). Returns project item IDs in input order.
+    pub async fn add_draft_issues_batch(
+        &self,
+        project_id: &str,
+        drafts: &[(String, String)],
+    ) -> Result<Vec<Result<ProjectItemInfo, String>>> {
+        if drafts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut batch = Batch::mutation(
+            "draft",
+            "addProjectV2DraftIssue",
+            "AddProjectV2DraftIssueInput!",
+            "{ projectItem { id } }",
+        );
+        for (title, body) in drafts {
+            batch.input(json!({
+                "projectId": project_id,
+                "title": title,
+                "body": body
+            }));
+        }
+
+        let response = self.run_batch(&batch).await?;
+
+        let mut results = Vec::with_capacity(drafts.len());
+        for i in 0..drafts.len() {
+            match response
+                .get(i)
+                .and_then(|d| d.get("projectItem"))
+                .and_then(|item| item.get("id"))
+                .and_then(|id| id.as_str())
+            {
+                Some(item_id) => results.push(Ok(ProjectItemInfo {
+                    item_id: item_id.to_string(),
+                    existing: false,
+                })),
+                None => results.push(Err(response.failure(i, "Failed to create draft"))),
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Batch remove items (issues or drafts) from a project
+    ///
+    /// Returns results in the same order as input.
+    pub async fn delete_project_items_batch(
+        &self,
+        project_id: &str,
+        item_ids: &[String],
+    ) -> Result<Vec<Result<(), String>>> {
+        if item_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut batch = Batch::mutation("delete", "deleteProjectV2Item", "DeleteProjectV2ItemInput!", "{ deletedItemId }");
+        for item_id in item_ids {
+            batch.input(json!({
+                "projectId": project_id,
+                "itemId": item_id
+            }));
+        }
+
+        let response = self.run_batch(&batch).await?;
+
+        Ok((0..item_ids.len())
+            .map(|i| {
+                response
+                    .get(i)
+                    .and_then(|d| d.get("deletedItemId"))
+                    .and_then(|id| id.as_str())
+                    .map(|_| ())
+                    .ok_or_else(|| response.failure(i, &format!("Failed to remove item {}", item_ids[i])))
+            })
+            .collect())
+    }
+
+    /// Get the draft issue behind a project item
+    ///
+    /// Returns None if the item no longer exists or is not a draft
+    /// (e.g. it was converted to an issue in the GitHub UI).
+    pub async fn get_draft_issue(&self, item_id: &str) -> Result<Option<DraftIssueInfo>> {
+        let query = r#"
+            query($itemId: ID!) {
+                node(id: $itemId) {
+                    ... on ProjectV2Item {
+                        content {
+                            ... on DraftIssue {
+                                id
+                                title
+                                body
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({ "itemId": item_id });
+        let response: serde_json::Value = self.query(query, Some(variables)).await?;
+
+        let content = match response.get("node").and_then(|n| n.get("content")) {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        match (
+            content.get("id").and_then(|v| v.as_str()),
+            content.get("title").and_then(|v| v.as_str()),
+            content.get("body").and_then(|v| v.as_str()),
+        ) {
+            (Some(id), Some(title), Some(body)) => Ok(Some(DraftIssueInfo {
+                id: id.to_string(),
+                title: title.to_string(),
+                body: body.to_string(),
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    /// Update a draft issue's title and body
+    pub async fn update_draft_issue(&self, draft_id: &str, title: &str, body: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($input: UpdateProjectV2DraftIssueInput!) {
+                updateProjectV2DraftIssue(input: $input) {
+                    draftIssue {
+                        id
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "input": {
+                "draftIssueId": draft_id,
+                "title": title,
+                "body": body
+            }
+        });
+
+        let _: serde_json::Value = self.mutate(mutation, Some(variables)).await?;
+        Ok(())
+    }
+
+    /// Convert a draft project item into a real issue in the repository
+    pub async fn convert_draft_to_issue(&self, item_id: &str, repo_id: &str) -> Result<IssueInfo> {
+        let mutation = r#"
+            mutation($input: ConvertProjectV2DraftIssueItemToIssueInput!) {
+                convertProjectV2DraftIssueItemToIssue(input: $input) {
+                    item {
+                        content {
+                            ... on Issue {
+                                id
+                                number
+                                url
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "input": {
+                "itemId": item_id,
+                "repositoryId": repo_id
+            }
+        });
+
+        let response: serde_json::Value = self.mutate(mutation, Some(variables)).await?;
+
+        let issue = response
+            .get("convertProjectV2DraftIssueItemToIssue")
+            .and_then(|p| p.get("item"))
+            .and_then(|i| i.get("content"));
+
+        match issue.map(|i| {
+            (
+                i.get("id").and_then(|v| v.as_str()),
+                i.get("number").and_then(|v| v.as_u64()),
+                i.get("url").and_then(|v| v.as_str()),
+            )
+        }) {
+            Some((Some(id), Some(number), Some(url))) => Ok(IssueInfo {
+                id: id.to_string(),
+                number,
+                url: url.to_string(),
+            }),
+            _ => anyhow::bail!("Failed to convert draft item {} to an issue", item_id),
+        }
+    }
+}
+
+/// Whether GitHub refused to add an item because it's already there
+fn is_already_in_project(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("already in the project") || message.contains("already added")
 }
 
 /// Find a project matching by number or name (case-insensitive)
@@ -1033,4 +1514,20 @@ mod tests {
         assert_eq!(p.id, "P2");
         assert_eq!(p.number, 1);
     }
+
+    #[test]
+    fn test_iteration_contains_date() {
+        let iteration = IterationOption {
+            id: "IT_1".to_string(),
+            title: "Sprint 1".to_string(),
+            start_date: "2026-02-02".to_string(),
+            duration: 14,
+        };
+
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert!(iteration.contains_date(date("2026-02-02")));
+        assert!(iteration.contains_date(date("2026-02-15")));
+        assert!(!iteration.contains_date(date("2026-02-16"))); // end is exclusive
+        assert!(!iteration.contains_date(date("2026-02-01")));
+    }
 }
@@ -0,0 +1,303 @@
+use super::client::GitHubClient;
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Information about a pull request
+#[derive(Debug, Clone)]
+pub struct PullRequestInfo {
+    pub id: String,
+    pub number: u64,
+    pub title: String,
+    pub body: String,
+    pub state: String,  // OPEN, CLOSED, or MERGED
+    pub url: String,
+    /// Name of the PR's head branch
+    pub head_ref: String,
+}
+
+/// An issue's node ID with its comments
+#[derive(Debug, Clone)]
+pub struct IssueComments {
+    pub issue_id: String,
+    pub comments: Vec<IssueComment>,
+}
+
+/// A comment on an issue
+#[derive(Debug, Clone)]
+pub struct IssueComment {
+    pub id: String,
+    pub body: String,
+}
+
+// Response types for GraphQL queries
+
+#[derive(Deserialize)]
+struct PullRequestsResponse {
+    repository: Option<PullRequestsRepository>,
+}
+
+#[derive(Deserialize)]
+struct PullRequestsRepository {
+    #[serde(rename = "pullRequests")]
+    pull_requests: PullRequestConnection,
+}
+
+#[derive(Deserialize)]
+struct PullRequestConnection {
+    nodes: Vec<PullRequestNode>,
+}
+
+#[derive(Deserialize)]
+struct PullRequestNode {
+    id: String,
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    url: String,
+    #[serde(rename = "headRefName")]
+    head_ref_name: String,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct UpdatePullRequestResponse {
+    #[serde(rename = "updatePullRequest")]
+    update_pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct IssueCommentsResponse {
+    repository: Option<IssueCommentsRepository>,
+}
+
+#[derive(Deserialize)]
+struct IssueCommentsRepository {
+    issue: Option<IssueCommentsNode>,
+}
+
+#[derive(Deserialize)]
+struct IssueCommentsNode {
+    id: String,
+    comments: CommentConnection,
+}
+
+#[derive(Deserialize)]
+struct CommentConnection {
+    nodes: Vec<CommentNode>,
+}
+
+#[derive(Deserialize)]
+struct CommentNode {
+    id: String,
+    body: String,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct AddCommentResponse {
+    #[serde(rename = "addComment")]
+    add_comment: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct UpdateCommentResponse {
+    #[serde(rename = "updateIssueComment")]
+    update_issue_comment: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct DeleteCommentResponse {
+    #[serde(rename = "deleteIssueComment")]
+    delete_issue_comment: Option<serde_json::Value>,
+}
+
+impl GitHubClient {
+    /// List the most recently updated pull requests in a repository
+    pub async fn list_pull_requests(&self, owner: &str, name: &str) -> Result<Vec<PullRequestInfo>> {
+        let query = r#"
+            query($owner: String!, $name: String!) {
+                repository(owner: $owner, name: $name) {
+                    pullRequests(first: 100, orderBy: {field: UPDATED_AT, direction: DESC}) {
+                        nodes {
+                            id
+                            number
+                            title
+                            body
+                            state
+                            url
+                            headRefName
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "owner": owner,
+            "name": name
+        });
+
+        let response: PullRequestsResponse = self.query(query, Some(variables)).await?;
+
+        let repo = response
+            .repository
+            .ok_or_else(|| anyhow::anyhow!("Repository {}/{} not found", owner, name))?;
+
+        Ok(repo
+            .pull_requests
+            .nodes
+            .into_iter()
+            .map(|n| PullRequestInfo {
+                id: n.id,
+                number: n.number,
+                title: n.title,
+                body: n.body.unwrap_or_default(),
+                state: n.state,
+                url: n.url,
+                head_ref: n.head_ref_name,
+            })
+            .collect())
+    }
+
+    /// Replace a pull request's description
+    pub async fn update_pull_request_body(&self, pr_id: &str, body: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($input: UpdatePullRequestInput!) {
+                updatePullRequest(input: $input) {
+                    pullRequest {
+                        id
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "input": {
+                "pullRequestId": pr_id,
+                "body": body
+            }
+        });
+
+        let _response: UpdatePullRequestResponse = self.mutate(mutation, Some(variables)).await?;
+        Ok(())
+    }
+
+    /// Get an issue's node ID and its most recent comments
+    pub async fn get_issue_comments(
+        &self,
+        owner: &str,
+        name: &str,
+        number: u64,
+    ) -> Result<IssueComments> {
+        let query = r#"
+            query($owner: String!, $name: String!, $number: Int!) {
+                repository(owner: $owner, name: $name) {
+                    issue(number: $number) {
+                        id
+                        comments(last: 100) {
+                            nodes {
+                                id
+                                body
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "owner": owner,
+            "name": name,
+            "number": number
+        });
+
+        let response: IssueCommentsResponse = self.query(query, Some(variables)).await?;
+
+        let issue = response
+            .repository
+            .and_then(|r| r.issue)
+            .ok_or_else(|| anyhow::anyhow!("Issue #{} not found", number))?;
+
+        Ok(IssueComments {
+            issue_id: issue.id,
+            comments: issue
+                .comments
+                .nodes
+                .into_iter()
+                .map(|c| IssueComment { id: c.id, body: c.body })
+                .collect(),
+        })
+    }
+
+    /// Add a comment to an issue or pull request
+    pub async fn add_comment(&self, subject_id: &str, body: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($input: AddCommentInput!) {
+                addComment(input: $input) {
+                    commentEdge {
+                        node {
+                            id
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "input": {
+                "subjectId": subject_id,
+                "body": body
+            }
+        });
+
+        let _response: AddCommentResponse = self.mutate(mutation, Some(variables)).await?;
+        Ok(())
+    }
+
+    /// Replace the body of an issue comment
+    pub async fn update_comment(&self, comment_id: &str, body: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($input: UpdateIssueCommentInput!) {
+                updateIssueComment(input: $input) {
+                    issueComment {
+                        id
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "input": {
+                "id": comment_id,
+                "body": body
+            }
+        });
+
+        let _response: UpdateCommentResponse = self.mutate(mutation, Some(variables)).await?;
+        Ok(())
+    }
+
+    /// Delete an issue comment
+    pub async fn delete_comment(&self, comment_id: &str) -> Result<()> {
+        let mutation = r#"
+            mutation($input: DeleteIssueCommentInput!) {
+                deleteIssueComment(input: $input) {
+                    clientMutationId
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "input": {
+                "id": comment_id
+            }
+        });
+
+        let _response: DeleteCommentResponse = self.mutate(mutation, Some(variables)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,442 @@
+use super::client::{check_response, GitHubClient};
+use super::issues::{created_before, AssigneeChange, ExistingIssue, IssueAssignee, IssueCreate, IssueInfo, IssueUpdate, LabelInfo};
+use anyhow::{Context, Result};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// REST root and repository for `[github] api = "rest"`
+///
+/// REST addresses issues by number, labels by name, and users by login, while
+/// the sync engine passes GraphQL node IDs around, since projects and
+/// sub-issues still need them. In REST mode label and user "IDs" are their
+/// names; issue node IDs are mapped back to numbers as issues are seen.
+pub struct RestApi {
+    base: String,
+    owner: String,
+    name: String,
+    /// Issue node ID -> number
+    numbers: Mutex<HashMap<String, u64>>,
+}
+
+impl RestApi {
+    pub(super) fn new(base: &str, owner: &str, name: &str) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            owner: owner.to_string(),
+            name: name.to_string(),
+            numbers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn repo_path(&self, path: &str) -> String {
+        format!("/repos/{}/{}{}", self.owner, self.name, path)
+    }
+
+    fn remember(&self, issue: &RestIssue) {
+        self.numbers.lock().unwrap().insert(issue.node_id.clone(), issue.number);
+    }
+}
+
+#[derive(Deserialize)]
+struct RestRepository {
+    node_id: String,
+}
+
+#[derive(Deserialize)]
+struct RestUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct RestLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RestIssue {
+    node_id: String,
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    state: String,
+    html_url: String,
+    #[serde(default)]
+    updated_at: String,
+    #[serde(default)]
+    created_at: String,
+    #[serde(default)]
+    closed_at: Option<String>,
+    #[serde(default)]
+    reactions: Option<RestReactions>,
+    #[serde(default)]
+    assignees: Vec<RestUser>,
+    #[serde(default)]
+    labels: Vec<RestLabel>,
+    /// Set when the "issue" is a pull request
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct RestReactions {
+    #[serde(rename = "+1", default)]
+    thumbs_up: u64,
+}
+
+impl RestIssue {
+    fn into_existing(self) -> ExistingIssue {
+        ExistingIssue {
+            id: self.node_id,
+            number: self.number,
+            title: self.title,
+            body: self.body.unwrap_or_default(),
+            // GraphQL spells states in capitals, and the engine compares them
+            state: self.state.to_uppercase(),
+            url: self.html_url,
+            updated_at: self.updated_at,
+            created_at: self.created_at,
+            closed_at: self.closed_at,
+            assignees: self
+                .assignees
+                .into_iter()
+                .map(|u| IssueAssignee { id: u.login.clone(), login: u.login })
+                .collect(),
+            labels: self.labels.into_iter().map(|l| l.name).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RestSearchCount {
+    total_count: u64,
+}
+
+#[derive(Deserialize)]
+struct IssueNumberResponse {
+    node: Option<IssueNumberNode>,
+}
+
+#[derive(Deserialize)]
+struct IssueNumberNode {
+    number: Option<u64>,
+}
+
+impl GitHubClient {
+    /// Send a REST request and parse the JSON response
+    async fn rest<T: serde::de::DeserializeOwned>(
+        &self,
+        rest: &RestApi,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let is_get = method == Method::GET;
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", rest.base, path))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(query);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        if !is_get {
+            self.pace().await;
+        }
+        let started = std::time::Instant::now();
+        let response = request.send().await;
+        self.record_request(started);
+        let response = check_response(response).await?;
+        response.json().await.context("Failed to parse GitHub API response")
+    }
+
+    /// Issue number for a node ID, asking GraphQL for issues not seen yet
+    async fn rest_issue_number(&self, rest: &RestApi, issue_id: &str) -> Result<u64> {
+        if let Some(number) = rest.numbers.lock().unwrap().get(issue_id) {
+            return Ok(*number);
+        }
+        let query = r#"
+            query($id: ID!) {
+                node(id: $id) {
+                    ... on Issue {
+                        number
+                    }
+                }
+            }
+        "#;
+        let response: IssueNumberResponse = self.query(query, Some(json!({ "id": issue_id }))).await?;
+        let number = response
+            .node
+            .and_then(|n| n.number)
+            .ok_or_else(|| anyhow::anyhow!("Issue {} not found", issue_id))?;
+        rest.numbers.lock().unwrap().insert(issue_id.to_string(), number);
+        Ok(number)
+    }
+
+    pub(super) async fn rest_repository_id(&self, rest: &RestApi) -> Result<String> {
+        let repo: RestRepository = self.rest(rest, Method::GET, &rest.repo_path(""), &[], None).await?;
+        Ok(repo.node_id)
+    }
+
+    /// The login itself, once GitHub confirms the user exists
+    pub(super) async fn rest_user_id(&self, rest: &RestApi, username: &str) -> Result<String> {
+        let user: RestUser = self
+            .rest(rest, Method::GET, &format!("/users/{}", username), &[], None)
+            .await
+            .with_context(|| format!("User '{}' not found", username))?;
+        Ok(user.login)
+    }
+
+    pub(super) async fn rest_labels(&self, rest: &RestApi) -> Result<Vec<LabelInfo>> {
+        let labels: Vec<RestLabel> = self
+            .rest(rest, Method::GET, &rest.repo_path("/labels"), &[("per_page", "100")], None)
+            .await?;
+        Ok(labels
+            .into_iter()
+            .map(|l| LabelInfo {
+                id: l.name.clone(),
+                name: l.name,
+            })
+            .collect())
+    }
+
+    pub(super) async fn rest_create_label(&self, rest: &RestApi, name: &str, color: &str) -> Result<LabelInfo> {
+        let body = json!({ "name": name, "color": color });
+        let label: RestLabel = self.rest(rest, Method::POST, &rest.repo_path("/labels"), &[], Some(body)).await?;
+        Ok(LabelInfo {
+            id: label.name.clone(),
+            name: label.name,
+        })
+    }
+
+    /// Create issues one request at a time; issue types are GraphQL-only
+    pub(super) async fn rest_create_issues(
+        &self,
+        rest: &RestApi,
+        creates: &[IssueCreate],
+        assignees: Option<&[String]>,
+    ) -> Vec<Result<IssueInfo, String>> {
+        let mut results = Vec::with_capacity(creates.len());
+        for create in creates {
+            let mut body = json!({
+                "title": create.title,
+                "body": create.body,
+            });
+            if !create.label_ids.is_empty() {
+                body["labels"] = json!(create.label_ids);
+            }
+            if !create.assignee_ids.is_empty() {
+                body["assignees"] = json!(create.assignee_ids);
+            } else if let Some(assignees) = assignees.filter(|a| !a.is_empty()) {
+                body["assignees"] = json!(assignees);
+            }
+            let result: Result<RestIssue> =
+                self.rest(rest, Method::POST, &rest.repo_path("/issues"), &[], Some(body)).await;
+            results.push(match result {
+                Ok(issue) => {
+                    rest.remember(&issue);
+                    Ok(IssueInfo {
+                        id: issue.node_id,
+                        number: issue.number,
+                        url: issue.html_url,
+                    })
+                }
+                Err(e) => Err(e.to_string()),
+            });
+        }
+        results
+    }
+
+    /// Fetch issues by number, leaving out missing ones
+    pub(super) async fn rest_issues(&self, rest: &RestApi, numbers: &[u64]) -> Result<HashMap<u64, ExistingIssue>> {
+        let mut issues = HashMap::new();
+        for number in numbers {
+            let path = rest.repo_path(&format!("/issues/{}", number));
+            let issue: RestIssue = match self.rest(rest, Method::GET, &path, &[], None).await {
+                Ok(issue) => issue,
+                Err(e) if e.to_string().contains("404") => continue,
+                Err(e) => return Err(e),
+            };
+            rest.remember(&issue);
+            issues.insert(*number, issue.into_existing());
+        }
+        Ok(issues)
+    }
+
+    /// 👍 reactions on issues by number, leaving out missing ones
+    pub(super) async fn rest_thumbs_up(&self, rest: &RestApi, numbers: &[u64]) -> Result<HashMap<u64, u64>> {
+        let mut counts = HashMap::new();
+        for number in numbers {
+            let path = rest.repo_path(&format!("/issues/{}", number));
+            let issue: RestIssue = match self.rest(rest, Method::GET, &path, &[], None).await {
+                Ok(issue) => issue,
+                Err(e) if e.to_string().contains("404") => continue,
+                Err(e) => return Err(e),
+            };
+            counts.insert(*number, issue.reactions.map_or(0, |r| r.thumbs_up));
+        }
+        Ok(counts)
+    }
+
+    /// Every open issue, optionally only those with a label, skipping pull
+    /// requests
+    pub(super) async fn rest_open_issues(&self, rest: &RestApi, label: Option<&str>) -> Result<Vec<ExistingIssue>> {
+        let path = rest.repo_path("/issues");
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let page = page.to_string();
+            let mut query = vec![("state", "open"), ("per_page", "100"), ("page", page.as_str())];
+            if let Some(label) = label {
+                query.push(("labels", label));
+            }
+            let batch: Vec<RestIssue> = self.rest(rest, Method::GET, &path, &query, None).await?;
+            let done = batch.len() < 100;
+            issues.extend(batch.into_iter().filter(|i| i.pull_request.is_none()).map(|issue| {
+                rest.remember(&issue);
+                issue.into_existing()
+            }));
+            if done {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Issues created at or after `since`, newest first, skipping pull
+    /// requests
+    pub(super) async fn rest_issues_created_since(
+        &self,
+        rest: &RestApi,
+        since: chrono::DateTime<chrono::FixedOffset>,
+    ) -> Result<Vec<ExistingIssue>> {
+        let path = rest.repo_path("/issues");
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let page = page.to_string();
+            let query = [
+                ("state", "all"),
+                ("sort", "created"),
+                ("direction", "desc"),
+                ("per_page", "100"),
+                ("page", page.as_str()),
+            ];
+            let batch: Vec<RestIssue> = self.rest(rest, Method::GET, &path, &query, None).await?;
+            let full = batch.len() == 100;
+            let mut reached_older = false;
+            for issue in batch {
+                if created_before(&issue.created_at, since) {
+                    reached_older = true;
+                    break;
+                }
+                if issue.pull_request.is_none() {
+                    rest.remember(&issue);
+                    issues.push(issue.into_existing());
+                }
+            }
+            if reached_older || !full {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    pub(super) async fn rest_count_open_assigned(&self, rest: &RestApi, logins: &[String]) -> Result<Vec<u64>> {
+        let mut counts = Vec::with_capacity(logins.len());
+        for login in logins {
+            let q = format!("repo:{}/{} is:issue is:open assignee:{}", rest.owner, rest.name, login);
+            let search: RestSearchCount = self
+                .rest(rest, Method::GET, "/search/issues", &[("q", &q), ("per_page", "1")], None)
+                .await?;
+            counts.push(search.total_count);
+        }
+        Ok(counts)
+    }
+
+    /// Update titles and bodies, keyed by issue node ID like the GraphQL batch
+    pub(super) async fn rest_update_issues(
+        &self,
+        rest: &RestApi,
+        updates: &[IssueUpdate],
+    ) -> HashMap<String, Result<IssueInfo, String>> {
+        let mut results = HashMap::new();
+        for update in updates {
+            let result = async {
+                let number = self.rest_issue_number(rest, &update.issue_id).await?;
+                let body = json!({ "title": update.title, "body": update.body });
+                let path = rest.repo_path(&format!("/issues/{}", number));
+                let issue: RestIssue = self.rest(rest, Method::PATCH, &path, &[], Some(body)).await?;
+                anyhow::Ok(IssueInfo {
+                    id: issue.node_id,
+                    number: issue.number,
+                    url: issue.html_url,
+                })
+            };
+            results.insert(update.issue_id.clone(), result.await.map_err(|e| e.to_string()));
+        }
+        results
+    }
+
+    /// Add and remove assignees by login, keyed by issue node ID like the
+    /// GraphQL batch
+    pub(super) async fn rest_update_assignees(
+        &self,
+        rest: &RestApi,
+        changes: &[AssigneeChange],
+    ) -> HashMap<String, Result<(), String>> {
+        let mut results = HashMap::new();
+        for change in changes {
+            let result = async {
+                let number = self.rest_issue_number(rest, &change.issue_id).await?;
+                let path = rest.repo_path(&format!("/issues/{}/assignees", number));
+                for (method, logins) in [(Method::POST, &change.add), (Method::DELETE, &change.remove)] {
+                    if !logins.is_empty() {
+                        let _: RestIssue = self
+                            .rest(rest, method, &path, &[], Some(json!({ "assignees": logins })))
+                            .await?;
+                    }
+                }
+                anyhow::Ok(())
+            };
+            results.insert(change.issue_id.clone(), result.await.map_err(|e| e.to_string()));
+        }
+        results
+    }
+
+    /// Add labels by name, keyed by issue node ID like the GraphQL batch
+    pub(super) async fn rest_add_labels(
+        &self,
+        rest: &RestApi,
+        additions: &[(String, Vec<String>)],
+    ) -> HashMap<String, Result<(), String>> {
+        let mut results = HashMap::new();
+        for (issue_id, names) in additions {
+            let result = async {
+                let number = self.rest_issue_number(rest, issue_id).await?;
+                let path = rest.repo_path(&format!("/issues/{}/labels", number));
+                let _: Vec<RestLabel> = self
+                    .rest(rest, Method::POST, &path, &[], Some(json!({ "labels": names })))
+                    .await?;
+                anyhow::Ok(())
+            };
+            results.insert(issue_id.clone(), result.await.map_err(|e| e.to_string()));
+        }
+        results
+    }
+
+    /// Set issues' state to "open" or "closed"
+    pub(super) async fn rest_set_state(&self, rest: &RestApi, issue_ids: &[String], state: &str) -> Result<()> {
+        for issue_id in issue_ids {
+            let number = self.rest_issue_number(rest, issue_id).await?;
+            let path = rest.repo_path(&format!("/issues/{}", number));
+            let _: RestIssue = self
+                .rest(rest, Method::PATCH, &path, &[], Some(json!({ "state": state })))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 // Sub-issue relationship management
 
+use super::batch::Batch;
 use super::client::GitHubClient;
 use anyhow::Result;
 use serde::Deserialize;
@@ -63,13 +64,8 @@ impl GitHubClient {
         match self.mutate::<AddSubIssueResponse>(mutation, Some(variables)).await {
             Ok(_) => Ok(()),
             Err(e) => {
-                let err_str = e.to_string().to_lowercase();
                 // GitHub returns these errors if already linked
-                if err_str.contains("already a sub-issue") || 
-                   err_str.contains("is already a child") ||
-                   err_str.contains("already has this sub-issue") ||
-                   err_str.contains("duplicate sub-issues") ||
-                   err_str.contains("may only have one parent") {
+                if is_already_linked(&e.to_string()) {
                     Ok(()) // Treat as success - idempotent
                 } else {
                     Err(e)
@@ -90,52 +86,26 @@ impl GitHubClient {
             return Ok(Vec::new());
         }
 
-        // Build dynamic mutation with aliases
-        let mutations: Vec<String> = links
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
-                format!(
-                    "link_{i}: addSubIssue(input: $input_{i}) {{ subIssue {{ id }} }}"
-                )
-            })
-            .collect();
-
-        // Build variable definitions
-        let var_defs: Vec<String> = links
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format!("$input_{}: AddSubIssueInput!", i))
-            .collect();
-
-        let mutation = format!(
-            "mutation({}) {{\n  {}\n}}",
-            var_defs.join(", "),
-            mutations.join("\n  ")
-        );
-
-        // Build variables object
-        let mut variables = serde_json::Map::new();
-        for (i, link) in links.iter().enumerate() {
-            variables.insert(
-                format!("input_{}", i),
-                json!({
-                    "issueId": link.parent_issue_id,
-                    "subIssueId": link.child_issue_id
-                }),
-            );
+        let mut batch = Batch::mutation("link", "addSubIssue", "AddSubIssueInput!", "{ subIssue { id } }");
+        for link in links {
+            batch.input(json!({
+                "issueId": link.parent_issue_id,
+                "subIssueId": link.child_issue_id
+            }));
         }
 
         // Execute - handle "already linked" errors as success (idempotent)
-        match self
-            .mutate::<serde_json::Value>(&mutation, Some(serde_json::Value::Object(variables)))
-            .await
-        {
+        match self.run_batch(&batch).await {
             Ok(response) => {
                 let mut results = Vec::with_capacity(links.len());
                 for i in 0..links.len() {
-                    let key = format!("link_{}", i);
-                    if let Some(data) = response.get(&key) {
+                    if let Some(error) = response.error(i) {
+                        if is_already_linked(error) {
+                            results.push(Ok(()));
+                        } else {
+                            results.push(Err(response.failure(i, "Failed to add sub-issue")));
+                        }
+                    } else if let Some(data) = response.get(i) {
                         if data.get("subIssue").is_some() {
                             results.push(Ok(()));
                         } else {
@@ -149,14 +119,8 @@ impl GitHubClient {
                 Ok(results)
             }
             Err(e) => {
-                let err_str = e.to_string().to_lowercase();
                 // If error is "already linked", treat all as success (idempotent)
-                if err_str.contains("already a sub-issue")
-                    || err_str.contains("is already a child")
-                    || err_str.contains("already has this sub-issue")
-                    || err_str.contains("duplicate sub-issues")
-                    || err_str.contains("may only have one parent")
-                {
+                if is_already_linked(&e.to_string()) {
                     Ok(vec![Ok(()); links.len()])
                 } else {
                     Err(e)
@@ -165,3 +129,13 @@ impl GitHubClient {
         }
     }
 }
+
+/// Whether GitHub refused a sub-issue link because it already exists
+fn is_already_linked(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("already a sub-issue")
+        || message.contains("is already a child")
+        || message.contains("already has this sub-issue")
+        || message.contains("duplicate sub-issues")
+        || message.contains("may only have one parent")
+}
@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const BLOCK_START: &str = "# >>> ttr >>>";
+const BLOCK_END: &str = "# <<< ttr <<<";
+
+/// A git hook ttr knows how to install
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Fails the push when tickets have unpushed changes
+    PrePush,
+    /// Prints local sync state after each commit
+    PostCommit,
+}
+
+impl Hook {
+    /// Hook file name under the hooks directory
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::PrePush => "pre-push",
+            Hook::PostCommit => "post-commit",
+        }
+    }
+
+    /// Shell command run by the hook
+    fn command(self) -> &'static str {
+        match self {
+            Hook::PrePush => "ttr push --check || exit 1",
+            Hook::PostCommit => "ttr status --quick || true",
+        }
+    }
+}
+
+/// Locate the hooks directory of the current git repository
+///
+/// Respects `core.hooksPath` and worktrees via `git rev-parse --git-path`.
+pub fn hooks_dir() -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Not inside a git repository");
+    }
+
+    Ok(PathBuf::from(String::from_utf8(output.stdout)?.trim()))
+}
+
+/// Install a hook, preserving any existing non-ttr hook content
+pub fn install(dir: &Path, hook: Hook) -> Result<PathBuf> {
+    let path = dir.join(hook.name());
+    let existing = if path.exists() {
+        Some(fs::read_to_string(&path)?)
+    } else {
+        None
+    };
+
+    fs::create_dir_all(dir)?;
+    fs::write(&path, add_block(existing.as_deref(), hook.command()))
+        .with_context(|| format!("Failed to write hook: {}", path.display()))?;
+    make_executable(&path)?;
+
+    Ok(path)
+}
+
+/// Remove ttr's block from a hook, deleting the file if nothing else remains
+///
+/// Returns false if the hook had no ttr block.
+pub fn uninstall(dir: &Path, hook: Hook) -> Result<bool> {
+    let path = dir.join(hook.name());
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(&path)?;
+    if !content.contains(BLOCK_START) {
+        return Ok(false);
+    }
+
+    match remove_block(&content) {
+        Some(rest) => fs::write(&path, rest)?,
+        None => fs::remove_file(&path)?,
+    }
+    Ok(true)
+}
+
+/// Add (or replace) the ttr block in hook content
+fn add_block(existing: Option<&str>, command: &str) -> String {
+    let block = format!("{}\n{}\n{}\n", BLOCK_START, command, BLOCK_END);
+
+    match existing.and_then(remove_block) {
+        Some(rest) => format!("{}\n{}", rest.trim_end(), block),
+        None => format!("#!/bin/sh\n{}", block),
+    }
+}
+
+/// Strip the ttr block, returning None if only a shebang would remain
+fn remove_block(content: &str) -> Option<String> {
+    let mut in_block = false;
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            if *line == BLOCK_START {
+                in_block = true;
+                return false;
+            }
+            if *line == BLOCK_END {
+                in_block = false;
+                return false;
+            }
+            !in_block
+        })
+        .collect();
+
+    let has_content = lines
+        .iter()
+        .any(|l| !l.trim().is_empty() && !l.starts_with("#!"));
+    if has_content {
+        Some(format!("{}\n", lines.join("\n").trim_end()))
+    } else {
+        None
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(perms.mode() | 0o755);
+    fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_add_block_new_hook() {
+        let content = add_block(None, "ttr push --check || exit 1");
+        assert_eq!(
+            content,
+            "#!/bin/sh\n# >>> ttr >>>\nttr push --check || exit 1\n# <<< ttr <<<\n"
+        );
+    }
+
+    #[test]
+    fn test_add_block_preserves_existing_hook() {
+        let existing = "#!/bin/sh\nmake lint\n";
+        let content = add_block(Some(existing), "ttr push --check || exit 1");
+        assert!(content.starts_with("#!/bin/sh\nmake lint\n"));
+        assert!(content.contains(BLOCK_START));
+
+        // Reinstalling replaces rather than duplicates the block
+        let again = add_block(Some(&content), "ttr push --check || exit 1");
+        assert_eq!(again, content);
+    }
+
+    #[test]
+    fn test_install_and_uninstall() {
+        let dir = TempDir::new().unwrap();
+        let other = dir.path().join("pre-push");
+        fs::write(&other, "#!/bin/sh\nmake lint\n").unwrap();
+
+        install(dir.path(), Hook::PrePush).unwrap();
+        install(dir.path(), Hook::PostCommit).unwrap();
+
+        assert!(uninstall(dir.path(), Hook::PrePush).unwrap());
+        assert_eq!(fs::read_to_string(&other).unwrap(), "#!/bin/sh\nmake lint\n");
+
+        // A hook that only held ttr's block is removed entirely
+        assert!(uninstall(dir.path(), Hook::PostCommit).unwrap());
+        assert!(!dir.path().join("post-commit").exists());
+        assert!(!uninstall(dir.path(), Hook::PostCommit).unwrap());
+    }
+}
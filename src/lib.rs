@@ -1,5 +1,35 @@
+pub mod audit;
 pub mod auth;
+pub mod board;
+pub mod branch;
+pub mod burndown;
+pub mod cache;
+pub mod commits;
 pub mod config;
+pub mod conflicts;
+pub mod config_schema;
+pub mod deps;
+pub mod frontmatter;
 pub mod github;
+pub mod hooks;
+pub mod lint;
+pub mod lock;
+pub mod notify;
+pub mod preflight;
+pub mod pull;
+pub mod push_plan;
+pub mod queue;
+pub mod rename;
+pub mod report;
+pub mod secrets;
+pub mod state;
+pub mod stats;
 pub mod sync;
+pub mod template;
 pub mod ticket;
+pub mod timings;
+pub mod transfer;
+pub mod tree;
+pub mod undo;
+pub mod watch;
+pub mod worklog;
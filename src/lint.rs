@@ -0,0 +1,266 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::config::ExternalLintConfig;
+use crate::ticket::Ticket;
+
+/// How a lint rule failure is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Warn,
+    Error,
+}
+
+/// A ticket check, with its default severity
+struct Rule {
+    name: &'static str,
+    default: Severity,
+    /// Problem description if the ticket breaks the rule
+    check: fn(&Ticket, &[Ticket]) -> Option<String>,
+}
+
+const RULES: &[Rule] = &[
+    Rule {
+        name: "title-required",
+        default: Severity::Error,
+        check: |t, _| (t.title.trim().is_empty() || t.title == "Untitled").then(|| "missing `# ` title".to_string()),
+    },
+    Rule {
+        name: "description-required",
+        default: Severity::Warn,
+        check: |t, _| t.body.trim().is_empty().then(|| "empty description".to_string()),
+    },
+    Rule {
+        name: "bug-acceptance-criteria",
+        default: Severity::Warn,
+        check: |t, _| {
+            (t.ticket_type == "bug" && t.acceptance_criteria().is_none())
+                .then(|| "bug has no acceptance criteria".to_string())
+        },
+    },
+    Rule {
+        name: "p0-assignee",
+        default: Severity::Warn,
+        check: |t, _| {
+            (t.priority == 0 && t.assignee.is_none()).then(|| "priority 0 ticket has no assignee".to_string())
+        },
+    },
+    Rule {
+        name: "known-references",
+        default: Severity::Error,
+        check: |t, all| {
+            let unknown: Vec<&str> = t
+                .deps
+                .iter()
+                .chain(&t.links)
+                .chain(&t.parent)
+                .chain(&t.duplicate_of)
+                .filter(|id| !all.iter().any(|other| other.id == **id))
+                .map(|id| id.as_str())
+                .collect();
+            (!unknown.is_empty()).then(|| format!("references unknown ticket(s): {}", unknown.join(", ")))
+        },
+    },
+];
+
+/// A rule a ticket breaks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub ticket: String,
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    /// Line in the ticket file the finding points at, if any
+    pub line: Option<usize>,
+}
+
+/// Rule name `[lint.external]` findings are reported under
+pub const EXTERNAL_RULE: &str = "external";
+
+/// Names of all lint rules
+pub fn rule_names() -> impl Iterator<Item = &'static str> {
+    RULES.iter().map(|r| r.name)
+}
+
+/// Check `tickets` against every rule not turned off in `severities`
+///
+/// `all_tickets` resolves references, so a subset can be linted on its own.
+pub fn lint(tickets: &[Ticket], all_tickets: &[Ticket], severities: &HashMap<String, Severity>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for ticket in tickets {
+        for rule in RULES {
+            let severity = severities.get(rule.name).copied().unwrap_or(rule.default);
+            if severity == Severity::Off {
+                continue;
+            }
+            if let Some(message) = (rule.check)(ticket, all_tickets) {
+                findings.push(Finding {
+                    ticket: ticket.id.clone(),
+                    rule: rule.name,
+                    severity,
+                    message,
+                    line: None,
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// Run the `[lint.external]` command on each ticket's markdown
+///
+/// The markdown after the frontmatter goes to the command's stdin, with
+/// `TTR_TICKET_ID` and `TTR_TICKET_PATH` set. Each non-empty output line is
+/// a finding; one like `12:5: message`, or `stdin.md:12:5:rule:message` as
+/// `vale --output=line` prints, points at that line of the markdown, mapped
+/// to its line in the ticket file.
+pub fn lint_external(tickets: &[Ticket], external: &ExternalLintConfig) -> Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    if external.severity == Severity::Off {
+        return Ok(findings);
+    }
+    for ticket in tickets {
+        let output = run_external(&external.cmd, ticket)?;
+        let offset = markdown_offset(ticket);
+        for line in output.lines().filter(|l| !l.trim().is_empty()) {
+            let (at, message) = parse_external_line(line);
+            findings.push(Finding {
+                ticket: ticket.id.clone(),
+                rule: EXTERNAL_RULE,
+                severity: external.severity,
+                message,
+                line: at.zip(offset).map(|(at, offset)| at + offset),
+            });
+        }
+    }
+    Ok(findings)
+}
+
+/// The command's output for one ticket
+///
+/// Checkers exit non-zero when they find problems, so only a failure with
+/// nothing on stdout is an error.
+fn run_external(cmd: &str, ticket: &Ticket) -> Result<String> {
+    let mut child = Command::new("sh")
+        .args(["-c", cmd])
+        .env("TTR_TICKET_ID", &ticket.id)
+        .env("TTR_TICKET_PATH", &ticket.path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run lint command `{}`", cmd))?;
+    // A command that exits without reading its input closes the pipe early
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(ticket.markdown.as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("Failed to run lint command `{}`", cmd))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if !output.status.success() && stdout.trim().is_empty() {
+        anyhow::bail!(
+            "Lint command `{}` failed on {}: {}",
+            cmd,
+            ticket.id,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(stdout)
+}
+
+/// Lines in the ticket file before its markdown starts, None if it can't
+/// be found
+fn markdown_offset(ticket: &Ticket) -> Option<usize> {
+    let first = ticket.markdown.lines().next()?;
+    let raw = fs::read_to_string(&ticket.path).ok()?;
+    // Past the frontmatter, which could repeat the first line
+    let body_start = raw.strip_prefix("---").and_then(|rest| rest.find("\n---")).map_or(0, |end| end + 7);
+    let start = body_start + raw.get(body_start..)?.find(first)?;
+    Some(raw[..start].matches('\n').count())
+}
+
+/// The line number an output line points at (1-based, within the
+/// markdown) and its message
+///
+/// The line is the first or second `:`-separated field that's a number; a
+/// column right after it is dropped, as is a file name before it.
+fn parse_external_line(line: &str) -> (Option<usize>, String) {
+    let parts: Vec<&str> = line.split(':').collect();
+    let is_number = |p: &&str| p.trim().parse::<usize>().is_ok();
+    let Some(i) = parts.iter().take(2).position(is_number) else {
+        return (None, line.trim().to_string());
+    };
+    let mut rest = i + 1;
+    if parts.get(rest).is_some_and(is_number) {
+        rest += 1;
+    }
+    (parts[i].trim().parse().ok(), parts[rest.min(parts.len())..].join(":").trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::LoadOptions;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lint_rules_and_severities() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "---\nid: a\ntype: bug\npriority: 0\ndeps: [zzz]\n---\n# A\n").unwrap();
+        fs::write(dir.path().join("b.md"), "---\nid: b\nparent: a\n---\n# B\n\nDone.\n").unwrap();
+        let tickets = Ticket::load_all(dir.path(), LoadOptions::default()).unwrap();
+
+        let findings = lint(&tickets, &tickets, &HashMap::new());
+        let rules: Vec<(&str, &str, Severity)> =
+            findings.iter().map(|f| (f.ticket.as_str(), f.rule, f.severity)).collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("a", "description-required", Severity::Warn),
+                ("a", "bug-acceptance-criteria", Severity::Warn),
+                ("a", "p0-assignee", Severity::Warn),
+                ("a", "known-references", Severity::Error),
+            ]
+        );
+
+        let severities = HashMap::from([
+            ("known-references".to_string(), Severity::Off),
+            ("p0-assignee".to_string(), Severity::Error),
+        ]);
+        let findings = lint(&tickets, &tickets, &severities);
+        assert_eq!(findings.len(), 3);
+        assert!(findings.iter().any(|f| f.rule == "p0-assignee" && f.severity == Severity::Error));
+    }
+
+    #[test]
+    fn test_lint_external_maps_lines_to_ticket_file() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "---\nid: a\n---\n# A\n\nTeh body.\n").unwrap();
+        let tickets = Ticket::load_all(dir.path(), LoadOptions::default()).unwrap();
+        let config = crate::config::Config::parse(
+            "[github]\nrepo = \"o/r\"\n\n[lint]\np0-assignee = \"error\"\n\n[lint.external]\n\
+             cmd = \"grep -n Teh | sed 's/:.*/:1:Spelling:Did you mean The?/; s/^/stdin.md:/'; echo '1 warning'\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.lint.rules["p0-assignee"], Severity::Error);
+        let external = config.lint.external.unwrap();
+
+        let findings = lint_external(&tickets, &external).unwrap();
+        let found: Vec<(Option<usize>, &str)> = findings.iter().map(|f| (f.line, f.message.as_str())).collect();
+        assert_eq!(found, vec![(Some(6), "Spelling:Did you mean The?"), (None, "1 warning")]);
+        assert!(findings.iter().all(|f| f.rule == EXTERNAL_RULE && f.severity == Severity::Warn));
+
+        let failing = ExternalLintConfig { cmd: "echo oops >&2; exit 2".to_string(), severity: Severity::Error };
+        let err = lint_external(&tickets, &failing).unwrap_err();
+        assert_eq!(err.to_string(), "Lint command `echo oops >&2; exit 2` failed on a: oops");
+    }
+}
@@ -0,0 +1,215 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Lock file, relative to the tickets directory
+pub const LOCK_FILE: &str = ".sync.lock";
+
+/// Locks from other hosts older than this are considered abandoned
+const STALE_AFTER_HOURS: i64 = 1;
+
+/// Who holds the lock, as written to the lock file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub pid: u32,
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub command: String,
+    pub started: String,
+}
+
+impl LockInfo {
+    /// Whether the holder is gone: its process on this host has exited, or
+    /// for a holder that can't be checked, the lock is old
+    ///
+    /// A live process keeps its lock however long it runs, e.g. a paced push.
+    fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        if self.host == hostname() {
+            if let Some(alive) = process_alive(self.pid) {
+                return !alive;
+            }
+        }
+        DateTime::parse_from_rfc3339(&self.started)
+            .map(|t| now - t.with_timezone(&Utc) > Duration::hours(STALE_AFTER_HOURS))
+            .unwrap_or(true)
+    }
+}
+
+/// Exclusive lock on the tickets directory, released when dropped
+#[derive(Debug)]
+pub struct SyncLock {
+    path: PathBuf,
+}
+
+impl SyncLock {
+    /// Acquire the lock for a mutating command, replacing a stale lock
+    pub fn acquire(tickets_dir: &Path, command: &str) -> Result<Self> {
+        let path = tickets_dir.join(LOCK_FILE);
+        let info = LockInfo {
+            pid: std::process::id(),
+            host: hostname(),
+            command: command.to_string(),
+            started: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        };
+
+        // One retry after clearing a stale lock
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(serde_json::to_string(&info)?.as_bytes())
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    match read(&path) {
+                        Some(holder) if !holder.is_stale(Utc::now()) => anyhow::bail!(
+                            "Another ttr {} is running (pid {} on {}, started {}).\n\
+                             If it isn't, rerun with --force-unlock.",
+                            holder.command,
+                            holder.pid,
+                            holder.host,
+                            holder.started
+                        ),
+                        Some(holder) => eprintln!(
+                            "WARN    Removing stale lock from pid {} on {} (started {})",
+                            holder.pid, holder.host, holder.started
+                        ),
+                        None => eprintln!("WARN    Removing unreadable lock {}", path.display()),
+                    }
+                    let _ = fs::remove_file(&path);
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to create {}", path.display()));
+                }
+            }
+        }
+
+        anyhow::bail!("Failed to acquire {}", path.display())
+    }
+}
+
+impl Drop for SyncLock {
+    fn drop(&mut self) {
+        // Leave the lock alone if it was forcibly taken over
+        if read(&self.path).is_some_and(|holder| holder.pid == std::process::id()) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Remove the lock regardless of who holds it, returning the previous holder
+pub fn force_unlock(tickets_dir: &Path) -> Result<Option<LockInfo>> {
+    let path = tickets_dir.join(LOCK_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let holder = read(&path);
+    fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(holder)
+}
+
+fn read(path: &Path) -> Option<LockInfo> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn hostname() -> String {
+    Command::new("hostname")
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Whether a process on this host is running, or `None` if that can't be told
+#[cfg(unix)]
+fn process_alive(pid: u32) -> Option<bool> {
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .output()
+        .ok()
+        .map(|o| o.status.success())
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> Option<bool> {
+    // Can't tell; rely on the age check
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        let dir = TempDir::new().unwrap();
+
+        let lock = SyncLock::acquire(dir.path(), "push").unwrap();
+        assert!(dir.path().join(LOCK_FILE).exists());
+
+        let err = SyncLock::acquire(dir.path(), "push").unwrap_err();
+        assert!(err.to_string().contains("--force-unlock"));
+
+        drop(lock);
+        assert!(!dir.path().join(LOCK_FILE).exists());
+        assert!(SyncLock::acquire(dir.path(), "push").is_ok());
+    }
+
+    fn holder(pid: u32, host: &str, started: &str) -> LockInfo {
+        LockInfo {
+            pid,
+            host: host.to_string(),
+            command: "push".to_string(),
+            started: started.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_stale_lock_is_replaced() {
+        let dir = TempDir::new().unwrap();
+        let stale = holder(std::process::id(), "other-host", "2020-01-01T00:00:00Z");
+        fs::write(dir.path().join(LOCK_FILE), serde_json::to_string(&stale).unwrap()).unwrap();
+
+        let _lock = SyncLock::acquire(dir.path(), "plan").unwrap();
+        let holder = read(&dir.path().join(LOCK_FILE)).unwrap();
+        assert_eq!(holder.command, "plan");
+    }
+
+    #[test]
+    fn test_live_holder_on_this_host_keeps_old_lock() {
+        let now = Utc::now();
+        let started = now.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+        // Running longer than the cutoff doesn't give the lock away
+        assert!(!holder(std::process::id(), &hostname(), "2020-01-01T00:00:00Z").is_stale(now));
+        assert!(holder(std::process::id(), "other-host", "2020-01-01T00:00:00Z").is_stale(now));
+        assert!(!holder(std::process::id(), "other-host", &started).is_stale(now));
+
+        #[cfg(unix)]
+        {
+            let mut child = Command::new("true").spawn().unwrap();
+            let pid = child.id();
+            child.wait().unwrap();
+            assert!(holder(pid, &hostname(), &started).is_stale(now));
+        }
+    }
+
+    #[test]
+    fn test_force_unlock() {
+        let dir = TempDir::new().unwrap();
+        assert!(force_unlock(dir.path()).unwrap().is_none());
+
+        let lock = SyncLock::acquire(dir.path(), "push").unwrap();
+        let holder = force_unlock(dir.path()).unwrap().unwrap();
+        assert_eq!(holder.pid, std::process::id());
+        assert!(SyncLock::acquire(dir.path(), "push").is_ok());
+        drop(lock);
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
-use ticket_to_ride::{auth, config::Config, github::client::GitHubClient, sync::SyncEngine, ticket::Ticket};
+use clap::{Parser, Subcommand, ValueEnum};
+use ticket_to_ride::{audit::AuditLog, auth, cache::IssueCache, config::Config, conflicts, github::client::GitHubClient, lock::{self, SyncLock}, queue::PushQueue, state::{InterruptedPush, SyncState}, sync::{self, SyncEngine, SyncOptions, SyncSummary}, ticket::{self, LoadOptions, Ticket}, timings};
 
 #[derive(Parser)]
 #[command(name = "ttr")]
@@ -8,6 +8,18 @@ use ticket_to_ride::{auth, config::Config, github::client::GitHubClient, sync::S
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Remove a leftover sync lock before running
+    #[arg(long, global = true)]
+    force_unlock: bool,
+    /// Skip tickets whose ID is already used by another file instead of failing
+    #[arg(long, global = true)]
+    skip_duplicates: bool,
+    /// Refetch cached labels and issue types from GitHub
+    #[arg(long, global = true)]
+    refresh_cache: bool,
+    /// Abort when any ticket file fails to load instead of skipping it
+    #[arg(long, global = true)]
+    strict: bool,
 }
 
 #[derive(Subcommand)]
@@ -16,12 +28,240 @@ enum Commands {
     Push {
         /// Specific ticket IDs to sync (syncs all if omitted)
         ids: Vec<String>,
+        /// Report pending changes without pushing; exit 1 if any
+        #[arg(long)]
+        check: bool,
+        /// Finish a push stopped by Ctrl-C, with its tickets and options
+        #[arg(long, conflicts_with_all = ["ids", "check"])]
+        resume: bool,
+        /// Record the tickets for a later `--flush` instead of contacting GitHub
+        #[arg(long, conflicts_with_all = ["check", "resume"])]
+        queue: bool,
+        /// Push the tickets recorded with `--queue`
+        #[arg(long, conflicts_with_all = ["ids", "check", "resume", "queue"])]
+        flush: bool,
+        /// Push only the tickets earlier pushes failed on, with their options
+        #[arg(long, conflicts_with_all = ["ids", "check", "resume", "queue", "flush"])]
+        retry_failed: bool,
+        /// Print time per phase and GitHub request latencies at the end
+        #[arg(long)]
+        timings: bool,
+        /// Fail instead of warning when closing a ticket that open tickets depend on
+        #[arg(long)]
+        strict_deps: bool,
+        /// Push tickets even if their text looks like it contains tokens or passwords
+        #[arg(long)]
+        allow_secrets: bool,
+        /// Skip projects: no project items, fields, or drafts
+        #[arg(long)]
+        no_project: bool,
+        /// Skip linking sub-issues
+        #[arg(long)]
+        no_subissues: bool,
+        /// Skip labels on new issues
+        #[arg(long)]
+        no_labels: bool,
+        /// Skip syncing project Status, Type, and custom fields for existing issues
+        #[arg(long)]
+        no_status_sync: bool,
+        /// Send at most this many mutations a minute, checkpointing for `--resume`
+        #[arg(long, value_name = "REQUESTS_PER_MINUTE", value_parser = clap::value_parser!(u32).range(1..))]
+        pace: Option<u32>,
+        /// Create issues for only the N highest-priority new tickets; updates still all go out
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with_all = ["check", "resume", "queue", "retry_failed"],
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        top: Option<u32>,
+    },
+    /// Write what a push would change to a plan file for review, without pushing
+    PlanPush {
+        /// Specific ticket IDs to plan (plans all if omitted)
+        ids: Vec<String>,
+        /// Where to write the plan
+        #[arg(long)]
+        out: std::path::PathBuf,
+    },
+    /// Push exactly what a `plan-push` plan lists, refusing if its tickets or
+    /// issues changed since
+    Apply {
+        /// Plan file written by `ttr plan-push`
+        plan: std::path::PathBuf,
+    },
+    /// Pull title and status changes made on GitHub, then push local changes
+    Sync {
+        /// Specific ticket IDs to sync (syncs all if omitted)
+        ids: Vec<String>,
     },
     /// Show sync status of tickets
     Status {
         /// Quick mode: skip GitHub fetch, just show local state
         #[arg(short, long)]
         quick: bool,
+        /// Show blocked and ready tickets from dependencies instead
+        #[arg(short, long)]
+        blocked: bool,
+        /// Order synced tickets, e.g. by 👍 reactions on their issues
+        #[arg(long, value_enum, conflicts_with_all = ["quick", "blocked"])]
+        sort: Option<StatusSort>,
+        /// List open GitHub issues no ticket tracks instead
+        #[arg(long, conflicts_with_all = ["quick", "blocked", "sort"])]
+        remote_only: bool,
+        /// With --remote-only, only issues with this label
+        #[arg(long, requires = "remote_only")]
+        label: Option<String>,
+    },
+    /// Interactive board of tickets by status
+    Board,
+    /// Watch ticket files and push changes as they are saved
+    Watch {
+        /// Quiet period in milliseconds before pushing a batch of edits
+        #[arg(short, long, default_value_t = 1500)]
+        debounce: u64,
+        /// Preview what would be pushed without touching GitHub
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+        /// Ask for confirmation before each push
+        #[arg(short, long)]
+        confirm: bool,
+    },
+    /// Summarize the backlog
+    Stats {
+        /// Output JSON instead of tables
+        #[arg(long)]
+        json: bool,
+        /// Weeks of opened/closed history to show
+        #[arg(short, long, default_value_t = 8)]
+        weeks: usize,
+    },
+    /// Show completed/remaining work per project iteration
+    Burndown {
+        /// Project to report on (defaults to the first configured project)
+        #[arg(short, long)]
+        project: Option<String>,
+        /// Draw an ASCII burndown chart for the current iteration
+        #[arg(short, long)]
+        chart: bool,
+    },
+    /// Write a markdown report of tickets out of step with GitHub
+    Report {
+        /// File to write the report to (stdout if omitted)
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Report labels, statuses, iterations, and assignees a push would need
+    /// to create or couldn't resolve, without changing anything
+    Preflight {
+        /// Specific ticket IDs to check (checks all if omitted)
+        ids: Vec<String>,
+    },
+    /// Assign tickets to a project iteration and status in bulk
+    Plan {
+        /// Specific ticket IDs to plan (all open tickets if omitted)
+        ids: Vec<String>,
+        /// Only tickets with this tag (repeatable)
+        #[arg(short, long)]
+        tag: Vec<String>,
+        /// Only tickets at this priority or higher (0 = highest)
+        #[arg(short, long)]
+        priority: Option<u8>,
+        /// Iteration to assign (name, @current, @next, @current+N)
+        #[arg(short, long)]
+        iteration: Option<String>,
+        /// Project status to set (option name or ticket status)
+        #[arg(short, long)]
+        status: Option<String>,
+    },
+    /// Create and check out a git branch for a ticket
+    Branch {
+        /// Ticket ID
+        id: String,
+    },
+    /// Read, change, or check sync.toml
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Check tickets against the lint rules
+    Lint {
+        /// Specific ticket IDs to check (checks all if omitted)
+        ids: Vec<String>,
+    },
+    /// Change ticket IDs in bulk
+    Id {
+        #[command(subcommand)]
+        action: IdAction,
+    },
+    /// Rename a ticket ID and update references to it
+    Rename {
+        /// Current ticket ID
+        old: String,
+        /// New ticket ID
+        new: String,
+    },
+    /// Move a ticket's issue to another repository
+    Transfer {
+        /// Ticket ID
+        id: String,
+        /// Repository to move the issue to (owner/name)
+        #[arg(long)]
+        to: String,
+    },
+    /// Rewrite sync.toml from an older ttr in the current layout
+    UpgradeConfig {
+        /// Show the changes without writing sync.toml
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+    /// Rewrite issue body markers created by older ttr versions in the current format
+    MigrateMarkers {
+        /// Show which issues would change without updating them
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+    /// Close tickets referenced by closing keywords in commit messages
+    ScanCommits {
+        /// Git revision range to scan (e.g. main..HEAD)
+        range: String,
+        /// Show which tickets would close without changing anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+        /// Close tickets locally without pushing to GitHub
+        #[arg(long)]
+        no_push: bool,
+    },
+    /// Revert the most recent push recorded in the audit log
+    Undo {
+        /// Show what would be reverted without changing anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+        /// Restore local external-refs without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Show epics and their children as a tree
+    Tree {
+        /// Show only the subtree under this ticket
+        id: Option<String>,
+    },
+    /// Show the audit log entries for one ticket
+    History {
+        /// Ticket ID (partial IDs are accepted)
+        id: String,
+        /// Print changed values in full instead of their first line
+        #[arg(long)]
+        full: bool,
+    },
+    /// Install git hooks that run ttr
+    InstallHooks {
+        /// Also install a post-commit hook running `ttr status --quick`
+        #[arg(long)]
+        post_commit: bool,
+        /// Remove ttr from the git hooks instead
+        #[arg(long)]
+        uninstall: bool,
     },
     /// Create .tickets/sync.toml configuration
     Init {
@@ -40,71 +280,1751 @@ enum Commands {
     },
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+/// Orders for `ttr status --sort`
+#[derive(Clone, Copy, ValueEnum)]
+enum StatusSort {
+    /// Most 👍 reactions first, listing every synced ticket
+    Reactions,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print a value, e.g. `ttr config get github.repo`
+    Get {
+        /// Dotted key
+        key: String,
+    },
+    /// Set a value, keeping comments, e.g. `ttr config set labels.sync_tags false`
+    Set {
+        /// Dotted key
+        key: String,
+        /// New value (TOML syntax, or a plain string)
+        value: String,
+    },
+    /// Check that sync.toml parses and is valid
+    Validate,
+    /// Print the JSON Schema for sync.toml
+    Schema,
+}
+
+#[derive(Subcommand)]
+enum IdAction {
+    /// Swap the ID prefix of every ticket, e.g. `ttr id remap ttr- t2r-`
+    Remap {
+        /// Prefix to replace
+        from: String,
+        /// New prefix
+        to: String,
+        /// Show the new IDs without changing anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let sync_options = SyncOptions { refresh_cache: cli.refresh_cache, ..Default::default() };
+    let load = LoadOptions { skip_duplicates: cli.skip_duplicates, strict: cli.strict, ..Default::default() };
+
+    if cli.force_unlock {
+        let (_, tickets_dir) = Config::load()?;
+        match lock::force_unlock(&tickets_dir)? {
+            Some(holder) => println!(
+                "Removed lock held by pid {} on {} (ttr {}, started {})",
+                holder.pid, holder.host, holder.command, holder.started
+            ),
+            None => println!("No sync lock to remove"),
+        }
+    }
+
+    match cli.command {
+        Commands::Push { ids, check, resume, queue, flush, retry_failed, timings, strict_deps, allow_secrets, no_project, no_subissues, no_labels, no_status_sync, pace, top } => {
+            let options = SyncOptions {
+                projects: !no_project,
+                sub_issues: !no_subissues,
+                labels: !no_labels,
+                status: !no_status_sync,
+                strict_deps,
+                allow_secrets,
+                pace,
+                top,
+                timings,
+                ..sync_options
+            };
+            match (queue, flush, retry_failed) {
+                (true, _, _) => cmd_push_queue(ids, load),
+                (_, true, _) => cmd_push_flush(options, load).await,
+                (_, _, true) => cmd_push_retry_failed(options, load).await,
+                _ => cmd_push(ids, check, resume, options, load).await,
+            }
+        }
+        Commands::PlanPush { ids, out } => cmd_plan_push(ids, out, sync_options, load).await,
+        Commands::Apply { plan } => cmd_apply(plan, sync_options, load).await,
+        Commands::Sync { ids } => cmd_sync(ids, sync_options, load).await,
+        Commands::Status { remote_only: true, label, .. } => cmd_status_remote_only(label, load).await,
+        Commands::Status { quick, blocked: true, .. } => cmd_status_blocked(quick, load).await,
+        Commands::Status { quick, blocked: false, sort, .. } => cmd_status(quick, sort, load).await,
+        Commands::Board => cmd_board(sync_options, load).await,
+        Commands::Watch { debounce, dry_run, confirm } => cmd_watch(debounce, dry_run, confirm, sync_options, load).await,
+        Commands::Stats { json, weeks } => cmd_stats(json, weeks, load),
+        Commands::Burndown { project, chart } => cmd_burndown(project, chart).await,
+        Commands::Report { output } => cmd_report(output, sync_options, load).await,
+        Commands::Preflight { ids } => cmd_preflight(ids, load).await,
+        Commands::Plan { ids, tag, priority, iteration, status } => {
+            cmd_plan(ids, tag, priority, iteration, status, sync_options, load).await
+        }
+        Commands::Branch { id } => cmd_branch(id, load),
+        Commands::Config { action } => cmd_config(action),
+        Commands::Lint { ids } => cmd_lint(ids, load),
+        Commands::Id { action: IdAction::Remap { from, to, dry_run } } => cmd_id_remap(from, to, dry_run, load),
+        Commands::Rename { old, new } => cmd_rename(old, new, load),
+        Commands::Transfer { id, to } => cmd_transfer(id, to, load).await,
+        Commands::UpgradeConfig { dry_run } => cmd_upgrade_config(dry_run),
+        Commands::MigrateMarkers { dry_run } => cmd_migrate_markers(dry_run, load).await,
+        Commands::ScanCommits { range, dry_run, no_push } => cmd_scan_commits(range, dry_run, no_push, sync_options, load).await,
+        Commands::Undo { dry_run, yes } => cmd_undo(dry_run, yes, load).await,
+        Commands::Tree { id } => cmd_tree(id, load),
+        Commands::History { id, full } => cmd_history(id, full, load),
+        Commands::InstallHooks { post_commit, uninstall } => cmd_install_hooks(post_commit, uninstall),
+        Commands::Init { repo, project, assignee, force } => cmd_init(repo, project, assignee, force).await,
+    }
+}
+
+async fn cmd_push(ids: Vec<String>, check: bool, resume: bool, options: SyncOptions, load: LoadOptions) -> Result<()> {
+    if check {
+        return check_tickets(&ids, options, load).await;
+    }
+
+    let (ids, options) = if resume {
+        let (_, tickets_dir) = Config::load()?;
+        match SyncState::load(&tickets_dir)?.interrupted {
+            Some(push) => {
+                println!("Resuming push interrupted at {}", push.at);
+                // Cache and timing flags are for this run, not the saved push
+                let options = SyncOptions { refresh_cache: options.refresh_cache, timings: options.timings, ..push.options };
+                (push.ids, options)
+            }
+            None => {
+                println!("No interrupted push to resume");
+                return Ok(());
+            }
+        }
+    } else {
+        (ids, options)
+    };
+
+    handle_interrupt();
+    let summary = push_tickets(&ids, options, load).await?;
+    notify("push", &summary, &[], 0).await?;
+
+    if summary.interrupted {
+        std::process::exit(130);
+    }
+    if summary.failed > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Record tickets to push later, without contacting GitHub
+fn cmd_push_queue(ids: Vec<String>, load: LoadOptions) -> Result<()> {
+    let (config, tickets_dir) = Config::load()?;
+    // A flush may be rewriting the queue
+    let _lock = SyncLock::acquire(&tickets_dir, "push")?;
+    let all_tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let ids = resolve_ids(&all_tickets, &ids)?;
+    let tickets = select_tickets(&all_tickets, &ids);
+    if tickets.is_empty() {
+        println!("No tickets matched the provided IDs: {:?}", ids);
+        return Ok(());
+    }
+
+    let mut queue = PushQueue::load(&tickets_dir)?;
+    for ticket in &tickets {
+        let op = queue.add(ticket);
+        println!("QUEUE   {}  ({})", ticket.id, op.as_str());
+    }
+    queue.save(&tickets_dir)?;
+
+    println!("\n{} ticket(s) queued. Run `ttr push --flush` when online.", queue.entries.len());
+    Ok(())
+}
+
+/// Push queued tickets, keeping the ones that didn't go through queued
+async fn cmd_push_flush(options: SyncOptions, load: LoadOptions) -> Result<()> {
+    let (config, tickets_dir) = Config::load()?;
+    // Held until the queue is saved, so tickets queued meanwhile aren't lost
+    let lock = SyncLock::acquire(&tickets_dir, "push")?;
+    let mut queue = PushQueue::load(&tickets_dir)?;
+    if queue.entries.is_empty() {
+        println!("Push queue is empty");
+        return Ok(());
+    }
+
+    // Tickets deleted since they were queued have nothing left to push
+    let (loaded, parsed) =
+        timings::Span::time("parse tickets", || Ticket::load_all_counted(&tickets_dir, config.load_options(load)));
+    let (all_tickets, unloaded) = loaded?;
+    let ids: Vec<String> = queue
+        .ids()
+        .into_iter()
+        .filter(|id| all_tickets.iter().any(|t| t.id == *id))
+        .collect();
+
+    handle_interrupt();
+    let summary = if ids.is_empty() {
+        SyncSummary::default()
+    } else {
+        let tickets = push_selection(&all_tickets, &ids, &[]);
+        push_selected(config, &tickets_dir, tickets, &all_tickets, unloaded, parsed, options).await?
+    };
+
+    queue.entries.retain(|e| {
+        ids.contains(&e.ticket)
+            && if summary.interrupted {
+                !summary.in_sync.contains(&e.ticket)
+            } else {
+                summary.unresolved.contains(&e.ticket)
+            }
+    });
+    queue.save(&tickets_dir)?;
+    // `exit` below skips destructors
+    drop(lock);
+    if !queue.entries.is_empty() {
+        println!("{} ticket(s) still queued", queue.entries.len());
+    }
+    notify("push", &summary, &[], 0).await?;
+
+    if summary.interrupted {
+        std::process::exit(130);
+    }
+    if summary.failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Push again only the tickets earlier pushes failed on
+async fn cmd_push_retry_failed(options: SyncOptions, load: LoadOptions) -> Result<()> {
+    let (config, tickets_dir) = Config::load()?;
+    let lock = SyncLock::acquire(&tickets_dir, "push")?;
+    let Some(failed) = SyncState::load(&tickets_dir)?.failed else {
+        println!("No failed tickets to retry");
+        return Ok(());
+    };
+    let (loaded, parsed) =
+        timings::Span::time("parse tickets", || Ticket::load_all_counted(&tickets_dir, config.load_options(load)));
+    let (all_tickets, unloaded) = loaded?;
+
+    // Tickets deleted since have nothing left to push
+    let tickets = select_tickets(&all_tickets, &failed.ids);
+    if tickets.is_empty() {
+        println!("None of the failed tickets exist anymore");
+        let mut state = SyncState::load(&tickets_dir)?;
+        state.failed = None;
+        return state.save(&tickets_dir);
+    }
+    println!("Retrying {} ticket(s) that failed as of {}", tickets.len(), failed.at);
+
+    handle_interrupt();
+    let options = SyncOptions { refresh_cache: options.refresh_cache, timings: options.timings, ..failed.options };
+    let summary = push_selected(config, &tickets_dir, tickets, &all_tickets, unloaded, parsed, options).await?;
+    // `exit` below skips destructors
+    drop(lock);
+    notify("push", &summary, &[], 0).await?;
+
+    if summary.interrupted {
+        std::process::exit(130);
+    }
+    if summary.failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Stop pushes cleanly on Ctrl-C
+///
+/// The running phase finishes and writes back its results; a second Ctrl-C
+/// quits at once.
+fn handle_interrupt() {
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("\nInterrupted: finishing in-flight requests (Ctrl-C again to quit now)");
+            sync::request_cancel();
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(130);
+            }
+        }
+    });
+}
+
+/// Sync tickets to GitHub and print a summary
+async fn push_tickets(ids: &[String], options: SyncOptions, load: LoadOptions) -> Result<SyncSummary> {
+    push_tickets_except(ids, &[], options, load).await
+}
+
+/// Sync tickets to GitHub, leaving out the `exclude` IDs
+async fn push_tickets_except(ids: &[String], exclude: &[String], options: SyncOptions, load: LoadOptions) -> Result<SyncSummary> {
+    // Load config
+    let (config, tickets_dir) = Config::load()?;
+    let _lock = SyncLock::acquire(&tickets_dir, "push")?;
+
+    // Load ALL tickets (needed for dependency lookup)
+    let (loaded, parsed) =
+        timings::Span::time("parse tickets", || Ticket::load_all_counted(&tickets_dir, config.load_options(load)));
+    let (all_tickets, unloaded) = loaded?;
+
+    if all_tickets.is_empty() {
+        println!("No tickets found in {}", tickets_dir.display());
+        return Ok(SyncSummary::default());
+    }
+
+    // IDs from the command line may be prefixes or aliases
+    let ids = if ids.is_empty() { Vec::new() } else { resolve_ids(&all_tickets, ids)? };
+    let tickets = push_selection(&all_tickets, &ids, exclude);
+    if tickets.is_empty() {
+        println!("No tickets matched the provided IDs: {:?}", ids);
+        return Ok(SyncSummary::default());
+    }
+
+    push_selected(config, &tickets_dir, tickets, &all_tickets, unloaded, parsed, options).await
+}
+
+/// Tickets a push of `ids` covers, leaving out the `exclude` IDs
+fn push_selection(all_tickets: &[Ticket], ids: &[String], exclude: &[String]) -> Vec<Ticket> {
+    // Filter to specific IDs if provided, but keep all_tickets for lookup
+    let mut tickets = select_tickets(all_tickets, ids);
+    tickets.retain(|t| !exclude.contains(&t.id));
+    if tickets.is_empty() {
+        return tickets;
+    }
+
+    // Synced dependencies list their dependents under "Blocks", and epics
+    // list their children, so refresh them too
+    let dep_ids: Vec<String> = tickets
+        .iter()
+        .flat_map(|t| t.deps.iter().chain(&t.parent).cloned())
+        .collect();
+    for dep in all_tickets.iter().filter(|t| t.is_synced() && dep_ids.contains(&t.id)) {
+        if !tickets.iter().any(|t| t.id == dep.id) {
+            tickets.push(dep.clone());
+        }
+    }
+    tickets
+}
+
+/// Push `tickets` and print a summary; the caller holds the sync lock
+async fn push_selected(
+    config: Config,
+    tickets_dir: &std::path::Path,
+    mut tickets: Vec<Ticket>,
+    all_tickets: &[Ticket],
+    unloaded: usize,
+    parsed: timings::Span,
+    options: SyncOptions,
+) -> Result<SyncSummary> {
+    // Get auth token
+    let token = auth::get_github_token()?;
+
+    // Create GitHub client
+    let mut client = GitHubClient::for_config(token, &config)?;
+    if let Some(per_minute) = options.pace {
+        client = client.with_pace(per_minute);
+    }
+
+    println!("Syncing {} ticket(s) to {}...\n", tickets.len(), config.github.repo);
+
+    // Create sync engine and run (pass all_tickets for dependency lookup)
+    let mut engine = SyncEngine::with_options(client, config, tickets_dir, options)
+        .await?
+        .with_audit_log(AuditLog::new(tickets_dir))
+        .with_sync_state(tickets_dir);
+    if let Some(timings) = engine.timings() {
+        timings.add(parsed);
+    }
+    let summary = engine.sync(&mut tickets, all_tickets).await?;
+
+    // Tickets matching their issues are the baseline for the next `ttr sync`
+    let mut state = SyncState::load(tickets_dir)?;
+    state.record(tickets.iter().filter(|t| summary.in_sync.contains(&t.id)));
+    if summary.interrupted {
+        state.interrupted = Some(InterruptedPush {
+            ids: tickets.iter().map(|t| t.id.clone()).collect(),
+            options,
+            at: chrono::Utc::now().to_rfc3339(),
+        });
+    } else if state
+        .interrupted
+        .as_ref()
+        .is_some_and(|push| push.ids.iter().all(|id| tickets.iter().any(|t| t.id == *id)))
+    {
+        // A complete push over the same tickets finished the job
+        state.interrupted = None;
+    }
+    // An interrupted push only got through to some of its tickets
+    let pushed: Vec<String> = tickets
+        .iter()
+        .filter(|t| !summary.interrupted || summary.in_sync.contains(&t.id))
+        .map(|t| t.id.clone())
+        .collect();
+    state.record_failures(&pushed, &summary.failed_ids, options);
+    state.save(tickets_dir)?;
+
+    // Print summary
+    println!();
+    println!(
+        "Summary: {} created, {} updated, {} skipped, {} failed",
+        summary.created, summary.updated, summary.skipped, summary.failed
+    );
+    if unloaded > 0 {
+        println!("{} ticket file(s) could not be loaded and were left out (see warnings above)", unloaded);
+    }
+    if summary.interrupted {
+        println!("Push interrupted. Run `ttr push --resume` to finish it.");
+    }
+    if let Some(timings) = engine.timings() {
+        print!("\n{}", timings.report());
+    }
+
+    Ok(summary)
+}
+
+/// Write the changes a push would make to a plan file
+async fn cmd_plan_push(ids: Vec<String>, out: std::path::PathBuf, options: SyncOptions, load: LoadOptions) -> Result<()> {
+    let (config, tickets_dir) = Config::load()?;
+    let all_tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let ids = if ids.is_empty() { ids } else { resolve_ids(&all_tickets, &ids)? };
+    let tickets = push_selection(&all_tickets, &ids, &[]);
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+    let mut engine = SyncEngine::with_options(client, config, &tickets_dir, options).await?;
+    let plan = engine.plan_push(&tickets, &all_tickets).await?;
+    plan.save(&out)?;
+
+    println!(
+        "\nPlan: {} change(s), {} skipped. Saved to {}; run `ttr apply {}` to push it.",
+        plan.changes.len(),
+        plan.skipped.len(),
+        out.display(),
+        out.display()
+    );
+    Ok(())
+}
+
+/// Push the tickets a plan changes, provided nothing moved since planning
+async fn cmd_apply(path: std::path::PathBuf, options: SyncOptions, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::push_plan::PushPlan;
+
+    let plan = PushPlan::load(&path)?;
+    let (config, tickets_dir) = Config::load()?;
+    let lock = SyncLock::acquire(&tickets_dir, "apply")?;
+    let (loaded, parsed) =
+        timings::Span::time("parse tickets", || Ticket::load_all_counted(&tickets_dir, config.load_options(load)));
+    let (all_tickets, unloaded) = loaded?;
+
+    if plan.changes.is_empty() {
+        println!("Nothing to apply in {}", path.display());
+        return Ok(());
+    }
+
+    let (owner, repo_name) = config.github.repo_parts()?;
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+    let updated_at = client.get_issues_updated_at(owner, repo_name, &plan.issue_numbers()).await?;
+
+    let stale = plan.stale_reasons(&config.github.repo, &all_tickets, &updated_at);
+    if !stale.is_empty() {
+        for reason in &stale {
+            eprintln!("STALE   {}", reason);
+        }
+        anyhow::bail!("{} is out of date; run `ttr plan-push` again", path.display());
+    }
+
+    // Exactly the planned tickets: no dependents are refreshed alongside
+    let tickets = select_tickets(&all_tickets, &plan.ids());
+    handle_interrupt();
+    let summary = push_selected(config, &tickets_dir, tickets, &all_tickets, unloaded, parsed, options).await?;
+    // `exit` below skips destructors
+    drop(lock);
+    notify("push", &summary, &[], 0).await?;
+
+    if summary.interrupted {
+        std::process::exit(130);
+    }
+    if summary.failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn cmd_sync(ids: Vec<String>, options: SyncOptions, load: LoadOptions) -> Result<()> {
+    use std::collections::HashMap;
+    use ticket_to_ride::pull::{self, Merge, TicketMerge};
+    use ticket_to_ride::state::Baseline;
+    use ticket_to_ride::ticket::DISCUSSION_SECTION;
+
+    let (config, tickets_dir) = Config::load()?;
+    let mut ids = ids;
+    handle_interrupt();
+
+    // Tickets changed on both sides are left alone until resolved by hand
+    let mut conflicted: Vec<String> = Vec::new();
+    let mut pulled: std::collections::HashSet<String> = std::collections::HashSet::new();
+    {
+        let _lock = SyncLock::acquire(&tickets_dir, "sync")?;
+        let all_tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+        if !ids.is_empty() {
+            ids = resolve_ids(&all_tickets, &ids)?;
+        }
+        let state = SyncState::load(&tickets_dir)?;
+
+        // Without a baseline there's nothing to merge against; the push sets one
+        let mut tickets: Vec<Ticket> = select_tickets(&all_tickets, &ids)
+            .into_iter()
+            .filter(|t| t.is_synced() && state.baselines.contains_key(&t.id))
+            .collect();
+
+        if !tickets.is_empty() {
+            let (owner, repo_name) = config.github.repo_parts()?;
+            let token = auth::get_github_token()?;
+            let client = GitHubClient::for_config(token, &config)?;
+
+            let numbers: Vec<u64> = tickets.iter().filter_map(|t| t.github_issue_number()).collect();
+            let issues = client.get_issues_batch(owner, repo_name, &numbers).await?;
+
+            // Project Status, from the first project (in routing order) that has the issue
+            let issue_ids: Vec<String> = issues.values().map(|i| i.id.clone()).collect();
+            let mut project_status: HashMap<String, (String, &HashMap<String, String>)> = HashMap::new();
+            for route in config.project_routes().into_iter().filter(|r| !r.fields.status.is_empty()) {
+                let Some(project) = client.find_project(owner, repo_name, route.name).await? else {
+                    continue;
+                };
+                let statuses = client
+                    .get_project_item_statuses_batch(&project.id, &issue_ids, &route.fields.status_field)
+                    .await?;
+                for (issue_id, option) in statuses {
+                    project_status.entry(issue_id).or_insert((option, &route.fields.status));
+                }
+            }
+
+            for ticket in &mut tickets {
+                let Some(issue) = ticket.github_issue_number().and_then(|n| issues.get(&n)) else {
+                    continue;
+                };
+                // Issues without the managed label aren't pulled from; the push labels ttr's own
+                if !sync::is_managed(issue, &config) {
+                    println!("SKIP    {}  (#{} lacks the managed label)", ticket.id, issue.number);
+                    continue;
+                }
+                let base = &state.baselines[&ticket.id];
+                let title_format = config.sync.title_format.as_deref();
+                let (option, status_map) = match project_status.get(&issue.id) {
+                    Some((option, map)) => (Some(option.as_str()), *map),
+                    None => (None, &config.project.status),
+                };
+                let remote = Baseline {
+                    title: {
+                        let title = sync::strip_badge(&config.mapping.emoji, &issue.title);
+                        sync::parse_title(title_format, &ticket.id, title).unwrap_or(title).to_string()
+                    },
+                    status: pull::remote_status(&issue.state, option, status_map, &base.status),
+                };
+                let merge = TicketMerge::new(base, &Baseline::of(ticket), &remote);
+
+                let conflicts = merge.conflicts();
+                if !conflicts.is_empty() {
+                    println!("SKIP    {}  ({} changed locally and on GitHub)", ticket.id, conflicts.join(" and "));
+                    conflicted.push(ticket.id.clone());
+                    continue;
+                }
+                if let Merge::Pull(title) = &merge.title {
+                    ticket.write_title(title)?;
+                    println!("PULL    {}  title → {}", ticket.id, title);
+                    pulled.insert(ticket.id.clone());
+                }
+                if let Merge::Pull(status) = &merge.status {
+                    if status == "closed" {
+                        ticket.write_closed(issue.closed_at.as_deref())?;
+                    } else {
+                        ticket.write_status(status)?;
+                    }
+                    println!("PULL    {}  status → {}", ticket.id, status);
+                    pulled.insert(ticket.id.clone());
+                }
+            }
+            println!();
+        }
+
+        // Discussion comments land in the ticket's Discussion section
+        let mut discussions: Vec<Ticket> = select_tickets(&all_tickets, &ids)
+            .into_iter()
+            .filter(|t| t.discussion_number().is_some())
+            .collect();
+        if !discussions.is_empty() {
+            let (owner, repo_name) = config.github.repo_parts()?;
+            let token = auth::get_github_token()?;
+            let client = GitHubClient::with_network(token, &config.network)?;
+            for ticket in &mut discussions {
+                let number = ticket.discussion_number().expect("filtered above");
+                let comments = client.get_discussion_comments(owner, repo_name, number).await?;
+                if comments.is_empty() {
+                    continue;
+                }
+                if ticket.write_section(DISCUSSION_SECTION, &pull::discussion_section(&comments))? {
+                    println!("PULL    {}  {} discussion comment(s)", ticket.id, comments.len());
+                    pulled.insert(ticket.id.clone());
+                }
+            }
+        }
+    }
+
+    let summary = push_tickets_except(&ids, &conflicted, options, load).await?;
+    notify("sync", &summary, &conflicted, pulled.len()).await?;
+
+    if summary.interrupted {
+        std::process::exit(130);
+    }
+    if summary.failed > 0 || !conflicted.is_empty() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Post a push or sync summary to the `[notify]` webhooks
+async fn notify(command: &str, summary: &SyncSummary, conflicts: &[String], pulled: usize) -> Result<()> {
+    use ticket_to_ride::notify::{self, Report};
+
+    let (config, _) = Config::load()?;
+    let report = Report {
+        command,
+        repo: &config.github.repo,
+        summary,
+        conflicts,
+        pulled,
+    };
+    notify::send(&config.notify, &config.network, &report).await;
+    Ok(())
+}
+
+/// Tickets matching the given IDs (all tickets if none given)
+fn select_tickets(all_tickets: &[Ticket], ids: &[String]) -> Vec<Ticket> {
+    if ids.is_empty() {
+        all_tickets.to_vec()
+    } else {
+        let matched: std::collections::HashSet<&str> = ids
+            .iter()
+            .flat_map(|id| ticket::match_id(all_tickets, id))
+            .map(|t| t.id.as_str())
+            .collect();
+        all_tickets.iter().filter(|t| matched.contains(t.id.as_str())).cloned().collect()
+    }
+}
+
+/// Turn ID arguments into exact ticket IDs, asking which was meant when one
+/// matches several tickets
+///
+/// Arguments that match nothing are kept as given so callers report them.
+fn resolve_ids(all_tickets: &[Ticket], ids: &[String]) -> Result<Vec<String>> {
+    let mut resolved = Vec::new();
+    for id in ids {
+        match ticket::match_id(all_tickets, id).as_slice() {
+            [] => resolved.push(id.clone()),
+            [t] => resolved.push(t.id.clone()),
+            matches => resolved.extend(choose_tickets(id, matches, true)?.into_iter().map(|t| t.id.clone())),
+        }
+    }
+    // An empty list would mean every ticket
+    if resolved.is_empty() {
+        anyhow::bail!("No tickets selected");
+    }
+    Ok(resolved)
+}
+
+/// Ask which of several matching tickets an argument meant
+///
+/// Without a terminal to ask on this fails, listing the candidates.
+fn choose_tickets<'a>(arg: &str, matches: &[&'a Ticket], allow_all: bool) -> Result<Vec<&'a Ticket>> {
+    use std::io::{self, BufRead, IsTerminal, Write};
+
+    let listed = matches.iter().map(|t| t.id.as_str()).collect::<Vec<_>>().join(", ");
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("'{}' matches {} tickets: {}. Use a longer ID.", arg, matches.len(), listed);
+    }
+
+    println!("'{}' matches {} tickets:", arg, matches.len());
+    for (i, t) in matches.iter().enumerate() {
+        println!("  {}) {}  {}", i + 1, t.id, t.title);
+    }
+    loop {
+        let all = if allow_all { ", a for all" } else { "" };
+        print!("Which one? [1-{}{}, Enter to skip] ", matches.len(), all);
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().lock().read_line(&mut input)?;
+        match input.trim() {
+            "" => return Ok(Vec::new()),
+            "a" | "A" if allow_all => return Ok(matches.to_vec()),
+            choice => match choice.parse::<usize>() {
+                Ok(n) if (1..=matches.len()).contains(&n) => return Ok(vec![matches[n - 1]]),
+                _ => println!("Not a choice: {}", choice),
+            },
+        }
+    }
+}
+
+/// Report what a push would change; exit 1 if anything is pending
+async fn check_tickets(ids: &[String], options: SyncOptions, load: LoadOptions) -> Result<()> {
+    let (config, tickets_dir) = Config::load()?;
+    let all_tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let ids = if ids.is_empty() { Vec::new() } else { resolve_ids(&all_tickets, ids)? };
+    let tickets = select_tickets(&all_tickets, &ids);
+
+    if tickets.is_empty() {
+        return Ok(());
+    }
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+
+    let mut engine = SyncEngine::with_options(client, config, &tickets_dir, options).await?;
+    let summary = engine.check(&tickets, &all_tickets).await?;
+
+    if summary.pending > 0 {
+        eprintln!(
+            "\n{} ticket(s) have changes not pushed to GitHub. Run `ttr push` first.",
+            summary.pending
+        );
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+async fn cmd_board(options: SyncOptions, load: LoadOptions) -> Result<()> {
+    use std::io::{self, BufRead};
+    use ticket_to_ride::board::{self, Board, BoardExit};
+
+    let (config, tickets_dir) = Config::load()?;
+    let mut board = Board::load(&tickets_dir, config.load_options(load))?;
+
+    loop {
+        let mut terminal = ratatui::init();
+        let exit = board::run(&mut terminal, &mut board);
+        ratatui::restore();
+
+        match exit? {
+            BoardExit::Quit => return Ok(()),
+            BoardExit::Push(ids) => {
+                // Push output goes to the normal screen, then return to the board
+                let message = match push_tickets(&ids, options, load).await {
+                    Ok(summary) => format!(
+                        "Pushed: {} created, {} updated, {} failed",
+                        summary.created, summary.updated, summary.failed
+                    ),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        format!("Push failed: {}", e)
+                    }
+                };
+
+                println!("\nPress Enter to return to the board");
+                let mut input = String::new();
+                io::stdin().lock().read_line(&mut input)?;
+
+                board.reload()?;
+                board.set_message(message);
+            }
+        }
+    }
+}
+
+async fn cmd_watch(debounce: u64, dry_run: bool, confirm: bool, options: SyncOptions, load: LoadOptions) -> Result<()> {
+    use std::io::{self, BufRead, Write};
+    use std::time::Duration;
+    use ticket_to_ride::watch::TicketWatcher;
+
+    let (config, tickets_dir) = Config::load()?;
+    let mut watcher = TicketWatcher::new(&tickets_dir, Duration::from_millis(debounce), config.sync.parent_dirs)?;
+
+    println!(
+        "Watching {} for changes{} (Ctrl-C to stop)",
+        tickets_dir.display(),
+        if dry_run { " [dry run]" } else { "" }
+    );
+
+    while let Some(paths) = watcher.next_batch() {
+        let mut tickets = Vec::new();
+        for path in &paths {
+            match Ticket::parse(path) {
+                Ok(ticket) => tickets.push(ticket),
+                Err(e) => eprintln!("Warning: Failed to parse {}: {}", path.display(), e),
+            }
+        }
+        if tickets.is_empty() {
+            continue;
+        }
+
+        println!();
+        for ticket in &tickets {
+            let action = if ticket.is_synced() {
+                "UPDATE  "
+            } else if ticket.draft_item_id().is_some() {
+                "DRAFT   "
+            } else if ticket.discussion_number().is_some() {
+                "DISCUSS "
+            } else {
+                "CREATE  "
+            };
+            println!("{}{}  {}", action, ticket.id, ticket.title);
+        }
+
+        if dry_run {
+            println!("Dry run: {} ticket(s) would be pushed", tickets.len());
+            continue;
+        }
+
+        if confirm {
+            print!("Push {} ticket(s)? [y/N] ", tickets.len());
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().lock().read_line(&mut input)?;
+            if !input.trim().eq_ignore_ascii_case("y") {
+                println!("Skipped");
+                watcher.drain();
+                continue;
+            }
+        }
+
+        let ids: Vec<String> = tickets.into_iter().map(|t| t.id).collect();
+        if let Err(e) = push_tickets(&ids, options, load).await {
+            eprintln!("Error: {}", e);
+        }
+
+        // Ignore events from external-ref write-backs, including late ones
+        watcher.ignore_own_writes();
+    }
+
+    Ok(())
+}
+
+fn cmd_stats(json: bool, weeks: usize, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::stats;
+
+    let (config, tickets_dir) = Config::load()?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let today = chrono::Local::now().date_naive();
+    let stats = stats::compute(&tickets, today, weeks);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    println!("Tickets: {} total", stats.total);
+
+    let sections: [(&str, Vec<(String, usize)>); 4] = [
+        ("Status", stats.by_status.iter().map(|(k, v)| (k.clone(), *v)).collect()),
+        ("Type", stats.by_type.iter().map(|(k, v)| (k.clone(), *v)).collect()),
+        ("Priority", stats.by_priority.iter().map(|(k, v)| (format!("P{}", k), *v)).collect()),
+        ("Tags", stats.by_tag.iter().map(|(k, v)| (k.clone(), *v)).collect()),
+    ];
+    for (heading, rows) in &sections {
+        if rows.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{}:", heading);
+        for (name, count) in rows {
+            println!("  {:<14} {:>4}", name, count);
+        }
+    }
+
+    println!();
+    println!("Sync coverage:");
+    println!("  {:<14} {:>4}", "synced", stats.sync.synced);
+    if stats.sync.drafts > 0 {
+        println!("  {:<14} {:>4}", "drafts", stats.sync.drafts);
+    }
+    println!("  {:<14} {:>4}", "unsynced", stats.sync.unsynced);
+
+    if let Some(age) = stats.average_open_age_days {
+        println!();
+        println!("Average open ticket age: {:.1} days", age);
+    }
+    if let Some(days) = stats.average_days_to_close {
+        println!("Average time to close: {:.1} days", days);
+    }
+
+    if !stats.recently_closed.is_empty() {
+        println!();
+        println!("Recently closed:");
+        for ticket in &stats.recently_closed {
+            let opened = ticket.created.as_deref().unwrap_or("?");
+            let days = ticket.days_open.map(|d| format!("  ({}d)", d)).unwrap_or_default();
+            println!("  {:<12} {} → {}{}  {}", ticket.id, opened, ticket.closed, days, ticket.title);
+        }
+    }
+
+    if !stats.weeks.is_empty() {
+        println!();
+        println!("  {:<12} {:>6} {:>6} {:>6}", "Week of", "Opened", "Closed", "Open");
+        for week in &stats.weeks {
+            println!(
+                "  {:<12} {:>6} {:>6} {:>6}",
+                week.week, week.opened, week.closed, week.open
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_burndown(project: Option<String>, chart: bool) -> Result<()> {
+    use ticket_to_ride::burndown;
+    use ticket_to_ride::github::projects::ProjectFieldType;
+
+    let (config, _) = Config::load()?;
+    let (owner, repo_name) = config.github.repo_parts()?;
+
+    let routes = config.project_routes();
+    let route = match &project {
+        Some(name) => routes
+            .iter()
+            .find(|r| r.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| anyhow::anyhow!("Project '{}' is not configured", name))?,
+        None => routes
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No project configured. Set [github] project in sync.toml."))?,
+    };
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+
+    let info = client
+        .find_project(owner, repo_name, route.name)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", route.name))?;
+
+    let fields = client.get_project_fields(&info.id).await?;
+    let field_name = &route.fields.iteration_field;
+    let iterations = fields
+        .iter()
+        .find(|f| f.name.eq_ignore_ascii_case(field_name))
+        .and_then(|f| match &f.field_type {
+            ProjectFieldType::Iteration { active, completed } => {
+                Some(active.iter().chain(completed.iter()).cloned().collect::<Vec<_>>())
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!("Project '{}' has no iteration field '{}'", info.title, field_name)
+        })?;
+
+    let items = client.get_project_iteration_items(&info.id, field_name).await?;
+    let today = chrono::Local::now().date_naive();
+    let burndowns = burndown::compute(&iterations, &items, today);
+
+    println!("Project: {}  (field: {})", info.title, field_name);
+    if burndowns.is_empty() {
+        println!("\nNo iterations have started yet");
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "  {:<20} {:<23} {:>5} {:>5} {:>5}",
+        "Iteration", "Dates", "Total", "Done", "Left"
+    );
+    for b in &burndowns {
+        println!(
+            "{} {:<20} {:<23} {:>5} {:>5} {:>5}",
+            if b.current { "*" } else { " " },
+            b.title,
+            format!("{} – {}", b.start.format("%Y-%m-%d"), b.end.format("%m-%d")),
+            b.total,
+            b.completed,
+            b.remaining
+        );
+    }
+
+    if let Some(velocity) = burndown::average_velocity(&burndowns) {
+        println!();
+        println!("Average velocity: {:.1} items per iteration", velocity);
+    }
+
+    if chart {
+        let target = burndowns.iter().find(|b| b.current).or(burndowns.last());
+        if let Some(b) = target {
+            println!();
+            println!("{} burndown (# remaining, | ideal):", b.title);
+            print!("{}", burndown::render_chart(b, 40));
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_report(output: Option<std::path::PathBuf>, options: SyncOptions, load: LoadOptions) -> Result<()> {
+    use anyhow::Context;
+    use ticket_to_ride::report;
+
+    let (config, tickets_dir) = Config::load()?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let repo = config.github.repo.clone();
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+    let mut engine = SyncEngine::with_options(client, config, &tickets_dir, options).await?;
+    let drift = engine.drift(&tickets, &tickets).await?;
+    let markdown = report::render_markdown(&drift, &repo, &ticket::now_timestamp());
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, &markdown)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "{} of {} ticket(s) drifted; report written to {}",
+                drift.drifted(),
+                drift.checked,
+                path.display()
+            );
+        }
+        None => print!("{}", markdown),
+    }
+    Ok(())
+}
+
+async fn cmd_preflight(ids: Vec<String>, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::preflight::{self, Level};
+
+    let (config, tickets_dir) = Config::load()?;
+    let all_tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let ids = if ids.is_empty() { Vec::new() } else { resolve_ids(&all_tickets, &ids)? };
+    let tickets = select_tickets(&all_tickets, &ids);
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+    let remote = preflight::fetch(&client, &config).await?;
+    let findings = preflight::check(&tickets, &config, &remote);
+
+    print!("{}", preflight::render(&findings));
+    let to_create = findings.iter().filter(|f| f.level == Level::Create).count();
+    let problems = findings.len() - to_create;
+    println!(
+        "\nChecked {} ticket(s): {} to create, {} problem(s)",
+        tickets.len(),
+        to_create,
+        problems
+    );
+
+    if problems > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn cmd_plan(
+    ids: Vec<String>,
+    tags: Vec<String>,
+    priority: Option<u8>,
+    iteration: Option<String>,
+    status: Option<String>,
+    options: SyncOptions,
+    load: LoadOptions,
+) -> Result<()> {
+    if iteration.is_none() && status.is_none() {
+        anyhow::bail!("Nothing to plan. Pass --iteration and/or --status.");
+    }
+
+    let (config, tickets_dir) = Config::load()?;
+    let lock = SyncLock::acquire(&tickets_dir, "plan")?;
+    let all_tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    // Explicit IDs may include closed tickets; filters only consider open ones
+    let ids = if ids.is_empty() { Vec::new() } else { resolve_ids(&all_tickets, &ids)? };
+    let tickets: Vec<Ticket> = select_tickets(&all_tickets, &ids)
+        .into_iter()
+        .filter(|t| !ids.is_empty() || t.status != "closed")
+        .filter(|t| tags.is_empty() || tags.iter().any(|tag| t.tags.iter().any(|tt| tt.eq_ignore_ascii_case(tag))))
+        .filter(|t| priority.is_none_or(|p| t.priority <= p))
+        .collect();
+
+    if tickets.is_empty() {
+        println!("No tickets matched the plan filter");
+        return Ok(());
+    }
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+
+    println!("Planning {} ticket(s)...\n", tickets.len());
+
+    let engine = SyncEngine::with_options(client, config, &tickets_dir, options)
+        .await?
+        .with_audit_log(AuditLog::new(&tickets_dir));
+    let summary = engine
+        .plan(&tickets, iteration.as_deref(), status.as_deref())
+        .await?;
+    // `exit` below skips destructors
+    drop(lock);
+
+    println!();
+    println!(
+        "Summary: {} planned, {} added to project, {} skipped, {} failed",
+        summary.planned, summary.added, summary.skipped, summary.failed
+    );
+
+    // Describe the resulting sprint scope, leaving out skipped and failed tickets
+    let mut by_type: Vec<(&str, usize)> = Vec::new();
+    let mut by_priority = [0usize; 5];
+    for ticket in tickets.iter().filter(|t| summary.planned_ids.contains(&t.id)) {
+        match by_type.iter_mut().find(|(t, _)| *t == ticket.ticket_type) {
+            Some((_, count)) => *count += 1,
+            None => by_type.push((&ticket.ticket_type, 1)),
+        }
+        by_priority[(ticket.priority as usize).min(4)] += 1;
+    }
+    by_type.sort();
+
+    println!();
+    println!("Scope:");
+    if let Some(ref it) = iteration {
+        println!("  Iteration: {}", it);
+    }
+    if let Some(ref st) = status {
+        println!("  Status:    {}", st);
+    }
+    for (ticket_type, count) in &by_type {
+        println!("  {:<10} {:>3}", ticket_type, count);
+    }
+    let priorities: Vec<String> = by_priority
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| **n > 0)
+        .map(|(p, n)| format!("P{}: {}", p, n))
+        .collect();
+    if !priorities.is_empty() {
+        println!("  {}", priorities.join("  "));
+    }
+
+    if summary.failed > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn cmd_branch(id: String, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::branch;
+
+    let (config, tickets_dir) = Config::load()?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    // A partial ID that matches several tickets needs a choice
+    let mut ticket = match ticket::match_id(&tickets, &id).as_slice() {
+        [] => anyhow::bail!("No ticket matches '{}'", id),
+        [t] => (*t).clone(),
+        matches => match choose_tickets(&id, matches, false)?.as_slice() {
+            [t] => (*t).clone(),
+            _ => return Ok(()),
+        },
+    };
+
+    // Reuse a recorded branch so renaming the ticket doesn't fork a new one
+    let name = match &ticket.branch {
+        Some(b) => b.clone(),
+        None => branch::branch_name(&config.git.branch_pattern, &ticket),
+    };
+
+    if branch::checkout(&name)? {
+        println!("Created branch {}", name);
+    } else {
+        println!("Switched to branch {}", name);
+    }
+
+    if ticket.branch.as_deref() != Some(name.as_str()) {
+        ticket.write_branch(&name)?;
+    }
+
+    Ok(())
+}
+
+fn cmd_config(action: ConfigAction) -> Result<()> {
+    use ticket_to_ride::config::{self, find_tickets_dir};
+
+    // The schema doesn't need a config to exist
+    if let ConfigAction::Schema = action {
+        let schema = ticket_to_ride::config_schema::json_schema();
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    let config_path = find_tickets_dir()?.join("sync.toml");
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}\nRun 'ttr init' to create one.", config_path.display(), e))?;
+
+    match action {
+        ConfigAction::Get { key } => match config::get_value(&content, &key)? {
+            Some(value) => println!("{}", value),
+            None => anyhow::bail!("{} is not set in {}", key, config_path.display()),
+        },
+        ConfigAction::Set { key, value } => {
+            let updated = config::set_value(&content, &key, &value)?;
+            std::fs::write(&config_path, updated)?;
+            println!("Set {} in {}", key, config_path.display());
+        }
+        ConfigAction::Validate => {
+            Config::parse(&content).map_err(|e| anyhow::anyhow!("{}: {:#}", config_path.display(), e))?;
+            println!("{} is valid", config_path.display());
+        }
+        ConfigAction::Schema => {}
+    }
+
+    Ok(())
+}
+
+fn cmd_lint(ids: Vec<String>, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::lint::{self, Severity};
+
+    let (config, tickets_dir) = Config::load()?;
+    for rule in config.lint.rules.keys() {
+        if !lint::rule_names().any(|name| name == rule) {
+            eprintln!("WARN    Unknown lint rule '{}' in sync.toml", rule);
+        }
+    }
+
+    let all_tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let ids = if ids.is_empty() { Vec::new() } else { resolve_ids(&all_tickets, &ids)? };
+    let tickets = select_tickets(&all_tickets, &ids);
+    let mut findings = lint::lint(&tickets, &all_tickets, &config.lint.rules);
+    if let Some(external) = &config.lint.external {
+        findings.extend(lint::lint_external(&tickets, external)?);
+        findings.sort_by_key(|f| tickets.iter().position(|t| t.id == f.ticket));
+    }
+
+    for finding in &findings {
+        let tag = match finding.severity {
+            Severity::Error => "ERROR",
+            _ => "WARN",
+        };
+        let location = match (finding.line, tickets.iter().find(|t| t.id == finding.ticket)) {
+            (Some(line), Some(ticket)) => format!("  ({}:{})", ticket.path.display(), line),
+            _ => String::new(),
+        };
+        println!("{:<8}{}  {}: {}{}", tag, finding.ticket, finding.rule, finding.message, location);
+    }
+
+    let errors = findings.iter().filter(|f| f.severity == Severity::Error).count();
+    println!(
+        "\nChecked {} ticket(s): {} error(s), {} warning(s)",
+        tickets.len(),
+        errors,
+        findings.len() - errors
+    );
+
+    if errors > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn cmd_rename(old: String, new: String, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::rename;
+
+    let (config, tickets_dir) = Config::load()?;
+    let _lock = SyncLock::acquire(&tickets_dir, "rename")?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    let summary = rename::rename(&tickets, &old, &new)?;
+
+    println!("RENAME  {} -> {}  ({})", old, new, summary.path.display());
+    for (id, fields) in &summary.updated {
+        println!("UPDATE  {}  ({})", id, fields.join(", "));
+    }
+    if tickets.iter().any(|t| t.id == old && t.is_synced()) {
+        println!("\nThe issue marker will be updated on the next push");
+    }
+
+    Ok(())
+}
+
+fn cmd_id_remap(from: String, to: String, dry_run: bool, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::rename;
+
+    let (config, tickets_dir) = Config::load()?;
+    let _lock = SyncLock::acquire(&tickets_dir, "id remap")?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    let renames = rename::remap_ids(&tickets, &from, &to)?;
+    if renames.is_empty() {
+        println!("No ticket IDs start with '{}'", from);
+        return Ok(());
+    }
+    let synced = tickets.iter().filter(|t| renames.contains_key(&t.id) && t.is_synced()).count();
+
+    if dry_run {
+        for (old, new) in &renames {
+            println!("RENAME  {} -> {}", old, new);
+        }
+        println!("\nDry run: {} ticket(s) would be renamed", renames.len());
+        return Ok(());
+    }
+
+    let summary = rename::remap(&tickets, &renames)?;
+    let mut state = SyncState::load(&tickets_dir)?;
+    state.rename_ids(&renames);
+    state.save(&tickets_dir)?;
+
+    for (old, new, path) in &summary.renamed {
+        println!("RENAME  {} -> {}  ({})", old, new, path.display());
+    }
+    for (id, fields) in &summary.updated {
+        println!("UPDATE  {}  ({})", id, fields.join(", "));
+    }
+    println!("\nRenamed {} ticket(s)", summary.renamed.len());
+    if synced > 0 {
+        println!("The markers of {} issue(s) will be updated on the next push", synced);
+    }
+
+    Ok(())
+}
+
+async fn cmd_transfer(id: String, to: String, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::transfer::{self, Move};
+
+    let (config, tickets_dir) = Config::load()?;
+    let _lock = SyncLock::acquire(&tickets_dir, "transfer")?;
+    let mut tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    let Some((to_owner, to_name)) = to.split_once('/').filter(|(o, n)| !o.is_empty() && !n.contains('/')) else {
+        anyhow::bail!("Invalid repo format '{}'. Expected 'owner/repo'", to);
+    };
+    let home = config.github.repo.as_str();
+    let ticket = tickets
+        .iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| anyhow::anyhow!("No ticket with ID '{}'", id))?;
+    let (from, old) = match (ticket.issue_elsewhere(), ticket.github_issue_number()) {
+        (Some((repo, number)), _) => (repo.to_string(), number),
+        (None, Some(number)) => (home.to_string(), number),
+        (None, None) => anyhow::bail!("Ticket '{}' has no synced issue to transfer", id),
+    };
+    if from == to {
+        anyhow::bail!("Issue for '{}' is already in {}", id, to);
+    }
+
+    // Transfers are GraphQL-only, so this skips any REST transport setting
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::with_network(token, &config.network)?;
+    let (from_owner, from_name) = from.split_once('/').unwrap_or((&from, ""));
+    let issue = client
+        .get_issues_batch(from_owner, from_name, &[old])
+        .await?
+        .remove(&old)
+        .ok_or_else(|| anyhow::anyhow!("Issue #{} not found in {}", old, from))?;
+    let repository_id = client.get_repository_id(to_owner, to_name).await?;
+    let moved = client.transfer_issue(&issue.id, &repository_id).await?;
+
+    let ticket = tickets.iter_mut().find(|t| t.id == id).expect("ticket found above");
+    ticket.write_transfer((to != home).then_some(to.as_str()), moved.number, &moved.url)?;
+    println!("TRANSFER  {}  {}#{} → {}#{}", id, from, old, to, moved.number);
+
+    let change = Move {
+        home,
+        from: &from,
+        old,
+        to: &to,
+        new: moved.number,
+    };
+    for updated in transfer::fix_references(&tickets, &id, &change)? {
+        println!("UPDATE  {}  (references)", updated);
+    }
+
+    Ok(())
+}
+
+fn cmd_upgrade_config(dry_run: bool) -> Result<()> {
+    use ticket_to_ride::config::{self, find_tickets_dir, CONFIG_VERSION};
+
+    let config_path = find_tickets_dir()?.join("sync.toml");
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}\nRun 'ttr init' to create one.", config_path.display(), e))?;
+
+    let (updated, changes) = config::upgrade(&content)?;
+    if changes.is_empty() {
+        println!("{} is already at config version {}", config_path.display(), CONFIG_VERSION);
+        return Ok(());
+    }
+    for change in &changes {
+        println!("  {}", change);
+    }
+    if dry_run {
+        println!("\nWould upgrade {} to config version {} (dry run)", config_path.display(), CONFIG_VERSION);
+    } else {
+        std::fs::write(&config_path, updated)?;
+        println!("\nUpgraded {} to config version {}", config_path.display(), CONFIG_VERSION);
+    }
+    Ok(())
+}
+
+async fn cmd_migrate_markers(dry_run: bool, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::audit::{AuditAction, AuditEntry};
+    use ticket_to_ride::github::issues::IssueUpdate;
 
-    match cli.command {
-        Commands::Push { ids } => cmd_push(ids).await,
-        Commands::Status { quick } => cmd_status(quick).await,
-        Commands::Init { repo, project, assignee, force } => cmd_init(repo, project, assignee, force),
+    let (config, tickets_dir) = Config::load()?;
+    let lock = SyncLock::acquire(&tickets_dir, "migrate-markers")?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    let (owner, repo_name) = config.github.repo_parts()?;
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+
+    let synced: Vec<(&Ticket, u64)> = tickets
+        .iter()
+        .filter_map(|t| t.github_issue_number().map(|n| (t, n)))
+        .collect();
+    let numbers: Vec<u64> = synced.iter().map(|(_, n)| *n).collect();
+    let issues = client.get_issues_batch(owner, repo_name, &numbers).await?;
+
+    // (ticket ID, issue number, old body) for each update
+    let mut migrations: Vec<(&str, u64, &str)> = Vec::new();
+    let mut updates = Vec::new();
+    for (ticket, number) in &synced {
+        let Some(issue) = issues.get(number) else {
+            continue;
+        };
+        if !sync::has_ticket_marker(&issue.body, ticket) {
+            println!("SKIP    {} → #{}  (marker missing or mismatched)", ticket.id, number);
+            continue;
+        }
+        let Some(body) = sync::upgrade_marker(&issue.body) else {
+            continue;
+        };
+        println!("MIGRATE {} → #{}", ticket.id, number);
+        migrations.push((&ticket.id, *number, &issue.body));
+        updates.push(IssueUpdate {
+            issue_id: issue.id.clone(),
+            title: issue.title.clone(),
+            body,
+            issue_type_id: None,
+        });
+    }
+
+    if updates.is_empty() {
+        println!("All issue markers are up to date");
+        return Ok(());
     }
+    if dry_run {
+        println!("\nDry run: {} issue(s) would be migrated", updates.len());
+        return Ok(());
+    }
+
+    let audit = AuditLog::new(&tickets_dir);
+    let results = client.update_issues_batch(&updates).await?;
+    let mut failed = 0;
+    for ((id, number, old_body), update) in migrations.iter().zip(&updates) {
+        match results.get(&update.issue_id) {
+            Some(Ok(_)) => audit.record(
+                AuditEntry::new(AuditAction::Update, id)
+                    .issue(*number)
+                    .node(&update.issue_id)
+                    .change("body", Some(old_body), Some(&update.body)),
+            ),
+            Some(Err(e)) => {
+                eprintln!("ERROR   {} → #{}: {}", id, number, e);
+                failed += 1;
+            }
+            None => {
+                eprintln!("ERROR   {} → #{}: no result", id, number);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("\nSummary: {} migrated, {} failed", updates.len() - failed, failed);
+    if failed > 0 {
+        // `exit` skips destructors
+        drop(lock);
+        std::process::exit(1);
+    }
+
+    Ok(())
 }
 
-async fn cmd_push(ids: Vec<String>) -> Result<()> {
-    // Load config
+async fn cmd_scan_commits(range: String, dry_run: bool, no_push: bool, options: SyncOptions, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::commits::{resolve_reference, scan_range};
+
     let (config, tickets_dir) = Config::load()?;
+    let mut tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let commits = scan_range(&range)?;
 
-    // Get auth token
-    let token = auth::get_github_token()?;
+    let mut closed: Vec<String> = Vec::new();
+    for commit in &commits {
+        for reference in &commit.references {
+            let Some(ticket) = resolve_reference(&tickets, reference) else {
+                continue;
+            };
+            if ticket.status == "closed" || closed.contains(&ticket.id) {
+                continue;
+            }
+            println!("CLOSE   {}  ({} {})", ticket.id, commit.sha, commit.subject);
+            closed.push(ticket.id.clone());
+        }
+    }
 
-    // Create GitHub client
-    let client = GitHubClient::new(token)?;
+    if closed.is_empty() {
+        println!("No open tickets referenced by closing keywords in {}", range);
+        return Ok(());
+    }
 
-    // Load ALL tickets (needed for dependency lookup)
-    let all_tickets = Ticket::load_all(&tickets_dir)?;
+    if dry_run {
+        println!("\nDry run: {} ticket(s) would be closed", closed.len());
+        return Ok(());
+    }
 
-    if all_tickets.is_empty() {
-        println!("No tickets found in {}", tickets_dir.display());
+    for ticket in tickets.iter_mut().filter(|t| closed.contains(&t.id)) {
+        ticket.write_closed(None)?;
+    }
+    println!("\nClosed {} ticket(s) locally", closed.len());
+
+    if no_push {
         return Ok(());
     }
 
-    // Filter to specific IDs if provided, but keep all_tickets for lookup
-    let mut tickets: Vec<Ticket> = if ids.is_empty() {
-        all_tickets.clone()
-    } else {
-        all_tickets
-            .iter()
-            .filter(|t| ids.iter().any(|id| t.id == *id || t.id.contains(id)))
-            .cloned()
-            .collect()
+    println!();
+    let summary = push_tickets(&closed, options, load).await?;
+    if summary.failed > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn cmd_tree(id: Option<String>, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::tree;
+
+    let (config, tickets_dir) = Config::load()?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    let root = match &id {
+        None => None,
+        Some(id) => match ticket::match_id(&tickets, id).as_slice() {
+            [] => anyhow::bail!("No ticket matches '{}'", id),
+            [t] => Some(t.id.clone()),
+            matches => match choose_tickets(id, matches, false)?.as_slice() {
+                [t] => Some(t.id.clone()),
+                _ => return Ok(()),
+            },
+        },
     };
 
-    if tickets.is_empty() {
-        println!("No tickets matched the provided IDs: {:?}", ids);
+    let view = tree::render(&tickets, root.as_deref());
+    if view.lines.is_empty() && view.orphans.is_empty() {
+        println!("No tickets have a parent");
         return Ok(());
     }
+    for line in &view.lines {
+        println!("{}", line);
+    }
+    if !view.orphans.is_empty() {
+        if !view.lines.is_empty() {
+            println!();
+        }
+        println!("Orphans:");
+        for line in &view.orphans {
+            println!("{}", line);
+        }
+    }
+    if view.standalone > 0 {
+        println!("\n{} ticket(s) with no parent or children not shown", view.standalone);
+    }
 
-    println!("Syncing {} ticket(s) to {}...\n", tickets.len(), config.github.repo);
+    Ok(())
+}
 
-    // Create sync engine and run (pass all_tickets for dependency lookup)
-    let mut engine = SyncEngine::new(client, config).await?;
-    let summary = engine.sync(&mut tickets, &all_tickets).await?;
+fn cmd_history(id: String, full: bool, load: LoadOptions) -> Result<()> {
+    use ticket_to_ride::audit;
+
+    let (config, tickets_dir) = Config::load()?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+    let entries = AuditLog::read(&tickets_dir)?;
+
+    // A deleted ticket is still looked up by its exact ID; a renamed one
+    // brings the entries recorded under its previous IDs
+    let ids = match ticket::match_id(&tickets, &id).as_slice() {
+        [] => vec![id.clone()],
+        [t] => std::iter::once(&t.id).chain(&t.previous_ids).cloned().collect(),
+        matches => match choose_tickets(&id, matches, false)?.as_slice() {
+            [t] => std::iter::once(&t.id).chain(&t.previous_ids).cloned().collect(),
+            _ => return Ok(()),
+        },
+    };
+
+    let history = audit::history(&entries, &ids);
+    if history.is_empty() {
+        println!("No audit log entries for {}", ids[0]);
+        return Ok(());
+    }
+
+    for entry in &history {
+        let mut line = format!("{}  {}{}", entry.timestamp, entry.action.tag(), entry.ticket);
+        if let Some(issue) = entry.issue {
+            line.push_str(&format!(" → #{}", issue));
+        }
+        line.push_str(&format!("  (run {})", entry.run));
+        if let Some(run) = &entry.undoes {
+            line.push_str(&format!("  undoes {}", run));
+        }
+        println!("{}", line);
+        for change in &entry.changes {
+            if full {
+                println!("    {}:", change.field);
+                for (label, value) in [("old", &change.old), ("new", &change.new)] {
+                    match value {
+                        Some(v) => {
+                            println!("      {}:", label);
+                            for l in v.lines() {
+                                println!("        {}", l);
+                            }
+                        }
+                        None => println!("      {}: (none)", label),
+                    }
+                }
+            } else {
+                println!(
+                    "    {}: {} → {}",
+                    change.field,
+                    audit::summarize(change.old.as_deref()),
+                    audit::summarize(change.new.as_deref())
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_undo(dry_run: bool, yes: bool, load: LoadOptions) -> Result<()> {
+    use std::io::{self, BufRead, Write};
+    use ticket_to_ride::undo::{self, UndoStep};
+
+    let (config, tickets_dir) = Config::load()?;
+    let lock = SyncLock::acquire(&tickets_dir, "undo")?;
+    let entries = AuditLog::read(&tickets_dir)?;
+
+    let Some(run) = undo::last_run(&entries) else {
+        println!("Nothing to undo: no pushes in the audit log");
+        return Ok(());
+    };
+    let plan = undo::plan(&entries, run);
+
+    println!("Undoing push {}...\n", run);
+    for step in &plan.steps {
+        match step {
+            UndoStep::Close { ticket, issue, .. } => println!("CLOSE   {} → #{}", ticket, issue),
+            UndoStep::Reopen { ticket, issue, .. } => println!("REOPEN  {} → #{}", ticket, issue),
+            UndoStep::Restore { ticket, issue, .. } => println!("RESTORE {} → #{}", ticket, issue),
+            UndoStep::RemoveItem { ticket, item_id, .. } => {
+                println!("REMOVE  {}  project item {}", ticket, item_id)
+            }
+        }
+    }
+    for (ticket, reason) in &plan.skipped {
+        println!("SKIP    {}  ({} can't be undone)", ticket, reason);
+    }
+
+    if plan.is_empty() {
+        println!("\nNothing in the last push can be undone");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "\nDry run: {} change(s) and {} external-ref(s) would be reverted",
+            plan.steps.len(),
+            plan.external_refs.len()
+        );
+        return Ok(());
+    }
+
+    let (owner, repo_name) = config.github.repo_parts()?;
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+    let audit = AuditLog::new(&tickets_dir);
 
-    // Print summary
     println!();
-    println!(
-        "Summary: {} created, {} updated, {} skipped, {} failed",
-        summary.created, summary.updated, summary.skipped, summary.failed
-    );
+    let summary = undo::apply(&client, owner, repo_name, &plan, &audit, run).await;
+
+    // Local external-refs are only restored on request: without them the next
+    // push creates new issues
+    if !plan.external_refs.is_empty() {
+        println!();
+        let confirmed = yes || {
+            print!("Restore external-ref in {} ticket(s)? [y/N] ", plan.external_refs.len());
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().lock().read_line(&mut input)?;
+            input.trim().eq_ignore_ascii_case("y")
+        };
+
+        if confirmed {
+            let mut tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+            for (id, old) in &plan.external_refs {
+                let Some(ticket) = tickets.iter_mut().find(|t| t.id == *id) else {
+                    eprintln!("Warning: ticket {} not found", id);
+                    continue;
+                };
+                ticket.restore_external_ref(old.as_deref())?;
+                println!("REF     {}  external-ref → {}", id, old.as_deref().unwrap_or("(none)"));
+            }
+        } else {
+            println!("Kept local external-refs");
+        }
+    }
+
+    println!();
+    println!("Summary: {} reverted, {} failed", summary.reverted, summary.failed);
 
     if summary.failed > 0 {
+        // `exit` skips destructors
+        drop(lock);
         std::process::exit(1);
     }
 
     Ok(())
 }
 
+fn cmd_install_hooks(post_commit: bool, uninstall: bool) -> Result<()> {
+    use ticket_to_ride::hooks::{self, Hook};
+
+    let dir = hooks::hooks_dir()?;
+
+    if uninstall {
+        for hook in [Hook::PrePush, Hook::PostCommit] {
+            if hooks::uninstall(&dir, hook)? {
+                println!("Removed ttr from {} hook", hook.name());
+            }
+        }
+        return Ok(());
+    }
+
+    let mut selected = vec![Hook::PrePush];
+    if post_commit {
+        selected.push(Hook::PostCommit);
+    }
+
+    for hook in selected {
+        let path = hooks::install(&dir, hook)?;
+        println!("Installed {} hook: {}", hook.name(), path.display());
+    }
+
+    Ok(())
+}
+
 /// Try to detect GitHub repo from git remote origin
 fn detect_github_repo() -> Option<String> {
     use std::process::Command;
@@ -143,14 +2063,17 @@ fn detect_github_repo() -> Option<String> {
     None
 }
 
-async fn cmd_status(quick: bool) -> Result<()> {
-    use ticket_to_ride::sync::format_issue_body;
+async fn cmd_status(quick: bool, sort: Option<StatusSort>, load: LoadOptions) -> Result<()> {
+    use std::collections::HashMap;
+    use ticket_to_ride::github::pulls::PullRequestInfo;
+    use ticket_to_ride::sync::{format_issue_body_with_config, pull_request_for};
+    use ticket_to_ride::template::IssueTemplate;
 
     // Load config
     let (config, tickets_dir) = Config::load()?;
 
     // Load tickets
-    let tickets = Ticket::load_all(&tickets_dir)?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
 
     if tickets.is_empty() {
         println!("No tickets found in {}", tickets_dir.display());
@@ -162,10 +2085,19 @@ async fn cmd_status(quick: bool) -> Result<()> {
     let mut synced: Vec<&Ticket> = Vec::new();
     let mut modified: Vec<(&Ticket, &str)> = Vec::new();
     let mut conflicts: Vec<&Ticket> = Vec::new();
+    let mut drafts: Vec<&Ticket> = Vec::new();
+    let mut discussions: Vec<&Ticket> = Vec::new();
+    let mut pull_requests: Vec<(&Ticket, PullRequestInfo)> = Vec::new();
+    // Issue number -> 👍 count
+    let mut thumbs_up: HashMap<u64, u64> = HashMap::new();
 
     // Split into synced/unsynced first
     for ticket in &tickets {
-        if ticket.is_synced() {
+        if ticket.draft_item_id().is_some() {
+            drafts.push(ticket);
+        } else if ticket.discussion_number().is_some() {
+            discussions.push(ticket);
+        } else if ticket.is_synced() {
             synced.push(ticket);
         } else {
             unsynced.push(ticket);
@@ -176,19 +2108,51 @@ async fn cmd_status(quick: bool) -> Result<()> {
     if !quick && !synced.is_empty() {
         // Get auth token and create client
         let token = auth::get_github_token()?;
-        let client = GitHubClient::new(token)?;
+        let client = GitHubClient::for_config(token, &config)?;
         let (owner, repo_name) = config.github.repo_parts()?;
 
+        // Footers link the ticket file, so expected bodies need the branch
+        let default_branch = if config.sync.footer_link && config.sync.footer_branch.is_none() {
+            client.get_default_branch(owner, repo_name).await.unwrap_or_default()
+        } else {
+            None
+        };
+        let footer_base = sync::footer_blob_base(&config.sync, owner, repo_name, default_branch.as_deref());
+        let issue_template = config.sync.issue_template.as_deref().map(IssueTemplate::load).transpose()?;
+
         // Batch fetch all synced issues
         let issue_numbers: Vec<u64> = synced
             .iter()
             .filter_map(|t| t.github_issue_number())
             .collect();
 
-        let existing_issues = client
-            .get_issues_batch(owner, repo_name, &issue_numbers)
+        // Unchanged issues come from the cache; only their timestamps are fetched
+        let mut issue_cache = IssueCache::load(&tickets_dir);
+        let existing_issues = issue_cache
+            .get_issues(&client, owner, repo_name, &issue_numbers)
             .await
             .unwrap_or_default();
+        if let Err(e) = issue_cache.save(&tickets_dir) {
+            eprintln!("Warning: Failed to save issue cache: {}", e);
+        }
+
+        // Reactions don't bump updatedAt, so they're always fetched
+        match client.get_thumbs_up_counts(owner, repo_name, &issue_numbers).await {
+            Ok(counts) => thumbs_up = counts,
+            Err(e) => eprintln!("Warning: Failed to fetch reactions: {}", e),
+        }
+
+        // Pull requests opened from ticket branches
+        match client.list_pull_requests(owner, repo_name).await {
+            Ok(prs) => {
+                for ticket in &synced {
+                    if let Some(pr) = pull_request_for(&prs, &ticket.id) {
+                        pull_requests.push((ticket, pr.clone()));
+                    }
+                }
+            }
+            Err(e) => eprintln!("Warning: Failed to list pull requests: {}", e),
+        }
 
         // Re-categorize synced tickets based on GitHub state
         let mut still_synced: Vec<&Ticket> = Vec::new();
@@ -211,28 +2175,42 @@ async fn cmd_status(quick: bool) -> Result<()> {
                 }
             };
 
-            // Check for our marker
-            let marker = format!("<!-- ticket:{} -->", ticket.id);
-            if !existing.body.contains(&marker) {
+            // Check for our marker, unless the body is left to GitHub
+            let manage_body = sync::manages_body(ticket, &config.sync);
+            if manage_body && !sync::has_ticket_marker(&existing.body, ticket) {
                 conflicts.push(ticket);
                 continue;
             }
 
             // Check if content matches
-            let expected_body = format_issue_body(&ticket.id, &ticket.body);
-            let title_changed = existing.title != ticket.title;
-            let body_changed = existing.body != expected_body;
-            let state_should_be_closed = ticket.status == "closed";
+            let footer_url = footer_base.as_deref().map(|base| sync::ticket_file_url(base, ticket));
+            let public_body = ticket.public_body(&config.sync.private_sections);
+            let expected_body = format_issue_body_with_config(
+                &ticket.id,
+                &match &issue_template {
+                    Some(template) => template.fill(&public_body),
+                    None => public_body,
+                },
+                &config.sync,
+                footer_url.as_deref(),
+            );
+            let title = sync::issue_title(&config, ticket);
+            let title_changed = existing.title != title;
+            let body_changed = manage_body && !sync::body_matches(&existing.body, &expected_body);
+            let state_should_be_closed = ticket.closes_issue();
             let state_is_closed = existing.state == "CLOSED";
             let state_changed = state_should_be_closed != state_is_closed;
+            let assignees_changed = sync::assignees_differ(ticket, &config, existing);
 
-            if title_changed || body_changed || state_changed {
+            if title_changed || body_changed || state_changed || assignees_changed {
                 let reason = if title_changed {
                     "title changed"
                 } else if body_changed {
                     "body changed"
-                } else {
+                } else if state_changed {
                     "state changed"
+                } else {
+                    "assignee changed"
                 };
                 modified.push((ticket, reason));
             } else {
@@ -243,6 +2221,17 @@ async fn cmd_status(quick: bool) -> Result<()> {
         synced = still_synced;
     }
 
+    let reactions = |ticket: &Ticket| ticket.github_issue_number().and_then(|n| thumbs_up.get(&n)).copied().unwrap_or(0);
+    if let Some(StatusSort::Reactions) = sort {
+        // Stable, so equally upvoted tickets keep file order
+        synced.sort_by_key(|t| std::cmp::Reverse(reactions(t)));
+        modified.sort_by_key(|(t, _)| std::cmp::Reverse(reactions(t)));
+    }
+    let reactions_suffix = |ticket: &Ticket| match reactions(ticket) {
+        0 => String::new(),
+        n => format!("  👍 {}", n),
+    };
+
     // Print results
     println!("Repository: {}", config.github.repo);
     if quick {
@@ -252,6 +2241,12 @@ async fn cmd_status(quick: bool) -> Result<()> {
     println!("Tickets: {} total", tickets.len());
     println!("  Unsynced:  {:>3}  (will create new issues)", unsynced.len());
     println!("  Synced:    {:>3}  (up to date)", synced.len());
+    if !drafts.is_empty() {
+        println!("  Drafts:    {:>3}  (project draft items)", drafts.len());
+    }
+    if !discussions.is_empty() {
+        println!("  Discussed: {:>3}  (GitHub Discussions)", discussions.len());
+    }
     if !quick {
         println!("  Modified:  {:>3}  (will update)", modified.len());
         println!("  Conflicts: {:>3}  (modified outside ttr)", conflicts.len());
@@ -262,8 +2257,8 @@ async fn cmd_status(quick: bool) -> Result<()> {
         println!("Unsynced:");
         for ticket in &unsynced {
             println!(
-                "  {:<12} [{}]  {}",
-                ticket.id, ticket.ticket_type, ticket.title
+                "  {:<12} [{}]  {}{}",
+                ticket.id, ticket.ticket_type, ticket.title, criteria_suffix(ticket)
             );
         }
     }
@@ -274,8 +2269,8 @@ async fn cmd_status(quick: bool) -> Result<()> {
         for (ticket, reason) in &modified {
             let issue_num = ticket.github_issue_number().unwrap_or(0);
             println!(
-                "  {:<12} → #{:<5}  {} ({})",
-                ticket.id, issue_num, ticket.title, reason
+                "  {:<12} → #{:<5}  {}{} ({}){}",
+                ticket.id, issue_num, ticket.title, criteria_suffix(ticket), reason, reactions_suffix(ticket)
             );
         }
     }
@@ -289,17 +2284,32 @@ async fn cmd_status(quick: bool) -> Result<()> {
                 "  {:<12} → #{:<5}  {}",
                 ticket.id, issue_num, ticket.title
             );
+            let remote = conflicts::remote_path(&tickets_dir, &ticket.id);
+            if remote.exists() {
+                println!("  {:<12}   remote body: {}", "", remote.display());
+            }
+        }
+    }
+
+    if !pull_requests.is_empty() {
+        println!();
+        println!("Pull requests:");
+        for (ticket, pr) in &pull_requests {
+            println!(
+                "  {:<12} → PR #{:<5} {:<7} {}",
+                ticket.id, pr.number, pr.state, pr.title
+            );
         }
     }
 
-    if !synced.is_empty() && (unsynced.is_empty() || quick) {
+    if !synced.is_empty() && (unsynced.is_empty() || quick || sort.is_some()) {
         println!();
         println!("Synced:");
         for ticket in &synced {
             let issue_num = ticket.github_issue_number().unwrap_or(0);
             println!(
-                "  {:<12} → #{:<5}  {}",
-                ticket.id, issue_num, ticket.title
+                "  {:<12} → #{:<5}  {}{}{}",
+                ticket.id, issue_num, ticket.title, criteria_suffix(ticket), reactions_suffix(ticket)
             );
         }
     }
@@ -307,7 +2317,180 @@ async fn cmd_status(quick: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_init(
+async fn cmd_status_remote_only(label: Option<String>, load: LoadOptions) -> Result<()> {
+    let (config, tickets_dir) = Config::load()?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::for_config(token, &config)?;
+    let (owner, repo_name) = config.github.repo_parts()?;
+    let issues = client.list_open_issues(owner, repo_name, label.as_deref()).await?;
+
+    // Issues a ticket points at are tracked even if they lost their marker
+    let tracked: std::collections::HashSet<u64> = tickets.iter().filter_map(|t| t.github_issue_number()).collect();
+    let untracked: Vec<_> = issues
+        .iter()
+        .filter(|issue| sync::parse_marker(&issue.body).is_none() && !tracked.contains(&issue.number))
+        .collect();
+
+    println!("Repository: {}", config.github.repo);
+    let filter = label.map_or(String::new(), |l| format!(", labeled '{}'", l));
+    println!("Untracked: {:>3}  (open issues without a ticket{})", untracked.len(), filter);
+    if !untracked.is_empty() {
+        println!();
+        for issue in &untracked {
+            println!("  #{:<5}  {}", issue.number, issue.title);
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_status_blocked(quick: bool, load: LoadOptions) -> Result<()> {
+    use std::collections::{HashMap, HashSet};
+    use ticket_to_ride::deps;
+
+    let (config, tickets_dir) = Config::load()?;
+    let tickets = Ticket::load_all(&tickets_dir, config.load_options(load))?;
+
+    // A ticket counts as closed if it's closed locally or on GitHub
+    let mut closed: HashSet<String> = tickets
+        .iter()
+        .filter(|t| t.status == "closed")
+        .map(|t| t.id.clone())
+        .collect();
+    if !quick {
+        let issue_numbers: Vec<u64> = tickets.iter().filter_map(|t| t.github_issue_number()).collect();
+        if !issue_numbers.is_empty() {
+            let token = auth::get_github_token()?;
+            let client = GitHubClient::for_config(token, &config)?;
+            let (owner, repo_name) = config.github.repo_parts()?;
+
+            let mut issue_cache = IssueCache::load(&tickets_dir);
+            let issues = issue_cache.get_issues(&client, owner, repo_name, &issue_numbers).await?;
+            if let Err(e) = issue_cache.save(&tickets_dir) {
+                eprintln!("Warning: Failed to save issue cache: {}", e);
+            }
+            for ticket in &tickets {
+                let issue_closed = ticket
+                    .github_issue_number()
+                    .and_then(|n| issues.get(&n))
+                    .is_some_and(|issue| issue.state == "CLOSED");
+                if issue_closed {
+                    closed.insert(ticket.id.clone());
+                }
+            }
+        }
+    }
+
+    let report = deps::analyze(&tickets, &closed);
+    let by_id: HashMap<&str, &Ticket> = tickets.iter().map(|t| (t.id.as_str(), t)).collect();
+    let title = |id: &str| by_id.get(id).map(|t| t.title.as_str()).unwrap_or_default();
+
+    if quick {
+        println!("(quick mode - GitHub state not checked)");
+        println!();
+    }
+    println!("Blocked:   {:>3}  (waiting on open dependencies)", report.blocked.len());
+    println!("Ready:     {:>3}  (all dependencies closed)", report.ready.len());
+    println!("Needed:    {:>3}  (closed, but open tickets depend on them)", report.closed_with_open_dependents.len());
+
+    if !report.blocked.is_empty() {
+        println!();
+        println!("Blocked:");
+        for (id, open_deps) in &report.blocked {
+            println!("  {:<12} {}  (waiting on {})", id, title(id), open_deps.join(", "));
+        }
+    }
+
+    if !report.ready.is_empty() {
+        println!();
+        println!("Ready:");
+        for id in &report.ready {
+            println!("  {:<12} {}", id, title(id));
+        }
+    }
+
+    if !report.closed_with_open_dependents.is_empty() {
+        println!();
+        println!("Closed with open dependents:");
+        for (id, dependents) in &report.closed_with_open_dependents {
+            println!("  {:<12} {}  (needed by {})", id, title(id), dependents.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+/// Acceptance criteria progress for status lines, e.g. " [3/5]"
+fn criteria_suffix(ticket: &Ticket) -> String {
+    match ticket.acceptance_criteria() {
+        Some((done, total)) => format!(" [{}/{}]", done, total),
+        None => String::new(),
+    }
+}
+
+/// Look up a project's Status field and suggest a status mapping
+async fn discover_status_mapping(repo: &str, project: &str, status_field: &str) -> Result<Vec<(String, String)>> {
+    use ticket_to_ride::config::suggest_status_mapping;
+    use ticket_to_ride::github::projects::ProjectFieldType;
+
+    let (owner, name) = repo
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("Invalid repository format"))?;
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::new(token)?;
+
+    let project = client
+        .find_project(owner, name, project)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", project))?;
+
+    let fields = client.get_project_fields(&project.id).await?;
+    let options: Vec<&str> = fields
+        .iter()
+        .find(|f| f.name.eq_ignore_ascii_case(status_field))
+        .and_then(|f| match &f.field_type {
+            ProjectFieldType::SingleSelect { options } => {
+                Some(options.iter().map(|o| o.name.as_str()).collect())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    Ok(suggest_status_mapping(&options))
+}
+
+/// List projects accessible from the repo, for the init picker
+async fn list_accessible_projects(
+    repo: &str,
+) -> Result<Vec<ticket_to_ride::github::projects::ProjectInfo>> {
+    let (owner, name) = repo
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("Invalid repository format"))?;
+
+    let token = auth::get_github_token()?;
+    let client = GitHubClient::new(token)?;
+
+    client.list_projects(owner, name).await
+}
+
+/// Interpret a picker answer as a list index, project number, or exact title
+fn pick_project<'a>(
+    projects: &'a [ticket_to_ride::github::projects::ProjectInfo],
+    input: &str,
+) -> Option<&'a ticket_to_ride::github::projects::ProjectInfo> {
+    if let Some(num) = input.strip_prefix('#').and_then(|n| n.parse::<u64>().ok()) {
+        return projects.iter().find(|p| p.number == num);
+    }
+    if let Ok(idx) = input.parse::<usize>() {
+        return idx.checked_sub(1).and_then(|i| projects.get(i));
+    }
+    projects.iter().find(|p| p.title.eq_ignore_ascii_case(input))
+}
+
+async fn cmd_init(
     repo: Option<String>,
     project: Option<String>,
     assignee: Option<String>,
@@ -361,12 +2544,41 @@ fn cmd_init(
     let project = if let Some(p) = project {
         Some(p)
     } else if atty::is(atty::Stream::Stdin) {
-        print!("GitHub Project name (optional, press Enter to skip): ");
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().lock().read_line(&mut input)?;
-        let input = input.trim();
-        if input.is_empty() { None } else { Some(input.to_string()) }
+        // Offer a picker when projects can be listed, else fall back to free-form
+        match list_accessible_projects(&repo).await {
+            Ok(projects) if !projects.is_empty() => {
+                println!("Projects accessible from {}:", repo);
+                for (i, p) in projects.iter().enumerate() {
+                    println!("  {}. {} (#{})", i + 1, p.title, p.number);
+                }
+                loop {
+                    print!("Select a project [1-{}] (press Enter to skip): ", projects.len());
+                    io::stdout().flush()?;
+                    let mut input = String::new();
+                    io::stdin().lock().read_line(&mut input)?;
+                    let input = input.trim();
+                    if input.is_empty() {
+                        break None;
+                    }
+                    match pick_project(&projects, input) {
+                        Some(p) => break Some(p.title.clone()),
+                        None => println!("No project matches '{}'", input),
+                    }
+                }
+            }
+            result => {
+                match result {
+                    Ok(_) => println!("No projects found for {}", repo),
+                    Err(e) => eprintln!("Could not list projects: {}", e),
+                }
+                print!("GitHub Project name (optional, press Enter to skip): ");
+                io::stdout().flush()?;
+                let mut input = String::new();
+                io::stdin().lock().read_line(&mut input)?;
+                let input = input.trim();
+                if input.is_empty() { None } else { Some(input.to_string()) }
+            }
+        }
     } else {
         None
     };
@@ -387,20 +2599,23 @@ fn cmd_init(
 
     // Build config
     let mut config = format!(
-        r#"[github]
+        r#"version = {}
+
+[github]
 repo = "{}"
 "#,
+        ticket_to_ride::config::CONFIG_VERSION,
         repo
     );
 
     if let Some(p) = &project {
-        config.push_str(&format!("project = \"{}\"\n", p));
+        config.push_str(&format!("project = {}\n", toml_edit::Value::from(p.as_str())));
     } else {
         config.push_str("# project = \"Project Name\"  # Optional: GitHub Project to add issues to\n");
     }
 
     if let Some(a) = &assignee {
-        config.push_str(&format!("assignee = \"{}\"\n", a));
+        config.push_str(&format!("assignee = {}\n", toml_edit::Value::from(a.as_str())));
     } else {
         config.push_str("# assignee = \"username\"  # Optional: assign all issues to this user\n");
     }
@@ -423,6 +2638,23 @@ create_missing = true  # Create labels that don't exist
 "#,
     );
 
+    // Pre-populate the project Status mapping from the project's options
+    if let Some(p) = &project {
+        let status_field = ticket_to_ride::config::ProjectFieldsConfig::default().status_field;
+        match discover_status_mapping(&repo, p, &status_field).await {
+            Ok(mapping) if !mapping.is_empty() => {
+                println!("Discovered project {} options for '{}'", status_field, p);
+                config = ticket_to_ride::config::add_status_mapping(&config, &status_field, &mapping)?;
+            }
+            Ok(_) => {
+                println!("No matching Status options found in '{}'; add [project.status] manually", p);
+            }
+            Err(e) => {
+                eprintln!("WARN    Could not read project fields: {}", e);
+            }
+        }
+    }
+
     fs::write(&config_path, config)?;
     println!();
     println!("Created {}", config_path.display());
@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use serde_json::json;
+
+use crate::config::{NetworkConfig, NotifyConfig};
+use crate::github::client::http_client_builder;
+use crate::sync::SyncSummary;
+
+/// Most tickets listed by name in one message
+const MAX_LISTED: usize = 20;
+
+/// Chat service a webhook posts to, which decides the link syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chat {
+    Slack,
+    Matrix,
+}
+
+/// What a finished push or sync did
+#[derive(Debug)]
+pub struct Report<'a> {
+    /// "push" or "sync"
+    pub command: &'a str,
+    pub repo: &'a str,
+    pub summary: &'a SyncSummary,
+    /// Tickets changed locally and on GitHub, left alone
+    pub conflicts: &'a [String],
+    /// Tickets that took changes from GitHub
+    pub pulled: usize,
+}
+
+impl Report<'_> {
+    /// Whether there's anything worth a message
+    pub fn is_empty(&self) -> bool {
+        let s = self.summary;
+        s.created + s.updated + s.failed == 0 && self.conflicts.is_empty() && self.pulled == 0
+    }
+}
+
+/// The chat message for a report
+pub fn message(report: &Report, chat: Chat) -> String {
+    let s = report.summary;
+    let mut counts = vec![format!("{} created", s.created), format!("{} updated", s.updated)];
+    if report.pulled > 0 {
+        counts.push(format!("{} pulled", report.pulled));
+    }
+    counts.push(format!("{} skipped", s.skipped));
+    counts.push(format!("{} failed", s.failed));
+    if !report.conflicts.is_empty() {
+        counts.push(format!("{} conflicted", report.conflicts.len()));
+    }
+    let mut lines = vec![format!(
+        "ttr {} to {}: {}",
+        report.command,
+        escape(report.repo, chat),
+        counts.join(", ")
+    )];
+
+    for change in s.changes.iter().take(MAX_LISTED) {
+        let verb = if change.created { "Created" } else { "Updated" };
+        let id = escape(&change.id, chat);
+        let name = match (&change.url, chat) {
+            (Some(url), Chat::Slack) => format!("<{}|{}>", url, id),
+            (Some(url), Chat::Matrix) => format!("[{}]({})", id, url),
+            (None, _) => id,
+        };
+        lines.push(format!("• {} {}  {}", verb, name, escape(&change.title, chat)));
+    }
+    if s.changes.len() > MAX_LISTED {
+        lines.push(format!("• …and {} more", s.changes.len() - MAX_LISTED));
+    }
+    if !report.conflicts.is_empty() {
+        lines.push(format!("Conflicts: {}", escape(&report.conflicts.join(", "), chat)));
+    }
+    if !s.unresolved.is_empty() {
+        lines.push(format!("Not pushed: {}", escape(&s.unresolved.join(", "), chat)));
+    }
+    if s.interrupted {
+        lines.push("Push interrupted before it finished".to_string());
+    }
+    lines.join("\n")
+}
+
+/// Slack treats `&`, `<` and `>` as markup; Matrix bodies are markdown
+fn escape(text: &str, chat: Chat) -> String {
+    match chat {
+        Chat::Slack => text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"),
+        Chat::Matrix => text.replace('[', "\\[").replace(']', "\\]"),
+    }
+}
+
+/// Post a report to every configured webhook
+///
+/// A failed post only warns; the push itself already happened.
+pub async fn send(config: &NotifyConfig, network: &NetworkConfig, report: &Report<'_>) {
+    let hooks = [(Chat::Slack, &config.slack_webhook), (Chat::Matrix, &config.matrix_webhook)];
+    if report.is_empty() || hooks.iter().all(|(_, url)| url.is_none()) {
+        return;
+    }
+    for (chat, url) in hooks {
+        let Some(url) = url else {
+            continue;
+        };
+        if let Err(e) = post(network, url, &message(report, chat)).await {
+            eprintln!("WARN    {:?} notification failed: {:#}", chat, e);
+        }
+    }
+}
+
+/// Post a message to a Slack-style webhook (`{"text": ...}`)
+pub async fn post(network: &NetworkConfig, url: &str, text: &str) -> Result<()> {
+    let client = http_client_builder(network)?
+        .build()
+        .context("Failed to create HTTP client")?;
+    let response = client
+        .post(url)
+        .json(&json!({ "text": text }))
+        .send()
+        .await
+        .context("Failed to reach webhook")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("webhook returned {}: {}", status, body);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::Change;
+
+    #[test]
+    fn test_message_links_changes_per_chat() {
+        let summary = SyncSummary {
+            created: 1,
+            updated: 1,
+            failed: 1,
+            unresolved: vec!["t-3".to_string()],
+            changes: vec![
+                Change {
+                    id: "t-1".to_string(),
+                    title: "Fix <login> & logout".to_string(),
+                    url: Some("https://github.com/o/r/issues/1".to_string()),
+                    created: true,
+                },
+                Change {
+                    id: "t-2".to_string(),
+                    title: "Draft idea".to_string(),
+                    url: None,
+                    created: false,
+                },
+            ],
+            ..Default::default()
+        };
+        let conflicts = vec!["t-4".to_string()];
+        let report = Report {
+            command: "sync",
+            repo: "o/r",
+            summary: &summary,
+            conflicts: &conflicts,
+            pulled: 2,
+        };
+
+        assert_eq!(
+            message(&report, Chat::Slack),
+            "ttr sync to o/r: 1 created, 1 updated, 2 pulled, 0 skipped, 1 failed, 1 conflicted\n\
+             • Created <https://github.com/o/r/issues/1|t-1>  Fix &lt;login&gt; &amp; logout\n\
+             • Updated t-2  Draft idea\n\
+             Conflicts: t-4\n\
+             Not pushed: t-3"
+        );
+        assert!(message(&report, Chat::Matrix).contains("• Created [t-1](https://github.com/o/r/issues/1)  Fix <login> & logout"));
+
+        let quiet = SyncSummary {
+            skipped: 3,
+            ..Default::default()
+        };
+        let report = Report {
+            command: "push",
+            repo: "o/r",
+            summary: &quiet,
+            conflicts: &[],
+            pulled: 0,
+        };
+        assert!(report.is_empty());
+    }
+}
@@ -0,0 +1,316 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::config::{Config, ProjectRoute};
+use crate::github::client::GitHubClient;
+use crate::github::projects::{ProjectFieldInfo, ProjectFieldType};
+use crate::sync::{issue_labels, resolve_iteration_spec};
+use crate::ticket::Ticket;
+
+/// How a push would deal with something
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// The push creates it
+    Create,
+    /// It doesn't exist and the push won't create it
+    Missing,
+    /// It's named in the config or a ticket but GitHub doesn't know it
+    Unknown,
+}
+
+impl Level {
+    /// Output tag, padded to the usual 8 columns
+    pub fn tag(self) -> &'static str {
+        match self {
+            Level::Create => "CREATE  ",
+            Level::Missing => "MISSING ",
+            Level::Unknown => "UNKNOWN ",
+        }
+    }
+}
+
+/// One thing a push would need to create or can't resolve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub level: Level,
+    /// What it is, e.g. "label 'area:ui'"
+    pub what: String,
+    /// Tickets affected; empty for config-only problems
+    pub tickets: Vec<String>,
+}
+
+/// A configured project as found on GitHub
+#[derive(Debug, Clone)]
+pub struct RemoteProject {
+    /// Name or number from the config
+    pub name: String,
+    /// The project's fields, or None if it wasn't found
+    pub fields: Option<Vec<ProjectFieldInfo>>,
+}
+
+/// What GitHub has that a push depends on
+#[derive(Debug, Clone, Default)]
+pub struct Remote {
+    /// Label names, lowercase
+    pub labels: HashSet<String>,
+    /// Issue type names, lowercase; empty when the repo has none
+    pub issue_types: HashSet<String>,
+    /// Configured assignees GitHub didn't find, with the reason
+    pub unknown_assignees: Vec<(String, String)>,
+    /// One per project route, in route order
+    pub projects: Vec<RemoteProject>,
+}
+
+/// Look up everything `check` compares against
+///
+/// Nothing is created or changed. Only failing to reach the repository at
+/// all is an error; a missing project or user becomes part of the result.
+pub async fn fetch(client: &GitHubClient, config: &Config) -> Result<Remote> {
+    let (owner, repo_name) = config.github.repo_parts()?;
+    client.get_repository_id(owner, repo_name).await?;
+
+    let labels = client.get_labels(owner, repo_name).await?;
+    let capabilities = client.get_capabilities().await;
+    let issue_types = if capabilities.issue_types {
+        client.get_issue_types(owner, repo_name).await?
+    } else {
+        Vec::new()
+    };
+
+    let mut unknown_assignees = Vec::new();
+    for login in config.github.assignee_pool() {
+        if let Err(e) = client.get_user_id(&login).await {
+            unknown_assignees.push((login, e.to_string()));
+        }
+    }
+
+    let mut projects = Vec::new();
+    for route in config.project_routes() {
+        let fields = match client.find_project(owner, repo_name, route.name).await? {
+            Some(project) => Some(client.get_project_fields(&project.id).await?),
+            None => None,
+        };
+        projects.push(RemoteProject {
+            name: route.name.to_string(),
+            fields,
+        });
+    }
+
+    Ok(Remote {
+        labels: labels.into_iter().map(|l| l.name.to_lowercase()).collect(),
+        issue_types: issue_types.into_iter().map(|t| t.name.to_lowercase()).collect(),
+        unknown_assignees,
+        projects,
+    })
+}
+
+/// Everything a push of `tickets` would need to create or couldn't resolve
+pub fn check(tickets: &[Ticket], config: &Config, remote: &Remote) -> Vec<Finding> {
+    let mut findings = Findings::default();
+
+    // Labels only go on new issues
+    for ticket in tickets.iter().filter(|t| t.github_issue_number().is_none() && t.draft_item_id().is_none()) {
+        for label in issue_labels(ticket, &config.labels, !remote.issue_types.is_empty()) {
+            if remote.labels.contains(&label.to_lowercase()) {
+                continue;
+            }
+            let level = if config.labels.create_missing { Level::Create } else { Level::Missing };
+            findings.add(level, format!("label '{}'", label), &ticket.id);
+        }
+    }
+
+    // The managed label goes on every issue
+    if let Some(label) = &config.github.managed_label {
+        if !remote.labels.contains(&label.to_lowercase()) {
+            let level = if config.labels.create_missing { Level::Create } else { Level::Missing };
+            findings.config(level, format!("managed label '{}'", label));
+        }
+    }
+
+    if !remote.issue_types.is_empty() {
+        for (ticket_type, name) in &config.mapping.type_map {
+            if remote.issue_types.contains(&name.to_lowercase()) {
+                continue;
+            }
+            let what = format!("issue type '{}' (for type '{}')", name, ticket_type);
+            findings.config(Level::Unknown, what.clone());
+            for ticket in tickets.iter().filter(|t| t.ticket_type == *ticket_type) {
+                findings.add(Level::Unknown, what.clone(), &ticket.id);
+            }
+        }
+    }
+
+    for (login, reason) in &remote.unknown_assignees {
+        findings.config(Level::Unknown, format!("assignee '{}' ({})", login, reason));
+    }
+
+    let routes = config.project_routes();
+    for (i, (route, project)) in routes.iter().zip(&remote.projects).enumerate() {
+        // A ticket goes to the first project whose rules it matches
+        let routed: Vec<&Ticket> = tickets
+            .iter()
+            .filter(|t| routes.iter().position(|r| r.matches(&t.ticket_type, &t.tags)) == Some(i))
+            .collect();
+        match &project.fields {
+            None => {
+                findings.config(Level::Missing, format!("project '{}'", project.name));
+                for ticket in &routed {
+                    findings.add(Level::Missing, format!("project '{}'", project.name), &ticket.id);
+                }
+            }
+            Some(fields) => check_project_fields(&mut findings, route, fields, &routed),
+        }
+    }
+
+    findings.into_vec()
+}
+
+/// Status and iteration problems for the tickets routed to one project
+fn check_project_fields(findings: &mut Findings, route: &ProjectRoute, fields: &[ProjectFieldInfo], tickets: &[&Ticket]) {
+    let project = route.name;
+    let config = route.fields;
+    let field = |name: &str| fields.iter().find(|f| f.name.eq_ignore_ascii_case(name));
+
+    if !config.status.is_empty() {
+        match field(&config.status_field).map(|f| &f.field_type) {
+            Some(ProjectFieldType::SingleSelect { options }) => {
+                for option in config.status.values() {
+                    if !options.iter().any(|o| o.name.eq_ignore_ascii_case(option)) {
+                        findings.config(Level::Unknown, format!("Status option '{}' in '{}'", option, project));
+                    }
+                }
+                for ticket in tickets {
+                    if !config.status.keys().any(|s| s.eq_ignore_ascii_case(&ticket.status)) {
+                        let what = format!("status mapping for '{}' in '{}'", ticket.status, project);
+                        findings.add(Level::Missing, what, &ticket.id);
+                    }
+                }
+            }
+            _ => findings.config(Level::Missing, format!("single-select field '{}' in '{}'", config.status_field, project)),
+        }
+    }
+
+    let wants_iteration = config.iteration.is_some() || tickets.iter().any(|t| t.iteration.is_some());
+    match field(&config.iteration_field).map(|f| &f.field_type) {
+        Some(ProjectFieldType::Iteration { active, completed }) => {
+            if let Some(spec) = &config.iteration {
+                if let Err(e) = resolve_iteration_spec(spec, active) {
+                    findings.config(Level::Unknown, format!("iteration '{}' in '{}' ({})", spec, project, e.replace('\n', " ")));
+                }
+            }
+            for ticket in tickets {
+                let Some(name) = &ticket.iteration else {
+                    continue;
+                };
+                let known = if name.starts_with('@') {
+                    resolve_iteration_spec(name, active).is_ok()
+                } else {
+                    active.iter().chain(completed).any(|i| i.title.eq_ignore_ascii_case(name))
+                };
+                if !known {
+                    findings.add(Level::Unknown, format!("iteration '{}' in '{}'", name, project), &ticket.id);
+                }
+            }
+        }
+        _ if wants_iteration => {
+            findings.config(Level::Missing, format!("iteration field '{}' in '{}'", config.iteration_field, project))
+        }
+        _ => {}
+    }
+}
+
+/// Findings keyed by level and subject, gathering the tickets for each
+#[derive(Default)]
+struct Findings(BTreeMap<(Level, String), Vec<String>>);
+
+impl Findings {
+    fn add(&mut self, level: Level, what: String, ticket: &str) {
+        let tickets = self.0.entry((level, what)).or_default();
+        if !tickets.iter().any(|t| t == ticket) {
+            tickets.push(ticket.to_string());
+        }
+    }
+
+    /// A problem with the config itself, whichever tickets it affects
+    fn config(&mut self, level: Level, what: String) {
+        self.0.entry((level, what)).or_default();
+    }
+
+    fn into_vec(self) -> Vec<Finding> {
+        self.0
+            .into_iter()
+            .map(|((level, what), tickets)| Finding { level, what, tickets })
+            .collect()
+    }
+}
+
+/// Output lines for findings: `CREATE  label 'x'  (t-1, t-2)`
+pub fn render(findings: &[Finding]) -> String {
+    let mut out = String::new();
+    for finding in findings {
+        out.push_str(finding.level.tag());
+        out.push_str(&finding.what);
+        if !finding.tickets.is_empty() {
+            out.push_str(&format!("  ({})", finding.tickets.join(", ")));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::projects::{FieldOption, IterationOption};
+    use crate::ticket::TicketDir;
+
+    #[test]
+    fn test_check_reports_labels_statuses_and_iterations() {
+        let dir = TicketDir::new();
+        let tickets = vec![
+            dir.ticket("t-1", "status: open\ntags: [bug, area:ui]\n"),
+            dir.ticket("t-2", "status: review\ntags: [area:ui]\niteration: Sprint 9\n"),
+            dir.ticket("t-3", "status: open\ntags: [old]\nexternal-ref: gh-4\n"),
+        ];
+        let config = Config::parse(
+            "[github]\nrepo = \"o/r\"\nproject = \"Roadmap\"\n\n[project.status]\nopen = \"Todo\"\nclosed = \"Shipped\"\n",
+        )
+        .unwrap();
+        let option = |name: &str| FieldOption { id: format!("O_{}", name), name: name.to_string() };
+        let remote = Remote {
+            labels: HashSet::from(["bug".to_string()]),
+            projects: vec![RemoteProject {
+                name: "Roadmap".to_string(),
+                fields: Some(vec![
+                    ProjectFieldInfo {
+                        id: "F_1".to_string(),
+                        name: "Status".to_string(),
+                        field_type: ProjectFieldType::SingleSelect { options: vec![option("Todo"), option("Done")] },
+                    },
+                    ProjectFieldInfo {
+                        id: "F_2".to_string(),
+                        name: "Iteration".to_string(),
+                        field_type: ProjectFieldType::Iteration {
+                            active: vec![IterationOption {
+                                id: "IT_1".to_string(),
+                                title: "Sprint 1".to_string(),
+                                start_date: "2026-01-05".to_string(),
+                                duration: 14,
+                            }],
+                            completed: Vec::new(),
+                        },
+                    },
+                ]),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            render(&check(&tickets, &config, &remote)),
+            "CREATE  label 'area:ui'  (t-1, t-2)\n\
+             MISSING status mapping for 'review' in 'Roadmap'  (t-2)\n\
+             UNKNOWN Status option 'Shipped' in 'Roadmap'\n\
+             UNKNOWN iteration 'Sprint 9' in 'Roadmap'  (t-2)\n"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use crate::github::discussions::DiscussionComment;
+use crate::state::Baseline;
+
+/// Outcome of a three-way merge of one field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Merge {
+    /// Ticket and issue agree
+    InSync,
+    /// Changed on GitHub only: write the remote value into the ticket
+    Pull(String),
+    /// Changed locally only: the push updates the issue
+    Push,
+    /// Changed on both sides to different values
+    Conflict { local: String, remote: String },
+}
+
+/// Merge a field given its baseline, local, and remote values
+pub fn merge(base: &str, local: &str, remote: &str) -> Merge {
+    if local == remote {
+        Merge::InSync
+    } else if local == base {
+        Merge::Pull(remote.to_string())
+    } else if remote == base {
+        Merge::Push
+    } else {
+        Merge::Conflict {
+            local: local.to_string(),
+            remote: remote.to_string(),
+        }
+    }
+}
+
+/// Per-field merge results for one ticket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketMerge {
+    pub title: Merge,
+    pub status: Merge,
+}
+
+impl TicketMerge {
+    pub fn new(base: &Baseline, local: &Baseline, remote: &Baseline) -> Self {
+        Self {
+            title: merge(&base.title, &local.title, &remote.title),
+            status: merge(&base.status, &local.status, &remote.status),
+        }
+    }
+
+    /// Fields changed on both sides
+    pub fn conflicts(&self) -> Vec<&'static str> {
+        [("title", &self.title), ("status", &self.status)]
+            .into_iter()
+            .filter(|(_, m)| matches!(m, Merge::Conflict { .. }))
+            .map(|(field, _)| field)
+            .collect()
+    }
+}
+
+/// Ticket status implied by an issue's state and its project Status option
+///
+/// `status_map` is `[project.status]` (ticket status -> option). An open issue
+/// without a mapped option keeps the baseline status, reopened if it was closed.
+pub fn remote_status(
+    issue_state: &str,
+    project_status: Option<&str>,
+    status_map: &HashMap<String, String>,
+    base_status: &str,
+) -> String {
+    if issue_state == "CLOSED" {
+        return "closed".to_string();
+    }
+    let mapped = project_status.and_then(|option| {
+        status_map
+            .iter()
+            .filter(|(status, o)| o.eq_ignore_ascii_case(option) && *status != "closed")
+            .map(|(status, _)| status)
+            .min()
+    });
+    match mapped {
+        Some(status) => status.clone(),
+        None if base_status == "closed" => "open".to_string(),
+        None => base_status.to_string(),
+    }
+}
+
+/// Discussion comments as markdown for the ticket's `## Discussion` section
+///
+/// Comments are quoted so headings in them can't end the section.
+pub fn discussion_section(comments: &[DiscussionComment]) -> String {
+    let quoted: Vec<String> = comments
+        .iter()
+        .map(|c| {
+            let date = c.created_at.get(..10).unwrap_or(&c.created_at);
+            let body: Vec<String> = c
+                .body
+                .trim()
+                .lines()
+                .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+                .collect();
+            format!("**@{}** on {}:\n\n{}", c.author, date, body.join("\n"))
+        })
+        .collect();
+    quoted.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_way_merge() {
+        assert_eq!(merge("a", "a", "a"), Merge::InSync);
+        assert_eq!(merge("a", "a", "b"), Merge::Pull("b".to_string()));
+        assert_eq!(merge("a", "b", "a"), Merge::Push);
+        assert_eq!(merge("a", "b", "b"), Merge::InSync);
+        assert_eq!(
+            merge("a", "b", "c"),
+            Merge::Conflict {
+                local: "b".to_string(),
+                remote: "c".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_remote_status() {
+        let map = HashMap::from([
+            ("open".to_string(), "Todo".to_string()),
+            ("in_progress".to_string(), "In Progress".to_string()),
+            ("closed".to_string(), "Done".to_string()),
+        ]);
+        assert_eq!(remote_status("CLOSED", Some("In Progress"), &map, "open"), "closed");
+        assert_eq!(remote_status("OPEN", Some("in progress"), &map, "open"), "in_progress");
+        assert_eq!(remote_status("OPEN", None, &map, "in_progress"), "in_progress");
+        assert_eq!(remote_status("OPEN", Some("Done"), &map, "closed"), "open");
+    }
+
+    #[test]
+    fn test_discussion_section() {
+        let comment = |author: &str, body: &str| DiscussionComment {
+            author: author.to_string(),
+            body: body.to_string(),
+            created_at: "2026-03-04T10:00:00Z".to_string(),
+        };
+        let comments = [comment("alice", "+1\n\n## Not a heading"), comment("bob", "Ship it")];
+        assert_eq!(
+            discussion_section(&comments),
+            "**@alice** on 2026-03-04:\n\n> +1\n>\n> ## Not a heading\n\n**@bob** on 2026-03-04:\n\n> Ship it"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::sync::content_hash;
+use crate::ticket::Ticket;
+
+/// What a planned push does to a ticket's issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlannedOp {
+    Create,
+    Draft,
+    Discuss,
+    Update,
+}
+
+impl PlannedOp {
+    /// Output tag, as `ttr push --check` prints it
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::Create => "CREATE  ",
+            Self::Draft => "DRAFT   ",
+            Self::Discuss => "DISCUSS ",
+            Self::Update => "UPDATE  ",
+        }
+    }
+}
+
+/// One ticket a planned push changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedChange {
+    pub ticket: String,
+    pub op: PlannedOp,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<u64>,
+    /// What an update changes: "title", "body", "close", "reopen", "assignees", "labels"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    /// Hash of the ticket file when planned
+    pub ticket_hash: String,
+    /// The issue's `updated_at` when planned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue_updated_at: Option<String>,
+}
+
+/// A ticket the push would skip or fail on, for the reviewer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedSkip {
+    pub ticket: String,
+    pub reason: String,
+}
+
+/// Everything a push would change, written by `ttr plan-push` for review
+/// and run by `ttr apply`
+///
+/// The plan pins the ticket files and issues it was computed from; applying
+/// is refused once either has changed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PushPlan {
+    pub repo: String,
+    /// When it was planned (RFC 3339)
+    pub planned_at: String,
+    #[serde(default)]
+    pub changes: Vec<PlannedChange>,
+    #[serde(default)]
+    pub skipped: Vec<PlannedSkip>,
+}
+
+impl PushPlan {
+    pub fn new(repo: &str) -> Self {
+        Self {
+            repo: repo.to_string(),
+            planned_at: chrono::Utc::now().to_rfc3339(),
+            ..Default::default()
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("Invalid push plan in {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let raw = serde_json::to_string_pretty(self)?;
+        fs::write(path, raw + "\n").with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// IDs of tickets the plan changes
+    pub fn ids(&self) -> Vec<String> {
+        self.changes.iter().map(|c| c.ticket.clone()).collect()
+    }
+
+    /// Issue numbers the plan updates
+    pub fn issue_numbers(&self) -> Vec<u64> {
+        self.changes.iter().filter_map(|c| c.issue).collect()
+    }
+
+    /// Why the plan no longer matches the tickets and issues it was computed
+    /// from; empty when it can be applied
+    ///
+    /// `updated_at` holds GitHub's current timestamps for `issue_numbers()`;
+    /// issues missing from it were deleted.
+    pub fn stale_reasons(&self, repo: &str, tickets: &[Ticket], updated_at: &HashMap<u64, String>) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.repo != repo {
+            reasons.push(format!("planned for {}, but sync.toml targets {}", self.repo, repo));
+        }
+        for change in &self.changes {
+            match tickets.iter().find(|t| t.id == change.ticket) {
+                None => reasons.push(format!("{} no longer exists", change.ticket)),
+                Some(ticket) if ticket_hash(ticket).ok().as_ref() != Some(&change.ticket_hash) => {
+                    reasons.push(format!("{} changed since planning", change.ticket))
+                }
+                Some(_) => {}
+            }
+            let Some(number) = change.issue else {
+                continue;
+            };
+            match updated_at.get(&number) {
+                None => reasons.push(format!("#{} ({}) no longer exists", number, change.ticket)),
+                Some(now) if change.issue_updated_at.as_ref() != Some(now) => {
+                    reasons.push(format!("#{} ({}) changed on GitHub since planning", number, change.ticket))
+                }
+                Some(_) => {}
+            }
+        }
+        reasons
+    }
+}
+
+/// Hash of a ticket's file as it is on disk
+pub fn ticket_hash(ticket: &Ticket) -> Result<String> {
+    let raw = fs::read_to_string(&ticket.path).with_context(|| format!("Failed to read {}", ticket.path.display()))?;
+    Ok(content_hash(&raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::LoadOptions;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_plan_goes_stale_with_tickets_and_issues() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "---\nid: a\n---\n# A\n").unwrap();
+        fs::write(dir.path().join("b.md"), "---\nid: b\nexternal-ref: gh-2\n---\n# B\n").unwrap();
+        let tickets = Ticket::load_all(dir.path(), LoadOptions::default()).unwrap();
+
+        let mut plan = PushPlan::new("o/r");
+        for ticket in &tickets {
+            plan.changes.push(PlannedChange {
+                ticket: ticket.id.clone(),
+                op: if ticket.is_synced() { PlannedOp::Update } else { PlannedOp::Create },
+                title: ticket.title.clone(),
+                issue: ticket.github_issue_number(),
+                fields: Vec::new(),
+                ticket_hash: ticket_hash(ticket).unwrap(),
+                issue_updated_at: ticket.github_issue_number().map(|_| "2026-01-01T00:00:00Z".to_string()),
+            });
+        }
+        let path = dir.path().join("plan.json");
+        plan.save(&path).unwrap();
+        let plan = PushPlan::load(&path).unwrap();
+        assert_eq!(plan.ids(), vec!["a", "b"]);
+
+        let unchanged = HashMap::from([(2, "2026-01-01T00:00:00Z".to_string())]);
+        assert!(plan.stale_reasons("o/r", &tickets, &unchanged).is_empty());
+
+        let edited = HashMap::from([(2, "2026-01-02T00:00:00Z".to_string())]);
+        assert_eq!(plan.stale_reasons("o/r", &tickets, &edited), vec!["#2 (b) changed on GitHub since planning"]);
+        assert_eq!(plan.stale_reasons("o/r", &tickets, &HashMap::new()), vec!["#2 (b) no longer exists"]);
+
+        fs::write(dir.path().join("a.md"), "---\nid: a\n---\n# A, renamed\n").unwrap();
+        assert_eq!(
+            plan.stale_reasons("x/y", &tickets[1..], &unchanged),
+            vec!["planned for o/r, but sync.toml targets x/y", "a no longer exists"]
+        );
+        assert_eq!(plan.stale_reasons("o/r", &tickets, &unchanged), vec!["a changed since planning"]);
+    }
+}
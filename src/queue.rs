@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::ticket::Ticket;
+
+/// Offline push queue, relative to the tickets directory
+pub const QUEUE_FILE: &str = ".push-queue.json";
+
+/// What a queued push is expected to do, judged from the ticket alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueuedOp {
+    Create,
+    Update,
+    Close,
+}
+
+impl QueuedOp {
+    pub fn of(ticket: &Ticket) -> Self {
+        if !ticket.is_synced() {
+            Self::Create
+        } else if ticket.status == "closed" {
+            Self::Close
+        } else {
+            Self::Update
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Close => "close",
+        }
+    }
+}
+
+/// A ticket waiting to be pushed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedPush {
+    pub ticket: String,
+    pub op: QueuedOp,
+    /// When it was queued (RFC 3339)
+    pub queued_at: String,
+}
+
+/// Pushes recorded by `ttr push --queue` for a later `ttr push --flush`
+///
+/// Only ticket IDs are kept: the flush pushes the tickets as they are then,
+/// checked against GitHub like any other push.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PushQueue {
+    #[serde(default)]
+    pub entries: Vec<QueuedPush>,
+}
+
+impl PushQueue {
+    /// Load the queue, starting empty if there is none
+    pub fn load(tickets_dir: &Path) -> Result<Self> {
+        let path = tickets_dir.join(QUEUE_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("Invalid push queue in {}", path.display()))
+    }
+
+    /// Save the queue, removing the file once it is empty
+    pub fn save(&self, tickets_dir: &Path) -> Result<()> {
+        let path = tickets_dir.join(QUEUE_FILE);
+        if self.entries.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            return Ok(());
+        }
+        let raw = serde_json::to_string_pretty(self)?;
+        fs::write(&path, raw + "\n").with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Queue a ticket, replacing any earlier entry for it
+    pub fn add(&mut self, ticket: &Ticket) -> QueuedOp {
+        let op = QueuedOp::of(ticket);
+        self.entries.retain(|e| e.ticket != ticket.id);
+        self.entries.push(QueuedPush {
+            ticket: ticket.id.clone(),
+            op,
+            queued_at: chrono::Utc::now().to_rfc3339(),
+        });
+        op
+    }
+
+    /// IDs of queued tickets, oldest first
+    pub fn ids(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.ticket.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::LoadOptions;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_queue_round_trip() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "---\nid: a\n---\n# A\n").unwrap();
+        fs::write(dir.path().join("b.md"), "---\nid: b\nstatus: closed\nexternal-ref: gh-2\n---\n# B\n").unwrap();
+        let tickets = Ticket::load_all(dir.path(), LoadOptions::default()).unwrap();
+
+        let mut queue = PushQueue::load(dir.path()).unwrap();
+        let ops: Vec<QueuedOp> = tickets.iter().map(|t| queue.add(t)).collect();
+        assert_eq!(ops, vec![QueuedOp::Create, QueuedOp::Close]);
+        queue.add(&tickets[0]);
+        assert_eq!(queue.ids(), vec!["b", "a"]);
+        queue.save(dir.path()).unwrap();
+
+        let mut queue = PushQueue::load(dir.path()).unwrap();
+        assert_eq!(queue.ids(), vec!["b", "a"]);
+
+        queue.entries.clear();
+        queue.save(dir.path()).unwrap();
+        assert!(!dir.path().join(QUEUE_FILE).exists());
+    }
+}
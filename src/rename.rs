@@ -0,0 +1,257 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::frontmatter;
+use crate::ticket::Ticket;
+
+/// Frontmatter fields that refer to other tickets by ID
+const REFERENCE_FIELDS: &[&str] = &["deps", "links", "parent", "duplicate-of"];
+
+/// What a rename changed
+#[derive(Debug)]
+pub struct RenameSummary {
+    /// Where the renamed ticket now lives
+    pub path: PathBuf,
+    /// Tickets whose references were rewritten: (ticket ID, fields)
+    pub updated: Vec<(String, Vec<&'static str>)>,
+}
+
+/// What a prefix remap changed
+#[derive(Debug, Default)]
+pub struct RemapSummary {
+    /// Renamed tickets: (old ID, new ID, where it now lives)
+    pub renamed: Vec<(String, String, PathBuf)>,
+    /// Tickets kept under their ID whose references were rewritten:
+    /// (ticket ID, fields)
+    pub updated: Vec<(String, Vec<&'static str>)>,
+}
+
+/// Rename a ticket and rewrite references to it across `tickets`
+///
+/// A file named after the old ID is moved to match. The old ID is kept in
+/// `previous-ids` so the next push still recognizes the issue's marker and
+/// rewrites it.
+pub fn rename(tickets: &[Ticket], old: &str, new: &str) -> Result<RenameSummary> {
+    if !tickets.iter().any(|t| t.id == old) {
+        anyhow::bail!("No ticket with ID '{}'", old);
+    }
+    let renames = BTreeMap::from([(old.to_string(), new.to_string())]);
+    check(tickets, &renames)?;
+    let summary = apply(tickets, &renames)?;
+    Ok(RenameSummary {
+        path: summary.renamed.into_iter().next().map(|(_, _, path)| path).unwrap_or_default(),
+        updated: summary.updated,
+    })
+}
+
+/// New IDs for every ticket whose ID starts with `from`, with `to` in its
+/// place, checked like a rename
+pub fn remap_ids(tickets: &[Ticket], from: &str, to: &str) -> Result<BTreeMap<String, String>> {
+    if from.is_empty() {
+        anyhow::bail!("The prefix to replace can't be empty");
+    }
+    let renames: BTreeMap<String, String> = tickets
+        .iter()
+        .filter_map(|t| t.id.strip_prefix(from).map(|rest| (t.id.clone(), format!("{}{}", to, rest))))
+        .collect();
+    check(tickets, &renames)?;
+    Ok(renames)
+}
+
+/// Rename every ticket in `renames` (old ID -> new ID) at once, rewriting
+/// references across `tickets` like `rename`
+pub fn remap(tickets: &[Ticket], renames: &BTreeMap<String, String>) -> Result<RemapSummary> {
+    check(tickets, renames)?;
+    apply(tickets, renames)
+}
+
+/// Refuse renames to invalid or taken IDs before touching any file
+///
+/// A new ID may not be any current ID, even one being renamed away, so
+/// rewriting references never chains one rename into another.
+fn check(tickets: &[Ticket], renames: &BTreeMap<String, String>) -> Result<()> {
+    for (old, new) in renames {
+        if new.is_empty() || new.contains(|c: char| c.is_whitespace() || c == '/' || c == '\\') {
+            anyhow::bail!("Invalid ticket ID '{}'", new);
+        }
+        if let Some(existing) = tickets.iter().find(|t| t.id == *new) {
+            anyhow::bail!("Ticket '{}' already exists ({})", new, existing.path.display());
+        }
+        if let Some(existing) = tickets.iter().find(|t| t.aliases.iter().any(|a| a == new)) {
+            anyhow::bail!("'{}' is already an alias of {}", new, existing.id);
+        }
+        if let Some((other, _)) = renames.iter().find(|(o, n)| *n == new && *o != old) {
+            anyhow::bail!("Both '{}' and '{}' would become '{}'", old, other, new);
+        }
+        let Some(ticket) = tickets.iter().find(|t| t.id == *old) else {
+            continue;
+        };
+        let path = new_path(ticket, new);
+        if path != ticket.path && path.exists() {
+            anyhow::bail!("{} already exists", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Where a renamed ticket lives: a file named after the old ID moves
+fn new_path(ticket: &Ticket, new: &str) -> PathBuf {
+    if ticket.path.file_stem().is_some_and(|stem| stem == ticket.id.as_str()) {
+        ticket.path.with_file_name(format!("{}.md", new))
+    } else {
+        ticket.path.clone()
+    }
+}
+
+fn apply(tickets: &[Ticket], renames: &BTreeMap<String, String>) -> Result<RemapSummary> {
+    let mut summary = RemapSummary::default();
+
+    for ticket in tickets {
+        let fields: Vec<&'static str> = REFERENCE_FIELDS
+            .iter()
+            .copied()
+            .filter(|field| match *field {
+                "parent" => ticket.parent.as_ref().is_some_and(|p| renames.contains_key(p)),
+                "deps" => ticket.deps.iter().any(|d| renames.contains_key(d)),
+                "duplicate-of" => ticket.duplicate_of.as_ref().is_some_and(|d| renames.contains_key(d)),
+                _ => ticket.links.iter().any(|l| renames.contains_key(l)),
+            })
+            .collect();
+        let new = renames.get(&ticket.id);
+        if fields.is_empty() && new.is_none() {
+            continue;
+        }
+
+        let mut content = read(&ticket.path)?;
+        // A parent taken from the epic directory is written out, since the
+        // directory keeps the old name
+        let dir_parent = ticket
+            .subdir
+            .as_ref()
+            .filter(|dir| ticket.parent.as_ref() == Some(*dir))
+            .and_then(|dir| renames.get(dir));
+        for field in &fields {
+            if let (&"parent", Some(parent)) = (field, dir_parent) {
+                content = frontmatter::set_field(&content, "parent", parent)
+                    .ok_or_else(|| anyhow::anyhow!("No frontmatter found in {}", ticket.path.display()))?;
+                continue;
+            }
+            // `duplicate-of` may be spelled `duplicate_of`
+            let keys: &[&str] = if *field == "duplicate-of" { &["duplicate-of", "duplicate_of"] } else { &[field] };
+            for key in keys {
+                for (old, new) in renames {
+                    content = frontmatter::rename_value(&content, key, old, new)
+                        .ok_or_else(|| anyhow::anyhow!("No frontmatter found in {}", ticket.path.display()))?;
+                }
+            }
+        }
+
+        let Some(new) = new else {
+            fs::write(&ticket.path, content)
+                .with_context(|| format!("Failed to write ticket: {}", ticket.path.display()))?;
+            summary.updated.push((ticket.id.clone(), fields));
+            continue;
+        };
+
+        // Renaming back drops the ID from the history rather than listing it twice
+        let mut previous_ids: Vec<String> = ticket.previous_ids.iter().filter(|id| *id != new).cloned().collect();
+        previous_ids.push(ticket.id.clone());
+
+        let path = new_path(ticket, new);
+        let content = frontmatter::set_field(&content, "id", new)
+            .and_then(|c| frontmatter::set_list(&c, "previous-ids", &previous_ids))
+            .ok_or_else(|| anyhow::anyhow!("No frontmatter found in {}", ticket.path.display()))?;
+        fs::write(&path, content).with_context(|| format!("Failed to write ticket: {}", path.display()))?;
+        if path != ticket.path {
+            fs::remove_file(&ticket.path)
+                .with_context(|| format!("Failed to remove {}", ticket.path.display()))?;
+        }
+        summary.renamed.push((ticket.id.clone(), new.clone(), path));
+    }
+
+    Ok(summary)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read ticket: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::TicketDir;
+
+    #[test]
+    fn test_rename_rewrites_references() {
+        let dir = TicketDir::new();
+        dir.write("t-1.md", "---\nid: t-1\nexternal-ref: gh-5\n---\n# One\n");
+        dir.write("t-2.md", "---\nid: t-2\ndeps: [t-1, t-10]\nparent: t-1\n---\n# Two\n");
+        dir.write("t-3.md", "---\nid: t-3\nlinks:\n- t-1\n---\n# Three\n");
+        dir.write("t-10.md", "---\nid: t-10\n---\n# Ten\n");
+        dir.write("t-4.md", "---\nid: t-4\nduplicate_of: t-1\n---\n# Four\n");
+        let tickets = dir.load();
+
+        let summary = rename(&tickets, "t-1", "auth-1").unwrap();
+
+        assert_eq!(summary.path, dir.path().join("auth-1.md"));
+        assert!(!dir.path().join("t-1.md").exists());
+        assert_eq!(
+            summary.updated,
+            vec![
+                ("t-2".to_string(), vec!["deps", "parent"]),
+                ("t-3".to_string(), vec!["links"]),
+                ("t-4".to_string(), vec!["duplicate-of"]),
+            ]
+        );
+
+        let tickets = dir.load();
+        let renamed = tickets.iter().find(|t| t.id == "auth-1").unwrap();
+        assert_eq!(renamed.previous_ids, vec!["t-1"]);
+        assert_eq!(renamed.github_issue_number(), Some(5));
+        let two = tickets.iter().find(|t| t.id == "t-2").unwrap();
+        assert_eq!(two.deps, vec!["auth-1", "t-10"]);
+        assert_eq!(two.parent.as_deref(), Some("auth-1"));
+        let four = tickets.iter().find(|t| t.id == "t-4").unwrap();
+        assert_eq!(four.duplicate_of.as_deref(), Some("auth-1"));
+    }
+
+    #[test]
+    fn test_remap_renames_prefix_project_wide() {
+        let dir = TicketDir::new();
+        dir.write("ttr-1.md", "---\nid: ttr-1\nexternal-ref: gh-5\n---\n# One\n");
+        dir.write("ttr-2.md", "---\nid: ttr-2\ndeps: [ttr-1, x-1]\nparent: ttr-1\n---\n# Two\n");
+        dir.write("x-1.md", "---\nid: x-1\nlinks: [ttr-2]\n---\n# Other\n");
+        let tickets = dir.load();
+
+        let renames = remap_ids(&tickets, "ttr-", "t2r-").unwrap();
+        assert_eq!(renames.len(), 2);
+        let summary = remap(&tickets, &renames).unwrap();
+        assert_eq!(summary.renamed.len(), 2);
+        assert_eq!(summary.updated, vec![("x-1".to_string(), vec!["links"])]);
+
+        let tickets = dir.load();
+        let ids: Vec<&str> = tickets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["t2r-1", "t2r-2", "x-1"]);
+        assert_eq!(tickets[0].github_issue_number(), Some(5));
+        assert_eq!(tickets[0].previous_ids, vec!["ttr-1"]);
+        assert_eq!(tickets[1].deps, vec!["t2r-1", "x-1"]);
+        assert_eq!(tickets[1].parent.as_deref(), Some("t2r-1"));
+        assert_eq!(tickets[2].links, vec!["t2r-2"]);
+
+        // Renaming onto an existing ID is refused before anything is written
+        assert!(remap_ids(&tickets, "x-", "t2r-").is_err());
+    }
+
+    #[test]
+    fn test_rename_rejects_existing_id() {
+        let dir = TicketDir::new();
+        dir.write("a.md", "---\nid: a\n---\n# A\n");
+        dir.write("b.md", "---\nid: b\n---\n# B\n");
+        let tickets = dir.load();
+
+        assert!(rename(&tickets, "a", "b").is_err());
+        assert!(rename(&tickets, "missing", "c").is_err());
+    }
+}
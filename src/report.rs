@@ -0,0 +1,155 @@
+/// How a ticket differs from GitHub
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftKind {
+    /// No issue yet; the next push creates one
+    Unpushed,
+    /// Fields the next push would change on the issue
+    Changed(Vec<&'static str>),
+    /// The issue lost its ttr marker, so pushes skip it
+    Edited,
+    /// The recorded issue wasn't found
+    Missing,
+    /// The project item's Status doesn't match the ticket's status
+    ProjectStatus {
+        project: String,
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+/// One ticket out of step with GitHub
+#[derive(Debug, Clone)]
+pub struct Drift {
+    pub id: String,
+    pub title: String,
+    pub issue: Option<u64>,
+    pub kind: DriftKind,
+}
+
+/// Everything a drift check found
+#[derive(Debug, Default)]
+pub struct DriftReport {
+    /// Tickets compared
+    pub checked: usize,
+    pub drift: Vec<Drift>,
+}
+
+impl DriftReport {
+    /// Tickets with at least one difference
+    pub fn drifted(&self) -> usize {
+        let mut ids: Vec<&str> = self.drift.iter().map(|d| d.id.as_str()).collect();
+        ids.dedup();
+        ids.len()
+    }
+}
+
+/// A report table: heading, header row, and the row for a drift if it belongs
+type Section<'a> = (&'a str, &'a str, &'a dyn Fn(&Drift) -> Option<String>);
+
+/// Render a drift report as markdown, one table per kind of drift
+pub fn render_markdown(report: &DriftReport, repo: &str, generated: &str) -> String {
+    let mut out = format!(
+        "# Drift report\n\n`{}`, generated {}. {} ticket(s) checked, {} drifted.\n",
+        repo,
+        generated,
+        report.checked,
+        report.drifted()
+    );
+    if report.drift.is_empty() {
+        out.push_str("\nNo drift: every ticket matches GitHub.\n");
+        return out;
+    }
+
+    let issue = |d: &Drift| d.issue.map_or_else(String::new, |n| format!("#{}", n));
+    let sections: [Section; 4] = [
+        ("Not on GitHub", "| Ticket | Title |", &|d| {
+            matches!(d.kind, DriftKind::Unpushed).then(|| format!("| {} | {} |", d.id, cell(&d.title)))
+        }),
+        ("Differs from its issue", "| Ticket | Issue | Differs |", &|d| match &d.kind {
+            DriftKind::Changed(fields) => Some(format!("| {} | {} | {} |", d.id, issue(d), fields.join(", "))),
+            _ => None,
+        }),
+        ("Project status", "| Ticket | Issue | Project | Expected | On GitHub |", &|d| match &d.kind {
+            DriftKind::ProjectStatus { project, expected, actual } => Some(format!(
+                "| {} | {} | {} | {} | {} |",
+                d.id,
+                issue(d),
+                cell(project),
+                cell(expected),
+                actual.as_deref().map_or("(not set)".to_string(), cell)
+            )),
+            _ => None,
+        }),
+        ("Needs attention", "| Ticket | Issue | Problem |", &|d| {
+            let problem = match d.kind {
+                DriftKind::Edited => "issue modified outside ttr",
+                DriftKind::Missing => "issue not found",
+                _ => return None,
+            };
+            Some(format!("| {} | {} | {} |", d.id, issue(d), problem))
+        }),
+    ];
+
+    for (heading, header, row) in sections {
+        let rows: Vec<String> = report.drift.iter().filter_map(row).collect();
+        if rows.is_empty() {
+            continue;
+        }
+        let separator = "|---".repeat(header.matches('|').count() - 1);
+        out.push_str(&format!("\n## {} ({})\n\n{}\n{}|\n", heading, rows.len(), header, separator));
+        for row in rows {
+            out.push_str(&row);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Keep text from breaking out of a table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drift(id: &str, issue: Option<u64>, kind: DriftKind) -> Drift {
+        Drift {
+            id: id.to_string(),
+            title: format!("{} title", id),
+            issue,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_render_markdown_groups_drift() {
+        let report = DriftReport {
+            checked: 6,
+            drift: vec![
+                drift("t-1", None, DriftKind::Unpushed),
+                drift("t-2", Some(4), DriftKind::Changed(vec!["title", "state"])),
+                drift("t-2", Some(4), DriftKind::ProjectStatus {
+                    project: "Roadmap".to_string(),
+                    expected: "Done".to_string(),
+                    actual: None,
+                }),
+                drift("t-3", Some(7), DriftKind::Edited),
+            ],
+        };
+
+        assert_eq!(
+            render_markdown(&report, "o/r", "2026-01-01T00:00:00Z"),
+            "# Drift report\n\n`o/r`, generated 2026-01-01T00:00:00Z. 6 ticket(s) checked, 3 drifted.\n\
+             \n## Not on GitHub (1)\n\n| Ticket | Title |\n|---|---|\n| t-1 | t-1 title |\n\
+             \n## Differs from its issue (1)\n\n| Ticket | Issue | Differs |\n|---|---|---|\n| t-2 | #4 | title, state |\n\
+             \n## Project status (1)\n\n| Ticket | Issue | Project | Expected | On GitHub |\n|---|---|---|---|---|\n\
+             | t-2 | #4 | Roadmap | Done | (not set) |\n\
+             \n## Needs attention (1)\n\n| Ticket | Issue | Problem |\n|---|---|---|\n| t-3 | #7 | issue modified outside ttr |\n"
+        );
+
+        let clean = DriftReport { checked: 2, drift: Vec::new() };
+        assert!(render_markdown(&clean, "o/r", "now").ends_with("No drift: every ticket matches GitHub.\n"));
+    }
+}
@@ -0,0 +1,220 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use crate::sync::SyncOptions;
+use crate::ticket::Ticket;
+
+/// Sync state file, relative to the tickets directory
+pub const STATE_FILE: &str = ".sync-state.json";
+
+/// Ticket fields as they were when ticket and issue last agreed
+///
+/// The common ancestor for three-way merges in `ttr sync`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub title: String,
+    pub status: String,
+}
+
+impl Baseline {
+    pub fn of(ticket: &Ticket) -> Self {
+        Self {
+            title: ticket.title.clone(),
+            status: ticket.status.clone(),
+        }
+    }
+}
+
+/// What ttr remembers between runs, in `.tickets/.sync-state.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    /// Ticket ID -> baseline
+    #[serde(default)]
+    pub baselines: BTreeMap<String, Baseline>,
+    /// Push stopped by Ctrl-C, for `ttr push --resume`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interrupted: Option<InterruptedPush>,
+    /// Tickets recent pushes failed on, for `ttr push --retry-failed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed: Option<FailedPush>,
+    /// Ticket ID -> when issue creation was last attempted without
+    /// confirming success (RFC 3339)
+    ///
+    /// The create may still have gone through, so the next push looks for
+    /// the issue before creating another.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub create_attempts: BTreeMap<String, String>,
+    /// Login the last round-robin assignment went to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_assignee: Option<String>,
+    /// IDs of tickets whose issues ttr pinned, so dropping `pinned` unpins
+    /// them without touching issues pinned by hand
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub pinned: BTreeSet<String>,
+    /// IDs of archived tickets whose issues ttr locked
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub locked: BTreeSet<String>,
+    /// Ticket ID -> keys of the worklog entries commented on its issue
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub worklog: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// A push that stopped before all its phases ran
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterruptedPush {
+    /// IDs of the tickets the push was syncing
+    pub ids: Vec<String>,
+    pub options: SyncOptions,
+    /// When it stopped (RFC 3339)
+    pub at: String,
+}
+
+/// Tickets pushes failed on and haven't pushed since
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedPush {
+    pub ids: Vec<String>,
+    /// Options of the latest push that failed
+    pub options: SyncOptions,
+    /// When it finished (RFC 3339)
+    pub at: String,
+}
+
+impl SyncState {
+    /// Load the state, starting empty if there is none yet
+    pub fn load(tickets_dir: &Path) -> Result<Self> {
+        let path = tickets_dir.join(STATE_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("Invalid sync state in {}", path.display()))
+    }
+
+    pub fn save(&self, tickets_dir: &Path) -> Result<()> {
+        let path = tickets_dir.join(STATE_FILE);
+        let raw = serde_json::to_string_pretty(self)?;
+        fs::write(&path, raw + "\n").with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Record tickets as matching their issues
+    pub fn record<'a>(&mut self, tickets: impl IntoIterator<Item = &'a Ticket>) {
+        for ticket in tickets {
+            self.baselines.insert(ticket.id.clone(), Baseline::of(ticket));
+        }
+    }
+
+    /// Follow renamed tickets: `renames` maps old IDs to new ones
+    pub fn rename_ids(&mut self, renames: &BTreeMap<String, String>) {
+        let rename = |id: String| renames.get(&id).cloned().unwrap_or(id);
+        self.baselines = std::mem::take(&mut self.baselines).into_iter().map(|(k, v)| (rename(k), v)).collect();
+        self.create_attempts =
+            std::mem::take(&mut self.create_attempts).into_iter().map(|(k, v)| (rename(k), v)).collect();
+        self.pinned = std::mem::take(&mut self.pinned).into_iter().map(rename).collect();
+        self.locked = std::mem::take(&mut self.locked).into_iter().map(rename).collect();
+        self.worklog = std::mem::take(&mut self.worklog).into_iter().map(|(k, v)| (rename(k), v)).collect();
+        for ids in [self.interrupted.as_mut().map(|i| &mut i.ids), self.failed.as_mut().map(|f| &mut f.ids)]
+            .into_iter()
+            .flatten()
+        {
+            for id in ids.iter_mut() {
+                *id = rename(std::mem::take(id));
+            }
+        }
+    }
+
+    /// Note which of a finished push's tickets failed; earlier failures
+    /// outside the push stay recorded
+    pub fn record_failures(&mut self, pushed: &[String], failed: &[String], options: SyncOptions) {
+        let mut ids: Vec<String> = self
+            .failed
+            .take()
+            .map(|f| f.ids)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|id| !pushed.contains(id))
+            .collect();
+        ids.extend(failed.iter().cloned());
+        if ids.is_empty() {
+            return;
+        }
+        self.failed = Some(FailedPush {
+            ids,
+            options,
+            at: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::LoadOptions;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sync_state_round_trip() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "---\nid: a\nstatus: in_progress\n---\n# A\n").unwrap();
+        let tickets = Ticket::load_all(dir.path(), LoadOptions::default()).unwrap();
+
+        let mut state = SyncState::load(dir.path()).unwrap();
+        assert!(state.baselines.is_empty());
+        state.record(&tickets);
+        state.save(dir.path()).unwrap();
+
+        let state = SyncState::load(dir.path()).unwrap();
+        assert_eq!(
+            state.baselines["a"],
+            Baseline {
+                title: "A".to_string(),
+                status: "in_progress".to_string()
+            }
+        );
+        assert!(state.interrupted.is_none());
+        assert!(state.create_attempts.is_empty());
+    }
+
+    #[test]
+    fn test_interrupted_push_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let mut state = SyncState::default();
+        let interrupted = InterruptedPush {
+            ids: vec!["a".to_string(), "b".to_string()],
+            options: SyncOptions {
+                labels: false,
+                ..SyncOptions::default()
+            },
+            at: "2026-01-01T00:00:00Z".to_string(),
+        };
+        state.interrupted = Some(interrupted.clone());
+        state.save(dir.path()).unwrap();
+
+        let mut state = SyncState::load(dir.path()).unwrap();
+        assert_eq!(state.interrupted, Some(interrupted));
+
+        state.interrupted = None;
+        state.save(dir.path()).unwrap();
+        let raw = fs::read_to_string(dir.path().join(STATE_FILE)).unwrap();
+        assert!(!raw.contains("interrupted"));
+    }
+
+    #[test]
+    fn test_record_failures_keeps_failures_outside_the_push() {
+        let ids = |ids: &[&str]| ids.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let mut state = SyncState::default();
+        state.record_failures(&ids(&["a", "b", "c"]), &ids(&["a", "b"]), SyncOptions::default());
+        assert_eq!(state.failed.as_ref().unwrap().ids, ids(&["a", "b"]));
+
+        let options = SyncOptions { labels: false, ..SyncOptions::default() };
+        state.record_failures(&ids(&["a", "d"]), &ids(&["d"]), options);
+        let failed = state.failed.as_ref().unwrap();
+        assert_eq!(failed.ids, ids(&["b", "d"]));
+        assert_eq!(failed.options, options);
+
+        state.record_failures(&ids(&["b", "d"]), &[], SyncOptions::default());
+        assert!(state.failed.is_none());
+    }
+}
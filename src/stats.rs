@@ -0,0 +1,277 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::ticket::Ticket;
+
+/// Backlog statistics for `ttr stats`
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub total: usize,
+    pub by_status: BTreeMap<String, usize>,
+    pub by_type: BTreeMap<String, usize>,
+    pub by_priority: BTreeMap<u8, usize>,
+    pub by_tag: BTreeMap<String, usize>,
+    /// Average age in days of open tickets with a `created` date
+    pub average_open_age_days: Option<f64>,
+    /// Average days from `created` to `closed` for closed tickets with both
+    pub average_days_to_close: Option<f64>,
+    /// Most recently closed tickets, newest first
+    pub recently_closed: Vec<ClosedTicket>,
+    pub sync: SyncCoverage,
+    /// Weekly opened/closed counts, oldest first
+    pub weeks: Vec<WeekStats>,
+}
+
+/// A closed ticket with when it was opened and closed
+#[derive(Debug, Serialize)]
+pub struct ClosedTicket {
+    pub id: String,
+    pub title: String,
+    /// YYYY-MM-DD
+    pub created: Option<String>,
+    /// YYYY-MM-DD
+    pub closed: String,
+    pub days_open: Option<i64>,
+}
+
+/// How many closed tickets `recently_closed` lists
+const RECENTLY_CLOSED: usize = 5;
+
+/// How many tickets are tracked on GitHub
+#[derive(Debug, Default, Serialize)]
+pub struct SyncCoverage {
+    pub synced: usize,
+    pub drafts: usize,
+    pub unsynced: usize,
+}
+
+/// Tickets opened and closed in a week (Monday start)
+#[derive(Debug, Serialize)]
+pub struct WeekStats {
+    /// Monday of the week (YYYY-MM-DD)
+    pub week: String,
+    pub opened: usize,
+    pub closed: usize,
+    /// Tickets open at the end of the week
+    pub open: usize,
+}
+
+/// Parse the date part of a timestamp like `2026-01-29T12:00:00Z`
+fn parse_date(timestamp: &str) -> Option<NaiveDate> {
+    timestamp
+        .get(..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Compute statistics over tickets as of `today`, with `weeks` of history
+///
+/// The timeline needs `created` dates; closed tickets without a `closed`
+/// timestamp count as opened but never as open.
+pub fn compute(tickets: &[Ticket], today: NaiveDate, weeks: usize) -> Stats {
+    let mut by_status = BTreeMap::new();
+    let mut by_type = BTreeMap::new();
+    let mut by_priority = BTreeMap::new();
+    let mut by_tag = BTreeMap::new();
+    let mut sync = SyncCoverage::default();
+    let mut open_ages: Vec<i64> = Vec::new();
+
+    for ticket in tickets {
+        *by_status.entry(ticket.status.clone()).or_insert(0) += 1;
+        *by_type.entry(ticket.ticket_type.clone()).or_insert(0) += 1;
+        *by_priority.entry(ticket.priority).or_insert(0) += 1;
+        for tag in &ticket.tags {
+            *by_tag.entry(tag.clone()).or_insert(0) += 1;
+        }
+
+        if ticket.is_synced() {
+            sync.synced += 1;
+        } else if ticket.draft_item_id().is_some() {
+            sync.drafts += 1;
+        } else {
+            sync.unsynced += 1;
+        }
+
+        if ticket.status != "closed" {
+            if let Some(created) = ticket.created.as_deref().and_then(parse_date) {
+                open_ages.push((today - created).num_days());
+            }
+        }
+    }
+
+    let average_open_age_days = if open_ages.is_empty() {
+        None
+    } else {
+        Some(open_ages.iter().sum::<i64>() as f64 / open_ages.len() as f64)
+    };
+
+    let recently_closed = closed_tickets(tickets);
+    let close_times: Vec<i64> = recently_closed.iter().filter_map(|t| t.days_open).collect();
+    let average_days_to_close = if close_times.is_empty() {
+        None
+    } else {
+        Some(close_times.iter().sum::<i64>() as f64 / close_times.len() as f64)
+    };
+
+    Stats {
+        total: tickets.len(),
+        by_status,
+        by_type,
+        by_priority,
+        by_tag,
+        average_open_age_days,
+        average_days_to_close,
+        recently_closed: recently_closed.into_iter().take(RECENTLY_CLOSED).collect(),
+        sync,
+        weeks: timeline(tickets, today, weeks),
+    }
+}
+
+/// Closed tickets with a `closed` date, newest first
+fn closed_tickets(tickets: &[Ticket]) -> Vec<ClosedTicket> {
+    let mut closed: Vec<ClosedTicket> = tickets
+        .iter()
+        .filter(|t| t.status == "closed")
+        .filter_map(|t| {
+            let closed = t.closed.as_deref().and_then(parse_date)?;
+            let created = t.created.as_deref().and_then(parse_date);
+            Some(ClosedTicket {
+                id: t.id.clone(),
+                title: t.title.clone(),
+                created: created.map(|d| d.format("%Y-%m-%d").to_string()),
+                closed: closed.format("%Y-%m-%d").to_string(),
+                days_open: created.map(|c| (closed - c).num_days()),
+            })
+        })
+        .collect();
+    closed.sort_by(|a, b| b.closed.cmp(&a.closed).then_with(|| a.id.cmp(&b.id)));
+    closed
+}
+
+fn timeline(tickets: &[Ticket], today: NaiveDate, weeks: usize) -> Vec<WeekStats> {
+    // (created, closed) dates; closed tickets with unknown close dates are
+    // treated as closed since creation
+    let spans: Vec<(NaiveDate, Option<NaiveDate>)> = tickets
+        .iter()
+        .filter_map(|t| {
+            let created = t.created.as_deref().and_then(parse_date)?;
+            let closed = if t.status == "closed" {
+                Some(t.closed.as_deref().and_then(parse_date).unwrap_or(created))
+            } else {
+                None
+            };
+            Some((created, closed))
+        })
+        .collect();
+
+    let current = week_start(today);
+    (0..weeks)
+        .rev()
+        .map(|i| {
+            let start = current - Duration::weeks(i as i64);
+            let end = start + Duration::days(6);
+            let in_week = |d: NaiveDate| d >= start && d <= end;
+
+            WeekStats {
+                week: start.format("%Y-%m-%d").to_string(),
+                opened: spans.iter().filter(|(c, _)| in_week(*c)).count(),
+                closed: tickets
+                    .iter()
+                    .filter(|t| t.status == "closed")
+                    .filter_map(|t| t.closed.as_deref().and_then(parse_date))
+                    .filter(|d| in_week(*d))
+                    .count(),
+                open: spans
+                    .iter()
+                    .filter(|(c, closed)| *c <= end && closed.is_none_or(|d| d > end))
+                    .count(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::TicketDir;
+
+    fn ticket(id: &str, status: &str, created: Option<&str>, closed: Option<&str>) -> Ticket {
+        let mut frontmatter = format!("status: {}\ntype: task\ntags: [core]\n", status);
+        for (key, value) in [("created", created), ("closed", closed)] {
+            if let Some(value) = value {
+                frontmatter.push_str(&format!("{}: {}\n", key, value));
+            }
+        }
+        TicketDir::new().ticket(id, &frontmatter)
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_counts_and_age() {
+        let mut synced = ticket("a", "open", Some("2026-03-01T09:00:00Z"), None);
+        synced.external_ref = Some("gh-1".to_string());
+        let tickets = vec![
+            synced,
+            ticket("b", "open", Some("2026-03-05"), None),
+            ticket("c", "closed", None, None),
+        ];
+
+        let stats = compute(&tickets, date("2026-03-11"), 2);
+
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.by_status.get("open"), Some(&2));
+        assert_eq!(stats.by_tag.get("core"), Some(&3));
+        assert_eq!(stats.sync.synced, 1);
+        assert_eq!(stats.sync.unsynced, 2);
+        // Ages 10 and 6 days
+        assert_eq!(stats.average_open_age_days, Some(8.0));
+    }
+
+    #[test]
+    fn test_weekly_timeline() {
+        let tickets = vec![
+            ticket("a", "open", Some("2026-03-02"), None),
+            ticket("b", "closed", Some("2026-03-03"), Some("2026-03-10T08:00:00Z")),
+            ticket("c", "open", Some("2026-03-11"), None),
+        ];
+
+        // 2026-03-02 and 2026-03-09 are Mondays
+        let stats = compute(&tickets, date("2026-03-12"), 2);
+
+        assert_eq!(stats.weeks.len(), 2);
+        assert_eq!(stats.weeks[0].week, "2026-03-02");
+        assert_eq!(stats.weeks[0].opened, 2);
+        assert_eq!(stats.weeks[0].closed, 0);
+        assert_eq!(stats.weeks[0].open, 2);
+        assert_eq!(stats.weeks[1].opened, 1);
+        assert_eq!(stats.weeks[1].closed, 1);
+        assert_eq!(stats.weeks[1].open, 2);
+    }
+
+    #[test]
+    fn test_recently_closed_and_time_to_close() {
+        let tickets = vec![
+            ticket("a", "closed", Some("2026-03-01"), Some("2026-03-05T10:00:00Z")),
+            ticket("b", "closed", Some("2026-03-02"), Some("2026-03-12T10:00:00Z")),
+            ticket("c", "closed", None, Some("2026-03-08")),
+            ticket("d", "closed", Some("2026-03-02"), None),
+        ];
+
+        let stats = compute(&tickets, date("2026-03-12"), 0);
+
+        let ids: Vec<&str> = stats.recently_closed.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "a"]);
+        assert_eq!(stats.recently_closed[0].created.as_deref(), Some("2026-03-02"));
+        assert_eq!(stats.recently_closed[0].days_open, Some(10));
+        assert_eq!(stats.recently_closed[1].days_open, None);
+        // 4 and 10 days
+        assert_eq!(stats.average_days_to_close, Some(7.0));
+    }
+}
@@ -1,11 +1,45 @@
-use crate::config::Config;
-use crate::github::client::GitHubClient;
-use crate::github::issues::{ExistingIssue, IssueCreate, IssueUpdate};
-use crate::github::projects::{ProjectFieldInfo, ProjectFieldType, ProjectInfo};
+use crate::audit::{AuditAction, AuditEntry, AuditLog};
+use crate::cache::RepoMetadata;
+use crate::commits::CLOSING_KEYWORDS;
+use crate::deps;
+use crate::conflicts;
+use crate::config::{
+    ApiTransport, AssigneeStrategy, Config, CriteriaCheck, DepsFormat, LabelsConfig, MappingConfig, MarkerPosition, MentionLinks, PrLinkMode, ProjectFieldsConfig,
+    route_matches,
+    SyncConfig,
+};
+use crate::github::client::{is_schema_error, GitHubClient};
+use crate::github::issues::{AssigneeChange, ExistingIssue, IssueAssignee, IssueCreate, IssueUpdate, MAX_PINNED_ISSUES};
+use crate::github::projects::{
+    IterationOption, ProjectFieldInfo, ProjectFieldType, ProjectFieldValue, ProjectInfo,
+};
+use crate::github::pulls::PullRequestInfo;
 use crate::github::subissues::SubIssueLink;
-use crate::ticket::Ticket;
+use crate::push_plan::{self, PlannedChange, PlannedOp, PlannedSkip, PushPlan};
+use crate::report::{Drift, DriftKind, DriftReport};
+use crate::secrets;
+use crate::state::{InterruptedPush, SyncState};
+use crate::ticket::{now_timestamp, Ticket, DISCUSSION_SECTION};
+use crate::template::IssueTemplate;
+use crate::timings::{Phase, Recorder};
+use crate::worklog;
 use anyhow::Result;
-use std::collections::HashMap;
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by Ctrl-C to stop a push once in-flight requests and write-backs finish
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Ask a running push to stop before its next phase
+pub fn request_cancel() {
+    CANCELLED.store(true, Ordering::Relaxed);
+}
+
+fn cancelled() -> bool {
+    CANCELLED.load(Ordering::Relaxed)
+}
 
 /// Cached project field information for setting Status/Iteration
 #[derive(Debug, Clone)]
@@ -14,6 +48,93 @@ struct ProjectFieldsCache {
     status: Option<StatusFieldCache>,
     /// Iteration field ID and the iteration ID to use
     iteration: Option<IterationFieldCache>,
+    /// Project fields set from custom frontmatter keys
+    custom: Vec<CustomFieldCache>,
+}
+
+/// A project field mapped from a custom frontmatter key or the ticket type
+#[derive(Debug, Clone)]
+struct CustomFieldCache {
+    /// Frontmatter key
+    key: String,
+    field_id: String,
+    field_name: String,
+    kind: CustomFieldKind,
+    source: FieldSource,
+}
+
+/// Where a mapped project field gets its value
+#[derive(Debug, Clone)]
+enum FieldSource {
+    /// The custom frontmatter key
+    Custom,
+    /// The ticket type, renamed through `[mapping.type]`
+    Type(HashMap<String, String>),
+    /// Tags with this prefix, e.g. `area` for `area:backend`, minus the prefix
+    Tag(String),
+    /// The ticket's `estimate-hours`
+    Estimate,
+    /// The ticket's `spent-hours` plus its descendants'
+    Spent,
+}
+
+#[derive(Debug, Clone)]
+enum CustomFieldKind {
+    Text,
+    Number,
+    Date,
+    /// option name (lowercase) -> option ID
+    SingleSelect(HashMap<String, String>),
+}
+
+impl CustomFieldCache {
+    /// The ticket's value for the field, as written
+    ///
+    /// `spent` holds each ticket's hours with its descendants' added in.
+    fn raw_value(&self, ticket: &Ticket, spent: &HashMap<String, f64>) -> Option<String> {
+        match &self.source {
+            FieldSource::Custom => ticket.custom_value(&self.key),
+            FieldSource::Type(type_map) => Some(
+                type_map
+                    .get(&ticket.ticket_type)
+                    .cloned()
+                    .unwrap_or_else(|| ticket.ticket_type.clone()),
+            ),
+            FieldSource::Tag(prefix) => {
+                let values: Vec<&str> = ticket
+                    .tags
+                    .iter()
+                    .filter_map(|tag| tag.strip_prefix(prefix.as_str())?.strip_prefix(':'))
+                    .filter(|value| !value.is_empty())
+                    .collect();
+                (!values.is_empty()).then(|| values.join(", "))
+            }
+            FieldSource::Estimate => ticket.estimate_hours.map(|h| h.to_string()),
+            FieldSource::Spent => spent.get(&ticket.id).map(|h| h.to_string()),
+        }
+    }
+
+    /// The field value for a ticket, if it sets the key to something valid
+    fn value_for(&self, ticket: &Ticket, spent: &HashMap<String, f64>) -> Option<ProjectFieldValue> {
+        let raw = self.raw_value(ticket, spent)?;
+        let value = match &self.kind {
+            CustomFieldKind::Text => Some(ProjectFieldValue::Text(raw.clone())),
+            CustomFieldKind::Number => raw.parse().ok().map(ProjectFieldValue::Number),
+            CustomFieldKind::Date => NaiveDate::parse_from_str(raw.get(..10).unwrap_or(&raw), "%Y-%m-%d")
+                .ok()
+                .map(|d| ProjectFieldValue::Date(d.format("%Y-%m-%d").to_string())),
+            CustomFieldKind::SingleSelect(options) => options
+                .get(&raw.to_lowercase())
+                .map(|id| ProjectFieldValue::SingleSelect(id.clone())),
+        };
+        if value.is_none() {
+            eprintln!(
+                "WARN    {} {}: '{}' is not a valid value for project field '{}'",
+                ticket.id, self.key, raw, self.field_name
+            );
+        }
+        value
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -26,7 +147,46 @@ struct StatusFieldCache {
 #[derive(Debug, Clone)]
 struct IterationFieldCache {
     field_id: String,
-    iteration_id: String,
+    /// Iteration from config, used when a ticket doesn't pick its own
+    default_iteration_id: Option<String>,
+    /// Active and upcoming iterations, in start date order
+    active: Vec<IterationOption>,
+    /// Completed iterations (only matched by name or due date)
+    completed: Vec<IterationOption>,
+}
+
+impl IterationFieldCache {
+    /// All known iterations, active first
+    fn all(&self) -> impl Iterator<Item = &IterationOption> {
+        self.active.iter().chain(self.completed.iter())
+    }
+}
+
+/// A resolved project with its cached fields
+///
+/// Routes naming the same project share one target, with their field
+/// settings merged.
+#[derive(Debug, Clone)]
+struct ProjectTarget {
+    info: ProjectInfo,
+    fields_config: ProjectFieldsConfig,
+    /// Cached project field info for Status/Iteration, fetched on first use
+    fields: tokio::sync::OnceCell<Option<ProjectFieldsCache>>,
+}
+
+/// A route's filters, and the index of the project it leads to
+#[derive(Debug, Clone)]
+struct RouteTarget {
+    types: Vec<String>,
+    tags: Vec<String>,
+    project: usize,
+}
+
+impl RouteTarget {
+    /// Check if a ticket takes this route
+    fn matches(&self, ticket: &Ticket) -> bool {
+        route_matches(&self.types, &self.tags, &ticket.ticket_type, &ticket.tags)
+    }
 }
 
 /// Result of syncing a single ticket
@@ -34,6 +194,8 @@ struct IterationFieldCache {
 pub enum SyncResult {
     Created { issue_id: String, issue_number: u64, url: String },
     Updated { issue_number: u64 },
+    Drafted { item_id: String, project: String, updated: bool },
+    Discussed { number: u64, url: String, updated: bool },
     Skipped { reason: String },
     Failed { error: String },
 }
@@ -43,8 +205,26 @@ struct PendingCreate {
     ticket_idx: usize,
     title: String,
     body: String,
-    label_ids: Vec<String>,
+    /// Label names, resolved to IDs once every create's labels exist
+    labels: Vec<String>,
     issue_type_id: Option<String>,
+    /// User ID of the ticket's own assignee; others go to the configured ones
+    assignee_id: Option<String>,
+}
+
+/// Pending draft item create for batch processing
+struct PendingDraft {
+    result_idx: usize,
+    title: String,
+    body: String,
+}
+
+/// A project item whose Status `ttr report` compares with its ticket
+struct StatusCheck<'a> {
+    ticket: &'a Ticket,
+    issue_number: u64,
+    issue_id: String,
+    expected: String,
 }
 
 /// Pending update for batch processing
@@ -57,6 +237,10 @@ struct PendingUpdate {
     needs_close: bool,
     needs_reopen: bool,
     issue_type_id: Option<String>,
+    /// User IDs to add and remove, if the assignee changed
+    assignees: Option<(Vec<String>, Vec<String>)>,
+    /// Whether the issue still needs `[github] managed_label`
+    needs_managed_label: bool,
 }
 
 /// Result of checking if an update is needed
@@ -71,6 +255,8 @@ enum UpdateCheck {
         body: String,
         needs_close: bool,
         needs_reopen: bool,
+        assignees: Option<(Vec<String>, Vec<String>)>,
+        needs_managed_label: bool,
     },
 }
 
@@ -81,6 +267,57 @@ pub struct SyncSummary {
     pub updated: u32,
     pub skipped: u32,
     pub failed: u32,
+    /// IDs of tickets whose issue matches them after the push
+    pub in_sync: Vec<String>,
+    /// IDs of tickets that failed or were skipped with changes left unpushed
+    pub unresolved: Vec<String>,
+    /// IDs of tickets that failed
+    pub failed_ids: Vec<String>,
+    /// Whether Ctrl-C stopped the push before all phases ran
+    pub interrupted: bool,
+    /// Tickets the push created or updated, in ticket order
+    pub changes: Vec<Change>,
+}
+
+/// A ticket a push created or updated on GitHub
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub id: String,
+    pub title: String,
+    /// Link to the issue or discussion; None for draft items
+    pub url: Option<String>,
+    pub created: bool,
+}
+
+impl Change {
+    fn of(ticket: &Ticket, url: Option<String>, created: bool) -> Self {
+        Self {
+            id: ticket.id.clone(),
+            title: ticket.title.clone(),
+            url,
+            created,
+        }
+    }
+}
+
+/// Summary of a push check
+#[derive(Debug, Default)]
+pub struct CheckSummary {
+    /// Tickets a push would create or update
+    pub pending: u32,
+    /// Tickets a push would skip or fail on
+    pub conflicts: u32,
+}
+
+/// Summary of a plan operation
+#[derive(Debug, Default)]
+pub struct PlanSummary {
+    pub planned: u32,
+    pub added: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    /// IDs of tickets whose project items were updated
+    pub planned_ids: Vec<String>,
 }
 
 /// Orchestrates syncing tickets to GitHub
@@ -90,71 +327,223 @@ pub struct SyncEngine {
     repo_id: String,
     owner: String,
     repo_name: String,
-    assignee_id: Option<String>,
+    assignees: Vec<(String, String)>,          // (login, user ID) new issues are assigned to
+    user_ids: HashMap<String, Option<String>>,  // login (lowercase) -> user ID, None if not found
     label_cache: HashMap<String, String>,       // label name -> label ID
     ticket_to_issue: HashMap<String, u64>,      // ticket ID -> GitHub issue number
+    elsewhere: HashMap<String, String>,         // ticket ID -> reference, for transferred issues and discussions
+    closed_tickets: HashSet<String>,            // IDs of closed tickets, for task lists
+    dependents: HashMap<String, Vec<String>>,   // ticket ID -> IDs of tickets depending on it
+    related: HashMap<String, Vec<String>>,      // ticket ID -> linked ticket IDs, both directions
+    duplicates: HashMap<String, Vec<String>>,   // ticket ID -> IDs of tickets marked duplicates of it
+    blob_base: Option<String>,                  // URL prefix for repo files on the default branch
+    footer_base: Option<String>,                // URL prefix the footer links ticket files under
+    issue_template: Option<IssueTemplate>,      // Headings issue bodies are laid out under
+    children: HashMap<String, Vec<String>>,     // ticket ID -> IDs of tickets with it as parent
+    spent: HashMap<String, f64>,                // ticket ID -> spent hours, its descendants' included
     issue_type_cache: HashMap<String, String>,  // issue type name (lowercase) -> ID
-    project: Option<ProjectInfo>,               // Project to add issues to (if configured)
-    project_fields: Option<ProjectFieldsCache>, // Cached project field info for Status/Iteration
+    projects: Vec<ProjectTarget>,               // Projects to add issues to, each once
+    routes: Vec<RouteTarget>,                   // Routes into `projects`, in routing order
+    audit: AuditLog,
+    state_dir: Option<PathBuf>,                 // Where create attempts are recorded, if anywhere
+    sub_issues_supported: bool,                 // Whether the instance has sub-issues
+    timings: Option<Recorder>,                  // Phase and request timings, with `options.timings`
+    options: SyncOptions,
+}
+
+/// Which parts of a push run, for minimal pushes when an API is flaky
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SyncOptions {
+    /// Add issues to projects, set their fields, and sync draft items
+    pub projects: bool,
+    /// Link sub-issues from `parent`
+    pub sub_issues: bool,
+    /// Apply tag and type labels to new issues
+    pub labels: bool,
+    /// Sync project Status, Type, and custom fields for existing issues
+    pub status: bool,
+    /// Fail, rather than warn, when closing a ticket open tickets depend on
+    pub strict_deps: bool,
+    /// Push tickets even when their text looks like it contains credentials
+    #[serde(default)]
+    pub allow_secrets: bool,
+    /// Most mutations a minute, for huge first pushes; checkpoints as it goes
+    #[serde(default)]
+    pub pace: Option<u32>,
+    /// Create issues for at most this many tickets, highest priority first
+    #[serde(default)]
+    pub top: Option<u32>,
+    /// Refetch labels and issue types instead of using the cached ones
+    /// (`--refresh-cache`)
+    #[serde(skip)]
+    pub refresh_cache: bool,
+    /// Record per-phase and per-request timings (`--timings`)
+    #[serde(skip)]
+    pub timings: bool,
+}
+
+/// Creates between checkpoints of a paced push
+const PACE_CHECKPOINT: usize = 25;
+
+/// Minutes before the earliest leftover create attempt that issues are
+/// listed from, in case GitHub's clock is behind ours
+const CREATE_CLOCK_SKEW_MINUTES: i64 = 5;
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            projects: true,
+            sub_issues: true,
+            labels: true,
+            status: true,
+            strict_deps: false,
+            allow_secrets: false,
+            pace: None,
+            top: None,
+            refresh_cache: false,
+            timings: false,
+        }
+    }
 }
 
 impl SyncEngine {
     /// Create a new sync engine
-    pub async fn new(client: GitHubClient, config: Config) -> Result<Self> {
+    ///
+    /// Labels and issue types are cached in `tickets_dir` for `[cache] ttl`.
+    pub async fn new(client: GitHubClient, config: Config, tickets_dir: &Path) -> Result<Self> {
+        Self::with_options(client, config, tickets_dir, SyncOptions::default()).await
+    }
+
+    /// Create a sync engine that runs only the phases enabled in `options`
+    ///
+    /// Without `projects`, configured projects aren't even looked up.
+    pub async fn with_options(
+        client: GitHubClient,
+        config: Config,
+        tickets_dir: &Path,
+        options: SyncOptions,
+    ) -> Result<Self> {
+        let timings = options.timings.then(Recorder::default);
+        let client = match &timings {
+            Some(recorder) => client.with_timings(recorder.clone()),
+            None => client,
+        };
+        let _timing = Phase::start(timings.as_ref(), "startup lookups");
         let (owner, repo_name) = config.github.repo_parts()?;
+        let issue_template = config.sync.issue_template.as_deref().map(IssueTemplate::load).transpose()?;
         let owner = owner.to_string();
         let repo_name = repo_name.to_string();
 
         // Get repository ID
         let repo_id = client.get_repository_id(&owner, &repo_name).await?;
 
-        // Get assignee ID if configured
-        let assignee_id = if let Some(ref username) = config.github.assignee {
-            Some(client.get_user_id(username).await?)
-        } else {
-            None
-        };
+        // Get assignee IDs if configured
+        let mut assignees = Vec::new();
+        for login in config.github.assignee_pool() {
+            let id = client.get_user_id(&login).await?;
+            assignees.push((login, id));
+        }
 
-        // Pre-fetch labels
-        let labels = client.get_labels(&owner, &repo_name).await?;
-        let label_cache: HashMap<String, String> = labels
-            .into_iter()
-            .map(|l| (l.name.to_lowercase(), l.id))
-            .collect();
+        // Sub-issues and issue types aren't on every plan or GHES version
+        let capabilities = client.get_capabilities().await;
+        if !capabilities.issue_types && !config.mapping.type_map.is_empty() {
+            eprintln!("NOTE    Issue types aren't available on this GitHub instance; [mapping.type] is ignored");
+        }
 
-        // Pre-fetch issue types (org-level feature, empty for personal repos)
-        let issue_types = client.get_issue_types(&owner, &repo_name).await?;
-        let issue_type_cache: HashMap<String, String> = issue_types
-            .into_iter()
-            .map(|t| (t.name.to_lowercase(), t.id))
-            .collect();
+        // Labels and issue types (org-level feature, empty for personal repos)
+        // Over REST, label IDs are names, so each API keeps its own cache
+        let repo = match config.github.api {
+            ApiTransport::Graphql => format!("{}/{}", owner, repo_name),
+            ApiTransport::Rest => format!("{}/{} (rest)", owner, repo_name),
+        };
+        let (label_cache, issue_type_cache, cached) =
+            match RepoMetadata::load(tickets_dir, &repo, config.cache.ttl, options.refresh_cache) {
+                Some(metadata) => (metadata.labels, metadata.issue_types, true),
+                None => {
+                    let labels = client.get_labels(&owner, &repo_name).await?;
+                    let label_cache: HashMap<String, String> = labels
+                        .into_iter()
+                        .map(|l| (l.name.to_lowercase(), l.id))
+                        .collect();
+
+                    let issue_types = if capabilities.issue_types {
+                        client.get_issue_types(&owner, &repo_name).await?
+                    } else {
+                        Vec::new()
+                    };
+                    let issue_type_cache: HashMap<String, String> = issue_types
+                        .into_iter()
+                        .map(|t| (t.name.to_lowercase(), t.id))
+                        .collect();
+
+                    let metadata = RepoMetadata::new(&repo, label_cache, issue_type_cache);
+                    if let Err(e) = metadata.save(tickets_dir) {
+                        eprintln!("WARN    Could not write cache: {}", e);
+                    }
+                    (metadata.labels, metadata.issue_types, false)
+                }
+            };
 
         // Validate issue type mappings
         if let Err(e) = validate_issue_type_mappings(&config.mapping.type_map, &issue_type_cache) {
+            if cached {
+                anyhow::bail!("{}\n(issue types were cached; rerun with --refresh-cache if they changed)", e);
+            }
             anyhow::bail!("{}", e);
         }
 
-        // Find project if configured
-        let (project, project_fields) = if let Some(ref project_name) = config.github.project {
-            match client.find_project(&owner, &repo_name, project_name).await? {
-                Some(p) => {
-                    println!("Using project: {} (#{})", p.title, p.number);
-                    
-                    // Fetch and cache project fields if status/iteration mappings are configured
-                    let fields_cache = Self::setup_project_fields(&client, &p, &config).await?;
-                    
-                    (Some(p), fields_cache)
-                }
-                None => {
-                    anyhow::bail!(
-                        "Project '{}' not found. Check the project name or number in sync.toml.",
-                        project_name
-                    );
+        // Default branch, for turning relative file links into URLs
+        let needs_default_branch =
+            config.sync.absolute_links || (config.sync.footer_link && config.sync.footer_branch.is_none());
+        let default_branch = if needs_default_branch {
+            match client.get_default_branch(&owner, &repo_name).await {
+                Ok(branch) => branch,
+                Err(e) => {
+                    eprintln!("WARN    Could not read default branch, leaving file links relative: {}", e);
+                    None
                 }
             }
         } else {
-            (None, None)
+            None
         };
+        let blob_base = default_branch
+            .as_deref()
+            .filter(|_| config.sync.absolute_links)
+            .map(|branch| blob_url(&owner, &repo_name, branch));
+        let footer_base = footer_blob_base(&config.sync, &owner, &repo_name, default_branch.as_deref());
+
+        // Find configured projects, each with its own field cache
+        let mut projects: Vec<ProjectTarget> = Vec::new();
+        let mut project_routes = Vec::new();
+        let routes = if options.projects { config.project_routes() } else { Vec::new() };
+        for route in routes {
+            let Some(p) = client.find_project(&owner, &repo_name, route.name).await? else {
+                anyhow::bail!("Project '{}' not found. Check the project name or number in sync.toml.", route.name);
+            };
+            // Routes naming one project by title and by number share it
+            let project = match projects.iter().position(|target| target.info.id == p.id) {
+                Some(i) => {
+                    projects[i].fields_config.merge(route.fields).map_err(|e| {
+                        anyhow::anyhow!("Route '{}' to project '{}' has {} in sync.toml", route.name, p.title, e)
+                    })?;
+                    i
+                }
+                None => {
+                    println!("Using project: {} (#{})", p.title, p.number);
+                    projects.push(ProjectTarget {
+                        info: p,
+                        fields_config: route.fields.clone(),
+                        fields: tokio::sync::OnceCell::new(),
+                    });
+                    projects.len() - 1
+                }
+            };
+            project_routes.push(RouteTarget {
+                types: route.types.to_vec(),
+                tags: route.tags.to_vec(),
+                project,
+            });
+        }
 
         Ok(Self {
             client,
@@ -162,947 +551,4256 @@ impl SyncEngine {
             repo_id,
             owner,
             repo_name,
-            assignee_id,
+            user_ids: assignees.iter().map(|(login, id)| (login.to_lowercase(), Some(id.clone()))).collect(),
+            assignees,
             label_cache,
             ticket_to_issue: HashMap::new(), // Will be populated during sync
+            elsewhere: HashMap::new(),
+            closed_tickets: HashSet::new(),
+            dependents: HashMap::new(),
+            related: HashMap::new(),
+            duplicates: HashMap::new(),
+            blob_base,
+            footer_base,
+            issue_template,
+            children: HashMap::new(),
+            spent: HashMap::new(),
             issue_type_cache,
-            project,
-            project_fields,
+            projects,
+            routes: project_routes,
+            audit: AuditLog::disabled(),
+            state_dir: None,
+            sub_issues_supported: capabilities.sub_issues,
+            timings,
+            options,
         })
     }
 
-    /// Sync a list of tickets
-    /// 
-    /// `tickets` are the tickets to sync, `all_tickets` is used to build the
-    /// dependency lookup (for rendering "Depends on" references).
-    pub async fn sync(&mut self, tickets: &mut [Ticket], all_tickets: &[Ticket]) -> Result<SyncSummary> {
-        let mut summary = SyncSummary::default();
-        let mut results: Vec<(usize, SyncResult)> = Vec::new();
+    /// Record every change made on GitHub to an audit log
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Self {
+        self.audit = audit;
+        self
+    }
 
-        // Build ticket ID → issue number lookup for dependency resolution
-        // Use all_tickets so deps resolve even when pushing a subset
-        self.ticket_to_issue = all_tickets
+    /// Record issue creation attempts in the tickets directory's sync state,
+    /// and quarantine conflicted issue bodies there
+    ///
+    /// A create that errors may still have reached GitHub; recorded attempts
+    /// are looked up by marker before the ticket is created again.
+    pub fn with_sync_state(mut self, tickets_dir: &Path) -> Self {
+        self.state_dir = Some(tickets_dir.to_path_buf());
+        self
+    }
+
+    /// Timings recorded so far, if `options.timings` is set
+    pub fn timings(&self) -> Option<&Recorder> {
+        self.timings.as_ref()
+    }
+
+    /// Time a phase until the guard is dropped
+    fn phase(&self, name: &'static str) -> Phase {
+        Phase::start(self.timings.as_ref(), name)
+    }
+
+    /// Write the issue URL into a newly created ticket, if configured
+    ///
+    /// The external-ref is already written, so a failure here only warns.
+    fn write_issue_url(&self, ticket: &mut Ticket, issue_number: u64) {
+        if !self.config.sync.write_url {
+            return;
+        }
+        let url = format!("https://github.com/{}/{}/issues/{}", self.owner, self.repo_name, issue_number);
+        if let Err(e) = ticket.write_url(&url) {
+            eprintln!("WARN    {} failed to write url: {}", ticket.id, e);
+        }
+    }
+
+    /// Title for a ticket's issue or draft item
+    fn issue_title(&self, ticket: &Ticket) -> String {
+        issue_title(&self.config, ticket)
+    }
+
+    /// Find the project a ticket routes to (first match wins)
+    fn project_for(&self, ticket: &Ticket) -> Option<&ProjectTarget> {
+        self.project_index(ticket).map(|i| &self.projects[i])
+    }
+
+    /// Index in `projects` of the project a ticket routes to
+    fn project_index(&self, ticket: &Ticket) -> Option<usize> {
+        self.routes.iter().find(|r| r.matches(ticket)).map(|r| r.project)
+    }
+
+    /// Check if a ticket should live as a draft item in its routed project
+    fn is_draft_type(&self, ticket: &Ticket) -> bool {
+        self.project_for(ticket).is_some_and(|p| {
+            p.fields_config
+                .draft_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(&ticket.ticket_type))
+        })
+    }
+
+    /// Whether any configured project keeps this ticket's type as a draft,
+    /// without needing the project looked up
+    fn configured_draft_type(&self, ticket: &Ticket) -> bool {
+        self.config
+            .project_routes()
             .iter()
-            .filter_map(|t| t.github_issue_number().map(|n| (t.id.clone(), n)))
-            .collect();
+            .any(|route| route.fields.draft_types.iter().any(|t| t.eq_ignore_ascii_case(&ticket.ticket_type)))
+    }
 
-        // Batch fetch all existing issues upfront
-        // Include both tickets being synced AND their parents (for sub-issue linking)
-        let mut issue_numbers: Vec<u64> = tickets
+    /// Create, update, or convert draft project items
+    ///
+    /// Tickets that are no longer a draft type are converted to real issues
+    /// (returned as `Created` so they get external-refs, sub-issues, etc).
+    async fn sync_drafts(&mut self, tickets: &[Ticket], idxs: &[usize]) -> Vec<SyncResult> {
+        let mut results = vec![SyncResult::Failed { error: "Not processed".to_string() }; idxs.len()];
+
+        // Drafts becoming issues get the labels a create would
+        let converting: Vec<&Ticket> = idxs
             .iter()
-            .filter_map(|t| t.github_issue_number())
+            .map(|&idx| &tickets[idx])
+            .filter(|t| t.draft_item_id().is_some() && !self.is_draft_type(t))
             .collect();
+        let labels: Vec<String> = converting.iter().flat_map(|t| self.create_labels(t)).collect();
+        self.ensure_labels(&labels).await;
+        let mut converted: Vec<(usize, &Ticket)> = Vec::new();
 
-        // Also fetch parent issues (need their node IDs for sub-issue linking)
-        for ticket in tickets.iter() {
-            if let Some(ref parent_id) = ticket.parent {
-                if let Some(parent_num) = self.ticket_to_issue.get(parent_id) {
-                    if !issue_numbers.contains(parent_num) {
-                        issue_numbers.push(*parent_num);
+        // New drafts, batched per project
+        let mut new_drafts: Vec<(&ProjectTarget, Vec<PendingDraft>)> = Vec::new();
+
+        for (i, &idx) in idxs.iter().enumerate() {
+            let ticket = &tickets[idx];
+            let target = match self.project_for(ticket) {
+                Some(t) => t,
+                None => {
+                    results[i] = SyncResult::Failed { error: "draft item has no project route".to_string() };
+                    continue;
+                }
+            };
+
+            let item_id = match ticket.draft_item_id() {
+                Some(id) => id,
+                None => {
+                    let draft = PendingDraft {
+                        result_idx: i,
+                        title: self.issue_title(ticket),
+                        body: self.format_issue_body(ticket),
+                    };
+                    match new_drafts.iter_mut().find(|(t, _)| t.info.id == target.info.id) {
+                        Some((_, drafts)) => drafts.push(draft),
+                        None => new_drafts.push((target, vec![draft])),
                     }
+                    continue;
                 }
+            };
+
+            // No longer a draft type: promote to a real issue
+            if !self.is_draft_type(ticket) {
+                results[i] = match self.client.convert_draft_to_issue(item_id, &self.repo_id).await {
+                    Ok(info) => {
+                        converted.push((i, ticket));
+                        SyncResult::Created {
+                            issue_id: info.id,
+                            issue_number: info.number,
+                            url: info.url,
+                        }
+                    }
+                    Err(e) => SyncResult::Failed { error: e.to_string() },
+                };
+                continue;
             }
-        }
 
-        let existing_issues = if !issue_numbers.is_empty() {
-            self.client
-                .get_issues_batch(&self.owner, &self.repo_name, &issue_numbers)
-                .await
-                .unwrap_or_default()
-        } else {
-            HashMap::new()
-        };
+            // Still a draft: update title/body if they changed
+            results[i] = match self.client.get_draft_issue(item_id).await {
+                Ok(Some(draft)) => {
+                    let title = self.issue_title(ticket);
+                    let body = self.format_issue_body(ticket);
+                    if draft.title == title && draft.body == body {
+                        SyncResult::Skipped { reason: "no changes".to_string() }
+                    } else {
+                        match self.client.update_draft_issue(&draft.id, &title, &body).await {
+                            Ok(()) => SyncResult::Drafted {
+                                item_id: item_id.to_string(),
+                                project: target.info.title.clone(),
+                                updated: true,
+                            },
+                            Err(e) => SyncResult::Failed { error: e.to_string() },
+                        }
+                    }
+                }
+                Ok(None) => SyncResult::Failed {
+                    error: format!("Draft item {} not found (converted or deleted outside ttr?)", item_id),
+                },
+                Err(e) => SyncResult::Failed { error: e.to_string() },
+            };
+        }
 
-        // Phase 1: Categorize tickets
-        let mut pending_creates: Vec<PendingCreate> = Vec::new();
-        let mut pending_updates: Vec<PendingUpdate> = Vec::new();
+        self.finish_conversions(&converted, &results).await;
 
-        for (idx, ticket) in tickets.iter().enumerate() {
-            if ticket.is_synced() {
-                // Check if update is needed
-                match self.check_update_needed(ticket, &existing_issues) {
-                    UpdateCheck::NoChanges => {
-                        results.push((idx, SyncResult::Skipped { reason: "no changes".to_string() }));
-                    }
-                    UpdateCheck::Conflict(reason) => {
-                        results.push((idx, SyncResult::Skipped { reason }));
-                    }
-                    UpdateCheck::Error(e) => {
-                        results.push((idx, SyncResult::Failed { error: e }));
+        for (target, drafts) in new_drafts {
+            let inputs: Vec<(String, String)> = drafts
+                .iter()
+                .map(|d| (d.title.clone(), d.body.clone()))
+                .collect();
+            match self.client.add_draft_issues_batch(&target.info.id, &inputs).await {
+                Ok(add_results) => {
+                    for (draft, result) in drafts.iter().zip(add_results) {
+                        results[draft.result_idx] = match result {
+                            Ok(item) => SyncResult::Drafted {
+                                item_id: item.item_id,
+                                project: target.info.title.clone(),
+                                updated: false,
+                            },
+                            Err(e) => SyncResult::Failed { error: e },
+                        };
                     }
-                    UpdateCheck::NeedsUpdate { issue_id, issue_number, title, body, needs_close, needs_reopen } => {
-                        pending_updates.push(PendingUpdate {
-                            ticket_idx: idx,
-                            issue_id,
-                            issue_number,
-                            title,
-                            body,
-                            needs_close,
-                            needs_reopen,
-                            issue_type_id: self.resolve_issue_type_id(&ticket.ticket_type),
-                        });
+                }
+                Err(e) => {
+                    for draft in &drafts {
+                        results[draft.result_idx] = SyncResult::Failed { error: e.to_string() };
                     }
                 }
-            } else {
-                // Collect creates for batching
-                let label_ids = self.resolve_label_ids(&ticket.tags).await;
-                let issue_type_id = self.resolve_issue_type_id(&ticket.ticket_type);
-                pending_creates.push(PendingCreate {
-                    ticket_idx: idx,
-                    title: ticket.title.clone(),
-                    body: self.format_issue_body(ticket),
-                    label_ids,
-                    issue_type_id,
-                });
             }
         }
 
-        // Phase 2: Batch create issues
-        if !pending_creates.is_empty() {
-            let create_results = self.batch_create(&pending_creates).await;
-            for (pending, result) in pending_creates.iter().zip(create_results) {
-                // Write external-ref back to ticket file on success
-                if let SyncResult::Created { issue_number, .. } = &result {
-                    let ticket = &mut tickets[pending.ticket_idx];
-                    let external_ref = format!("gh-{}", issue_number);
-                    if let Err(e) = ticket.write_external_ref(&external_ref) {
-                        results.push((pending.ticket_idx, SyncResult::Failed {
-                            error: format!("Created #{} but failed to write external-ref: {}", issue_number, e),
-                        }));
-                        continue;
-                    }
-                }
-                results.push((pending.ticket_idx, result));
-            }
+        results
+    }
+
+    /// Sort creates highest priority first, then by ID, so `--top` and paced
+    /// pushes create what matters most before the rest
+    ///
+    /// Returns the results for creates left past `--top`, which are dropped.
+    fn order_creates(&self, pending: &mut Vec<PendingCreate>, tickets: &[Ticket]) -> Vec<(usize, SyncResult)> {
+        pending.sort_by(|a, b| {
+            let (a, b) = (&tickets[a.ticket_idx], &tickets[b.ticket_idx]);
+            (a.priority, &a.id).cmp(&(b.priority, &b.id))
+        });
+        let Some(top) = self.options.top else {
+            return Vec::new();
+        };
+        pending
+            .split_off((top as usize).min(pending.len()))
+            .into_iter()
+            .map(|p| (p.ticket_idx, SyncResult::Skipped { reason: format!("past --top {}", top) }))
+            .collect()
+    }
+
+    /// Whether a ticket's issue may close while open tickets depend on it
+    ///
+    /// Returns a warning to print if it may, or the ticket's error under
+    /// `--strict-deps`.
+    fn check_open_dependents(&self, ticket: &Ticket, issue_number: u64, all_tickets: &[Ticket]) -> Result<Option<String>, String> {
+        let dependents = deps::open_dependents(all_tickets, &self.closed_tickets, &ticket.id);
+        if dependents.is_empty() {
+            return Ok(None);
         }
+        let open = format!("open tickets depend on it: {}", dependents.join(", "));
+        if self.options.strict_deps {
+            return Err(format!("not closing #{}: {}", issue_number, open));
+        }
+        Ok(Some(format!("{} closing while {}", ticket.id, open)))
+    }
 
-        // Phase 3: Batch update issues
-        if !pending_updates.is_empty() {
-            let update_results = self.batch_update(&pending_updates).await;
-            for (pending, result) in pending_updates.iter().zip(update_results) {
-                results.push((pending.ticket_idx, result));
+    /// Give issues converted from drafts the labels, assignee and issue type
+    /// a create would have set
+    ///
+    /// The issue exists either way, so failures warn rather than fail the
+    /// ticket; its external-ref still needs writing.
+    async fn finish_conversions(&self, converted: &[(usize, &Ticket)], results: &[SyncResult]) {
+        let issue_id = |i: usize| match &results[i] {
+            SyncResult::Created { issue_id, .. } => issue_id.clone(),
+            _ => unreachable!("converted drafts are created"),
+        };
+
+        let updates: Vec<IssueUpdate> = converted
+            .iter()
+            .filter_map(|&(i, ticket)| {
+                Some(IssueUpdate {
+                    issue_id: issue_id(i),
+                    title: self.issue_title(ticket),
+                    body: split_body(&self.format_issue_body(ticket)).0,
+                    issue_type_id: Some(self.resolve_issue_type_id(&ticket.ticket_type)?),
+                })
+            })
+            .collect();
+        if !updates.is_empty() {
+            let warn = |e: &str| eprintln!("WARN    Could not set issue type on converted draft: {}", e);
+            match self.client.update_issues_batch(&updates).await {
+                Ok(by_issue) => by_issue.values().filter_map(|r| r.as_ref().err()).for_each(|e| warn(e)),
+                Err(e) => warn(&e.to_string()),
             }
         }
 
-        // Sort by original index and print results
-        results.sort_by_key(|(idx, _)| *idx);
+        let additions: Vec<(String, Vec<String>)> = converted
+            .iter()
+            .map(|&(i, ticket)| (issue_id(i), self.label_ids(&self.create_labels(ticket))))
+            .filter(|(_, ids)| !ids.is_empty())
+            .collect();
+        if !additions.is_empty() {
+            let warn = |e: &str| eprintln!("WARN    Could not label converted draft: {}", e);
+            match self.client.add_labels_batch(&additions).await {
+                Ok(by_issue) => by_issue.values().filter_map(|r| r.as_ref().err()).for_each(|e| warn(e)),
+                Err(e) => warn(&e.to_string()),
+            }
+        }
 
-        for (idx, result) in &results {
-            let ticket = &tickets[*idx];
-            match result {
-                SyncResult::Created { issue_number, url, .. } => {
-                    println!(
-                        "CREATE  {} → #{}  {}",
-                        ticket.id, issue_number, ticket.title
-                    );
-                    println!("  └─ {}", url);
-                    summary.created += 1;
-                }
-                SyncResult::Updated { issue_number } => {
-                    println!(
-                        "UPDATE  {} → #{}  {}",
-                        ticket.id, issue_number, ticket.title
-                    );
-                    summary.updated += 1;
-                }
-                SyncResult::Skipped { reason } => {
-                    println!("SKIP    {}  ({})", ticket.id, reason);
-                    summary.skipped += 1;
+        // Tickets naming their own assignee don't take a turn from the pool
+        let own: Vec<Option<String>> = converted
+            .iter()
+            .map(|(_, ticket)| ticket.assignee.as_ref().and_then(|_| self.desired_assignee_id(ticket)))
+            .collect();
+        let mut picks = self.pick_assignees(own.iter().filter(|id| id.is_none()).count()).await.into_iter();
+        let mut turns: Vec<Option<String>> = Vec::with_capacity(converted.len());
+        let mut changes: Vec<AssigneeChange> = Vec::new();
+        for (&(i, _), own) in converted.iter().zip(own) {
+            let add = match own {
+                Some(id) => {
+                    turns.push(None);
+                    vec![id]
                 }
-                SyncResult::Failed { error } => {
-                    println!("FAIL    {}  {}", ticket.id, error);
-                    summary.failed += 1;
+                None => {
+                    let (ids, turn) = picks.next().unwrap_or_default();
+                    turns.push(turn);
+                    ids
                 }
+            };
+            if !add.is_empty() {
+                changes.push(AssigneeChange { issue_id: issue_id(i), add, remove: Vec::new() });
             }
         }
+        if !changes.is_empty() {
+            let warn = |e: &str| eprintln!("WARN    Could not assign converted draft: {}", e);
+            match self.client.update_assignees_batch(&changes).await {
+                Ok(by_issue) => by_issue.values().filter_map(|r| r.as_ref().err()).for_each(|e| warn(e)),
+                Err(e) => warn(&e.to_string()),
+            }
+        }
+        let created: Vec<SyncResult> = converted.iter().map(|&(i, _)| results[i].clone()).collect();
+        self.record_last_turn(&turns, &created);
+    }
 
-        // Phase 4: Link sub-issues (parent/child relationships)
-        self.link_sub_issues(tickets, all_tickets, &results, &existing_issues).await;
+    /// Label names a new issue gets: the ticket's labels plus the managed one
+    fn create_labels(&self, ticket: &Ticket) -> Vec<String> {
+        let mut labels = if self.options.labels {
+            issue_labels(ticket, &self.config.labels, !self.issue_type_cache.is_empty())
+        } else {
+            Vec::new()
+        };
+        labels.extend(self.config.github.managed_label.clone());
+        labels
+    }
 
-        // Phase 5: Add to project and set fields for new issues
-        self.add_to_project(&results, tickets).await;
+    /// Whether a ticket syncs to a discussion rather than an issue
+    ///
+    /// Already-synced discussions stay discussions even if their tags change.
+    fn is_discussion(&self, ticket: &Ticket) -> bool {
+        ticket.discussion_number().is_some()
+            || (ticket.external_ref.is_none() && self.config.discussions.wants(&ticket.tags))
+    }
 
-        // Phase 6: Sync project Status for all synced tickets
-        self.sync_project_status(tickets, &existing_issues).await;
+    /// Create discussions for new tickets and update existing ones
+    async fn sync_discussions(&self, tickets: &[Ticket], idxs: &[usize]) -> Vec<SyncResult> {
+        let numbers: Vec<u64> = idxs.iter().filter_map(|&i| tickets[i].discussion_number()).collect();
+        let existing = match self.client.get_discussions_batch(&self.owner, &self.repo_name, &numbers).await {
+            Ok(existing) => existing,
+            Err(e) => return vec![SyncResult::Failed { error: e.to_string() }; idxs.len()],
+        };
 
-        Ok(summary)
+        let mut category_id: Option<Result<String, String>> = None;
+        let mut results = Vec::with_capacity(idxs.len());
+        for &idx in idxs {
+            let ticket = &tickets[idx];
+            let title = self.issue_title(ticket);
+            let body = self.format_issue_body(ticket);
+            let manage_body = manages_body(ticket, &self.config.sync);
+
+            let result = match ticket.discussion_number() {
+                Some(number) => match existing.get(&number) {
+                    None => SyncResult::Failed { error: format!("Discussion #{} not found", number) },
+                    Some(discussion) if manage_body && !has_ticket_marker(&discussion.body, ticket) => {
+                        SyncResult::Skipped { reason: "discussion modified outside ttr".to_string() }
+                    }
+                    Some(discussion) if discussion.title == title && (!manage_body || body_matches(&discussion.body, &body)) => {
+                        SyncResult::Skipped { reason: "no changes".to_string() }
+                    }
+                    Some(discussion) => {
+                        let body = if manage_body { &body } else { &discussion.body };
+                        match self.client.update_discussion(&discussion.id, &title, body).await {
+                            Ok(info) => SyncResult::Discussed { number: info.number, url: info.url, updated: true },
+                            Err(e) => SyncResult::Failed { error: e.to_string() },
+                        }
+                    }
+                },
+                None => {
+                    // Looked up once, and only when something is created
+                    if category_id.is_none() {
+                        category_id = Some(self.discussion_category_id().await);
+                    }
+                    match category_id.as_ref().expect("looked up above") {
+                        Ok(category_id) => {
+                            match self.client.create_discussion(&self.repo_id, category_id, &title, &body).await {
+                                Ok(info) => SyncResult::Discussed { number: info.number, url: info.url, updated: false },
+                                Err(e) => SyncResult::Failed { error: e.to_string() },
+                            }
+                        }
+                        Err(e) => SyncResult::Failed { error: e.clone() },
+                    }
+                }
+            };
+            results.push(result);
+        }
+        results
     }
 
-    /// Add newly created issues to the configured project and set field values
-    async fn add_to_project(&self, results: &[(usize, SyncResult)], tickets: &[Ticket]) {
-        let project = match &self.project {
-            Some(p) => p,
-            None => return, // No project configured
+    /// ID of the `[discussions] category`
+    async fn discussion_category_id(&self) -> Result<String, String> {
+        let Some(name) = self.config.discussions.category.as_deref() else {
+            return Err("No discussion category configured. Set [discussions] category in sync.toml.".to_string());
         };
+        let categories = self
+            .client
+            .get_discussion_categories(&self.owner, &self.repo_name)
+            .await
+            .map_err(|e| e.to_string())?;
+        categories
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .map(|c| c.id.clone())
+            .ok_or_else(|| {
+                let available: Vec<&str> = categories.iter().map(|c| c.name.as_str()).collect();
+                format!(
+                    "Discussion category '{}' not found (available: {}); are Discussions enabled for the repo?",
+                    name,
+                    available.join(", ")
+                )
+            })
+    }
 
-        // Collect issue info for newly created issues
-        // (issue_id, ticket_id, ticket_status)
-        let mut issue_info: Vec<(String, &str, &str)> = Vec::new();
-        for (idx, result) in results {
-            if let SyncResult::Created { issue_id, .. } = result {
-                let ticket = &tickets[*idx];
-                issue_info.push((issue_id.clone(), &ticket.id, &ticket.status));
+    /// Sync a list of tickets
+    /// 
+    /// `tickets` are the tickets to sync, `all_tickets` is used to build the
+    /// dependency lookup (for rendering "Depends on" references).
+    pub async fn sync(&mut self, tickets: &mut [Ticket], all_tickets: &[Ticket]) -> Result<SyncSummary> {
+        let mut summary = SyncSummary::default();
+        let mut results: Vec<(usize, SyncResult)> = Vec::new();
+
+        let timing = self.phase("fetch issues");
+        let existing_issues = self.fetch_existing_issues(tickets, all_tickets).await;
+        self.resolve_assignees(tickets).await;
+        drop(timing);
+
+        // Phase 1: Categorize tickets
+        let timing = self.phase("categorize");
+        let mut pending_creates: Vec<PendingCreate> = Vec::new();
+        let mut pending_updates: Vec<PendingUpdate> = Vec::new();
+
+        let mut draft_idxs: Vec<usize> = Vec::new();
+        let mut discussion_idxs: Vec<usize> = Vec::new();
+
+        for (idx, ticket) in tickets.iter().enumerate() {
+            // Drafts live only in projects; skip rather than create them as issues
+            if !self.options.projects && (ticket.draft_item_id().is_some() || self.configured_draft_type(ticket)) {
+                results.push((idx, SyncResult::Skipped { reason: "project sync disabled".to_string() }));
+                continue;
             }
-        }
 
-        if issue_info.is_empty() {
-            return;
-        }
+            // Discussions are handled separately too
+            if self.is_discussion(ticket) {
+                if let Some(error) = self.secrets_error(ticket) {
+                    results.push((idx, SyncResult::Failed { error }));
+                    continue;
+                }
+                discussion_idxs.push(idx);
+                continue;
+            }
 
-        // Batch add to project
-        let ids: Vec<String> = issue_info.iter().map(|(id, _, _)| id.clone()).collect();
-        let add_results = match self.client.add_issues_to_project_batch(&project.id, &ids).await {
-            Ok(results) => results,
-            Err(e) => {
-                eprintln!("\nWARN    Failed to add issues to project: {}", e);
-                return;
+            // Draft project items are handled separately
+            if ticket.draft_item_id().is_some() || (!ticket.is_synced() && self.is_draft_type(ticket)) {
+                if let Some(error) = self.secrets_error(ticket) {
+                    results.push((idx, SyncResult::Failed { error }));
+                    continue;
+                }
+                draft_idxs.push(idx);
+                continue;
             }
-        };
 
-        // Collect successfully added items with their item IDs
-        // (item_id, ticket_id, ticket_status)
-        let mut added_items: Vec<(String, &str, &str)> = Vec::new();
-        
-        println!();
-        for ((_, ticket_id, ticket_status), result) in issue_info.iter().zip(add_results) {
-            match result {
-                Ok(item_info) => {
-                    println!("PROJECT {} → {} (added)", ticket_id, project.title);
-                    if !item_info.item_id.is_empty() {
-                        added_items.push((item_info.item_id, ticket_id, ticket_status));
+            // Transferred issues are out of this repo's reach
+            if let Some((repo, _)) = ticket.issue_elsewhere() {
+                results.push((idx, SyncResult::Skipped { reason: format!("issue lives in {}", repo) }));
+                continue;
+            }
+
+            if ticket.is_synced() {
+                // Check if update is needed
+                let check = self.check_update_needed(ticket, &existing_issues);
+                self.quarantine_conflict(ticket, &check, &existing_issues);
+                match check {
+                    UpdateCheck::NoChanges => {
+                        summary.in_sync.push(ticket.id.clone());
+                        results.push((idx, SyncResult::Skipped { reason: "no changes".to_string() }));
+                    }
+                    UpdateCheck::Conflict(reason) => {
+                        results.push((idx, SyncResult::Skipped { reason }));
+                    }
+                    UpdateCheck::Error(e) => {
+                        results.push((idx, SyncResult::Failed { error: e }));
+                    }
+                    UpdateCheck::NeedsUpdate { issue_id, issue_number, title, body, needs_close, needs_reopen, assignees, needs_managed_label } => {
+                        if let Some(error) = self.secrets_error(ticket) {
+                            results.push((idx, SyncResult::Failed { error }));
+                            continue;
+                        }
+                        if needs_close {
+                            if let Some((done, total)) = ticket.acceptance_criteria().filter(|(d, t)| d < t) {
+                                let unchecked = format!("{}/{} acceptance criteria checked", done, total);
+                                match self.config.sync.unchecked_criteria {
+                                    CriteriaCheck::Allow => {}
+                                    CriteriaCheck::Warn => eprintln!("WARN    {} closing with {}", ticket.id, unchecked),
+                                    CriteriaCheck::Fail => {
+                                        results.push((idx, SyncResult::Failed {
+                                            error: format!("not closing #{}: only {}", issue_number, unchecked),
+                                        }));
+                                        continue;
+                                    }
+                                }
+                            }
+                        }
+                        if needs_close {
+                            match self.check_open_dependents(ticket, issue_number, all_tickets) {
+                                Ok(Some(warning)) => eprintln!("WARN    {}", warning),
+                                Ok(None) => {}
+                                Err(error) => {
+                                    results.push((idx, SyncResult::Failed { error }));
+                                    continue;
+                                }
+                            }
+                        }
+                        pending_updates.push(PendingUpdate {
+                            ticket_idx: idx,
+                            issue_id,
+                            issue_number,
+                            title,
+                            body,
+                            needs_close,
+                            needs_reopen,
+                            issue_type_id: self.resolve_issue_type_id(&ticket.ticket_type),
+                            assignees,
+                            needs_managed_label,
+                        });
                     }
                 }
-                Err(e) => {
-                    eprintln!("WARN    {} project add failed: {}", ticket_id, e);
+            } else {
+                if let Some(error) = self.secrets_error(ticket) {
+                    results.push((idx, SyncResult::Failed { error }));
+                    continue;
                 }
+                // Collect creates for batching
+                let labels = self.create_labels(ticket);
+                let issue_type_id = self.resolve_issue_type_id(&ticket.ticket_type);
+                let assignee_id = ticket.assignee.as_ref().and_then(|_| self.desired_assignee_id(ticket));
+                pending_creates.push(PendingCreate {
+                    ticket_idx: idx,
+                    title: self.issue_title(ticket),
+                    body: self.format_issue_body(ticket),
+                    labels,
+                    issue_type_id,
+                    assignee_id,
+                });
             }
         }
 
-        // Set field values if we have items and field config
-        if !added_items.is_empty() {
-            if let Some(ref fields_cache) = self.project_fields {
-                self.set_project_field_values(&project.id, &added_items, fields_cache).await;
+        results.extend(self.order_creates(&mut pending_creates, tickets));
+
+        let labels: Vec<String> = pending_creates.iter().flat_map(|p| p.labels.iter().cloned()).collect();
+        self.ensure_labels(&labels).await;
+
+        drop(timing);
+
+        // Phase 2: Batch create issues
+        let timing = self.phase("batch create");
+        if !pending_creates.is_empty() && !cancelled() {
+            // Earlier attempts that may have gone through are looked up first
+            let mut recovered = self.recover_creates(tickets, &pending_creates).await;
+            let to_create: Vec<&PendingCreate> =
+                pending_creates.iter().filter(|p| !recovered.contains_key(&p.ticket_idx)).collect();
+
+            // Paced pushes write back and checkpoint every few creates, so
+            // a push that dies partway can be resumed without duplicates
+            let chunk_size = match self.options.pace {
+                Some(_) => PACE_CHECKPOINT,
+                None => to_create.len().max(1),
+            };
+            let mut done = 0;
+            for chunk in to_create.chunks(chunk_size) {
+                if done > 0 && cancelled() {
+                    break;
+                }
+                let attempted_at = chrono::Utc::now().to_rfc3339();
+                self.update_state(|state| {
+                    for pending in chunk {
+                        state.create_attempts.insert(tickets[pending.ticket_idx].id.clone(), attempted_at.clone());
+                    }
+                });
+                let created = self.batch_create(chunk).await;
+                self.update_state(|state| {
+                    for (pending, result) in chunk.iter().zip(&created) {
+                        if let SyncResult::Created { .. } = result {
+                            state.create_attempts.remove(&tickets[pending.ticket_idx].id);
+                        }
+                    }
+                });
+                for (pending, result) in chunk.iter().zip(created) {
+                    self.finish_create(tickets, pending, result, &mut results).await;
+                }
+
+                done += chunk.len();
+                if self.options.pace.is_some() && done < to_create.len() {
+                    let ids: Vec<String> = tickets.iter().map(|t| t.id.clone()).collect();
+                    let options = self.options;
+                    self.update_state(|state| {
+                        state.interrupted = Some(InterruptedPush { ids, options, at: chrono::Utc::now().to_rfc3339() });
+                    });
+                    println!("PACE    {}/{} issue(s) created; checkpoint saved", done, to_create.len());
+                }
+            }
+            for pending in &pending_creates {
+                if let Some(result) = recovered.remove(&pending.ticket_idx) {
+                    self.finish_create(tickets, pending, result, &mut results).await;
+                }
             }
         }
-    }
-
-    /// Set project field values (Status, Iteration) on newly added items
-    async fn set_project_field_values(
-        &self,
-        project_id: &str,
-        items: &[(String, &str, &str)], // (item_id, ticket_id, ticket_status)
-        fields_cache: &ProjectFieldsCache,
-    ) {
-        // Set Status field values
-        if let Some(ref status_cache) = fields_cache.status {
-            // Build (item_id, option_id) pairs for items with status mappings
-            let status_updates: Vec<(String, String)> = items
-                .iter()
-                .filter_map(|(item_id, _, ticket_status)| {
-                    status_cache
-                        .status_to_option
-                        .get(&ticket_status.to_lowercase())
-                        .map(|option_id| (item_id.clone(), option_id.clone()))
-                })
-                .collect();
 
-            if !status_updates.is_empty() {
-                match self
-                    .client
-                    .set_project_items_single_select_batch(
-                        project_id,
-                        &status_cache.field_id,
-                        &status_updates,
-                    )
-                    .await
-                {
-                    Ok(results) => {
-                        let success_count = results.iter().filter(|r| r.is_ok()).count();
-                        let fail_count = results.len() - success_count;
-                        if fail_count > 0 {
-                            eprintln!("WARN    {} status updates failed", fail_count);
+        drop(timing);
+
+        // Phase 2b: Create, update, or convert draft project items
+        let timing = self.phase("project ops");
+        if !draft_idxs.is_empty() && !cancelled() {
+            let draft_results = self.sync_drafts(tickets, &draft_idxs).await;
+            for (idx, result) in draft_idxs.iter().zip(draft_results) {
+                let external_ref = match &result {
+                    SyncResult::Created { issue_number, .. } => Some(format!("gh-{}", issue_number)),
+                    SyncResult::Drafted { item_id, updated: false, .. } => Some(format!("draft-{}", item_id)),
+                    _ => None,
+                };
+                let ticket = &mut tickets[*idx];
+                let old_ref = ticket.external_ref.clone();
+                let written = match &external_ref {
+                    Some(external_ref) => ticket.write_external_ref(external_ref),
+                    None => Ok(()),
+                };
+
+                let entry = match &result {
+                    SyncResult::Created { issue_id, issue_number, .. } => Some(
+                        AuditEntry::new(AuditAction::Create, &ticket.id)
+                            .issue(*issue_number)
+                            .node(issue_id),
+                    ),
+                    SyncResult::Drafted { item_id, project, updated } => {
+                        let mut entry = AuditEntry::new(AuditAction::Draft, &ticket.id)
+                            .node(item_id)
+                            .change("title", None, Some(&self.issue_title(ticket)));
+                        if !updated {
+                            if let Some(target) = self.projects.iter().find(|p| p.info.title == *project) {
+                                entry = entry.change("project", None, Some(&target.info.id));
+                            }
                         }
+                        Some(entry)
                     }
-                    Err(e) => {
-                        eprintln!("WARN    Failed to set project status: {}", e);
+                    _ => None,
+                };
+                if let (SyncResult::Created { issue_number, .. }, Ok(())) = (&result, &written) {
+                    self.write_issue_url(ticket, *issue_number);
+                }
+                if let Some(mut entry) = entry {
+                    if let (Some(external_ref), Ok(())) = (&external_ref, &written) {
+                        entry = entry.change("external-ref", old_ref.as_deref(), Some(external_ref));
                     }
+                    self.audit.record(entry);
                 }
+
+                if let (Some(external_ref), Err(e)) = (external_ref, written) {
+                    results.push((*idx, SyncResult::Failed {
+                        error: format!("Synced as {} but failed to write external-ref: {}", external_ref, e),
+                    }));
+                    continue;
+                }
+                results.push((*idx, result));
             }
         }
 
-        // Set Iteration field values (all items get same iteration)
-        if let Some(ref iteration_cache) = fields_cache.iteration {
-            let item_ids: Vec<String> = items.iter().map(|(id, _, _)| id.clone()).collect();
-
-            match self
-                .client
-                .set_project_items_iteration_batch(
-                    project_id,
-                    &iteration_cache.field_id,
-                    &iteration_cache.iteration_id,
-                    &item_ids,
-                )
-                .await
-            {
-                Ok(results) => {
-                    let success_count = results.iter().filter(|r| r.is_ok()).count();
-                    let fail_count = results.len() - success_count;
-                    if fail_count > 0 {
-                        eprintln!("WARN    {} iteration updates failed", fail_count);
+        drop(timing);
+
+        // Phase 2c: Create or update discussions
+        let timing = self.phase("discussions");
+        if !discussion_idxs.is_empty() && !cancelled() {
+            let discussion_results = self.sync_discussions(tickets, &discussion_idxs).await;
+            for (idx, result) in discussion_idxs.iter().zip(discussion_results) {
+                if let SyncResult::Discussed { number, updated: false, .. } = &result {
+                    let external_ref = format!("ghd-{}", number);
+                    if let Err(e) = tickets[*idx].write_external_ref(&external_ref) {
+                        results.push((*idx, SyncResult::Failed {
+                            error: format!("Created discussion #{} but failed to write external-ref: {}", number, e),
+                        }));
+                        continue;
                     }
                 }
-                Err(e) => {
-                    eprintln!("WARN    Failed to set project iteration: {}", e);
+                if let SyncResult::Skipped { .. } = &result {
+                    summary.in_sync.push(tickets[*idx].id.clone());
                 }
+                results.push((*idx, result));
             }
         }
-    }
-
-    /// Sync project Status field for all synced tickets
-    /// 
-    /// This updates the project Status for tickets that already exist in the project,
-    /// ensuring their project status matches the ticket status.
-    async fn sync_project_status(
-        &self,
-        tickets: &[Ticket],
-        existing_issues: &HashMap<u64, ExistingIssue>,
-    ) {
-        // Skip if no project or no status field configured
-        let project = match &self.project {
-            Some(p) => p,
-            None => return,
-        };
-
-        let fields_cache = match &self.project_fields {
-            Some(f) => f,
-            None => return,
-        };
 
-        let status_cache = match &fields_cache.status {
-            Some(s) => s,
-            None => return,
-        };
-
-        // Collect synced tickets with status mappings
-        // (issue_node_id, ticket_id, option_id)
-        let mut tickets_to_sync: Vec<(String, &str, String)> = Vec::new();
-
-        for ticket in tickets {
-            // Skip unsynced tickets (handled by add_to_project)
-            let issue_number = match ticket.github_issue_number() {
-                Some(n) => n,
-                None => continue,
-            };
+        drop(timing);
 
-            // Get issue node ID from existing_issues
-            let issue_node_id = match existing_issues.get(&issue_number) {
-                Some(issue) => &issue.id,
-                None => continue,
-            };
+        // Phase 3: Batch update issues
+        let timing = self.phase("batch update");
+        if !pending_updates.is_empty() && !cancelled() {
+            let closes_duplicate = |p: &PendingUpdate| p.needs_close && tickets[p.ticket_idx].duplicate_of.is_some();
+            if pending_updates.iter().any(closes_duplicate) {
+                self.ensure_labels(&[DUPLICATE_LABEL.to_string()]).await;
+            }
+            if let Some(label) = self.config.github.managed_label.clone() {
+                if pending_updates.iter().any(|p| p.needs_managed_label) {
+                    self.ensure_labels(&[label]).await;
+                }
+            }
+            let update_results = self.batch_update(&pending_updates).await;
+            for (pending, result) in pending_updates.iter().zip(update_results) {
+                if let SyncResult::Updated { .. } = result {
+                    if closes_duplicate(pending) {
+                        self.mark_duplicate(&tickets[pending.ticket_idx], &pending.issue_id).await;
+                    }
+                    if let Some(existing) = existing_issues.get(&pending.issue_number) {
+                        let ticket_id = &tickets[pending.ticket_idx].id;
+                        self.record_update(ticket_id, pending, existing);
 
-            // Check if we have a status mapping for this ticket
-            if let Some(option_id) = status_cache
-                .status_to_option
-                .get(&ticket.status.to_lowercase())
-            {
-                tickets_to_sync.push((issue_node_id.clone(), &ticket.id, option_id.clone()));
+                        let (_, continuation) = split_body(&pending.body);
+                        if !continuation.is_empty() || existing.body.contains(CONTINUED_NOTE) {
+                            self.sync_continuation(ticket_id, pending.issue_number, &continuation).await;
+                        }
+                    }
+                }
+                results.push((pending.ticket_idx, result));
             }
         }
 
-        if tickets_to_sync.is_empty() {
-            return;
-        }
+        drop(timing);
 
-        // Get issue IDs that need status updates
-        let issue_ids: Vec<String> = tickets_to_sync
-            .iter()
-            .map(|(id, _, _)| id.clone())
-            .collect();
+        self.record_timestamps(tickets, &results, &existing_issues);
 
-        // Fetch project item IDs for these issues
-        let item_ids = match self
-            .client
-            .get_project_item_ids_batch(&project.id, &issue_ids)
-            .await
-        {
-            Ok(ids) => ids,
-            Err(e) => {
-                eprintln!("WARN    Failed to fetch project item IDs: {}", e);
-                return;
-            }
-        };
+        // Sort by original index and print results
+        results.sort_by_key(|(idx, _)| *idx);
 
-        // Build (item_id, option_id) pairs for items we found
-        let status_updates: Vec<(String, String)> = tickets_to_sync
-            .iter()
-            .filter_map(|(issue_id, _, option_id)| {
-                item_ids
-                    .get(issue_id)
-                    .map(|item_id| (item_id.clone(), option_id.clone()))
-            })
-            .collect();
-
-        if status_updates.is_empty() {
-            return; // No items in project to update
-        }
-
-        // Batch update status
-        match self
-            .client
-            .set_project_items_single_select_batch(
-                &project.id,
-                &status_cache.field_id,
-                &status_updates,
-            )
-            .await
-        {
-            Ok(results) => {
-                let success_count = results.iter().filter(|r| r.is_ok()).count();
-                if success_count > 0 {
-                    println!("STATUS  {} project item(s) synced", success_count);
+        for (idx, result) in &results {
+            let ticket = &tickets[*idx];
+            match result {
+                SyncResult::Created { issue_number, url, .. } => {
+                    println!(
+                        "CREATE  {} → #{}  {}",
+                        ticket.id, issue_number, ticket.title
+                    );
+                    println!("  └─ {}", url);
+                    summary.created += 1;
+                    summary.in_sync.push(ticket.id.clone());
+                    summary.changes.push(Change::of(ticket, Some(url.clone()), true));
                 }
-                let fail_count = results.len() - success_count;
-                if fail_count > 0 {
-                    eprintln!("WARN    {} project status updates failed", fail_count);
+                SyncResult::Updated { issue_number } => {
+                    println!(
+                        "UPDATE  {} → #{}  {}",
+                        ticket.id, issue_number, ticket.title
+                    );
+                    summary.updated += 1;
+                    summary.in_sync.push(ticket.id.clone());
+                    let url = format!("https://github.com/{}/{}/issues/{}", self.owner, self.repo_name, issue_number);
+                    summary.changes.push(Change::of(ticket, Some(url), false));
+                }
+                SyncResult::Drafted { project, updated, .. } => {
+                    if *updated {
+                        println!("DRAFT   {} → {}  {} (updated)", ticket.id, project, ticket.title);
+                        summary.updated += 1;
+                    } else {
+                        println!("DRAFT   {} → {}  {}", ticket.id, project, ticket.title);
+                        summary.created += 1;
+                    }
+                    summary.changes.push(Change::of(ticket, None, !*updated));
+                }
+                SyncResult::Discussed { number, url, updated } => {
+                    if *updated {
+                        println!("DISCUSS {} → #{}  {} (updated)", ticket.id, number, ticket.title);
+                        summary.updated += 1;
+                    } else {
+                        println!("DISCUSS {} → #{}  {}", ticket.id, number, ticket.title);
+                        println!("  └─ {}", url);
+                        summary.created += 1;
+                    }
+                    summary.in_sync.push(ticket.id.clone());
+                    summary.changes.push(Change::of(ticket, Some(url.clone()), !*updated));
+                }
+                SyncResult::Skipped { reason } => {
+                    println!("SKIP    {}  ({})", ticket.id, reason);
+                    summary.skipped += 1;
+                    if !summary.in_sync.contains(&ticket.id) {
+                        summary.unresolved.push(ticket.id.clone());
+                    }
+                }
+                SyncResult::Failed { error } => {
+                    println!("FAIL    {}  {}", ticket.id, error);
+                    summary.failed += 1;
+                    summary.unresolved.push(ticket.id.clone());
+                    summary.failed_ids.push(ticket.id.clone());
                 }
             }
-            Err(e) => {
-                eprintln!("WARN    Failed to update project status: {}", e);
+        }
+
+        // Phase 4: Link sub-issues (parent/child relationships)
+        let timing = self.phase("link ops");
+        if self.options.sub_issues && !cancelled() {
+            if self.sub_issues_supported {
+                self.link_sub_issues(tickets, all_tickets, &results, &existing_issues).await;
+            } else if tickets.iter().any(|t| t.parent.is_some()) {
+                eprintln!("NOTE    Sub-issues aren't available on this GitHub instance; parents aren't linked");
             }
         }
-    }
 
-    /// Check if a ticket needs updating, returns update details if so
-    fn check_update_needed(
-        &self,
-        ticket: &Ticket,
-        existing_issues: &HashMap<u64, ExistingIssue>,
-    ) -> UpdateCheck {
-        let issue_number = match ticket.github_issue_number() {
-            Some(n) => n,
-            None => return UpdateCheck::Conflict("invalid external-ref".to_string()),
-        };
+        drop(timing);
 
-        let existing = match existing_issues.get(&issue_number) {
-            Some(issue) => issue,
-            None => return UpdateCheck::Error(format!("Issue #{} not found", issue_number)),
-        };
+        // Phase 5: Add to project and set fields for new issues
+        let timing = self.phase("project ops");
+        if !cancelled() {
+            self.add_to_project(&results, tickets).await;
+        }
 
-        // Check for our marker
-        let marker = format!("<!-- ticket:{} -->", ticket.id);
-        if !existing.body.contains(&marker) {
-            return UpdateCheck::Conflict("issue modified outside ttr".to_string());
+        // Phase 6: Sync project Status, Type, and custom fields for all synced tickets
+        if self.options.status && !cancelled() {
+            self.sync_project_status(tickets, &existing_issues).await;
         }
 
-        // Format new body
-        let new_body = self.format_issue_body(ticket);
+        drop(timing);
 
-        // Check if update is needed
-        let title_changed = existing.title != ticket.title;
-        let body_changed = existing.body != new_body;
-        let state_should_be_closed = ticket.status == "closed";
-        let state_is_closed = existing.state == "CLOSED";
-        let state_changed = state_should_be_closed != state_is_closed;
+        // Phase 7: Link pull requests opened from ticket branches
+        let timing = self.phase("link ops");
+        if !cancelled() {
+            self.link_pull_requests(tickets).await;
+        }
 
-        if !title_changed && !body_changed && !state_changed {
-            return UpdateCheck::NoChanges;
+        drop(timing);
+
+        // Phase 8: Pin and unpin issues to match `pinned`, and lock the
+        // conversations of archived tickets
+        let timing = self.phase("pin ops");
+        if !cancelled() {
+            let issue_ids = issue_node_ids(&existing_issues, &results);
+            self.sync_pins(tickets, &issue_ids).await;
+            self.sync_locks(tickets, &issue_ids).await;
         }
 
-        UpdateCheck::NeedsUpdate {
-            issue_id: existing.id.clone(),
-            issue_number,
-            title: ticket.title.clone(),
-            body: new_body,
-            needs_close: state_changed && state_should_be_closed,
-            needs_reopen: state_changed && !state_should_be_closed,
+        drop(timing);
+
+        // Phase 9: Comment worklog entries logged since the last push
+        let _timing = self.phase("worklog ops");
+        if !cancelled() {
+            self.comment_worklogs(tickets).await;
         }
+
+        summary.interrupted = cancelled();
+        Ok(summary)
     }
 
-    /// Batch update multiple issues
-    async fn batch_update(&self, pending: &[PendingUpdate]) -> Vec<SyncResult> {
-        let mut results = vec![SyncResult::Failed { error: "Not processed".to_string() }; pending.len()];
+    /// Assign the project items of synced tickets to an iteration and/or status
+    ///
+    /// Tickets whose issues aren't in the project yet are added first.
+    /// `status` may be a project Status option name or a ticket status mapped
+    /// in `[project.status]`.
+    pub async fn plan(
+        &self,
+        tickets: &[Ticket],
+        iteration: Option<&str>,
+        status: Option<&str>,
+    ) -> Result<PlanSummary> {
+        let mut summary = PlanSummary::default();
 
-        // Collect content updates (title/body changes)
-        let updates: Vec<IssueUpdate> = pending
-            .iter()
-            .map(|p| IssueUpdate {
-                issue_id: p.issue_id.clone(),
-                title: p.title.clone(),
-                body: p.body.clone(),
-                issue_type_id: p.issue_type_id.clone(),
-            })
-            .collect();
+        if self.projects.is_empty() {
+            anyhow::bail!("No project configured. Set [github] project in sync.toml.");
+        }
 
-        // Batch update content
-        if !updates.is_empty() {
-            match self.client.update_issues_batch(&updates).await {
-                Ok(update_results) => {
-                    for (i, p) in pending.iter().enumerate() {
-                        if let Some(result) = update_results.get(&p.issue_id) {
-                            match result {
-                                Ok(_) => {
-                                    results[i] = SyncResult::Updated { issue_number: p.issue_number };
-                                }
-                                Err(e) => {
-                                    results[i] = SyncResult::Failed { error: e.clone() };
-                                }
-                            }
-                        }
-                    }
+        // Group tickets by the project they route to
+        let mut by_project: Vec<Vec<&Ticket>> = vec![Vec::new(); self.projects.len()];
+        for ticket in tickets {
+            match self.project_index(ticket) {
+                Some(i) => by_project[i].push(ticket),
+                None => {
+                    println!("SKIP    {}  (no project route)", ticket.id);
+                    summary.skipped += 1;
                 }
-                Err(e) => {
-                    // All updates failed
-                    for i in 0..pending.len() {
-                        results[i] = SyncResult::Failed { error: e.to_string() };
+            }
+        }
+
+        for (target, tickets) in self.projects.iter().zip(by_project) {
+            if !tickets.is_empty() {
+                self.plan_project(target, &tickets, iteration, status, &mut summary)
+                    .await?;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Plan the tickets routed to a single project
+    async fn plan_project(
+        &self,
+        target: &ProjectTarget,
+        tickets: &[&Ticket],
+        iteration: Option<&str>,
+        status: Option<&str>,
+        summary: &mut PlanSummary,
+    ) -> Result<()> {
+        let project = &target.info;
+        let fields_config = &target.fields_config;
+        let fields = self.client.get_project_fields(&project.id).await?;
+
+        // Resolve the iteration (field_id, iteration_id)
+        let iteration_target = match iteration {
+            Some(spec) => {
+                let field_name = fields_config.iteration_field.to_lowercase();
+                let field = fields
+                    .iter()
+                    .find(|f| f.name.to_lowercase() == field_name)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Project field '{}' not found",
+                            fields_config.iteration_field
+                        )
+                    })?;
+                let active = match &field.field_type {
+                    ProjectFieldType::Iteration { active, .. } => active,
+                    _ => anyhow::bail!(
+                        "Project field '{}' is not an iteration field",
+                        fields_config.iteration_field
+                    ),
+                };
+                match resolve_iteration_spec(spec, active).map_err(|e| anyhow::anyhow!(e))? {
+                    Some(id) => Some((field.id.clone(), id)),
+                    None => anyhow::bail!("No iteration found for '{}'", spec),
+                }
+            }
+            None => None,
+        };
+
+        // Resolve the status option (field_id, option_id)
+        let status_target = match status {
+            Some(name) => {
+                let field_name = fields_config.status_field.to_lowercase();
+                let field = fields
+                    .iter()
+                    .find(|f| f.name.to_lowercase() == field_name)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Project field '{}' not found",
+                            fields_config.status_field
+                        )
+                    })?;
+                let options = match &field.field_type {
+                    ProjectFieldType::SingleSelect { options } => options,
+                    _ => anyhow::bail!(
+                        "Project field '{}' is not a single-select field",
+                        fields_config.status_field
+                    ),
+                };
+                // Allow ticket statuses (e.g. "in_progress") via the config mapping
+                let option_name = fields_config
+                    .status
+                    .get(&name.to_lowercase())
+                    .map(|s| s.as_str())
+                    .unwrap_or(name)
+                    .to_lowercase();
+                match options.iter().find(|o| o.name.to_lowercase() == option_name) {
+                    Some(o) => Some((field.id.clone(), o.id.clone())),
+                    None => {
+                        let available: Vec<&str> = options.iter().map(|o| o.name.as_str()).collect();
+                        anyhow::bail!(
+                            "Project status option '{}' not found.\nAvailable options: {:?}",
+                            name,
+                            available
+                        );
                     }
-                    return results;
+                }
+            }
+            None => None,
+        };
+
+        // Only synced tickets have project items
+        let mut synced: Vec<(&Ticket, u64)> = Vec::new();
+        for &ticket in tickets {
+            match ticket.github_issue_number() {
+                Some(n) => synced.push((ticket, n)),
+                None => {
+                    println!("SKIP    {}  (not synced)", ticket.id);
+                    summary.skipped += 1;
                 }
             }
         }
 
-        // Batch close issues
-        let to_close: Vec<String> = pending
-            .iter()
-            .filter(|p| p.needs_close)
-            .map(|p| p.issue_id.clone())
-            .collect();
+        if synced.is_empty() {
+            return Ok(());
+        }
 
-        if !to_close.is_empty() {
-            if let Err(e) = self.client.close_issues_batch(&to_close).await {
-                // Mark close failures
-                for (i, p) in pending.iter().enumerate() {
-                    if p.needs_close {
-                        results[i] = SyncResult::Failed {
-                            error: format!("Failed to close: {}", e),
-                        };
-                    }
+        // Resolve issue node IDs
+        let numbers: Vec<u64> = synced.iter().map(|(_, n)| *n).collect();
+        let existing = self
+            .client
+            .get_issues_batch(&self.owner, &self.repo_name, &numbers)
+            .await?;
+
+        let mut planned: Vec<(&Ticket, u64, String)> = Vec::new(); // (ticket, number, issue_id)
+        for (ticket, number) in synced {
+            match existing.get(&number) {
+                Some(issue) => planned.push((ticket, number, issue.id.clone())),
+                None => {
+                    println!("FAIL    {}  Issue #{} not found", ticket.id, number);
+                    summary.failed += 1;
                 }
             }
         }
 
-        // Batch reopen issues
-        let to_reopen: Vec<String> = pending
+        // Find project items, adding issues that aren't in the project yet
+        let issue_ids: Vec<String> = planned.iter().map(|(_, _, id)| id.clone()).collect();
+        let mut item_ids = self
+            .client
+            .get_project_item_ids_batch(&project.id, &issue_ids)
+            .await?;
+
+        let missing: Vec<String> = issue_ids
             .iter()
-            .filter(|p| p.needs_reopen)
-            .map(|p| p.issue_id.clone())
+            .filter(|id| !item_ids.contains_key(*id))
+            .cloned()
             .collect();
-
-        if !to_reopen.is_empty() {
-            if let Err(e) = self.client.reopen_issues_batch(&to_reopen).await {
-                // Mark reopen failures
-                for (i, p) in pending.iter().enumerate() {
-                    if p.needs_reopen {
-                        results[i] = SyncResult::Failed {
-                            error: format!("Failed to reopen: {}", e),
-                        };
+        if !missing.is_empty() {
+            let add_results = self
+                .client
+                .add_issues_to_project_batch(&project.id, &missing)
+                .await?;
+            for (issue_id, result) in missing.iter().zip(add_results) {
+                if let Ok(info) = result {
+                    if !info.existing {
+                        if let Some((ticket, _, _)) = planned.iter().find(|(_, _, id)| id == issue_id) {
+                            self.record_project_add(ticket, &project.id, &info.item_id);
+                        }
+                        summary.added += 1;
                     }
+                    item_ids.insert(issue_id.clone(), info.item_id);
                 }
             }
         }
 
-        results
-    }
-
-    /// Batch create multiple issues
-    async fn batch_create(&self, pending: &[PendingCreate]) -> Vec<SyncResult> {
-        if pending.is_empty() {
-            return Vec::new();
+        let mut items: Vec<(&Ticket, u64, String)> = Vec::new(); // (ticket, number, item_id)
+        for (ticket, number, issue_id) in planned {
+            match item_ids.get(&issue_id) {
+                Some(item_id) => items.push((ticket, number, item_id.clone())),
+                None => {
+                    println!("FAIL    {}  could not add #{} to {}", ticket.id, number, project.title);
+                    summary.failed += 1;
+                }
+            }
         }
 
-        let creates: Vec<IssueCreate> = pending
-            .iter()
-            .map(|p| IssueCreate {
-                title: p.title.clone(),
-                body: p.body.clone(),
-                label_ids: p.label_ids.clone(),
-                issue_type_id: p.issue_type_id.clone(),
-            })
-            .collect();
-
-        let assignee_ids: Option<Vec<String>> = self.assignee_id.clone().map(|id| vec![id]);
-        let assignee_slice = assignee_ids.as_deref();
+        // Apply field values, tracking which items failed
+        let mut failed = vec![false; items.len()];
 
-        match self.client.create_issues_batch(&self.repo_id, &creates, assignee_slice).await {
-            Ok(create_results) => {
-                create_results
-                    .into_iter()
-                    .map(|result| match result {
-                        Ok(info) => SyncResult::Created {
-                            issue_id: info.id,
-                            issue_number: info.number,
-                            url: info.url,
-                        },
-                        Err(e) => SyncResult::Failed { error: e },
-                    })
-                    .collect()
-            }
-            Err(e) => {
-                // All creates failed
-                vec![SyncResult::Failed { error: e.to_string() }; pending.len()]
+        if let Some((field_id, iteration_id)) = &iteration_target {
+            let ids: Vec<String> = items.iter().map(|(_, _, id)| id.clone()).collect();
+            let results = self
+                .client
+                .set_project_items_iteration_batch(&project.id, field_id, iteration_id, &ids)
+                .await?;
+            for (i, result) in results.iter().enumerate() {
+                failed[i] |= result.is_err();
+                if let (Ok(_), Some((ticket, _, item_id))) = (result, items.get(i)) {
+                    self.record_project_field(ticket, item_id, "iteration", iteration_id);
+                }
             }
         }
-    }
 
-    /// Resolve tag names to label IDs, creating labels if needed
-    async fn resolve_label_ids(&mut self, tags: &[String]) -> Vec<String> {
-        if !self.config.labels.sync_tags {
-            return Vec::new();
+        if let Some((field_id, option_id)) = &status_target {
+            let updates: Vec<(String, String)> = items
+                .iter()
+                .map(|(_, _, id)| (id.clone(), option_id.clone()))
+                .collect();
+            let results = self
+                .client
+                .set_project_items_single_select_batch(&project.id, field_id, &updates)
+                .await?;
+            for (i, result) in results.iter().enumerate() {
+                failed[i] |= result.is_err();
+                if let (Ok(_), Some((ticket, _, item_id)), Some(name)) = (result, items.get(i), status) {
+                    self.record_project_field(ticket, item_id, "status", name);
+                }
+            }
         }
 
-        let mut label_ids = Vec::new();
+        for ((ticket, number, _), failed) in items.iter().zip(failed) {
+            if failed {
+                println!("FAIL    {}  field update failed for #{}", ticket.id, number);
+                summary.failed += 1;
+            } else {
+                println!("PLAN    {} → #{}  {}", ticket.id, number, ticket.title);
+                summary.planned += 1;
+                summary.planned_ids.push(ticket.id.clone());
+            }
+        }
 
-        for tag in tags {
-            let tag_lower = tag.to_lowercase();
+        Ok(())
+    }
 
-            // Check cache first
-            if let Some(id) = self.label_cache.get(&tag_lower) {
-                label_ids.push(id.clone());
-                continue;
+    /// Add newly created issues to their routed projects and set field values
+    async fn add_to_project(&self, results: &[(usize, SyncResult)], tickets: &[Ticket]) {
+        for target in &self.projects {
+            // Collect issue info for newly created issues routed here
+            // (issue_id, ticket)
+            let mut issue_info: Vec<(String, &Ticket)> = Vec::new();
+            for (idx, result) in results {
+                if let SyncResult::Created { issue_id, .. } = result {
+                    let ticket = &tickets[*idx];
+                    if self.project_for(ticket).is_some_and(|p| p.info.id == target.info.id) {
+                        issue_info.push((issue_id.clone(), ticket));
+                    }
+                }
             }
 
-            // Try to get or create the label
-            if let Ok(Some(id)) = self
-                .client
-                .get_or_create_label(
-                    &self.owner,
-                    &self.repo_name,
-                    &self.repo_id,
-                    tag,
-                    self.config.labels.create_missing,
-                )
-                .await
-            {
-                self.label_cache.insert(tag_lower, id.clone());
-                label_ids.push(id);
+            if !issue_info.is_empty() {
+                self.add_to_single_project(target, &issue_info).await;
             }
         }
-
-        label_ids
     }
 
-    /// Resolve issue type ID from ticket type using config mapping
-    fn resolve_issue_type_id(&self, ticket_type: &str) -> Option<String> {
-        resolve_issue_type(ticket_type, &self.config.mapping.type_map, &self.issue_type_cache)
-    }
+    /// Add issues to one project and set its field values
+    async fn add_to_single_project(&self, target: &ProjectTarget, issue_info: &[(String, &Ticket)]) {
+        let project = &target.info;
+
+        // Batch add to project
+        let ids: Vec<String> = issue_info.iter().map(|(id, _)| id.clone()).collect();
+        let add_results = match self.client.add_issues_to_project_batch(&project.id, &ids).await {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("\nWARN    Failed to add issues to project: {}", e);
+                return;
+            }
+        };
+
+        // Collect successfully added items with their item IDs
+        // (item_id, ticket)
+        let mut added_items: Vec<(String, &Ticket)> = Vec::new();
+        
+        println!();
+        for ((_, ticket), result) in issue_info.iter().zip(add_results) {
+            match result {
+                Ok(item_info) => {
+                    if item_info.existing {
+                        println!("PROJECT {} → {} (already there)", ticket.id, project.title);
+                    } else {
+                        println!("PROJECT {} → {} (added)", ticket.id, project.title);
+                        self.record_project_add(ticket, &project.id, &item_info.item_id);
+                    }
+                    added_items.push((item_info.item_id, ticket));
+                }
+                Err(e) => {
+                    eprintln!("WARN    {} project add failed: {}", ticket.id, e);
+                }
+            }
+        }
+
+        // Set field values if we have items and field config
+        if !added_items.is_empty() {
+            if let Some(fields_cache) = self.project_fields(target).await {
+                self.set_project_field_values(&project.id, &added_items, fields_cache).await;
+            }
+        }
+    }
+
+    fn record_project_add(&self, ticket: &Ticket, project_id: &str, item_id: &str) {
+        let mut entry = AuditEntry::new(AuditAction::ProjectAdd, &ticket.id)
+            .change("project", None, Some(project_id));
+        if let Some(n) = ticket.github_issue_number() {
+            entry = entry.issue(n);
+        }
+        if !item_id.is_empty() {
+            entry = entry.node(item_id);
+        }
+        self.audit.record(entry);
+    }
+
+    fn record_project_field(&self, ticket: &Ticket, item_id: &str, field: &str, value: &str) {
+        let mut entry = AuditEntry::new(AuditAction::ProjectField, &ticket.id)
+            .node(item_id)
+            .change(field, None, Some(value));
+        if let Some(n) = ticket.github_issue_number() {
+            entry = entry.issue(n);
+        }
+        self.audit.record(entry);
+    }
+
+    /// Set project field values (Status, Iteration) on newly added items
+    async fn set_project_field_values(
+        &self,
+        project_id: &str,
+        items: &[(String, &Ticket)], // (item_id, ticket)
+        fields_cache: &ProjectFieldsCache,
+    ) {
+        // Set Status field values
+        if let Some(ref status_cache) = fields_cache.status {
+            // Build (item_id, option_id) pairs for items with status mappings
+            let mapped: Vec<(&String, &Ticket, &String)> = items
+                .iter()
+                .filter_map(|(item_id, ticket)| {
+                    status_cache
+                        .status_to_option
+                        .get(&ticket.status.to_lowercase())
+                        .map(|option_id| (item_id, *ticket, option_id))
+                })
+                .collect();
+            let status_updates: Vec<(String, String)> = mapped
+                .iter()
+                .map(|(item_id, _, option_id)| ((*item_id).clone(), (*option_id).clone()))
+                .collect();
+
+            if !status_updates.is_empty() {
+                match self
+                    .client
+                    .set_project_items_single_select_batch(
+                        project_id,
+                        &status_cache.field_id,
+                        &status_updates,
+                    )
+                    .await
+                {
+                    Ok(results) => {
+                        for ((item_id, ticket, _), result) in mapped.iter().zip(&results) {
+                            if result.is_ok() {
+                                self.record_project_field(ticket, item_id, "status", &ticket.status);
+                            }
+                        }
+                        let success_count = results.iter().filter(|r| r.is_ok()).count();
+                        let fail_count = results.len() - success_count;
+                        if fail_count > 0 {
+                            eprintln!("WARN    {} status updates failed", fail_count);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("WARN    Failed to set project status: {}", e);
+                    }
+                }
+            }
+        }
+
+        // Set Iteration field values, grouping items by their resolved iteration
+        if let Some(ref iteration_cache) = fields_cache.iteration {
+            let mut by_iteration: Vec<(String, Vec<(String, &Ticket)>)> = Vec::new();
+            for (item_id, ticket) in items {
+                let iteration_id = match resolve_ticket_iteration(
+                    &ticket.id,
+                    ticket.iteration.as_deref(),
+                    ticket.due.as_deref(),
+                    iteration_cache,
+                ) {
+                    Some(id) => id,
+                    None => continue,
+                };
+                let item = (item_id.clone(), *ticket);
+                match by_iteration.iter_mut().find(|(id, _)| *id == iteration_id) {
+                    Some((_, group)) => group.push(item),
+                    None => by_iteration.push((iteration_id, vec![item])),
+                }
+            }
+
+            for (iteration_id, group) in &by_iteration {
+                let item_ids: Vec<String> = group.iter().map(|(id, _)| id.clone()).collect();
+                match self
+                    .client
+                    .set_project_items_iteration_batch(
+                        project_id,
+                        &iteration_cache.field_id,
+                        iteration_id,
+                        &item_ids,
+                    )
+                    .await
+                {
+                    Ok(results) => {
+                        for ((item_id, ticket), result) in group.iter().zip(&results) {
+                            if result.is_ok() {
+                                self.record_project_field(ticket, item_id, "iteration", iteration_id);
+                            }
+                        }
+                        let success_count = results.iter().filter(|r| r.is_ok()).count();
+                        let fail_count = results.len() - success_count;
+                        if fail_count > 0 {
+                            eprintln!("WARN    {} iteration updates failed", fail_count);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("WARN    Failed to set project iteration: {}", e);
+                    }
+                }
+            }
+        }
+
+        if !fields_cache.custom.is_empty() {
+            self.set_custom_field_values(project_id, items, &fields_cache.custom).await;
+        }
+    }
+
+    /// Set project fields mapped from custom frontmatter keys and the ticket type
+    async fn set_custom_field_values(
+        &self,
+        project_id: &str,
+        items: &[(String, &Ticket)], // (item_id, ticket)
+        custom: &[CustomFieldCache],
+    ) {
+        let mut updates: Vec<(String, String, ProjectFieldValue)> = Vec::new();
+        let mut sources: Vec<(&Ticket, &str, &CustomFieldCache)> = Vec::new(); // (ticket, item_id, field)
+        for (item_id, ticket) in items {
+            for field in custom {
+                if let Some(value) = field.value_for(ticket, &self.spent) {
+                    updates.push((item_id.clone(), field.field_id.clone(), value));
+                    sources.push((ticket, item_id, field));
+                }
+            }
+        }
+
+        if updates.is_empty() {
+            return;
+        }
+
+        match self
+            .client
+            .set_project_item_field_values_batch(project_id, &updates)
+            .await
+        {
+            Ok(results) => {
+                let mut fail_count = 0;
+                for ((ticket, item_id, field), result) in sources.iter().zip(&results) {
+                    match result {
+                        Ok(()) => {
+                            let value = field.raw_value(ticket, &self.spent).unwrap_or_default();
+                            self.record_project_field(ticket, item_id, &field.key, &value);
+                        }
+                        Err(_) => fail_count += 1,
+                    }
+                }
+                if fail_count > 0 {
+                    eprintln!("WARN    {} project field updates failed", fail_count);
+                }
+            }
+            Err(e) => {
+                eprintln!("WARN    Failed to set project fields: {}", e);
+            }
+        }
+    }
+
+    /// Sync project Status, Type, and custom fields for all synced tickets
+    /// 
+    /// This updates the project fields for tickets that already exist in the project,
+    /// ensuring they match the ticket, not just what was set when it was added.
+    async fn sync_project_status(
+        &self,
+        tickets: &[Ticket],
+        existing_issues: &HashMap<u64, ExistingIssue>,
+    ) {
+        for target in &self.projects {
+            let routed: Vec<&Ticket> = tickets
+                .iter()
+                .filter(|t| self.project_for(t).is_some_and(|p| p.info.id == target.info.id))
+                .collect();
+            if !routed.is_empty() {
+                self.sync_single_project_status(target, &routed, existing_issues).await;
+            }
+        }
+    }
+
+    /// Sync project Status and custom fields for the tickets routed to one project
+    async fn sync_single_project_status(
+        &self,
+        target: &ProjectTarget,
+        tickets: &[&Ticket],
+        existing_issues: &HashMap<u64, ExistingIssue>,
+    ) {
+        let project = &target.info;
+
+        // Only tickets with existing issues are reconciled; don't fetch
+        // fields when there are none
+        if !tickets.iter().any(|t| t.github_issue_number().is_some_and(|n| existing_issues.contains_key(&n))) {
+            return;
+        }
+
+        // Skip if no project fields configured
+        let fields_cache = match self.project_fields(target).await {
+            Some(f) => f,
+            None => return,
+        };
+
+        let status_cache = fields_cache.status.as_ref();
+        let status_option = |ticket: &Ticket| {
+            status_cache.and_then(|c| c.status_to_option.get(&ticket.status.to_lowercase()))
+        };
+        let has_custom = |ticket: &Ticket| {
+            fields_cache.custom.iter().any(|f| f.raw_value(ticket, &self.spent).is_some())
+        };
+
+        // Collect synced tickets with status mappings or custom field values
+        // (issue_node_id, ticket)
+        let mut tickets_to_sync: Vec<(String, &Ticket)> = Vec::new();
+
+        for ticket in tickets {
+            // Skip unsynced tickets (handled by add_to_project)
+            let issue_number = match ticket.github_issue_number() {
+                Some(n) => n,
+                None => continue,
+            };
+
+            // Get issue node ID from existing_issues
+            let issue_node_id = match existing_issues.get(&issue_number) {
+                Some(issue) => &issue.id,
+                None => continue,
+            };
+
+            if status_option(ticket).is_some() || has_custom(ticket) {
+                tickets_to_sync.push((issue_node_id.clone(), ticket));
+            }
+        }
+
+        if tickets_to_sync.is_empty() {
+            return;
+        }
+
+        // Get issue IDs that need field updates
+        let issue_ids: Vec<String> = tickets_to_sync
+            .iter()
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        // Fetch project item IDs for these issues
+        let item_ids = match self
+            .client
+            .get_project_item_ids_batch(&project.id, &issue_ids)
+            .await
+        {
+            Ok(ids) => ids,
+            Err(e) => {
+                eprintln!("WARN    Failed to fetch project item IDs: {}", e);
+                return;
+            }
+        };
+
+        // (item_id, ticket) for items we found
+        let found: Vec<(String, &Ticket)> = tickets_to_sync
+            .iter()
+            .filter_map(|(issue_id, ticket)| item_ids.get(issue_id).map(|item_id| (item_id.clone(), *ticket)))
+            .collect();
+
+        if found.is_empty() {
+            return; // No items in project to update
+        }
+
+        if let Some(status_cache) = status_cache {
+            self.update_project_status(&project.id, status_cache, &found).await;
+        }
+
+        if !fields_cache.custom.is_empty() {
+            self.set_custom_field_values(&project.id, &found, &fields_cache.custom).await;
+        }
+    }
+
+    /// Batch set the Status of existing project items from their tickets
+    async fn update_project_status(
+        &self,
+        project_id: &str,
+        status_cache: &StatusFieldCache,
+        items: &[(String, &Ticket)], // (item_id, ticket)
+    ) {
+        // Build (item_id, option_id) pairs for tickets with status mappings
+        let mapped: Vec<(&String, &Ticket, &String)> = items
+            .iter()
+            .filter_map(|(item_id, ticket)| {
+                status_cache
+                    .status_to_option
+                    .get(&ticket.status.to_lowercase())
+                    .map(|option_id| (item_id, *ticket, option_id))
+            })
+            .collect();
+        let status_updates: Vec<(String, String)> = mapped
+            .iter()
+            .map(|(item_id, _, option_id)| ((*item_id).clone(), (*option_id).clone()))
+            .collect();
+
+        if status_updates.is_empty() {
+            return;
+        }
+
+        // Batch update status
+        match self
+            .client
+            .set_project_items_single_select_batch(
+                project_id,
+                &status_cache.field_id,
+                &status_updates,
+            )
+            .await
+        {
+            Ok(results) => {
+                for ((item_id, ticket, _), result) in mapped.iter().zip(&results) {
+                    if result.is_ok() {
+                        self.record_project_field(ticket, item_id, "status", &ticket.status);
+                    }
+                }
+                let success_count = results.iter().filter(|r| r.is_ok()).count();
+                if success_count > 0 {
+                    println!("STATUS  {} project item(s) synced", success_count);
+                }
+                let fail_count = results.len() - success_count;
+                if fail_count > 0 {
+                    eprintln!("WARN    {} project status updates failed", fail_count);
+                }
+            }
+            Err(e) => {
+                eprintln!("WARN    Failed to update project status: {}", e);
+            }
+        }
+    }
+
+    /// Index synced tickets and batch fetch their issues (plus parents)
+    async fn fetch_existing_issues(
+        &mut self,
+        tickets: &[Ticket],
+        all_tickets: &[Ticket],
+    ) -> HashMap<u64, ExistingIssue> {
+        // Build ticket ID → issue number lookup for dependency resolution
+        // Use all_tickets so deps resolve even when pushing a subset
+        self.ticket_to_issue = all_tickets
+            .iter()
+            .filter_map(|t| t.github_issue_number().map(|n| (t.id.clone(), n)))
+            .collect();
+        // Discussions share issue numbering, so `#N` links to them too
+        self.elsewhere = all_tickets
+            .iter()
+            .filter_map(|t| {
+                let issue_ref = match (t.issue_elsewhere(), t.discussion_number()) {
+                    (Some((repo, n)), _) => format!("{}#{}", repo, n),
+                    (None, Some(n)) => format!("#{}", n),
+                    (None, None) => return None,
+                };
+                Some((t.id.clone(), issue_ref))
+            })
+            .collect();
+        self.closed_tickets = all_tickets
+            .iter()
+            .filter(|t| t.closes_issue())
+            .map(|t| t.id.clone())
+            .collect();
+        self.dependents = reverse_dependencies(all_tickets);
+        self.related = symmetric_links(all_tickets);
+        self.duplicates = HashMap::new();
+        for ticket in all_tickets {
+            if let Some(ref original) = ticket.duplicate_of {
+                self.duplicates.entry(original.clone()).or_default().push(ticket.id.clone());
+            }
+        }
+        self.children = HashMap::new();
+        for ticket in all_tickets {
+            if let Some(ref parent) = ticket.parent {
+                self.children.entry(parent.clone()).or_default().push(ticket.id.clone());
+            }
+        }
+        self.spent = spent_totals(all_tickets);
+
+        // Batch fetch all existing issues upfront
+        // Include both tickets being synced AND their parents (for sub-issue linking)
+        let mut issue_numbers: Vec<u64> = tickets
+            .iter()
+            .filter_map(|t| t.github_issue_number())
+            .collect();
+
+        // Also fetch parent issues (need their node IDs for sub-issue linking)
+        for ticket in tickets.iter() {
+            if let Some(ref parent_id) = ticket.parent {
+                if let Some(parent_num) = self.ticket_to_issue.get(parent_id) {
+                    if !issue_numbers.contains(parent_num) {
+                        issue_numbers.push(*parent_num);
+                    }
+                }
+            }
+        }
+
+        if !issue_numbers.is_empty() {
+            self.client
+                .get_issues_batch(&self.owner, &self.repo_name, &issue_numbers)
+                .await
+                .unwrap_or_default()
+        } else {
+            HashMap::new()
+        }
+    }
+
+    /// Report what a push would change, without writing anything
+    pub async fn check(&mut self, tickets: &[Ticket], all_tickets: &[Ticket]) -> Result<CheckSummary> {
+        let plan = self.plan_push(tickets, all_tickets).await?;
+        Ok(CheckSummary {
+            pending: plan.changes.len() as u32,
+            conflicts: plan.skipped.len() as u32,
+        })
+    }
+
+    /// Work out what a push would change, printing it as it goes, without
+    /// writing anything
+    pub async fn plan_push(&mut self, tickets: &[Ticket], all_tickets: &[Ticket]) -> Result<PushPlan> {
+        let mut plan = PushPlan::new(&self.config.github.repo);
+        let existing_issues = self.fetch_existing_issues(tickets, all_tickets).await;
+        self.resolve_assignees(tickets).await;
+        let planned = |ticket: &Ticket, op: PlannedOp| -> Result<PlannedChange> {
+            Ok(PlannedChange {
+                ticket: ticket.id.clone(),
+                op,
+                title: ticket.title.clone(),
+                issue: None,
+                fields: Vec::new(),
+                ticket_hash: push_plan::ticket_hash(ticket)?,
+                issue_updated_at: None,
+            })
+        };
+
+        for ticket in tickets {
+            if ticket.draft_item_id().is_some() || ticket.issue_elsewhere().is_some() {
+                continue;
+            }
+
+            // Existing discussions aren't compared; new ones are pending
+            if self.is_discussion(ticket) {
+                if ticket.discussion_number().is_none() {
+                    println!("{}{}  {}", PlannedOp::Discuss.tag(), ticket.id, ticket.title);
+                    plan.changes.push(planned(ticket, PlannedOp::Discuss)?);
+                }
+                continue;
+            }
+
+            if !ticket.is_synced() {
+                let op = if self.is_draft_type(ticket) { PlannedOp::Draft } else { PlannedOp::Create };
+                println!("{}{}  {}", op.tag(), ticket.id, ticket.title);
+                plan.changes.push(planned(ticket, op)?);
+                continue;
+            }
+
+            match self.check_update_needed(ticket, &existing_issues) {
+                UpdateCheck::NoChanges => {}
+                UpdateCheck::NeedsUpdate {
+                    issue_number, title, body, needs_close, needs_reopen, assignees, needs_managed_label, ..
+                } => {
+                    let detail = if needs_close {
+                        " (close)"
+                    } else if needs_reopen {
+                        " (reopen)"
+                    } else {
+                        ""
+                    };
+                    println!("{}{} → #{}{}", PlannedOp::Update.tag(), ticket.id, issue_number, detail);
+
+                    let existing = &existing_issues[&issue_number];
+                    let changed = [
+                        ("title", existing.title != title),
+                        ("body", !body_matches(&existing.body, &body)),
+                        ("close", needs_close),
+                        ("reopen", needs_reopen),
+                        ("assignees", assignees.is_some()),
+                        ("labels", needs_managed_label),
+                    ];
+                    let mut change = planned(ticket, PlannedOp::Update)?;
+                    change.issue = Some(issue_number);
+                    change.fields = changed.iter().filter(|(_, c)| *c).map(|(f, _)| f.to_string()).collect();
+                    change.issue_updated_at = Some(existing.updated_at.clone());
+                    plan.changes.push(change);
+                }
+                UpdateCheck::Conflict(reason) | UpdateCheck::Error(reason) => {
+                    println!("WARN    {}  ({})", ticket.id, reason);
+                    plan.skipped.push(PlannedSkip { ticket: ticket.id.clone(), reason });
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Compare tickets with their issues and project items without changing
+    /// anything, for `ttr report`
+    pub async fn drift(&mut self, tickets: &[Ticket], all_tickets: &[Ticket]) -> Result<DriftReport> {
+        let mut report = DriftReport::default();
+        let existing_issues = self.fetch_existing_issues(tickets, all_tickets).await;
+        self.resolve_assignees(tickets).await;
+        let drift = |ticket: &Ticket, issue: Option<u64>, kind: DriftKind| Drift {
+            id: ticket.id.clone(),
+            title: ticket.title.clone(),
+            issue,
+            kind,
+        };
+
+        // Project items to compare, grouped by project
+        let mut status_checks: Vec<(&ProjectTarget, Vec<StatusCheck>)> = Vec::new();
+        for ticket in tickets {
+            if ticket.draft_item_id().is_some() || ticket.issue_elsewhere().is_some() || self.is_discussion(ticket) {
+                continue;
+            }
+            report.checked += 1;
+
+            let Some(issue_number) = ticket.github_issue_number() else {
+                report.drift.push(drift(ticket, None, DriftKind::Unpushed));
+                continue;
+            };
+            let Some(existing) = existing_issues.get(&issue_number) else {
+                report.drift.push(drift(ticket, Some(issue_number), DriftKind::Missing));
+                continue;
+            };
+            let manage_body = manages_body(ticket, &self.config.sync);
+            if manage_body && !has_ticket_marker(&existing.body, ticket) {
+                report.drift.push(drift(ticket, Some(issue_number), DriftKind::Edited));
+                continue;
+            }
+
+            let mut fields = Vec::new();
+            if existing.title != self.issue_title(ticket) {
+                fields.push("title");
+            }
+            if manage_body && !body_matches(&existing.body, &self.format_issue_body(ticket)) {
+                fields.push("body");
+            }
+            if ticket.closes_issue() != (existing.state == "CLOSED") {
+                fields.push("state");
+            }
+            if self.assignee_change(ticket, existing).is_some() {
+                fields.push("assignees");
+            }
+            if !is_managed(existing, &self.config) {
+                fields.push("labels");
+            }
+            if !fields.is_empty() {
+                report.drift.push(drift(ticket, Some(issue_number), DriftKind::Changed(fields)));
+            }
+
+            let Some(project) = self.project_for(ticket) else {
+                continue;
+            };
+            let Some(expected) = project.fields_config.status.get(&ticket.status) else {
+                continue;
+            };
+            let check = StatusCheck {
+                ticket,
+                issue_number,
+                issue_id: existing.id.clone(),
+                expected: expected.clone(),
+            };
+            match status_checks.iter_mut().find(|(p, _)| p.info.id == project.info.id) {
+                Some((_, checks)) => checks.push(check),
+                None => status_checks.push((project, vec![check])),
+            }
+        }
+
+        for (project, checks) in status_checks {
+            let issue_ids: Vec<String> = checks.iter().map(|c| c.issue_id.clone()).collect();
+            let statuses = self
+                .client
+                .get_project_item_statuses_batch(&project.info.id, &issue_ids, &project.fields_config.status_field)
+                .await?;
+            for check in checks {
+                let actual = statuses.get(&check.issue_id);
+                if actual != Some(&check.expected) {
+                    let kind = DriftKind::ProjectStatus {
+                        project: project.info.title.clone(),
+                        expected: check.expected,
+                        actual: actual.cloned(),
+                    };
+                    report.drift.push(drift(check.ticket, Some(check.issue_number), kind));
+                }
+            }
+        }
+
+        report.drift.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(report)
+    }
+
+    /// Check if a ticket needs updating, returns update details if so
+    fn check_update_needed(
+        &self,
+        ticket: &Ticket,
+        existing_issues: &HashMap<u64, ExistingIssue>,
+    ) -> UpdateCheck {
+        let issue_number = match ticket.github_issue_number() {
+            Some(n) => n,
+            None => return UpdateCheck::Conflict("invalid external-ref".to_string()),
+        };
+
+        let existing = match existing_issues.get(&issue_number) {
+            Some(issue) => issue,
+            None => return UpdateCheck::Error(format!("Issue #{} not found", issue_number)),
+        };
+
+        // Check for our marker; a renamed ticket still owns its old marker.
+        // Bodies ttr doesn't manage are free to lose it.
+        let manage_body = manages_body(ticket, &self.config.sync);
+        if manage_body && !has_ticket_marker(&existing.body, ticket) {
+            return UpdateCheck::Conflict("issue modified outside ttr".to_string());
+        }
+
+        // Format new body, keeping what collaborators put below the manual
+        // sentinel, or keep the issue's own
+        let new_body = if manage_body {
+            keep_manual_section(&self.format_issue_body(ticket), &existing.body)
+        } else {
+            existing.body.clone()
+        };
+
+        // Check if update is needed
+        let title = self.issue_title(ticket);
+        let title_changed = existing.title != title;
+        let body_changed = manage_body && !body_matches(&existing.body, &new_body);
+        let state_should_be_closed = ticket.closes_issue();
+        let state_is_closed = existing.state == "CLOSED";
+        let state_changed = state_should_be_closed != state_is_closed;
+        let assignees = self.assignee_change(ticket, existing);
+        let needs_managed_label = !is_managed(existing, &self.config);
+
+        if !title_changed && !body_changed && !state_changed && assignees.is_none() && !needs_managed_label {
+            return UpdateCheck::NoChanges;
+        }
+
+        UpdateCheck::NeedsUpdate {
+            issue_id: existing.id.clone(),
+            issue_number,
+            title,
+            body: new_body,
+            needs_close: state_changed && state_should_be_closed,
+            needs_reopen: state_changed && !state_should_be_closed,
+            assignees,
+            needs_managed_label,
+        }
+    }
+
+    /// Look up the users tickets' issues should be assigned to, warning once
+    /// per login GitHub doesn't know
+    async fn resolve_assignees(&mut self, tickets: &[Ticket]) {
+        for ticket in tickets {
+            if ticket.is_synced() && !self.config.sync.manage_assignees {
+                continue;
+            }
+            let Some(login) = desired_assignee(ticket, &self.config) else {
+                continue;
+            };
+            let key = login.to_lowercase();
+            if self.user_ids.contains_key(&key) {
+                continue;
+            }
+            let id = match self.client.get_user_id(&login).await {
+                Ok(id) => Some(id),
+                Err(e) => {
+                    eprintln!("WARN    Assignee '{}' not found, leaving assignees alone: {}", login, e);
+                    None
+                }
+            };
+            self.user_ids.insert(key, id);
+        }
+    }
+
+    /// User ID of the ticket's desired assignee, if it was found
+    fn desired_assignee_id(&self, ticket: &Ticket) -> Option<String> {
+        let login = desired_assignee(ticket, &self.config)?;
+        self.user_ids.get(&login.to_lowercase()).cloned().flatten()
+    }
+
+    /// User IDs to add to and remove from an issue so it's assigned to just
+    /// the ticket's desired assignee, or None if it already is or the
+    /// assignee wasn't found
+    fn assignee_change(&self, ticket: &Ticket, existing: &ExistingIssue) -> Option<(Vec<String>, Vec<String>)> {
+        if !assignees_differ(ticket, &self.config, existing) {
+            return None;
+        }
+        let login = desired_assignee(ticket, &self.config)?;
+        let id = self.desired_assignee_id(ticket)?;
+        let assigned = |a: &IssueAssignee| a.login.eq_ignore_ascii_case(&login);
+        let add = if existing.assignees.iter().any(assigned) { Vec::new() } else { vec![id] };
+        let remove: Vec<String> = existing.assignees.iter().filter(|a| !assigned(a)).map(|a| a.id.clone()).collect();
+        Some((add, remove))
+    }
+
+    /// Record an applied update, with a separate entry for a close or reopen
+    fn record_update(&self, ticket_id: &str, pending: &PendingUpdate, existing: &ExistingIssue) {
+        let mut entry = AuditEntry::new(AuditAction::Update, ticket_id)
+            .issue(pending.issue_number)
+            .node(&pending.issue_id);
+        if existing.title != pending.title {
+            entry = entry.change("title", Some(&existing.title), Some(&pending.title));
+        }
+        if existing.body != pending.body {
+            entry = entry.change("body", Some(&existing.body), Some(&pending.body));
+        }
+        if !entry.changes.is_empty() {
+            self.audit.record(entry);
+        }
+
+        let action = if pending.needs_close {
+            AuditAction::Close
+        } else if pending.needs_reopen {
+            AuditAction::Reopen
+        } else {
+            return;
+        };
+        let new_state = if pending.needs_close { "CLOSED" } else { "OPEN" };
+        self.audit.record(
+            AuditEntry::new(action, ticket_id)
+                .issue(pending.issue_number)
+                .node(&pending.issue_id)
+                .change("state", Some(&existing.state), Some(new_state)),
+        );
+    }
+
+    /// Fill in missing `created` and `closed` timestamps from the issues
+    ///
+    /// New issues count as created now, and tickets closed without a
+    /// timestamp take the issue's close time, or now if this push closed it.
+    /// Failures only warn.
+    fn record_timestamps(
+        &self,
+        tickets: &mut [Ticket],
+        results: &[(usize, SyncResult)],
+        existing_issues: &HashMap<u64, ExistingIssue>,
+    ) {
+        let created_now: HashSet<usize> = results
+            .iter()
+            .filter(|(_, r)| matches!(r, SyncResult::Created { .. }))
+            .map(|(idx, _)| *idx)
+            .collect();
+
+        for (idx, ticket) in tickets.iter_mut().enumerate() {
+            let existing = ticket.github_issue_number().and_then(|n| existing_issues.get(&n));
+            if existing.is_none() && !created_now.contains(&idx) {
+                continue;
+            }
+
+            let mut result = Ok(());
+            if ticket.created.is_none() {
+                let created = match existing {
+                    Some(issue) if !issue.created_at.is_empty() => issue.created_at.clone(),
+                    Some(_) => String::new(),
+                    None => now_timestamp(),
+                };
+                if !created.is_empty() {
+                    result = ticket.write_created(&created);
+                }
+            }
+            if result.is_ok() && ticket.status == "closed" && ticket.closed.is_none() {
+                let closed_at = existing
+                    .filter(|issue| issue.state == "CLOSED")
+                    .and_then(|issue| issue.closed_at.as_deref());
+                result = ticket.write_closed(closed_at);
+            }
+            if let Err(e) = result {
+                eprintln!("WARN    {} failed to write timestamps: {}", ticket.id, e);
+            }
+        }
+    }
+
+    /// Write a new issue's `external-ref` back to its ticket and record it
+    async fn finish_create(
+        &self,
+        tickets: &mut [Ticket],
+        pending: &PendingCreate,
+        result: SyncResult,
+        results: &mut Vec<(usize, SyncResult)>,
+    ) {
+        if let SyncResult::Created { issue_id, issue_number, .. } = &result {
+            let ticket = &mut tickets[pending.ticket_idx];
+            let external_ref = format!("gh-{}", issue_number);
+            let written = ticket.write_external_ref(&external_ref);
+
+            let mut entry = AuditEntry::new(AuditAction::Create, &ticket.id)
+                .issue(*issue_number)
+                .node(issue_id)
+                .change("title", None, Some(&pending.title));
+            if written.is_ok() {
+                entry = entry.change("external-ref", None, Some(&external_ref));
+                self.write_issue_url(ticket, *issue_number);
+            }
+            self.audit.record(entry);
+
+            if let Err(e) = written {
+                results.push((pending.ticket_idx, SyncResult::Failed {
+                    error: format!("Created #{} but failed to write external-ref: {}", issue_number, e),
+                }));
+                return;
+            }
+
+            let (_, continuation) = split_body(&pending.body);
+            if !continuation.is_empty() {
+                self.sync_continuation(&tickets[pending.ticket_idx].id, *issue_number, &continuation).await;
+            }
+        }
+        results.push((pending.ticket_idx, result));
+    }
+
+    /// Batch update multiple issues
+    async fn batch_update(&self, pending: &[PendingUpdate]) -> Vec<SyncResult> {
+        let mut results = vec![SyncResult::Failed { error: "Not processed".to_string() }; pending.len()];
+
+        // Collect content updates (title/body changes)
+        let updates: Vec<IssueUpdate> = pending
+            .iter()
+            .map(|p| IssueUpdate {
+                issue_id: p.issue_id.clone(),
+                title: p.title.clone(),
+                body: split_body(&p.body).0,
+                issue_type_id: p.issue_type_id.clone(),
+            })
+            .collect();
+
+        // Batch update content
+        if !updates.is_empty() {
+            match self.client.update_issues_batch(&updates).await {
+                Ok(update_results) => {
+                    for (i, p) in pending.iter().enumerate() {
+                        if let Some(result) = update_results.get(&p.issue_id) {
+                            match result {
+                                Ok(_) => {
+                                    results[i] = SyncResult::Updated { issue_number: p.issue_number };
+                                }
+                                Err(e) => {
+                                    results[i] = SyncResult::Failed { error: e.clone() };
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    // All updates failed
+                    for result in results.iter_mut() {
+                        *result = SyncResult::Failed { error: e.to_string() };
+                    }
+                    return results;
+                }
+            }
+        }
+
+        // Batch assignee changes
+        let changes: Vec<AssigneeChange> = pending
+            .iter()
+            .filter_map(|p| {
+                let (add, remove) = p.assignees.clone()?;
+                Some(AssigneeChange { issue_id: p.issue_id.clone(), add, remove })
+            })
+            .collect();
+        if !changes.is_empty() {
+            let assign_results = self.client.update_assignees_batch(&changes).await;
+            for (i, p) in pending.iter().enumerate() {
+                if p.assignees.is_none() || !matches!(results[i], SyncResult::Updated { .. }) {
+                    continue;
+                }
+                let error = match &assign_results {
+                    Ok(by_issue) => match by_issue.get(&p.issue_id) {
+                        Some(Err(e)) => e.clone(),
+                        _ => continue,
+                    },
+                    Err(e) => e.to_string(),
+                };
+                results[i] = SyncResult::Failed { error: format!("Failed to update assignees: {}", error) };
+            }
+        }
+
+        // Batch managed labels; the label may not exist if it couldn't be created
+        let managed = self.label_ids(self.config.github.managed_label.as_slice());
+        let additions: Vec<(String, Vec<String>)> = pending
+            .iter()
+            .filter(|p| p.needs_managed_label && !managed.is_empty())
+            .map(|p| (p.issue_id.clone(), managed.clone()))
+            .collect();
+        if !additions.is_empty() {
+            let label_results = self.client.add_labels_batch(&additions).await;
+            for (i, p) in pending.iter().enumerate() {
+                if !p.needs_managed_label || !matches!(results[i], SyncResult::Updated { .. }) {
+                    continue;
+                }
+                let error = match &label_results {
+                    Ok(by_issue) => match by_issue.get(&p.issue_id) {
+                        Some(Err(e)) => e.clone(),
+                        _ => continue,
+                    },
+                    Err(e) => e.to_string(),
+                };
+                results[i] = SyncResult::Failed { error: format!("Failed to add managed label: {}", error) };
+            }
+        }
+
+        // Batch close issues
+        let to_close: Vec<String> = pending
+            .iter()
+            .filter(|p| p.needs_close)
+            .map(|p| p.issue_id.clone())
+            .collect();
+
+        if !to_close.is_empty() {
+            if let Err(e) = self.client.close_issues_batch(&to_close).await {
+                // Mark close failures
+                for (i, p) in pending.iter().enumerate() {
+                    if p.needs_close {
+                        results[i] = SyncResult::Failed {
+                            error: format!("Failed to close: {}", e),
+                        };
+                    }
+                }
+            }
+        }
+
+        // Batch reopen issues
+        let to_reopen: Vec<String> = pending
+            .iter()
+            .filter(|p| p.needs_reopen)
+            .map(|p| p.issue_id.clone())
+            .collect();
+
+        if !to_reopen.is_empty() {
+            if let Err(e) = self.client.reopen_issues_batch(&to_reopen).await {
+                // Mark reopen failures
+                for (i, p) in pending.iter().enumerate() {
+                    if p.needs_reopen {
+                        results[i] = SyncResult::Failed {
+                            error: format!("Failed to reopen: {}", e),
+                        };
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    /// Find issues already created for tickets with a recorded create
+    /// attempt, by ticket index
+    ///
+    /// Found issues are returned as created. If the search itself fails the
+    /// tickets fail too, since creating them again could duplicate an issue.
+    async fn recover_creates(&self, tickets: &[Ticket], pending: &[PendingCreate]) -> HashMap<usize, SyncResult> {
+        let Some(dir) = &self.state_dir else {
+            return HashMap::new();
+        };
+        let attempts = match SyncState::load(dir) {
+            Ok(state) => state.create_attempts,
+            Err(e) => {
+                eprintln!("WARN    {}", e);
+                return HashMap::new();
+            }
+        };
+        let retries: Vec<&PendingCreate> =
+            pending.iter().filter(|p| attempts.contains_key(&tickets[p.ticket_idx].id)).collect();
+        if retries.is_empty() {
+            return HashMap::new();
+        }
+
+        // Search's index lags behind writes, so recent issues are listed
+        let since = retries
+            .iter()
+            .filter_map(|p| chrono::DateTime::parse_from_rfc3339(&attempts[&tickets[p.ticket_idx].id]).ok())
+            .map(|at| at.with_timezone(&chrono::Utc))
+            .min()
+            .unwrap_or_else(chrono::Utc::now)
+            - chrono::Duration::minutes(CREATE_CLOCK_SKEW_MINUTES);
+        let recent = match self.client.list_issues_created_since(&self.owner, &self.repo_name, &since.to_rfc3339()).await {
+            Ok(recent) => recent,
+            Err(e) => {
+                return retries
+                    .iter()
+                    .map(|p| {
+                        let error = format!("an earlier create may have succeeded, and looking for it failed: {}", e);
+                        (p.ticket_idx, SyncResult::Failed { error })
+                    })
+                    .collect();
+            }
+        };
+
+        let mut recovered = HashMap::new();
+        for pending in retries {
+            let id = &tickets[pending.ticket_idx].id;
+            let ours = |i: &&ExistingIssue| extract_ticket_marker(&i.body) == Some(id.as_str()) && is_managed(i, &self.config);
+            let Some(issue) = recent.iter().find(ours).cloned() else {
+                continue;
+            };
+            eprintln!("WARN    {} was already created as #{} by an earlier attempt; using it", id, issue.number);
+            recovered.insert(pending.ticket_idx, SyncResult::Created {
+                issue_id: issue.id,
+                issue_number: issue.number,
+                url: issue.url,
+            });
+        }
+        recovered
+    }
+
+    /// Save the remote body of a conflicted issue under `.conflicts/`, or
+    /// clear a saved one once the ticket no longer conflicts
+    fn quarantine_conflict(&self, ticket: &Ticket, check: &UpdateCheck, existing_issues: &HashMap<u64, ExistingIssue>) {
+        let Some(dir) = &self.state_dir else {
+            return;
+        };
+        let result = match check {
+            UpdateCheck::Conflict(_) => {
+                let Some(issue) = ticket.github_issue_number().and_then(|n| existing_issues.get(&n)) else {
+                    return;
+                };
+                conflicts::quarantine(dir, &ticket.id, &issue.body).map(|path| {
+                    eprintln!("WARN    {} remote body saved to {}", ticket.id, path.display());
+                })
+            }
+            UpdateCheck::Error(_) => return,
+            UpdateCheck::NoChanges | UpdateCheck::NeedsUpdate { .. } => conflicts::clear(dir, &ticket.id).map(|_| ()),
+        };
+        if let Err(e) = result {
+            eprintln!("WARN    {:#}", e);
+        }
+    }
+
+    /// Load, change, and save the sync state, if one is kept
+    fn update_state(&self, update: impl FnOnce(&mut SyncState)) {
+        let Some(dir) = &self.state_dir else {
+            return;
+        };
+        let result = SyncState::load(dir).and_then(|mut state| {
+            update(&mut state);
+            state.save(dir)
+        });
+        if let Err(e) = result {
+            eprintln!("WARN    Failed to update sync state: {}", e);
+        }
+    }
+
+    /// Assignee IDs for each of `count` new issues, with the login whose
+    /// turn it took when taking turns
+    async fn pick_assignees(&self, count: usize) -> Vec<(Vec<String>, Option<String>)> {
+        if self.assignees.len() <= 1 || count == 0 {
+            let ids: Vec<String> = self.assignees.iter().map(|(_, id)| id.clone()).collect();
+            return vec![(ids, None); count];
+        }
+
+        let logins: Vec<String> = self.assignees.iter().map(|(login, _)| login.clone()).collect();
+        let (picks, turns) = match self.config.github.assignee_strategy {
+            AssigneeStrategy::RoundRobin => (self.take_turns(&logins, count), true),
+            AssigneeStrategy::LeastOpen => {
+                match self.client.count_open_issues_assigned(&self.owner, &self.repo_name, &logins).await {
+                    Ok(open) => (least_loaded(open, count), false),
+                    Err(e) => {
+                        eprintln!("WARN    Could not count open issues per assignee, taking turns instead: {}", e);
+                        (self.take_turns(&logins, count), true)
+                    }
+                }
+            }
+        };
+        picks
+            .into_iter()
+            .map(|i| (vec![self.assignees[i].1.clone()], turns.then(|| logins[i].clone())))
+            .collect()
+    }
+
+    /// Round-robin picks, continuing after the last push's final pick
+    ///
+    /// The turn is only recorded once issues are created, see
+    /// `record_last_turn`.
+    fn take_turns(&self, logins: &[String], count: usize) -> Vec<usize> {
+        let last = self
+            .state_dir
+            .as_ref()
+            .and_then(|dir| SyncState::load(dir).ok())
+            .and_then(|state| state.last_assignee)
+            .and_then(|last| logins.iter().position(|l| *l == last));
+        rotate(logins.len(), last, count)
+    }
+
+    /// Remember whose turn the last created issue took, so failed or
+    /// interrupted creates don't move the rotation on
+    fn record_last_turn(&self, turns: &[Option<String>], results: &[SyncResult]) {
+        let last = turns
+            .iter()
+            .zip(results)
+            .filter(|(_, result)| matches!(result, SyncResult::Created { .. }))
+            .filter_map(|(turn, _)| turn.clone())
+            .next_back();
+        if let Some(login) = last {
+            self.update_state(|state| state.last_assignee = Some(login));
+        }
+    }
+
+    /// Batch create multiple issues
+    async fn batch_create(&self, pending: &[&PendingCreate]) -> Vec<SyncResult> {
+        if pending.is_empty() {
+            return Vec::new();
+        }
+
+        // Tickets naming their own assignee don't take a turn from the pool
+        let mut picks = self
+            .pick_assignees(pending.iter().filter(|p| p.assignee_id.is_none()).count())
+            .await
+            .into_iter();
+        let mut turns: Vec<Option<String>> = Vec::with_capacity(pending.len());
+        let creates: Vec<IssueCreate> = pending
+            .iter()
+            .map(|p| {
+                let assignee_ids = match &p.assignee_id {
+                    Some(id) => {
+                        turns.push(None);
+                        vec![id.clone()]
+                    }
+                    None => {
+                        let (ids, turn) = picks.next().unwrap_or_default();
+                        turns.push(turn);
+                        ids
+                    }
+                };
+                IssueCreate {
+                    title: p.title.clone(),
+                    body: split_body(&p.body).0,
+                    label_ids: self.label_ids(&p.labels),
+                    issue_type_id: p.issue_type_id.clone(),
+                    assignee_ids,
+                }
+            })
+            .collect();
+
+        let results: Vec<SyncResult> = match self.client.create_issues_batch(&self.repo_id, &creates, None).await {
+            Ok(create_results) => {
+                create_results
+                    .into_iter()
+                    .map(|result| match result {
+                        Ok(info) => SyncResult::Created {
+                            issue_id: info.id,
+                            issue_number: info.number,
+                            url: info.url,
+                        },
+                        Err(e) => SyncResult::Failed { error: e },
+                    })
+                    .collect()
+            }
+            Err(e) => {
+                // All creates failed
+                vec![SyncResult::Failed { error: e.to_string() }; pending.len()]
+            }
+        };
+        self.record_last_turn(&turns, &results);
+        results
+    }
+
+    /// Make sure labels are in the cache, creating missing ones if configured to
+    ///
+    /// Labels missing from the cache trigger one refetch (the cache may be
+    /// stale), then whatever is still missing is created in one request.
+    async fn ensure_labels(&mut self, names: &[String]) {
+        let mut missing: Vec<String> = Vec::new();
+        for name in names {
+            let lower = name.to_lowercase();
+            if !self.label_cache.contains_key(&lower) && !missing.iter().any(|m| m.to_lowercase() == lower) {
+                missing.push(name.clone());
+            }
+        }
+        if missing.is_empty() {
+            return;
+        }
+
+        match self.client.get_labels(&self.owner, &self.repo_name).await {
+            Ok(labels) => self
+                .label_cache
+                .extend(labels.into_iter().map(|l| (l.name.to_lowercase(), l.id))),
+            Err(e) => eprintln!("WARN    Could not refresh labels: {}", e),
+        }
+        missing.retain(|name| !self.label_cache.contains_key(&name.to_lowercase()));
+        if missing.is_empty() || !self.config.labels.create_missing {
+            return;
+        }
+
+        match self.client.create_labels_batch(&self.repo_id, &missing).await {
+            Ok(results) => {
+                for (name, result) in missing.iter().zip(results) {
+                    match result {
+                        Ok(label) => {
+                            println!("LABEL   {} (created)", label.name);
+                            self.label_cache.insert(name.to_lowercase(), label.id);
+                        }
+                        Err(e) => eprintln!("WARN    {}", e),
+                    }
+                }
+            }
+            Err(e) => eprintln!("WARN    Failed to create labels: {}", e),
+        }
+    }
+
+    /// Comment "Duplicate of #N" on a duplicate ticket's just-closed issue and
+    /// label it `duplicate`
+    ///
+    /// Failures only warn; the issue is already closed.
+    async fn mark_duplicate(&self, ticket: &Ticket, issue_id: &str) {
+        let Some(original) = &ticket.duplicate_of else {
+            return;
+        };
+        let refs = format_ticket_refs(std::slice::from_ref(original), &self.ticket_to_issue, &self.elsewhere);
+        if let Err(e) = self.client.add_comment(issue_id, &format!("Duplicate of {}", refs)).await {
+            eprintln!("WARN    {} duplicate comment failed: {}", ticket.id, e);
+        }
+        let labels = self.label_ids(&[DUPLICATE_LABEL.to_string()]);
+        if let Err(e) = self.client.add_labels_to_issue(issue_id, &labels).await {
+            eprintln!("WARN    {} duplicate label failed: {}", ticket.id, e);
+        }
+    }
+
+    /// Label IDs for names in the cache; labels that don't exist are left off
+    fn label_ids(&self, names: &[String]) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for name in names {
+            if let Some(id) = self.label_cache.get(&name.to_lowercase()) {
+                if !ids.contains(id) {
+                    ids.push(id.clone());
+                }
+            }
+        }
+        ids
+    }
+
+    /// The ticket's markdown without private sections
+    fn public_body(&self, ticket: &Ticket) -> String {
+        if self.is_discussion(ticket) {
+            // Comments pulled from the discussion don't go back into it
+            let mut private = self.config.sync.private_sections.clone();
+            private.push(DISCUSSION_SECTION.to_string());
+            ticket.public_body(&private)
+        } else {
+            ticket.public_body(&self.config.sync.private_sections)
+        }
+    }
+
+    /// Why a ticket can't be pushed, if its title or body looks like it holds credentials
+    fn secrets_error(&self, ticket: &Ticket) -> Option<String> {
+        if self.options.allow_secrets {
+            return None;
+        }
+        let body = self.public_body(ticket);
+        let mut findings: Vec<String> = secrets::scan(&ticket.title)
+            .iter()
+            .map(|f| format!("title: {} ({})", f.kind, f.redacted))
+            .collect();
+        findings.extend(secrets::scan(&body).iter().map(|f| format!("body {}", f)));
+        if findings.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "possible secrets, not pushed: {}; rerun with --allow-secrets if they're safe to publish",
+            findings.join(", ")
+        ))
+    }
+
+    /// Resolve issue type ID from ticket type using config mapping
+    fn resolve_issue_type_id(&self, ticket_type: &str) -> Option<String> {
+        resolve_issue_type(ticket_type, &self.config.mapping.type_map, &self.issue_type_cache)
+    }
+
+    /// Format the issue body with marker, content, and dependencies
+    fn format_issue_body(&self, ticket: &Ticket) -> String {
+        let body = self.public_body(ticket);
+        let mapping = &self.config.mapping;
+        let body = if mapping.mentions.is_empty() && !mapping.escape_unmapped_mentions {
+            body
+        } else {
+            map_user_mentions(&body, &mapping.mentions, mapping.escape_unmapped_mentions)
+        };
+        let body = match self.config.sync.mention_links {
+            MentionLinks::Issue => link_ticket_mentions(&body, &self.ticket_to_issue, |n| format!("#{}", n)),
+            MentionLinks::Url => link_ticket_mentions(&body, &self.ticket_to_issue, |n| {
+                format!("https://github.com/{}/{}/issues/{}", self.owner, self.repo_name, n)
+            }),
+            MentionLinks::None => body,
+        };
+        let body = match &self.blob_base {
+            Some(base) => {
+                // Links are relative to the ticket's directory
+                absolutize_links(&body, base, &ticket.repo_dirs())
+            }
+            None => body,
+        };
+        let body = match &self.issue_template {
+            // Discussions have no issue template to match
+            Some(template) if !self.is_discussion(ticket) => template.fill(&body),
+            _ => body,
+        };
+        let mut sections = Vec::new();
+        if !ticket.deps.is_empty() {
+            sections.push(match self.config.sync.deps_format {
+                DepsFormat::Inline => format_dependencies_section(&ticket.deps, &self.ticket_to_issue, &self.elsewhere),
+                DepsFormat::Tasklist => {
+                    format_dependencies_tasklist(&ticket.deps, &self.ticket_to_issue, &self.elsewhere, &self.closed_tickets)
+                }
+            });
+        }
+        if let Some(dependents) = self.dependents.get(&ticket.id) {
+            sections.push(format_blocks_section(dependents, &self.ticket_to_issue, &self.elsewhere));
+        }
+        if let Some(related) = self.related.get(&ticket.id) {
+            sections.push(format!("**Related:** {}", format_ticket_refs(related, &self.ticket_to_issue, &self.elsewhere)));
+        }
+        if let Some(original) = &ticket.duplicate_of {
+            let refs = format_ticket_refs(std::slice::from_ref(original), &self.ticket_to_issue, &self.elsewhere);
+            sections.push(format!("**Duplicate of:** {}", refs));
+        }
+        if let Some(duplicates) = self.duplicates.get(&ticket.id) {
+            sections.push(format!("**Duplicates:** {}", format_ticket_refs(duplicates, &self.ticket_to_issue, &self.elsewhere)));
+        }
+        // Epics roll up their children, for repos without the sub-issues UI
+        if ticket.ticket_type == "epic" {
+            if let Some(children) = self.children.get(&ticket.id) {
+                sections.push(format_tasklist(
+                    "**Children:**",
+                    children,
+                    &self.ticket_to_issue,
+                    &self.elsewhere,
+                    &self.closed_tickets,
+                ));
+            }
+        }
+        if let Some(totals) = worklog::totals_table(&worklog::entries(&ticket.markdown)) {
+            sections.push(totals);
+        }
+        let footer_url = self.footer_base.as_deref().map(|base| ticket_file_url(base, ticket));
+        format_issue_body_with_sections(&ticket.id, &body, &sections, &self.config.sync, footer_url.as_deref())
+    }
+
+    /// Link open pull requests whose branch names a synced ticket
+    ///
+    /// Depending on `[pull_requests] link`, appends "Closes #N" to the PR
+    /// description or comments the PR link on the issue. Both are idempotent.
+    async fn link_pull_requests(&self, tickets: &[Ticket]) {
+        let mode = self.config.pull_requests.link;
+        if mode == PrLinkMode::None || !tickets.iter().any(|t| t.is_synced()) {
+            return;
+        }
+
+        let prs = match self.client.list_pull_requests(&self.owner, &self.repo_name).await {
+            Ok(prs) => prs,
+            Err(e) => {
+                eprintln!("WARN    Failed to list pull requests: {}", e);
+                return;
+            }
+        };
+
+        for ticket in tickets {
+            let Some(issue_number) = ticket.github_issue_number() else {
+                continue;
+            };
+            let Some(pr) = pull_request_for(&prs, &ticket.id) else {
+                continue;
+            };
+            if pr.state != "OPEN" {
+                continue;
+            }
+
+            let linked = match mode {
+                PrLinkMode::Closes => match with_closing_keyword(&pr.body, issue_number) {
+                    Some(body) => self
+                        .client
+                        .update_pull_request_body(&pr.id, &body)
+                        .await
+                        .map(|_| true),
+                    None => Ok(false),
+                },
+                PrLinkMode::Comment => self.comment_pull_request(issue_number, pr).await,
+                PrLinkMode::None => Ok(false),
+            };
+
+            match linked {
+                Ok(true) => {
+                    println!("PR      {} → #{}  {}", ticket.id, pr.number, pr.url);
+                    self.audit.record(
+                        AuditEntry::new(AuditAction::Link, &ticket.id)
+                            .issue(issue_number)
+                            .node(&pr.id)
+                            .change("pull_request", None, Some(&pr.url)),
+                    );
+                }
+                Ok(false) => {}
+                Err(e) => eprintln!("WARN    {} PR #{} link failed: {}", ticket.id, pr.number, e),
+            }
+        }
+    }
+
+    /// Pin issues of tickets marked `pinned`, and unpin those ttr pinned
+    /// before whose flag is gone
+    ///
+    /// GitHub pins at most three issues per repository; tickets past the
+    /// limit warn and stay unpinned.
+    async fn sync_pins(&self, tickets: &[Ticket], issue_ids: &HashMap<u64, &str>) {
+        let recorded = self
+            .state_dir
+            .as_ref()
+            .and_then(|dir| SyncState::load(dir).ok())
+            .map(|state| state.pinned)
+            .unwrap_or_default();
+
+        let mut wanted = Vec::new();
+        let mut unwanted = Vec::new();
+        for ticket in tickets {
+            let Some(number) = ticket.github_issue_number() else {
+                continue;
+            };
+            if ticket.pinned {
+                wanted.push((ticket.id.as_str(), number));
+            } else if recorded.contains(&ticket.id) {
+                unwanted.push((ticket.id.as_str(), number));
+            }
+        }
+        if wanted.is_empty() && unwanted.is_empty() {
+            return;
+        }
+
+        let pinned_now: Vec<u64> = match self.client.get_pinned_issues(&self.owner, &self.repo_name).await {
+            Ok(issues) => issues.iter().map(|i| i.number).collect(),
+            Err(e) => {
+                eprintln!("WARN    Failed to list pinned issues: {}", e);
+                return;
+            }
+        };
+        let plan = plan_pins(&wanted, &unwanted, &pinned_now);
+
+        let mut unpinned = Vec::new();
+        for &(id, number) in &plan.unpin {
+            let result = match issue_ids.get(&number) {
+                Some(issue_id) => self.client.unpin_issue(issue_id).await,
+                None => Err(anyhow::anyhow!("issue #{} not found", number)),
+            };
+            match result {
+                Ok(()) => {
+                    println!("UNPIN   {} → #{}", id, number);
+                    unpinned.push(id.to_string());
+                }
+                Err(e) => eprintln!("WARN    {} unpin failed: {}", id, e),
+            }
+        }
+        // Issues someone already unpinned just drop out of the record
+        unpinned.extend(plan.forget.iter().map(|id| id.to_string()));
+
+        let mut pinned: Vec<String> = plan.keep.iter().map(|id| id.to_string()).collect();
+        for &(id, number) in &plan.pin {
+            let result = match issue_ids.get(&number) {
+                Some(issue_id) => self.client.pin_issue(issue_id).await,
+                None => Err(anyhow::anyhow!("issue #{} not found", number)),
+            };
+            match result {
+                Ok(()) => {
+                    println!("PIN     {} → #{}", id, number);
+                    pinned.push(id.to_string());
+                }
+                Err(e) => eprintln!("WARN    {} pin failed: {}", id, e),
+            }
+        }
+        for &(id, number) in &plan.over_limit {
+            eprintln!(
+                "WARN    {} not pinned: GitHub allows {} pinned issues per repository, and #{} would be one too many (unpin one first)",
+                id, MAX_PINNED_ISSUES, number
+            );
+        }
+
+        self.update_state(|state| {
+            for id in unpinned {
+                state.pinned.remove(&id);
+            }
+            state.pinned.extend(pinned);
+        });
+    }
+
+    /// Lock the conversations of archived tickets' issues, and unlock those
+    /// ttr locked once their ticket is no longer archived
+    async fn sync_locks(&self, tickets: &[Ticket], issue_ids: &HashMap<u64, &str>) {
+        let recorded = self
+            .state_dir
+            .as_ref()
+            .and_then(|dir| SyncState::load(dir).ok())
+            .map(|state| state.locked)
+            .unwrap_or_default();
+
+        let mut locked = Vec::new();
+        let mut unlocked = Vec::new();
+        for ticket in tickets {
+            let Some(number) = ticket.github_issue_number() else {
+                continue;
+            };
+            let was_locked = recorded.contains(&ticket.id);
+            if ticket.archived == was_locked {
+                continue;
+            }
+            let result = match issue_ids.get(&number) {
+                Some(issue_id) if ticket.archived => self.client.lock_issue(issue_id).await,
+                Some(issue_id) => self.client.unlock_issue(issue_id).await,
+                None => Err(anyhow::anyhow!("issue #{} not found", number)),
+            };
+            match result {
+                Ok(()) if ticket.archived => {
+                    println!("LOCK    {} → #{}", ticket.id, number);
+                    locked.push(ticket.id.clone());
+                }
+                Ok(()) => {
+                    println!("UNLOCK  {} → #{}", ticket.id, number);
+                    unlocked.push(ticket.id.clone());
+                }
+                Err(e) => eprintln!("WARN    {} lock update failed: {}", ticket.id, e),
+            }
+        }
+
+        if !locked.is_empty() || !unlocked.is_empty() {
+            self.update_state(|state| {
+                for id in &unlocked {
+                    state.locked.remove(id);
+                }
+                state.locked.extend(locked);
+            });
+        }
+    }
+
+    /// Comment each new `## Worklog` entry on the ticket's issue
+    ///
+    /// Entries commented before are recorded in the sync state; the issue's
+    /// comments are checked too, so a lost state file doesn't repeat them.
+    /// Failures only warn, leaving the entry for the next push.
+    async fn comment_worklogs(&self, tickets: &[Ticket]) {
+        let recorded = self
+            .state_dir
+            .as_ref()
+            .and_then(|dir| SyncState::load(dir).ok())
+            .map(|state| state.worklog)
+            .unwrap_or_default();
+
+        let mut commented: Vec<(String, String)> = Vec::new(); // (ticket ID, entry key)
+        for ticket in tickets {
+            let Some(number) = ticket.github_issue_number() else {
+                continue;
+            };
+            let done = recorded.get(&ticket.id);
+            let new: Vec<worklog::Entry> = worklog::entries(&ticket.markdown)
+                .into_iter()
+                .filter(|e| done.is_none_or(|keys| !keys.contains(&e.key())))
+                .collect();
+            if new.is_empty() {
+                continue;
+            }
+
+            let result = async {
+                let comments = self.client.get_issue_comments(&self.owner, &self.repo_name, number).await?;
+                for entry in &new {
+                    if !comments.comments.iter().any(|c| worklog::is_comment_for(&c.body, entry)) {
+                        self.client.add_comment(&comments.issue_id, &entry.comment(&ticket.id)).await?;
+                        println!(
+                            "WORKLOG {} → #{}  {} on {}",
+                            ticket.id,
+                            number,
+                            worklog::format_duration(entry.minutes),
+                            entry.at
+                        );
+                    }
+                    commented.push((ticket.id.clone(), entry.key()));
+                }
+                anyhow::Ok(())
+            };
+            if let Err(e) = result.await {
+                eprintln!("WARN    {} #{} worklog comments failed: {}", ticket.id, number, e);
+            }
+        }
+
+        if !commented.is_empty() {
+            self.update_state(|state| {
+                for (id, key) in commented {
+                    state.worklog.entry(id).or_default().insert(key);
+                }
+            });
+        }
+    }
+
+    /// Make an issue's continuation comments match `parts`
+    ///
+    /// Earlier continuation comments are edited in place, missing ones added,
+    /// and leftovers deleted. Failures only warn: the issue body is already
+    /// pushed.
+    async fn sync_continuation(&self, ticket_id: &str, issue_number: u64, parts: &[String]) {
+        let result = async {
+            let comments = self.client.get_issue_comments(&self.owner, &self.repo_name, issue_number).await?;
+            let ours: Vec<_> = comments.comments.iter().filter(|c| c.body.starts_with(CONTINUATION_PREFIX)).collect();
+            for (i, part) in parts.iter().enumerate() {
+                match ours.get(i) {
+                    Some(comment) if comment.body == *part => {}
+                    Some(comment) => self.client.update_comment(&comment.id, part).await?,
+                    None => self.client.add_comment(&comments.issue_id, part).await?,
+                }
+            }
+            for comment in ours.iter().skip(parts.len()) {
+                self.client.delete_comment(&comment.id).await?;
+            }
+            anyhow::Ok(())
+        };
+        if let Err(e) = result.await {
+            eprintln!("WARN    {} #{} continuation comments failed: {}", ticket_id, issue_number, e);
+        }
+    }
+
+    /// Comment a PR link on an issue unless an earlier comment has it
+    async fn comment_pull_request(&self, issue_number: u64, pr: &PullRequestInfo) -> Result<bool> {
+        let comments = self
+            .client
+            .get_issue_comments(&self.owner, &self.repo_name, issue_number)
+            .await?;
+        if comments.comments.iter().any(|c| c.body.contains(&pr.url)) {
+            return Ok(false);
+        }
+
+        let body = format!("Pull request: {}", pr.url);
+        self.client.add_comment(&comments.issue_id, &body).await?;
+        Ok(true)
+    }
+
+    /// Link sub-issues based on ticket parent relationships
+    /// 
+    /// This runs after all creates/updates, using a two-pass approach:
+    /// 1. Build a map of ticket_id → issue_node_id from existing issues and newly created ones
+    /// 2. For each ticket with a parent, link child to parent as a sub-issue
+    async fn link_sub_issues(
+        &self,
+        tickets: &[Ticket],
+        all_tickets: &[Ticket],
+        results: &[(usize, SyncResult)],
+        existing_issues: &HashMap<u64, ExistingIssue>,
+    ) {
+        // Build ticket_id → issue_node_id map
+        let mut ticket_to_node_id: HashMap<String, String> = HashMap::new();
+
+        // Add from existing issues (looked up at start of sync)
+        for ticket in all_tickets {
+            if let Some(issue_num) = ticket.github_issue_number() {
+                if let Some(existing) = existing_issues.get(&issue_num) {
+                    ticket_to_node_id.insert(ticket.id.clone(), existing.id.clone());
+                }
+            }
+        }
+
+        // Add from newly created issues in this sync
+        for (idx, result) in results {
+            if let SyncResult::Created { issue_id, .. } = result {
+                let ticket = &tickets[*idx];
+                ticket_to_node_id.insert(ticket.id.clone(), issue_id.clone());
+            }
+        }
+
+        // Collect sub-issue links to create
+        let mut links: Vec<(String, SubIssueLink)> = Vec::new(); // (child_ticket_id, link)
+
+        for ticket in tickets {
+            if let Some(ref parent_id) = ticket.parent {
+                // Look up both parent and child node IDs
+                let parent_node_id = ticket_to_node_id.get(parent_id);
+                let child_node_id = ticket_to_node_id.get(&ticket.id);
+
+                match (parent_node_id, child_node_id) {
+                    (Some(parent_id), Some(child_id)) => {
+                        links.push((
+                            ticket.id.clone(),
+                            SubIssueLink {
+                                parent_issue_id: parent_id.clone(),
+                                child_issue_id: child_id.clone(),
+                            },
+                        ));
+                    }
+                    (None, _) => {
+                        // Parent not synced - skip silently, it will link on next push
+                    }
+                    (_, None) => {
+                        // Child not synced - shouldn't happen since we just synced it
+                    }
+                }
+            }
+        }
+
+        if links.is_empty() {
+            return;
+        }
+
+        // Batch link sub-issues (single GraphQL mutation)
+        let sub_issue_links: Vec<SubIssueLink> = links.iter().map(|(_, link)| link.clone()).collect();
+        
+        match self.client.add_sub_issues_batch(&sub_issue_links).await {
+            Ok(results) => {
+                println!();
+                for ((child_id, link), result) in links.iter().zip(results) {
+                    // Find parent ticket ID for display
+                    let parent_ticket_id = all_tickets
+                        .iter()
+                        .find(|t| ticket_to_node_id.get(&t.id) == Some(&link.parent_issue_id))
+                        .map(|t| t.id.as_str())
+                        .unwrap_or("?");
+
+                    match result {
+                        Ok(()) => {
+                            println!("LINK    {} → {} (sub-issue)", child_id, parent_ticket_id);
+                            self.audit.record(
+                                AuditEntry::new(AuditAction::Link, child_id)
+                                    .node(&link.child_issue_id)
+                                    .change("parent", None, Some(parent_ticket_id)),
+                            );
+                        }
+                        Err(e) => {
+                            eprintln!("WARN    {} sub-issue link failed: {}", child_id, e);
+                        }
+                    }
+                }
+            }
+            Err(e) if is_schema_error(&e) => {
+                eprintln!("NOTE    Sub-issues aren't available on this GitHub instance; parents aren't linked");
+            }
+            Err(e) => {
+                eprintln!("\nWARN    sub-issue batch link failed: {}", e);
+            }
+        }
+    }
+
+    /// A project's field cache, fetched the first time a push needs it
+    ///
+    /// A failed fetch or invalid field mapping warns once and leaves the
+    /// project's fields unsynced for the rest of the run.
+    async fn project_fields<'a>(&self, target: &'a ProjectTarget) -> Option<&'a ProjectFieldsCache> {
+        let fields = target.fields.get_or_init(|| async {
+            Self::setup_project_fields(&self.client, &target.info, &target.fields_config, &self.config.mapping)
+                .await
+                .unwrap_or_else(|e| {
+                    eprintln!("WARN    Project '{}' fields not synced: {}", target.info.title, e);
+                    None
+                })
+        });
+        fields.await.as_ref()
+    }
+
+    /// Setup project fields cache by fetching and validating field mappings
+    async fn setup_project_fields(
+        client: &GitHubClient,
+        project: &ProjectInfo,
+        config: &ProjectFieldsConfig,
+        mapping: &MappingConfig,
+    ) -> Result<Option<ProjectFieldsCache>> {
+        // Fetch project fields (iteration field is always cached so tickets
+        // can pick their own iteration via frontmatter or due date)
+        let fields = client.get_project_fields(&project.id).await?;
+
+        // Setup status field cache
+        let status_cache = if !config.status.is_empty() {
+            Self::setup_status_field(&fields, config)?
+        } else {
+            None
+        };
+
+        // Setup iteration field cache
+        let iteration_cache = Self::setup_iteration_field(&fields, config)?;
+
+        let mut custom = Self::setup_custom_fields(&fields, config);
+        custom.extend(Self::setup_tag_fields(&fields, config, &custom));
+        if let Some(type_field) = Self::setup_type_field(&fields, mapping, &custom) {
+            custom.push(type_field);
+        }
+        custom.extend(Self::setup_hours_fields(&fields, config, &custom));
+
+        if status_cache.is_some() || iteration_cache.is_some() || !custom.is_empty() {
+            Ok(Some(ProjectFieldsCache {
+                status: status_cache,
+                iteration: iteration_cache,
+                custom,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Setup status field cache, validating options exist
+    fn setup_status_field(
+        fields: &[ProjectFieldInfo],
+        config: &ProjectFieldsConfig,
+    ) -> Result<Option<StatusFieldCache>> {
+        // Find the status field by name (case-insensitive)
+        let status_field_name = config.status_field.to_lowercase();
+        let status_field = fields.iter().find(|f| f.name.to_lowercase() == status_field_name);
+
+        let field = match status_field {
+            Some(f) => f,
+            None => {
+                eprintln!(
+                    "WARN    Project field '{}' not found, skipping status sync",
+                    config.status_field
+                );
+                return Ok(None);
+            }
+        };
+
+        // Get options from field
+        let options = match &field.field_type {
+            ProjectFieldType::SingleSelect { options } => options,
+            _ => {
+                eprintln!(
+                    "WARN    Project field '{}' is not a single-select field, skipping status sync",
+                    config.status_field
+                );
+                return Ok(None);
+            }
+        };
+
+        // Build status -> option ID mapping, validating each
+        let mut status_to_option = HashMap::new();
+        for (ticket_status, project_option_name) in &config.status {
+            let option_name_lower = project_option_name.to_lowercase();
+            let option = options.iter().find(|o| o.name.to_lowercase() == option_name_lower);
+
+            match option {
+                Some(o) => {
+                    status_to_option.insert(ticket_status.to_lowercase(), o.id.clone());
+                }
+                None => {
+                    let available: Vec<&str> = options.iter().map(|o| o.name.as_str()).collect();
+                    anyhow::bail!(
+                        "Project status option '{}' (for ticket status '{}') not found.\nAvailable options: {:?}",
+                        project_option_name,
+                        ticket_status,
+                        available
+                    );
+                }
+            }
+        }
+
+        Ok(Some(StatusFieldCache {
+            field_id: field.id.clone(),
+            status_to_option,
+        }))
+    }
+
+    /// Resolve `[project.custom_fields]` mappings, skipping unusable fields
+    fn setup_custom_fields(
+        fields: &[ProjectFieldInfo],
+        config: &ProjectFieldsConfig,
+    ) -> Vec<CustomFieldCache> {
+        let mut custom = Vec::new();
+        for (key, field_name) in &config.custom_fields {
+            let Some(field) = fields.iter().find(|f| f.name.eq_ignore_ascii_case(field_name)) else {
+                eprintln!("WARN    Project field '{}' not found, skipping {}", field_name, key);
+                continue;
+            };
+            let kind = match &field.field_type {
+                ProjectFieldType::Text => CustomFieldKind::Text,
+                ProjectFieldType::Number => CustomFieldKind::Number,
+                ProjectFieldType::Date => CustomFieldKind::Date,
+                ProjectFieldType::SingleSelect { options } => CustomFieldKind::SingleSelect(
+                    options
+                        .iter()
+                        .map(|o| (o.name.to_lowercase(), o.id.clone()))
+                        .collect(),
+                ),
+                _ => {
+                    eprintln!(
+                        "WARN    Project field '{}' can't be set from frontmatter, skipping {}",
+                        field_name, key
+                    );
+                    continue;
+                }
+            };
+            custom.push(CustomFieldCache {
+                key: key.clone(),
+                field_id: field.id.clone(),
+                field_name: field.name.clone(),
+                kind,
+                source: FieldSource::Custom,
+            });
+        }
+        custom.sort_by(|a, b| a.key.cmp(&b.key));
+        custom
+    }
+
+    /// Resolve `[project.tag_fields]` mappings, skipping unusable fields
+    fn setup_tag_fields(
+        fields: &[ProjectFieldInfo],
+        config: &ProjectFieldsConfig,
+        custom: &[CustomFieldCache],
+    ) -> Vec<CustomFieldCache> {
+        let mut tag_fields = Vec::new();
+        for (prefix, field_name) in &config.tag_fields {
+            // Accept `area`, `area:` or `area:*`
+            let prefix = prefix.trim_end_matches('*').trim_end_matches(':');
+            let Some(field) = fields.iter().find(|f| f.name.eq_ignore_ascii_case(field_name)) else {
+                eprintln!("WARN    Project field '{}' not found, skipping {}:* tags", field_name, prefix);
+                continue;
+            };
+            if custom.iter().chain(&tag_fields).any(|c| c.field_id == field.id) {
+                eprintln!("WARN    Project field '{}' is already mapped, skipping {}:* tags", field.name, prefix);
+                continue;
+            }
+            let kind = match &field.field_type {
+                ProjectFieldType::Text => CustomFieldKind::Text,
+                ProjectFieldType::SingleSelect { options } => CustomFieldKind::SingleSelect(
+                    options
+                        .iter()
+                        .map(|o| (o.name.to_lowercase(), o.id.clone()))
+                        .collect(),
+                ),
+                _ => {
+                    eprintln!(
+                        "WARN    Project field '{}' is not a text or single-select field, skipping {}:* tags",
+                        field.name, prefix
+                    );
+                    continue;
+                }
+            };
+            tag_fields.push(CustomFieldCache {
+                key: format!("{}:*", prefix),
+                field_id: field.id.clone(),
+                field_name: field.name.clone(),
+                kind,
+                source: FieldSource::Tag(prefix.to_string()),
+            });
+        }
+        tag_fields.sort_by(|a, b| a.key.cmp(&b.key));
+        tag_fields
+    }
+
+    /// Resolve `[mapping] type_field`, set from each ticket's type
+    ///
+    /// Projects without the field are skipped quietly, since it's named by
+    /// default; so are fields already mapped from a custom key.
+    fn setup_type_field(
+        fields: &[ProjectFieldInfo],
+        mapping: &MappingConfig,
+        custom: &[CustomFieldCache],
+    ) -> Option<CustomFieldCache> {
+        let field = fields.iter().find(|f| f.name.eq_ignore_ascii_case(&mapping.type_field))?;
+        if custom.iter().any(|c| c.field_id == field.id) {
+            return None;
+        }
+        let kind = match &field.field_type {
+            ProjectFieldType::Text => CustomFieldKind::Text,
+            ProjectFieldType::SingleSelect { options } => {
+                let options: HashMap<String, String> =
+                    options.iter().map(|o| (o.name.to_lowercase(), o.id.clone())).collect();
+                let mut missing: Vec<&str> = mapping
+                    .type_map
+                    .values()
+                    .filter(|name| !options.contains_key(&name.to_lowercase()))
+                    .map(String::as_str)
+                    .collect();
+                if !missing.is_empty() {
+                    missing.sort();
+                    eprintln!(
+                        "WARN    Project field '{}' has no option for {}",
+                        field.name,
+                        missing.join(", ")
+                    );
+                }
+                CustomFieldKind::SingleSelect(options)
+            }
+            _ => {
+                eprintln!(
+                    "WARN    Project field '{}' is not a text or single-select field, skipping type sync",
+                    field.name
+                );
+                return None;
+            }
+        };
+        Some(CustomFieldCache {
+            key: "type".to_string(),
+            field_id: field.id.clone(),
+            field_name: field.name.clone(),
+            kind,
+            source: FieldSource::Type(mapping.type_map.clone()),
+        })
+    }
+
+    /// Resolve `estimate_field` and `spent_field`, which must be number fields
+    fn setup_hours_fields(
+        fields: &[ProjectFieldInfo],
+        config: &ProjectFieldsConfig,
+        custom: &[CustomFieldCache],
+    ) -> Vec<CustomFieldCache> {
+        let mut hours_fields = Vec::new();
+        let configured = [
+            ("estimate-hours", &config.estimate_field, FieldSource::Estimate),
+            ("spent-hours", &config.spent_field, FieldSource::Spent),
+        ];
+        for (key, field_name, source) in configured {
+            let Some(field_name) = field_name else { continue };
+            let Some(field) = fields.iter().find(|f| f.name.eq_ignore_ascii_case(field_name)) else {
+                eprintln!("WARN    Project field '{}' not found, skipping {}", field_name, key);
+                continue;
+            };
+            if custom.iter().chain(&hours_fields).any(|c| c.field_id == field.id) {
+                eprintln!("WARN    Project field '{}' is already mapped, skipping {}", field.name, key);
+                continue;
+            }
+            if !matches!(field.field_type, ProjectFieldType::Number) {
+                eprintln!("WARN    Project field '{}' is not a number field, skipping {}", field.name, key);
+                continue;
+            }
+            hours_fields.push(CustomFieldCache {
+                key: key.to_string(),
+                field_id: field.id.clone(),
+                field_name: field.name.clone(),
+                kind: CustomFieldKind::Number,
+                source,
+            });
+        }
+        hours_fields
+    }
+
+    /// Setup iteration field cache, finding current iteration if @current
+    fn setup_iteration_field(
+        fields: &[ProjectFieldInfo],
+        config: &ProjectFieldsConfig,
+    ) -> Result<Option<IterationFieldCache>> {
+        let iteration_setting = config.iteration.as_ref();
+
+        // Find the iteration field by name (case-insensitive)
+        let iteration_field_name = config.iteration_field.to_lowercase();
+        let iteration_field = fields.iter().find(|f| f.name.to_lowercase() == iteration_field_name);
+
+        let field = match iteration_field {
+            Some(f) => f,
+            None => {
+                // Only worth a warning if the user asked for an iteration
+                if iteration_setting.is_some() {
+                    eprintln!(
+                        "WARN    Project field '{}' not found, skipping iteration sync",
+                        config.iteration_field
+                    );
+                }
+                return Ok(None);
+            }
+        };
+
+        // Get iterations from field
+        let (active, completed) = match &field.field_type {
+            ProjectFieldType::Iteration { active, completed } => (active, completed),
+            _ => {
+                if iteration_setting.is_some() {
+                    eprintln!(
+                        "WARN    Project field '{}' is not an iteration field, skipping iteration sync",
+                        config.iteration_field
+                    );
+                }
+                return Ok(None);
+            }
+        };
+
+        // Resolve the default iteration ID from config
+        let default_iteration_id = match iteration_setting {
+            None => None,
+            Some(setting) => match resolve_iteration_spec(setting, active) {
+                Ok(Some(id)) => Some(id),
+                Ok(None) => {
+                    if setting != "@none" {
+                        eprintln!(
+                            "WARN    No iteration found for '{}', skipping default iteration",
+                            setting
+                        );
+                    }
+                    None
+                }
+                Err(e) => anyhow::bail!("{}", e),
+            },
+        };
+
+        Ok(Some(IterationFieldCache {
+            field_id: field.id.clone(),
+            default_iteration_id,
+            active: active.clone(),
+            completed: completed.clone(),
+        }))
+    }
+}
+
+/// Turns for `count` new issues among `n` assignees, starting after `last`
+fn rotate(n: usize, last: Option<usize>, count: usize) -> Vec<usize> {
+    let start = last.map_or(0, |i| i + 1);
+    (0..count).map(|k| (start + k) % n).collect()
+}
+
+/// For each of `count` new issues, whoever has the fewest open issues so far
+///
+/// Ties go to the earlier assignee in the list.
+fn least_loaded(mut open: Vec<u64>, count: usize) -> Vec<usize> {
+    (0..count)
+        .map(|_| {
+            let i = (0..open.len()).min_by_key(|&i| open[i]).expect("at least one assignee");
+            open[i] += 1;
+            i
+        })
+        .collect()
+}
+
+/// Whether pushes update the ticket's issue body (`manage-body`, else
+/// `[sync] manage_body`)
+pub fn manages_body(ticket: &Ticket, config: &SyncConfig) -> bool {
+    ticket.manage_body.unwrap_or(config.manage_body)
+}
+
+/// GitHub login a ticket's issue should be assigned to: the ticket's
+/// `assignee`, mapped through `[mapping.mentions]`, else `[github] assignee`
+///
+/// None leaves assignees alone, as for issues spread over `assignees` and
+/// for assignees that can't be logins, like "Jane Doe".
+pub fn desired_assignee(ticket: &Ticket, config: &Config) -> Option<String> {
+    let Some(name) = &ticket.assignee else {
+        return config.github.assignee.clone();
+    };
+    let handle = name.trim().trim_start_matches('@');
+    let login = config
+        .mapping
+        .mentions
+        .iter()
+        .find(|(h, _)| h.trim_start_matches('@').eq_ignore_ascii_case(handle))
+        .map_or(handle, |(_, login)| login.trim_start_matches('@'));
+    (!login.is_empty() && !login.contains(char::is_whitespace)).then(|| login.to_string())
+}
+
+/// Whether an issue is assigned to someone other than just the ticket's
+/// desired assignee, when ttr manages assignees
+pub fn assignees_differ(ticket: &Ticket, config: &Config, existing: &ExistingIssue) -> bool {
+    if !config.sync.manage_assignees {
+        return false;
+    }
+    let Some(login) = desired_assignee(ticket, config) else {
+        return false;
+    };
+    existing.assignees.len() != 1 || !existing.assignees[0].login.eq_ignore_ascii_case(&login)
+}
+
+/// Whether an issue counts as ttr's: it carries `[github] managed_label`,
+/// or no managed label is configured
+pub fn is_managed(issue: &ExistingIssue, config: &Config) -> bool {
+    config
+        .github
+        .managed_label
+        .as_ref()
+        .is_none_or(|label| issue.labels.iter().any(|l| l.eq_ignore_ascii_case(label)))
+}
+
+/// Label put on issues closed as duplicates
+pub const DUPLICATE_LABEL: &str = "duplicate";
+
+/// Labels a new issue gets: its tags, if synced, its type label when the
+/// repo has no issue types, and `duplicate` for duplicate tickets
+pub fn issue_labels(ticket: &Ticket, config: &LabelsConfig, has_issue_types: bool) -> Vec<String> {
+    let tags = if config.sync_tags { ticket.tags.clone() } else { Vec::new() };
+    let type_label = config
+        .type_label
+        .as_ref()
+        .filter(|_| !has_issue_types)
+        .map(|format| format.replace("{type}", &ticket.ticket_type));
+    let duplicate = ticket.duplicate_of.as_ref().map(|_| DUPLICATE_LABEL.to_string());
+
+    let mut names: Vec<String> = Vec::new();
+    for name in tags.into_iter().chain(type_label).chain(duplicate) {
+        if !names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Resolve an iteration setting (`@current`, `@next`, `@current+N`, `@none`,
+/// or a name) to an active iteration ID
+pub fn resolve_iteration_spec(spec: &str, active: &[IterationOption]) -> Result<Option<String>, String> {
+    let offset = match spec {
+        "@none" => return Ok(None),
+        "@current" => Some(0),
+        "@next" => Some(1),
+        _ => match spec.strip_prefix("@current+") {
+            Some(n) => match n.parse::<usize>() {
+                Ok(n) => Some(n),
+                Err(_) => return Err(format!("Invalid iteration specifier '{}'", spec)),
+            },
+            None if spec.starts_with('@') => {
+                return Err(format!(
+                    "Unknown iteration specifier '{}'.\nExpected @current, @next, @current+N, @none, or an iteration name",
+                    spec
+                ));
+            }
+            None => None,
+        },
+    };
+
+    // The API returns active iterations in start order, current first
+    if let Some(offset) = offset {
+        return Ok(active.get(offset).map(|i| i.id.clone()));
+    }
+
+    // Find iteration by name
+    let name_lower = spec.to_lowercase();
+    match active.iter().find(|i| i.title.to_lowercase() == name_lower) {
+        Some(i) => Ok(Some(i.id.clone())),
+        None => {
+            let available: Vec<&str> = active.iter().map(|i| i.title.as_str()).collect();
+            Err(format!(
+                "Iteration '{}' not found.\nAvailable active iterations: {:?}",
+                spec, available
+            ))
+        }
+    }
+}
+
+/// Resolve which iteration a ticket belongs in
+///
+/// Precedence: the ticket's `iteration:` name or specifier, then the iteration whose date
+/// range contains the ticket's `due` date, then the configured default.
+fn resolve_ticket_iteration(
+    ticket_id: &str,
+    iteration: Option<&str>,
+    due: Option<&str>,
+    cache: &IterationFieldCache,
+) -> Option<String> {
+    if let Some(name) = iteration {
+        if name.starts_with('@') {
+            match resolve_iteration_spec(name, &cache.active) {
+                Ok(id) => return id,
+                Err(e) => eprintln!("WARN    {} {}", ticket_id, e.replace('\n', " ")),
+            }
+        } else {
+            let name_lower = name.to_lowercase();
+            match cache.all().find(|i| i.title.to_lowercase() == name_lower) {
+                Some(i) => return Some(i.id.clone()),
+                None => {
+                    eprintln!("WARN    {} iteration '{}' not found in project", ticket_id, name);
+                }
+            }
+        }
+    }
+
+    if let Some(due) = due {
+        // Accept plain dates as well as full timestamps
+        match NaiveDate::parse_from_str(due.get(..10).unwrap_or(due), "%Y-%m-%d") {
+            Ok(date) => {
+                if let Some(i) = cache.all().find(|i| i.contains_date(date)) {
+                    return Some(i.id.clone());
+                }
+            }
+            Err(_) => {
+                eprintln!("WARN    {} has invalid due date '{}'", ticket_id, due);
+            }
+        }
+    }
+
+    cache.default_iteration_id.clone()
+}
+
+/// Find the pull request whose head branch names a ticket
+///
+/// The ticket ID must appear as a whole segment of the branch name, e.g.
+/// `feature/ttr-0042-login`. Open PRs win over closed or merged ones.
+pub fn pull_request_for<'a>(prs: &'a [PullRequestInfo], ticket_id: &str) -> Option<&'a PullRequestInfo> {
+    let matching = prs.iter().filter(|pr| branch_names_ticket(&pr.head_ref, ticket_id));
+    let mut first = None;
+    for pr in matching {
+        if pr.state == "OPEN" {
+            return Some(pr);
+        }
+        first.get_or_insert(pr);
+    }
+    first
+}
+
+fn branch_names_ticket(branch: &str, ticket_id: &str) -> bool {
+    let branch = branch.to_lowercase();
+    let id = ticket_id.to_lowercase();
+    branch.match_indices(&id).any(|(start, _)| {
+        let before = branch[..start].chars().next_back();
+        let after = branch[start + id.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric()) && !after.is_some_and(|c| c.is_alphanumeric())
+    })
+}
+
+/// Append "Closes #N" to a PR body, or None if it already references the issue
+pub fn with_closing_keyword(body: &str, issue_number: u64) -> Option<String> {
+    let reference = format!("#{}", issue_number);
+    let lower = body.to_lowercase();
+    let already = CLOSING_KEYWORDS.iter().any(|kw| {
+        lower.match_indices(&format!("{} {}", kw, reference)).any(|(start, m)| {
+            !lower[start + m.len()..].starts_with(|c: char| c.is_ascii_digit())
+        })
+    });
+    if already {
+        return None;
+    }
+
+    let trimmed = body.trim_end();
+    if trimmed.is_empty() {
+        Some(format!("Closes {}", reference))
+    } else {
+        Some(format!("{}\n\nCloses {}", trimmed, reference))
+    }
+}
+
+/// Format an issue title from the `title_format` template
+///
+/// A ticket title that already has the template applied is used as-is, so
+/// formatting is stable across round trips.
+pub fn format_title(format: Option<&str>, ticket_id: &str, title: &str) -> String {
+    let Some(format) = format else {
+        return title.to_string();
+    };
+    let title = parse_title(Some(format), ticket_id, title).unwrap_or(title);
+    format.replace("{id}", ticket_id).replace("{title}", title)
+}
+
+/// Issue title for a ticket: `title_format` applied, then the type's
+/// `[mapping.emoji]` badge in front
+///
+/// Only the type's own badge is taken off a ticket title that already has
+/// it, so other emoji in titles are left alone.
+pub fn issue_title(config: &Config, ticket: &Ticket) -> String {
+    let badge = config.mapping.emoji.get(&ticket.ticket_type).filter(|badge| !badge.is_empty());
+    let title = badge
+        .and_then(|badge| ticket.title.strip_prefix(badge.as_str())?.strip_prefix(' '))
+        .unwrap_or(&ticket.title);
+    let title = format_title(config.sync.title_format.as_deref(), &ticket.id, title);
+    match badge {
+        Some(badge) => format!("{} {}", badge, title),
+        None => title,
+    }
+}
+
+/// An issue title without the `[mapping.emoji]` badge in front, if it has one
+///
+/// Any type's badge is stripped, so an issue whose ticket changed type
+/// still reads back as the same title. Longer badges are tried first, in
+/// case one starts with another.
+pub fn strip_badge<'a>(emoji: &HashMap<String, String>, title: &'a str) -> &'a str {
+    let mut badges: Vec<&str> = emoji.values().map(String::as_str).filter(|badge| !badge.is_empty()).collect();
+    badges.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    badges
+        .into_iter()
+        .find_map(|badge| title.strip_prefix(badge)?.strip_prefix(' '))
+        .unwrap_or(title)
+}
+
+/// Recover the ticket title from an issue title made with `title_format`
+///
+/// Returns None if the issue title doesn't match the template.
+pub fn parse_title<'a>(format: Option<&str>, ticket_id: &str, issue_title: &'a str) -> Option<&'a str> {
+    let Some(format) = format else {
+        return Some(issue_title);
+    };
+    let (prefix, suffix) = format.split_once("{title}")?;
+    let prefix = prefix.replace("{id}", ticket_id);
+    let suffix = suffix.replace("{id}", ticket_id);
+    issue_title
+        .strip_prefix(prefix.as_str())?
+        .strip_suffix(suffix.as_str())
+}
+
+/// Format the issue body with marker and content (public for testing)
+pub fn format_issue_body(ticket_id: &str, ticket_body: &str) -> String {
+    format_issue_body_with_deps(ticket_id, ticket_body, &[], &HashMap::new())
+}
+
+/// Format the issue body with marker and footer placed per `[sync]`
+///
+/// `footer_url` links the footer, see `ticket_file_url`.
+pub fn format_issue_body_with_config(
+    ticket_id: &str,
+    ticket_body: &str,
+    sync: &SyncConfig,
+    footer_url: Option<&str>,
+) -> String {
+    format_issue_body_with_sections(ticket_id, ticket_body, &[], sync, footer_url)
+}
+
+/// URL prefix for files on a branch of the repo
+pub fn blob_url(owner: &str, repo: &str, branch: &str) -> String {
+    format!("https://github.com/{}/{}/blob/{}", owner, repo, branch)
+}
+
+/// URL prefix the footer links ticket files under, if footer links are on
+///
+/// `[sync] footer_branch` wins over the repo's default branch.
+pub fn footer_blob_base(sync: &SyncConfig, owner: &str, repo: &str, default_branch: Option<&str>) -> Option<String> {
+    if !sync.footer || !sync.footer_link {
+        return None;
+    }
+    let branch = sync.footer_branch.as_deref().or(default_branch)?;
+    Some(blob_url(owner, repo, branch))
+}
+
+/// URL of a ticket's file, assuming its directory sits at the repo root
+pub fn ticket_file_url(blob_base: &str, ticket: &Ticket) -> String {
+    let mut parts = ticket.repo_dirs();
+    if let Some(name) = ticket.path.file_name() {
+        parts.push(name.to_string_lossy().to_string());
+    }
+    format!("{}/{}", blob_base, parts.join("/"))
+}
+
+/// Format the issue body with marker, content, and dependency references
+pub fn format_issue_body_with_deps(
+    ticket_id: &str,
+    ticket_body: &str,
+    deps: &[String],
+    ticket_to_issue: &HashMap<String, u64>,
+) -> String {
+    let mut sections = Vec::new();
+    // Add dependencies section if there are any
+    if !deps.is_empty() {
+        sections.push(format_dependencies_section(deps, ticket_to_issue, &HashMap::new()));
+    }
+    format_issue_body_with_sections(ticket_id, ticket_body, &sections, &SyncConfig::default(), None)
+}
+
+/// Format the issue body with marker, content, trailing sections, and footer
+///
+/// The marker carries a hash of everything else in the body, see `body_matches`.
+fn format_issue_body_with_sections(
+    ticket_id: &str,
+    ticket_body: &str,
+    sections: &[String],
+    sync: &SyncConfig,
+    footer_url: Option<&str>,
+) -> String {
+    let mut body = ticket_body.to_string();
+
+    for section in sections {
+        body.push_str("\n\n---\n");
+        body.push_str(section);
+    }
+
+    if sync.footer {
+        body.push_str("\n\n---\n");
+        let text = sync.footer_text.replace("{id}", ticket_id);
+        match footer_url {
+            Some(url) => body.push_str(&format!("<sub>[{}]({})</sub>", text, url)),
+            None => body.push_str(&format!("<sub>{}</sub>", text)),
+        }
+    }
+
+    let marker = format_marker(ticket_id, &content_hash(managed_part(&body)));
+    match sync.marker_position {
+        MarkerPosition::Top => format!("{}\n\n{}", marker, body),
+        MarkerPosition::Bottom => format!("{}\n\n{}", body, marker),
+    }
+}
+
+/// Current issue body marker format; older markers are still recognized
+pub const MARKER_VERSION: u32 = 2;
+
+/// The marker comment identifying an issue's ticket
+fn format_marker(ticket_id: &str, hash: &str) -> String {
+    format!("<!-- ttr:v{} ticket:{} hash:{} -->", MARKER_VERSION, ticket_id, hash)
+}
+
+/// Short hash of issue body content, insensitive to line endings and
+/// trailing whitespace so GitHub's normalization doesn't register as a change
+pub fn content_hash(content: &str) -> String {
+    // FNV-1a: stable across Rust versions, unlike `DefaultHasher`
+    let mut hash: u32 = 0x811c9dc5;
+    for line in content.trim().lines() {
+        for byte in line.trim_end().bytes().chain(std::iter::once(b'\n')) {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x01000193);
+        }
+    }
+    format!("{:08x}", hash)
+}
+
+/// Format the dependencies section for the issue body
+fn format_dependencies_section(
+    deps: &[String],
+    ticket_to_issue: &HashMap<String, u64>,
+    elsewhere: &HashMap<String, String>,
+) -> String {
+    format!("**Depends on:** {}", format_ticket_refs(deps, ticket_to_issue, elsewhere))
+}
+
+/// Format the section listing tickets that depend on this one
+fn format_blocks_section(
+    dependents: &[String],
+    ticket_to_issue: &HashMap<String, u64>,
+    elsewhere: &HashMap<String, String>,
+) -> String {
+    format!("**Blocks:** {}", format_ticket_refs(dependents, ticket_to_issue, elsewhere))
+}
+
+/// Links in both directions, since `links` is symmetric even when only one
+/// side lists it
+fn symmetric_links(tickets: &[Ticket]) -> HashMap<String, Vec<String>> {
+    let mut related: HashMap<String, Vec<String>> = HashMap::new();
+    let mut add = |from: &str, to: &str| {
+        let entry = related.entry(from.to_string()).or_default();
+        if from != to && !entry.iter().any(|id| id == to) {
+            entry.push(to.to_string());
+        }
+    };
+    for ticket in tickets {
+        for link in &ticket.links {
+            add(&ticket.id, link);
+            add(link, &ticket.id);
+        }
+    }
+    related.retain(|_, ids| !ids.is_empty());
+    related
+}
+
+/// Issue references for ticket IDs, noting those not yet synced
+///
+/// Issues transferred to another repository are referenced as `owner/name#N`.
+fn format_ticket_refs(
+    ids: &[String],
+    ticket_to_issue: &HashMap<String, u64>,
+    elsewhere: &HashMap<String, String>,
+) -> String {
+    let refs: Vec<String> = ids
+        .iter()
+        .map(|id| {
+            if let Some(issue_num) = ticket_to_issue.get(id) {
+                format!("#{}", issue_num)
+            } else if let Some(issue_ref) = elsewhere.get(id) {
+                issue_ref.clone()
+            } else {
+                format!("`{}` (not synced)", id)
+            }
+        })
+        .collect();
+    refs.join(", ")
+}
+
+/// Replace mentions of synced ticket IDs with links to their issues
+///
+/// Code spans, fenced code blocks, and IDs that are part of a path or URL
+/// are left alone, as are IDs that aren't synced.
+fn link_ticket_mentions(
+    body: &str,
+    ticket_to_issue: &HashMap<String, u64>,
+    link: impl Fn(u64) -> String,
+) -> String {
+    let is_id_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.');
+    let mut out = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.split('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if in_fence || trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            out.push(line.to_string());
+            continue;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut result = String::with_capacity(line.len());
+        let mut in_code = false;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '`' {
+                in_code = !in_code;
+            }
+            if in_code || !is_id_char(c) {
+                result.push(c);
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            while i < chars.len() && is_id_char(chars[i]) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            // Sentence punctuation isn't part of the ID
+            let id = word.trim_end_matches('.');
+            let before = start.checked_sub(1).map(|j| chars[j]);
+            let after = chars.get(start + id.chars().count()).copied().filter(|c| *c != '.');
+            let in_path = [before, after].iter().flatten().any(|c| matches!(c, '/' | '#' | '@' | ':'));
+
+            match ticket_to_issue.get(id) {
+                Some(number) if !in_path => {
+                    result.push_str(&link(*number));
+                    result.push_str(&word[id.len()..]);
+                }
+                _ => result.push_str(&word),
+            }
+        }
+        out.push(result);
+    }
+
+    out.join("\n")
+}
+
+/// Rewrite `@handle` mentions to the GitHub logins they map to
+///
+/// Handles match case-insensitively. Unmapped handles are left as written,
+/// or put in a code span with `escape_unmapped` so they ping no one. Code,
+/// email addresses, and team mentions (`@org/team`) are left alone.
+fn map_user_mentions(body: &str, mentions: &HashMap<String, String>, escape_unmapped: bool) -> String {
+    let is_handle_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.');
+    let mentions: HashMap<String, &str> = mentions
+        .iter()
+        .map(|(handle, login)| (handle.trim_start_matches('@').to_lowercase(), login.trim_start_matches('@')))
+        .collect();
+    let mut out = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.split('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if in_fence || trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            out.push(line.to_string());
+            continue;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut result = String::with_capacity(line.len());
+        let mut in_code = false;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '`' {
+                in_code = !in_code;
+            }
+            let after_word = i > 0 && is_handle_char(chars[i - 1]);
+            if in_code || c != '@' || after_word {
+                result.push(c);
+                i += 1;
+                continue;
+            }
 
-    /// Format the issue body with marker, content, and dependencies
-    fn format_issue_body(&self, ticket: &Ticket) -> String {
-        format_issue_body_with_deps(&ticket.id, &ticket.body, &ticket.deps, &self.ticket_to_issue)
+            let start = i + 1;
+            i = start;
+            while i < chars.len() && is_handle_char(chars[i]) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            // Sentence punctuation isn't part of the handle
+            let handle = word.trim_end_matches('.');
+            let rest = &word[handle.len()..];
+            let team = chars.get(start + handle.chars().count()) == Some(&'/');
+
+            match mentions.get(&handle.to_lowercase()) {
+                _ if handle.is_empty() || team => result.push_str(&format!("@{}", word)),
+                Some(login) => result.push_str(&format!("@{}{}", login, rest)),
+                None if escape_unmapped => result.push_str(&format!("`@{}`{}", handle, rest)),
+                None => result.push_str(&format!("@{}", word)),
+            }
+        }
+        out.push(result);
     }
 
-    /// Link sub-issues based on ticket parent relationships
-    /// 
-    /// This runs after all creates/updates, using a two-pass approach:
-    /// 1. Build a map of ticket_id → issue_node_id from existing issues and newly created ones
-    /// 2. For each ticket with a parent, link child to parent as a sub-issue
-    async fn link_sub_issues(
-        &self,
-        tickets: &[Ticket],
-        all_tickets: &[Ticket],
-        results: &[(usize, SyncResult)],
-        existing_issues: &HashMap<u64, ExistingIssue>,
-    ) {
-        // Build ticket_id → issue_node_id map
-        let mut ticket_to_node_id: HashMap<String, String> = HashMap::new();
+    out.join("\n")
+}
 
-        // Add from existing issues (looked up at start of sync)
-        for ticket in all_tickets {
-            if let Some(issue_num) = ticket.github_issue_number() {
-                if let Some(existing) = existing_issues.get(&issue_num) {
-                    ticket_to_node_id.insert(ticket.id.clone(), existing.id.clone());
-                }
-            }
+/// Rewrite relative markdown link targets to URLs under `blob_base`
+///
+/// `dir` is the ticket's directory relative to the repository root. URLs,
+/// anchors, code, and links climbing above the root are left alone.
+fn absolutize_links(body: &str, blob_base: &str, dir: &[String]) -> String {
+    let mut out = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.split('\n') {
+        let trimmed = line.trim_start();
+        let is_fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+        if is_fence {
+            in_fence = !in_fence;
+        }
+        if in_fence || is_fence {
+            out.push(line.to_string());
+            continue;
         }
 
-        // Add from newly created issues in this sync
-        for (idx, result) in results {
-            if let SyncResult::Created { issue_id, .. } = result {
-                let ticket = &tickets[*idx];
-                ticket_to_node_id.insert(ticket.id.clone(), issue_id.clone());
+        let mut result = String::with_capacity(line.len());
+        let mut rest = line;
+        while let Some(pos) = rest.find("](") {
+            let (before, after) = rest.split_at(pos + 2);
+            result.push_str(before);
+            let in_code = result.matches('`').count() % 2 == 1;
+            let Some(end) = after.find(')') else {
+                rest = after;
+                break;
+            };
+            let target = &after[..end];
+            match resolve_link(target, blob_base, dir) {
+                Some(url) if !in_code => result.push_str(&url),
+                _ => result.push_str(target),
             }
+            rest = &after[end..];
         }
+        result.push_str(rest);
+        out.push(result);
+    }
 
-        // Collect sub-issue links to create
-        let mut links: Vec<(String, SubIssueLink)> = Vec::new(); // (child_ticket_id, link)
+    out.join("\n")
+}
 
-        for ticket in tickets {
-            if let Some(ref parent_id) = ticket.parent {
-                // Look up both parent and child node IDs
-                let parent_node_id = ticket_to_node_id.get(parent_id);
-                let child_node_id = ticket_to_node_id.get(&ticket.id);
+/// Absolute URL for a relative link target, keeping any anchor and title
+fn resolve_link(target: &str, blob_base: &str, dir: &[String]) -> Option<String> {
+    let (path, title) = match target.find(' ') {
+        Some(i) => target.split_at(i),
+        None => (target, ""),
+    };
+    let (path, anchor) = match path.find(['#', '?']) {
+        Some(i) => path.split_at(i),
+        None => (path, ""),
+    };
+    if path.is_empty() || path.contains("://") || path.starts_with("mailto:") {
+        return None;
+    }
 
-                match (parent_node_id, child_node_id) {
-                    (Some(parent_id), Some(child_id)) => {
-                        links.push((
-                            ticket.id.clone(),
-                            SubIssueLink {
-                                parent_issue_id: parent_id.clone(),
-                                child_issue_id: child_id.clone(),
-                            },
-                        ));
-                    }
-                    (None, _) => {
-                        // Parent not synced - skip silently, it will link on next push
-                    }
-                    (_, None) => {
-                        // Child not synced - shouldn't happen since we just synced it
-                    }
-                }
+    let mut parts: Vec<&str> = if path.starts_with('/') {
+        Vec::new()
+    } else {
+        dir.iter().map(|s| s.as_str()).collect()
+    };
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
             }
+            _ => parts.push(part),
         }
+    }
+    Some(format!("{}/{}{}{}", blob_base, parts.join("/"), anchor, title))
+}
 
-        if links.is_empty() {
-            return;
+/// Spent hours per ticket, each ancestor's total including its descendants'
+///
+/// Tickets without hours anywhere below them are left out. A `parent` cycle
+/// stops the climb where it comes back around.
+fn spent_totals(tickets: &[Ticket]) -> HashMap<String, f64> {
+    let parents: HashMap<&str, &str> = tickets
+        .iter()
+        .filter_map(|t| Some((t.id.as_str(), t.parent.as_deref()?)))
+        .collect();
+    let mut totals: HashMap<String, f64> = HashMap::new();
+    for ticket in tickets {
+        let Some(hours) = ticket.spent_hours else { continue };
+        let mut seen = HashSet::new();
+        let mut id = Some(ticket.id.as_str());
+        while let Some(current) = id.filter(|id| seen.insert(*id)) {
+            *totals.entry(current.to_string()).or_default() += hours;
+            id = parents.get(current).copied();
         }
+    }
+    totals
+}
 
-        // Batch link sub-issues (single GraphQL mutation)
-        let sub_issue_links: Vec<SubIssueLink> = links.iter().map(|(_, link)| link.clone()).collect();
-        
-        match self.client.add_sub_issues_batch(&sub_issue_links).await {
-            Ok(results) => {
-                println!();
-                for ((child_id, link), result) in links.iter().zip(results) {
-                    // Find parent ticket ID for display
-                    let parent_ticket_id = all_tickets
-                        .iter()
-                        .find(|t| ticket_to_node_id.get(&t.id) == Some(&link.parent_issue_id))
-                        .map(|t| t.id.as_str())
-                        .unwrap_or("?");
-
-                    match result {
-                        Ok(()) => {
-                            println!("LINK    {} → {} (sub-issue)", child_id, parent_ticket_id);
-                        }
-                        Err(e) => {
-                            eprintln!("WARN    {} sub-issue link failed: {}", child_id, e);
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("\nWARN    sub-issue batch link failed: {}", e);
+/// Invert the deps graph: ticket ID -> IDs of the tickets depending on it
+fn reverse_dependencies(tickets: &[Ticket]) -> HashMap<String, Vec<String>> {
+    let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+    for ticket in tickets {
+        for dep in &ticket.deps {
+            let entry = dependents.entry(dep.clone()).or_default();
+            if !entry.contains(&ticket.id) {
+                entry.push(ticket.id.clone());
             }
         }
     }
+    dependents
+}
 
-    /// Setup project fields cache by fetching and validating field mappings
-    async fn setup_project_fields(
-        client: &GitHubClient,
-        project: &ProjectInfo,
-        config: &Config,
-    ) -> Result<Option<ProjectFieldsCache>> {
-        // Skip if no status mappings and no iteration configured
-        if config.project.status.is_empty() && config.project.iteration.is_none() {
-            return Ok(None);
+/// Issue node IDs by number, including issues created by this push
+fn issue_node_ids<'a>(
+    existing_issues: &'a HashMap<u64, ExistingIssue>,
+    results: &'a [(usize, SyncResult)],
+) -> HashMap<u64, &'a str> {
+    let mut ids: HashMap<u64, &str> = existing_issues.iter().map(|(n, issue)| (*n, issue.id.as_str())).collect();
+    for (_, result) in results {
+        if let SyncResult::Created { issue_id, issue_number, .. } = result {
+            ids.insert(*issue_number, issue_id);
         }
+    }
+    ids
+}
 
-        // Fetch project fields
-        let fields = client.get_project_fields(&project.id).await?;
-
-        // Setup status field cache
-        let status_cache = if !config.project.status.is_empty() {
-            Self::setup_status_field(&fields, config)?
-        } else {
-            None
-        };
+/// Pin and unpin changes for a push
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PinPlan<'a> {
+    pin: Vec<(&'a str, u64)>,
+    unpin: Vec<(&'a str, u64)>,
+    /// Wanted pins that are already pinned
+    keep: Vec<&'a str>,
+    /// Recorded pins that are no longer pinned on GitHub
+    forget: Vec<&'a str>,
+    /// Wanted pins past GitHub's limit
+    over_limit: Vec<(&'a str, u64)>,
+}
 
-        // Setup iteration field cache
-        let iteration_cache = if config.project.iteration.is_some() {
-            Self::setup_iteration_field(&fields, config)?
+/// Work out pins from the wanted and unwanted (ticket ID, issue number)
+/// pairs and the issues pinned now, unpinning first to make room
+fn plan_pins<'a>(wanted: &[(&'a str, u64)], unwanted: &[(&'a str, u64)], pinned_now: &[u64]) -> PinPlan<'a> {
+    let mut plan = PinPlan::default();
+    for &(id, number) in unwanted {
+        if pinned_now.contains(&number) {
+            plan.unpin.push((id, number));
         } else {
-            None
-        };
-
-        if status_cache.is_some() || iteration_cache.is_some() {
-            Ok(Some(ProjectFieldsCache {
-                status: status_cache,
-                iteration: iteration_cache,
-            }))
+            plan.forget.push(id);
+        }
+    }
+    let mut slots = MAX_PINNED_ISSUES.saturating_sub(pinned_now.len() - plan.unpin.len());
+    for &(id, number) in wanted {
+        if pinned_now.contains(&number) {
+            plan.keep.push(id);
+        } else if slots > 0 {
+            plan.pin.push((id, number));
+            slots -= 1;
         } else {
-            Ok(None)
+            plan.over_limit.push((id, number));
         }
     }
+    plan
+}
 
-    /// Setup status field cache, validating options exist
-    fn setup_status_field(
-        fields: &[ProjectFieldInfo],
-        config: &Config,
-    ) -> Result<Option<StatusFieldCache>> {
-        // Find the status field by name (case-insensitive)
-        let status_field_name = config.project.status_field.to_lowercase();
-        let status_field = fields.iter().find(|f| f.name.to_lowercase() == status_field_name);
+/// Format dependencies as a task list, checking off closed ones
+fn format_dependencies_tasklist(
+    deps: &[String],
+    ticket_to_issue: &HashMap<String, u64>,
+    elsewhere: &HashMap<String, String>,
+    closed: &HashSet<String>,
+) -> String {
+    format_tasklist("**Depends on:**", deps, ticket_to_issue, elsewhere, closed)
+}
 
-        let field = match status_field {
-            Some(f) => f,
-            None => {
-                eprintln!(
-                    "WARN    Project field '{}' not found, skipping status sync",
-                    config.project.status_field
-                );
-                return Ok(None);
-            }
-        };
+/// Format tickets as a task list under a heading, checking off closed ones
+fn format_tasklist(
+    heading: &str,
+    ids: &[String],
+    ticket_to_issue: &HashMap<String, u64>,
+    elsewhere: &HashMap<String, String>,
+    closed: &HashSet<String>,
+) -> String {
+    let mut section = heading.to_string();
+    for id in ids {
+        let check = if closed.contains(id) { "x" } else { " " };
+        match (ticket_to_issue.get(id), elsewhere.get(id)) {
+            (Some(issue_num), _) => section.push_str(&format!("\n- [{}] #{}", check, issue_num)),
+            (None, Some(issue_ref)) => section.push_str(&format!("\n- [{}] {}", check, issue_ref)),
+            (None, None) => section.push_str(&format!("\n- [{}] `{}` (not synced)", check, id)),
+        }
+    }
+    section
+}
 
-        // Get options from field
-        let options = match &field.field_type {
-            ProjectFieldType::SingleSelect { options } => options,
-            _ => {
-                eprintln!(
-                    "WARN    Project field '{}' is not a single-select field, skipping status sync",
-                    config.project.status_field
-                );
-                return Ok(None);
-            }
-        };
+/// Fields of an issue body marker
+///
+/// v1 is `<!-- ticket:ID -->`, optionally with `hash:HASH`; v2 adds a
+/// leading `ttr:v2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marker<'a> {
+    pub version: u32,
+    pub ticket: &'a str,
+    pub hash: Option<&'a str>,
+}
 
-        // Build status -> option ID mapping, validating each
-        let mut status_to_option = HashMap::new();
-        for (ticket_status, project_option_name) in &config.project.status {
-            let option_name_lower = project_option_name.to_lowercase();
-            let option = options.iter().find(|o| o.name.to_lowercase() == option_name_lower);
+/// Parse the first HTML comment in a body that names a ticket
+pub fn parse_marker(body: &str) -> Option<Marker<'_>> {
+    find_marker(body).map(|(marker, _)| marker)
+}
 
-            match option {
-                Some(o) => {
-                    status_to_option.insert(ticket_status.to_lowercase(), o.id.clone());
-                }
-                None => {
-                    let available: Vec<&str> = options.iter().map(|o| o.name.as_str()).collect();
-                    anyhow::bail!(
-                        "Project status option '{}' (for ticket status '{}') not found.\nAvailable options: {:?}",
-                        project_option_name,
-                        ticket_status,
-                        available
-                    );
-                }
+/// The marker and the byte range of its comment
+fn find_marker(body: &str) -> Option<(Marker<'_>, std::ops::Range<usize>)> {
+    let mut offset = 0;
+    while let Some(start) = body[offset..].find("<!--").map(|i| offset + i) {
+        let end = start + 4 + body[start + 4..].find("-->")? + 3;
+        let mut marker = Marker {
+            version: 1,
+            ticket: "",
+            hash: None,
+        };
+        for field in body[start + 4..end - 3].split_whitespace() {
+            if let Some(version) = field.strip_prefix("ttr:v") {
+                marker.version = version.parse().ok()?;
+            } else if let Some(id) = field.strip_prefix("ticket:") {
+                marker.ticket = id;
+            } else if let Some(hash) = field.strip_prefix("hash:") {
+                marker.hash = Some(hash);
             }
         }
-
-        Ok(Some(StatusFieldCache {
-            field_id: field.id.clone(),
-            status_to_option,
-        }))
+        if !marker.ticket.is_empty() {
+            return Some((marker, start..end));
+        }
+        offset = end;
     }
+    None
+}
 
-    /// Setup iteration field cache, finding current iteration if @current
-    fn setup_iteration_field(
-        fields: &[ProjectFieldInfo],
-        config: &Config,
-    ) -> Result<Option<IterationFieldCache>> {
-        let iteration_setting = match &config.project.iteration {
-            Some(s) => s,
-            None => return Ok(None),
-        };
+/// Extract ticket ID from issue body marker
+pub fn extract_ticket_marker(body: &str) -> Option<&str> {
+    parse_marker(body).map(|m| m.ticket)
+}
 
-        // Find the iteration field by name (case-insensitive)
-        let iteration_field_name = config.project.iteration_field.to_lowercase();
-        let iteration_field = fields.iter().find(|f| f.name.to_lowercase() == iteration_field_name);
+/// Content hash recorded in the issue body marker, if any
+pub fn marker_hash(body: &str) -> Option<&str> {
+    parse_marker(body).and_then(|m| m.hash)
+}
 
-        let field = match iteration_field {
-            Some(f) => f,
-            None => {
-                eprintln!(
-                    "WARN    Project field '{}' not found, skipping iteration sync",
-                    config.project.iteration_field
-                );
-                return Ok(None);
-            }
-        };
+/// Issue body content around the marker, which may be at the top or bottom,
+/// up to any manual section
+fn marker_content(body: &str) -> String {
+    let content = match find_marker(body) {
+        Some((_, range)) => format!("{}{}", &body[..range.start], &body[range.end..]),
+        None => body.to_string(),
+    };
+    managed_part(&content).to_string()
+}
 
-        // Get iterations from field
-        let (active, _completed) = match &field.field_type {
-            ProjectFieldType::Iteration { active, completed } => (active, completed),
-            _ => {
-                eprintln!(
-                    "WARN    Project field '{}' is not an iteration field, skipping iteration sync",
-                    config.project.iteration_field
-                );
-                return Ok(None);
-            }
-        };
+/// Starts the part of an issue body kept as is by updates
+pub const MANUAL_SENTINEL: &str = "<!-- ttr:manual -->";
 
-        // Resolve iteration ID
-        let iteration_id = if iteration_setting == "@current" {
-            // Use first active iteration
-            match active.first() {
-                Some(iter) => iter.id.clone(),
-                None => {
-                    eprintln!("WARN    No active iteration found, skipping iteration sync");
-                    return Ok(None);
-                }
-            }
-        } else {
-            // Find iteration by name
-            let iter_name_lower = iteration_setting.to_lowercase();
-            let iter = active.iter().find(|i| i.title.to_lowercase() == iter_name_lower);
+/// Content before the manual sentinel, which is all ttr compares
+fn managed_part(content: &str) -> &str {
+    content.find(MANUAL_SENTINEL).map_or(content, |i| &content[..i])
+}
 
-            match iter {
-                Some(i) => i.id.clone(),
-                None => {
-                    let available: Vec<&str> = active.iter().map(|i| i.title.as_str()).collect();
-                    anyhow::bail!(
-                        "Iteration '{}' not found.\nAvailable active iterations: {:?}",
-                        iteration_setting,
-                        available
-                    );
-                }
-            }
-        };
+/// The issue's manual section, from the sentinel to the end, without the
+/// marker if it sits below the sentinel
+fn manual_section(body: &str) -> Option<String> {
+    let start = body.find(MANUAL_SENTINEL)?;
+    let section = match find_marker(body) {
+        Some((_, range)) if range.start > start => format!("{}{}", &body[start..range.start], &body[range.end..]),
+        _ => body[start..].to_string(),
+    };
+    Some(section.trim_end().to_string())
+}
 
-        Ok(Some(IterationFieldCache {
-            field_id: field.id.clone(),
-            iteration_id,
-        }))
+/// Carry an issue's manual section over to its new body, ahead of a marker
+/// at the bottom
+///
+/// Bodies whose formatted content has its own sentinel are left alone.
+pub fn keep_manual_section(body: &str, existing: &str) -> String {
+    let Some(manual) = manual_section(existing) else {
+        return body.to_string();
+    };
+    if body.contains(MANUAL_SENTINEL) {
+        return body.to_string();
+    }
+    match find_marker(body) {
+        Some((_, range)) if body[range.end..].trim().is_empty() && !body[..range.start].trim().is_empty() => {
+            format!("{}\n\n{}\n\n{}", body[..range.start].trim_end(), manual, &body[range])
+        }
+        _ => format!("{}\n\n{}", body.trim_end(), manual),
     }
 }
 
-/// Format the issue body with marker and content (public for testing)
-pub fn format_issue_body(ticket_id: &str, ticket_body: &str) -> String {
-    format_issue_body_with_deps(ticket_id, ticket_body, &[], &HashMap::new())
+/// Rewrite an older marker in the current format, leaving the content as is
+///
+/// Returns None if the body has no marker or it's already current.
+pub fn upgrade_marker(body: &str) -> Option<String> {
+    let (marker, range) = find_marker(body)?;
+    if marker.version >= MARKER_VERSION {
+        return None;
+    }
+    let hash = content_hash(&marker_content(body));
+    Some(format!(
+        "{}{}{}",
+        &body[..range.start],
+        format_marker(marker.ticket, &hash),
+        &body[range.end..]
+    ))
 }
 
-/// Format the issue body with marker, content, and dependency references
-pub fn format_issue_body_with_deps(
-    ticket_id: &str,
-    ticket_body: &str,
-    deps: &[String],
-    ticket_to_issue: &HashMap<String, u64>,
-) -> String {
-    let mut body = format!("<!-- ticket:{} -->\n\n", ticket_id);
-    body.push_str(ticket_body);
+/// Whether an issue body carries the marker for a ticket, under its current
+/// or any previous ID
+pub fn has_ticket_marker(body: &str, ticket: &Ticket) -> bool {
+    extract_ticket_marker(body)
+        .is_some_and(|id| id == ticket.id || ticket.previous_ids.iter().any(|prev| prev == id))
+}
 
-    // Add dependencies section if there are any
-    if !deps.is_empty() {
-        body.push_str("\n\n---\n");
-        body.push_str(&format_dependencies_section(deps, ticket_to_issue));
+/// Whether an issue body already has the content ttr would push
+///
+/// With hashed markers on both sides the hashes are compared, after checking
+/// the issue's content still matches its own hash (so edits made on GitHub
+/// are pushed over). Unhashed markers from older versions compare content.
+/// Either way only the content counts, so an outdated marker alone isn't a
+/// change; `ttr migrate-markers` upgrades those.
+///
+/// Oversized bodies are compared as cut down by `split_body`; their marker
+/// hashes the full content, so changes to the part in comments count too.
+pub fn body_matches(existing: &str, expected: &str) -> bool {
+    match (parse_marker(existing), parse_marker(expected)) {
+        (Some(old), Some(new)) => {
+            let existing_hash = content_hash(&marker_content(existing));
+            let (sent, continuation) = split_body(expected);
+            let sent_hash = match (new.hash, continuation.is_empty()) {
+                (Some(hash), true) => hash.to_string(),
+                _ => content_hash(&marker_content(&sent)),
+            };
+            match (old.hash, new.hash) {
+                (Some(old_hash), Some(new_hash)) => old_hash == new_hash && existing_hash == sent_hash,
+                _ => existing_hash == sent_hash,
+            }
+        }
+        _ => existing == expected,
     }
-
-    body.push_str("\n\n---\n");
-    body.push_str(&format!("<sub>Synced from ticket `{}`</sub>", ticket_id));
-    body
 }
 
-/// Format the dependencies section for the issue body
-fn format_dependencies_section(deps: &[String], ticket_to_issue: &HashMap<String, u64>) -> String {
-    let refs: Vec<String> = deps
-        .iter()
-        .map(|dep_id| {
-            if let Some(issue_num) = ticket_to_issue.get(dep_id) {
-                format!("#{}", issue_num)
-            } else {
-                format!("`{}` (not synced)", dep_id)
-            }
-        })
-        .collect();
+/// GitHub's limit on the length of issue and comment bodies, in characters
+pub const MAX_BODY_CHARS: usize = 65_536;
+
+/// Room left under `MAX_BODY_CHARS` for separators
+const BODY_MARGIN: usize = 64;
+
+/// Ends an issue body cut short by `split_body`
+const CONTINUED_NOTE: &str = "*Continued in comments below: GitHub limits issue bodies to 65,536 characters.*";
 
-    format!("**Depends on:** {}", refs.join(", "))
+/// Starts each comment holding part of an oversized body
+const CONTINUATION_PREFIX: &str = "<!-- ttr:continued";
+
+/// Split a formatted issue body too long for GitHub into the issue body and
+/// continuation comments
+///
+/// The issue body is cut at a line break and ends with a note pointing to
+/// the comments. The marker stays in the issue body, where it was. Bodies
+/// within the limit come back whole with no comments.
+pub fn split_body(body: &str) -> (String, Vec<String>) {
+    if body.chars().count() <= MAX_BODY_CHARS {
+        return (body.to_string(), Vec::new());
+    }
+
+    let (marker, ticket, marker_first, content) = match find_marker(body) {
+        Some((marker, range)) => (
+            &body[range.clone()],
+            marker.ticket,
+            body[..range.start].trim().is_empty(),
+            format!("{}{}", &body[..range.start], &body[range.end..]),
+        ),
+        None => ("", "", true, body.to_string()),
+    };
+
+    let budget = MAX_BODY_CHARS - marker.chars().count() - CONTINUED_NOTE.len() - BODY_MARGIN;
+    let (head, mut rest) = split_at_line(content.trim(), budget);
+    let head = format!("{}\n\n{}", head.trim_end(), CONTINUED_NOTE);
+    let main = match (marker.is_empty(), marker_first) {
+        (true, _) => head,
+        (false, true) => format!("{}\n\n{}", marker, head),
+        (false, false) => format!("{}\n\n{}", head, marker),
+    };
+
+    let mut parts = Vec::new();
+    while !rest.trim().is_empty() {
+        let header = format!("{} ticket:{} part:{} -->", CONTINUATION_PREFIX, ticket, parts.len() + 1);
+        let (chunk, remainder) = split_at_line(rest, MAX_BODY_CHARS - header.len() - BODY_MARGIN);
+        parts.push(format!("{}\n\n{}", header, chunk.trim_start_matches('\n').trim_end()));
+        rest = remainder;
+    }
+    (main, parts)
 }
 
-/// Extract ticket ID from issue body marker
-pub fn extract_ticket_marker(body: &str) -> Option<&str> {
-    let start = body.find("<!-- ticket:")?;
-    let after_start = &body[start + 12..];
-    let end = after_start.find(" -->")?;
-    Some(&after_start[..end])
+/// Split after at most `max_chars` characters, at the last paragraph or line
+/// break if one falls in the second half
+fn split_at_line(s: &str, max_chars: usize) -> (&str, &str) {
+    let Some((limit, _)) = s.char_indices().nth(max_chars) else {
+        return (s, "");
+    };
+    let cut = s[..limit]
+        .rfind("\n\n")
+        .or_else(|| s[..limit].rfind('\n'))
+        .filter(|&i| i > limit / 2)
+        .unwrap_or(limit);
+    s.split_at(cut)
 }
 
 /// Resolve issue type ID from ticket type using config mapping and cache
@@ -1156,12 +4854,13 @@ pub fn validate_issue_type_mappings(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ticket::{LoadOptions, TicketDir};
 
     #[test]
     fn test_format_issue_body() {
         let body = format_issue_body("ttr-0001", "This is the description.\n\n## Design\n\nSome design notes.");
         
-        assert!(body.starts_with("<!-- ticket:ttr-0001 -->"));
+        assert!(body.starts_with("<!-- ttr:v2 ticket:ttr-0001 hash:"));
         assert!(body.contains("This is the description."));
         assert!(body.contains("## Design"));
         assert!(body.contains("Some design notes."));
@@ -1173,7 +4872,7 @@ mod tests {
         let body = format_issue_body("test-123", "Content");
         
         // Marker must be at the very start for conflict detection
-        assert!(body.starts_with("<!-- ticket:test-123 -->"));
+        assert!(body.starts_with("<!-- ttr:v2 ticket:test-123 hash:"));
     }
 
     #[test]
@@ -1182,6 +4881,149 @@ mod tests {
         assert_eq!(extract_ticket_marker(body), Some("ttr-0001"));
     }
 
+    #[test]
+    fn test_body_matches_by_hash() {
+        let expected = format_issue_body("ttr-0001", "Line one\nLine two");
+        assert_eq!(extract_ticket_marker(&expected), Some("ttr-0001"));
+        let hash = marker_hash(&expected).unwrap();
+        assert_eq!(hash.len(), 8);
+
+        // GitHub-side whitespace normalization isn't a change
+        let normalized = expected.replace('\n', "\r\n").replace("Line one", "Line one  ");
+        assert!(body_matches(&normalized, &expected));
+
+        // Edits made on GitHub no longer match the marker's hash
+        let edited = expected.replace("Line two", "Line 2");
+        assert!(!body_matches(&edited, &expected));
+
+        // Unhashed markers from older versions compare content
+        let legacy = "<!-- ticket:ttr-0001 -->\n\nLine one";
+        assert_eq!(marker_hash(legacy), None);
+        assert!(!body_matches(legacy, &expected));
+        let legacy = expected.replace(&format!("ttr:v2 ticket:ttr-0001 hash:{}", hash), "ticket:ttr-0001");
+        assert!(body_matches(&legacy, &expected));
+    }
+
+    #[test]
+    fn test_split_oversized_body() {
+        let short = format_issue_body("ttr-1", "Short");
+        assert_eq!(split_body(&short), (short.clone(), Vec::new()));
+
+        let paragraph = format!("{}\n", "word ".repeat(200));
+        let long = format_issue_body("ttr-1", &paragraph.repeat(150));
+        let (main, parts) = split_body(&long);
+        assert!(main.chars().count() <= MAX_BODY_CHARS);
+        assert!(main.starts_with("<!-- ttr:v2 ticket:ttr-1 hash:"));
+        assert!(main.ends_with(CONTINUED_NOTE));
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("<!-- ttr:continued ticket:ttr-1 part:1 -->\n\nword"));
+        assert!(parts.iter().all(|p| p.chars().count() <= MAX_BODY_CHARS));
+        assert!(parts[1].contains("Synced from ticket"));
+
+        // The truncated issue body matches the full expected body
+        assert!(body_matches(&main, &long));
+        let changed = format_issue_body("ttr-1", &format!("{}Tail", paragraph.repeat(150)));
+        assert!(!body_matches(&main, &changed));
+    }
+
+    #[test]
+    fn test_marker_and_footer_placement() {
+        let sync = SyncConfig {
+            footer: false,
+            marker_position: MarkerPosition::Bottom,
+            ..Default::default()
+        };
+        let body = format_issue_body_with_config("ttr-1", "Content", &sync, None);
+        assert!(body.starts_with("Content\n\n<!-- ttr:v2 ticket:ttr-1 hash:"));
+        assert!(!body.contains("Synced from"));
+        assert_eq!(extract_ticket_marker(&body), Some("ttr-1"));
+        assert!(body_matches(&body, &body));
+
+        let sync = SyncConfig {
+            footer_text: "Source: {id}".to_string(),
+            ..Default::default()
+        };
+        let body = format_issue_body_with_config("ttr-1", "Content", &sync, None);
+        assert!(body.ends_with("<sub>Source: ttr-1</sub>"));
+    }
+
+    #[test]
+    fn test_manual_section_survives_updates() {
+        let old = format_issue_body("ttr-1", "Content");
+        let noted = format!("{}\n\n{}\nIncident: https://status.example.com/42\n", old, MANUAL_SENTINEL);
+        // Notes below the sentinel aren't a change
+        assert!(body_matches(&noted, &old));
+
+        let new = keep_manual_section(&format_issue_body("ttr-1", "New content"), &noted);
+        assert!(!body_matches(&noted, &new));
+        assert!(new.ends_with("<!-- ttr:manual -->\nIncident: https://status.example.com/42"));
+        assert!(body_matches(&new, &new));
+
+        // A bottom marker stays at the bottom
+        let sync = SyncConfig {
+            footer: false,
+            marker_position: MarkerPosition::Bottom,
+            ..Default::default()
+        };
+        let old = format_issue_body_with_config("ttr-1", "Content", &sync, None);
+        let noted = format!("{}\n\n{}\nSee screenshot", old, MANUAL_SENTINEL);
+        let new = keep_manual_section(&format_issue_body_with_config("ttr-1", "Edited", &sync, None), &noted);
+        assert!(new.starts_with("Edited\n\n<!-- ttr:manual -->\nSee screenshot\n\n<!-- ttr:v2 ticket:ttr-1 hash:"));
+        assert!(body_matches(&new, &format_issue_body_with_config("ttr-1", "Edited", &sync, None)));
+
+        assert_eq!(keep_manual_section(&old, &old), old);
+    }
+
+    #[test]
+    fn test_footer_links_ticket_file() {
+        let mut sync = SyncConfig::default();
+        let base = footer_blob_base(&sync, "o", "r", Some("main")).unwrap();
+        assert_eq!(base, "https://github.com/o/r/blob/main");
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let tickets_dir = dir.path().join(".tickets");
+        std::fs::create_dir(&tickets_dir).unwrap();
+        std::fs::write(tickets_dir.join("ttr-1.md"), "---\nid: ttr-1\n---\n# One\n").unwrap();
+        let ticket = &Ticket::load_all(&tickets_dir, LoadOptions::default()).unwrap()[0];
+        let url = ticket_file_url(&base, ticket);
+        assert_eq!(url, "https://github.com/o/r/blob/main/.tickets/ttr-1.md");
+        let body = format_issue_body_with_config("ttr-1", "Content", &sync, Some(&url));
+        assert!(body.ends_with(&format!("<sub>[Synced from ticket `ttr-1`]({})</sub>", url)));
+
+        sync.footer_branch = Some("docs".to_string());
+        assert_eq!(
+            footer_blob_base(&sync, "o", "r", Some("main")).as_deref(),
+            Some("https://github.com/o/r/blob/docs")
+        );
+        sync.footer_link = false;
+        assert_eq!(footer_blob_base(&sync, "o", "r", Some("main")), None);
+    }
+
+    #[test]
+    fn test_parse_marker_versions() {
+        let v1 = "<!-- ticket:ttr-1 -->\n\nContent";
+        assert_eq!(
+            parse_marker(v1),
+            Some(Marker { version: 1, ticket: "ttr-1", hash: None })
+        );
+        let v2 = "<!-- note -->\n<!-- ttr:v2 ticket:ttr-1 hash:0a1b2c3d -->\n\nContent";
+        assert_eq!(
+            parse_marker(v2),
+            Some(Marker { version: 2, ticket: "ttr-1", hash: Some("0a1b2c3d") })
+        );
+        assert_eq!(parse_marker("<!-- note -->"), None);
+    }
+
+    #[test]
+    fn test_upgrade_marker() {
+        let upgraded = upgrade_marker("<!-- ticket:ttr-1 -->\n\nContent").unwrap();
+        assert!(upgraded.starts_with("<!-- ttr:v2 ticket:ttr-1 hash:"));
+        assert!(upgraded.ends_with("-->\n\nContent"));
+        assert!(body_matches(&upgraded, &upgraded));
+        assert_eq!(upgrade_marker(&upgraded), None);
+        assert_eq!(upgrade_marker("No marker"), None);
+    }
+
     #[test]
     fn test_extract_ticket_marker_missing() {
         let body = "Some content without marker";
@@ -1196,6 +5038,47 @@ mod tests {
         assert_eq!(extracted, Some(original_id));
     }
 
+    #[test]
+    fn test_format_title_roundtrip() {
+        let format = Some("[{id}] {title}");
+        let title = format_title(format, "ttr-1", "Fix login");
+        assert_eq!(title, "[ttr-1] Fix login");
+        assert_eq!(parse_title(format, "ttr-1", &title), Some("Fix login"));
+        // Already formatted titles aren't prefixed twice
+        assert_eq!(format_title(format, "ttr-1", &title), title);
+        assert_eq!(parse_title(format, "ttr-2", &title), None);
+        assert_eq!(format_title(None, "ttr-1", "Fix login"), "Fix login");
+    }
+
+    #[test]
+    fn test_emoji_badge_roundtrip() {
+        let config = Config::parse(
+            "[github]\nrepo = \"o/r\"\n\n[mapping.emoji]\nbug = \"🐛\"\nfeature = \"✨\"\n\n[sync]\ntitle_format = \"[{id}] {title}\"\n",
+        )
+        .unwrap();
+        let dir = TicketDir::new();
+        let ticket = Ticket::parse(&dir.write("t-1.md", "---\nid: t-1\ntype: bug\n---\n# Crash on save\n")).unwrap();
+
+        let title = issue_title(&config, &ticket);
+        assert_eq!(title, "🐛 [t-1] Crash on save");
+        let emoji = &config.mapping.emoji;
+        assert_eq!(parse_title(Some("[{id}] {title}"), "t-1", strip_badge(emoji, &title)), Some("Crash on save"));
+        // A badge pulled into the ticket title isn't doubled
+        let path = dir.write("t-1.md", "---\nid: t-1\ntype: bug\n---\n# 🐛 Crash on save\n");
+        assert_eq!(issue_title(&config, &Ticket::parse(&path).unwrap()), title);
+        // Other emoji in the title are kept, and types without a badge get none
+        dir.write("t-1.md", "---\nid: t-1\ntype: task\n---\n# ✨ Sparkle\n");
+        assert_eq!(issue_title(&config, &Ticket::parse(&path).unwrap()), "[t-1] ✨ Sparkle");
+
+        // Reading back, the longest matching badge wins whatever the map order
+        let emoji = HashMap::from([
+            ("chore".to_string(), "🧹".to_string()),
+            ("epic".to_string(), "🧹🧹".to_string()),
+        ]);
+        assert_eq!(strip_badge(&emoji, "🧹🧹 Tidy up"), "Tidy up");
+        assert_eq!(strip_badge(&emoji, "🧹 Tidy up"), "Tidy up");
+    }
+
     #[test]
     fn test_format_issue_body_with_deps_all_synced() {
         let mut lookup = HashMap::new();
@@ -1249,11 +5132,154 @@ mod tests {
         lookup.insert("dep-2".to_string(), 20);
 
         let deps = vec!["dep-1".to_string(), "dep-2".to_string(), "dep-3".to_string()];
-        let section = format_dependencies_section(&deps, &lookup);
+        let section = format_dependencies_section(&deps, &lookup, &HashMap::new());
 
         assert_eq!(section, "**Depends on:** #10, #20, `dep-3` (not synced)");
     }
 
+    #[test]
+    fn test_format_dependencies_tasklist() {
+        let mut lookup = HashMap::new();
+        lookup.insert("dep-1".to_string(), 10);
+        lookup.insert("dep-2".to_string(), 20);
+        let closed: HashSet<String> = ["dep-1".to_string()].into();
+
+        let deps = vec!["dep-1".to_string(), "dep-2".to_string(), "dep-3".to_string()];
+        let section = format_dependencies_tasklist(&deps, &lookup, &HashMap::new(), &closed);
+
+        assert_eq!(section, "**Depends on:**\n- [x] #10\n- [ ] #20\n- [ ] `dep-3` (not synced)");
+    }
+
+    #[test]
+    fn test_plan_pins_unpins_first_and_respects_limit() {
+        // #1 is pinned by hand; #2 and #3 by ttr, and t-3 no longer wants it
+        let wanted = [("t-2", 2), ("t-4", 4), ("t-5", 5), ("t-6", 6)];
+        let unwanted = [("t-3", 3), ("t-7", 7)];
+        let plan = plan_pins(&wanted, &unwanted, &[1, 2, 3]);
+
+        assert_eq!(plan.unpin, vec![("t-3", 3)]);
+        assert_eq!(plan.forget, vec!["t-7"]);
+        assert_eq!(plan.keep, vec!["t-2"]);
+        assert_eq!(plan.pin, vec![("t-4", 4)]);
+        assert_eq!(plan.over_limit, vec![("t-5", 5), ("t-6", 6)]);
+    }
+
+    #[test]
+    fn test_format_blocks_section() {
+        let mut lookup = HashMap::new();
+        lookup.insert("a".to_string(), 10);
+        let dependents = vec!["a".to_string(), "b".to_string()];
+
+        assert_eq!(format_blocks_section(&dependents, &lookup, &HashMap::new()), "**Blocks:** #10, `b` (not synced)");
+
+        let elsewhere: HashMap<String, String> = [("b".to_string(), "owner/other#3".to_string())].into();
+        assert_eq!(format_blocks_section(&dependents, &lookup, &elsewhere), "**Blocks:** #10, owner/other#3");
+    }
+
+    #[test]
+    fn test_symmetric_links() {
+        let dir = TicketDir::new();
+        dir.write("a.md", "---\nid: a\nlinks: [b, c]\n---\n# A\n");
+        dir.write("b.md", "---\nid: b\nlinks: [a]\n---\n# B\n");
+        dir.write("c.md", "---\nid: c\n---\n# C\n");
+        let tickets = dir.load();
+
+        let related = symmetric_links(&tickets);
+
+        assert_eq!(related["a"], vec!["b", "c"]);
+        assert_eq!(related["b"], vec!["a"]);
+        assert_eq!(related["c"], vec!["a"]);
+    }
+
+    #[test]
+    fn test_link_ticket_mentions() {
+        let mut lookup = HashMap::new();
+        lookup.insert("ttr-0007".to_string(), 7);
+        let link = |n: u64| format!("#{}", n);
+
+        let body = "See ttr-0007. Not ttr-00070 or ttr-0008.\nCode `ttr-0007` and docs/ttr-0007.md\n```\nttr-0007\n```\n(ttr-0007)";
+        assert_eq!(
+            link_ticket_mentions(body, &lookup, link),
+            "See #7. Not ttr-00070 or ttr-0008.\nCode `ttr-0007` and docs/ttr-0007.md\n```\nttr-0007\n```\n(#7)"
+        );
+    }
+
+    #[test]
+    fn test_map_user_mentions() {
+        let mut mentions = HashMap::new();
+        mentions.insert("drew".to_string(), "drew-myers".to_string());
+        let body = "ping @Drew and @sam.\nmail drew@example.com, team @org/devs\n`@drew` stays\n```\n@drew\n```";
+
+        assert_eq!(
+            map_user_mentions(body, &mentions, false),
+            "ping @drew-myers and @sam.\nmail drew@example.com, team @org/devs\n`@drew` stays\n```\n@drew\n```"
+        );
+        assert_eq!(
+            map_user_mentions(body, &mentions, true),
+            "ping @drew-myers and `@sam`.\nmail drew@example.com, team @org/devs\n`@drew` stays\n```\n@drew\n```"
+        );
+    }
+
+    #[test]
+    fn test_absolutize_links() {
+        let base = "https://github.com/o/r/blob/main";
+        let dir = vec![".tickets".to_string()];
+        let body = "See [design](../docs/design.md#api \"Design\") and ![img](img.png).\n\
+                    [site](https://example.com), [top](#intro), `[x](../a.md)`, [out](../../x.md)\n\
+                    ```\n[code](../docs/a.md)\n```";
+
+        assert_eq!(
+            absolutize_links(body, base, &dir),
+            "See [design](https://github.com/o/r/blob/main/docs/design.md#api \"Design\") and \
+             ![img](https://github.com/o/r/blob/main/.tickets/img.png).\n\
+             [site](https://example.com), [top](#intro), `[x](../a.md)`, [out](../../x.md)\n\
+             ```\n[code](../docs/a.md)\n```"
+        );
+    }
+
+    // Pull request linking tests
+
+    fn pr(number: u64, head_ref: &str, state: &str) -> PullRequestInfo {
+        PullRequestInfo {
+            id: format!("PR_{}", number),
+            number,
+            title: String::new(),
+            body: String::new(),
+            state: state.to_string(),
+            url: format!("https://github.com/o/r/pull/{}", number),
+            head_ref: head_ref.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pull_request_for_branch_segments() {
+        let prs = vec![
+            pr(1, "feature/ttr-10-login", "OPEN"),
+            pr(2, "TTR-1-fix", "MERGED"),
+        ];
+        assert_eq!(pull_request_for(&prs, "ttr-1").unwrap().number, 2);
+        assert_eq!(pull_request_for(&prs, "ttr-10").unwrap().number, 1);
+        assert!(pull_request_for(&prs, "ttr-100").is_none());
+    }
+
+    #[test]
+    fn test_pull_request_for_prefers_open() {
+        let prs = vec![pr(3, "ttr-5", "CLOSED"), pr(4, "ttr-5-retry", "OPEN")];
+        assert_eq!(pull_request_for(&prs, "ttr-5").unwrap().number, 4);
+    }
+
+    #[test]
+    fn test_with_closing_keyword() {
+        assert_eq!(
+            with_closing_keyword("Adds login.\n", 42).unwrap(),
+            "Adds login.\n\nCloses #42"
+        );
+        assert_eq!(with_closing_keyword("", 42).unwrap(), "Closes #42");
+        assert!(with_closing_keyword("Fixes #42", 42).is_none());
+        // A different issue with the same prefix doesn't count
+        assert!(with_closing_keyword("Fixes #421", 42).is_some());
+    }
+
     // Issue type resolution tests
 
     #[test]
@@ -1390,4 +5416,779 @@ mod tests {
         // Should pass - case insensitive matching
         assert!(validate_issue_type_mappings(&type_map, &cache).is_ok());
     }
+
+    // Iteration resolution tests
+
+    fn iteration_cache(default: Option<&str>) -> IterationFieldCache {
+        IterationFieldCache {
+            field_id: "F_iter".to_string(),
+            default_iteration_id: default.map(|s| s.to_string()),
+            completed: vec![],
+            active: vec![
+                IterationOption {
+                    id: "IT_1".to_string(),
+                    title: "Sprint 1".to_string(),
+                    start_date: "2026-02-02".to_string(),
+                    duration: 14,
+                },
+                IterationOption {
+                    id: "IT_2".to_string(),
+                    title: "Sprint 2".to_string(),
+                    start_date: "2026-02-16".to_string(),
+                    duration: 14,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_resolve_ticket_iteration_by_name() {
+        let cache = iteration_cache(Some("IT_1"));
+        assert_eq!(
+            resolve_ticket_iteration("t-1", Some("sprint 2"), None, &cache),
+            Some("IT_2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_ticket_iteration_by_due_date() {
+        let cache = iteration_cache(Some("IT_1"));
+        assert_eq!(
+            resolve_ticket_iteration("t-1", None, Some("2026-02-20"), &cache),
+            Some("IT_2".to_string())
+        );
+        // Full timestamps are accepted too
+        assert_eq!(
+            resolve_ticket_iteration("t-1", None, Some("2026-02-03T12:00:00Z"), &cache),
+            Some("IT_1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_ticket_iteration_falls_back_to_default() {
+        let cache = iteration_cache(Some("IT_1"));
+        // Due date outside every iteration
+        assert_eq!(
+            resolve_ticket_iteration("t-1", None, Some("2027-01-01"), &cache),
+            Some("IT_1".to_string())
+        );
+        // Unknown iteration name
+        assert_eq!(
+            resolve_ticket_iteration("t-1", Some("Sprint 99"), None, &cache),
+            Some("IT_1".to_string())
+        );
+
+        let cache = iteration_cache(None);
+        assert_eq!(resolve_ticket_iteration("t-1", None, None, &cache), None);
+    }
+
+    #[test]
+    fn test_resolve_ticket_iteration_specifier() {
+        let cache = iteration_cache(Some("IT_1"));
+        assert_eq!(
+            resolve_ticket_iteration("t-1", Some("@next"), None, &cache),
+            Some("IT_2".to_string())
+        );
+        // @none opts the ticket out of the default iteration
+        assert_eq!(resolve_ticket_iteration("t-1", Some("@none"), None, &cache), None);
+    }
+
+    #[test]
+    fn test_resolve_iteration_spec() {
+        let active = iteration_cache(None).active;
+        assert_eq!(resolve_iteration_spec("@current", &active), Ok(Some("IT_1".to_string())));
+        assert_eq!(resolve_iteration_spec("@next", &active), Ok(Some("IT_2".to_string())));
+        assert_eq!(resolve_iteration_spec("@current+1", &active), Ok(Some("IT_2".to_string())));
+        assert_eq!(resolve_iteration_spec("@current+5", &active), Ok(None));
+        assert_eq!(resolve_iteration_spec("@none", &active), Ok(None));
+        assert_eq!(resolve_iteration_spec("Sprint 2", &active), Ok(Some("IT_2".to_string())));
+        assert!(resolve_iteration_spec("Sprint 9", &active).is_err());
+        assert!(resolve_iteration_spec("@previous", &active).is_err());
+        assert!(resolve_iteration_spec("@current+x", &active).is_err());
+    }
+
+    #[test]
+    fn test_assignee_rotation_and_load() {
+        assert_eq!(rotate(3, None, 4), vec![0, 1, 2, 0]);
+        assert_eq!(rotate(3, Some(1), 3), vec![2, 0, 1]);
+        assert_eq!(least_loaded(vec![4, 1, 2], 4), vec![1, 1, 2, 1]);
+        assert_eq!(least_loaded(vec![0, 0], 3), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn test_manages_body_ticket_overrides_config() {
+        let dir = TicketDir::new();
+        let parse = |id: &str, extra: &str| dir.ticket(id, &format!("status: open\n{}", extra));
+        let plain = parse("t-1", "");
+        let kept = parse("t-2", "manage-body: false\n");
+        let managed = parse("t-3", "manage-body: true\n");
+
+        let mut config = SyncConfig::default();
+        assert!(manages_body(&plain, &config));
+        assert!(!manages_body(&kept, &config));
+
+        config.manage_body = false;
+        assert!(!manages_body(&plain, &config));
+        assert!(manages_body(&managed, &config));
+    }
+
+    #[test]
+    fn test_duplicate_ticket_closes_with_label() {
+        let dir = TicketDir::new();
+        let parse = |id: &str, extra: &str| dir.ticket(id, &format!("status: open\ntags: [ui]\n{}", extra));
+        let kebab = parse("t-1", "duplicate-of: t-7\n");
+        let snake = parse("t-2", "duplicate_of: t-7\n");
+        let plain = parse("t-3", "");
+
+        assert_eq!(kebab.duplicate_of.as_deref(), Some("t-7"));
+        assert_eq!(snake.duplicate_of.as_deref(), Some("t-7"));
+        assert!(kebab.closes_issue());
+        assert!(!plain.closes_issue());
+
+        let labels = LabelsConfig::default();
+        assert_eq!(issue_labels(&kebab, &labels, true), vec!["ui", "duplicate"]);
+        assert_eq!(issue_labels(&plain, &labels, true), vec!["ui"]);
+    }
+
+    #[test]
+    fn test_type_label_fills_in_for_issue_types() {
+        let dir = TicketDir::new();
+        let ticket = Ticket::parse(&dir.write("t-1.md", "---\nid: t-1\ntype: bug\ntags: [ui, Type:Bug]\n---\n# Title\n")).unwrap();
+
+        let mut labels = LabelsConfig::default();
+        assert_eq!(issue_labels(&ticket, &labels, false), vec!["ui", "Type:Bug"]);
+
+        // Only without issue types, and not twice when a tag already has it
+        labels.type_label = Some("type:{type}".to_string());
+        assert_eq!(issue_labels(&ticket, &labels, false), vec!["ui", "Type:Bug"]);
+        assert_eq!(issue_labels(&ticket, &labels, true), vec!["ui", "Type:Bug"]);
+        labels.type_label = Some("kind/{type}".to_string());
+        assert_eq!(issue_labels(&ticket, &labels, false), vec!["ui", "Type:Bug", "kind/bug"]);
+        assert_eq!(issue_labels(&ticket, &labels, true), vec!["ui", "Type:Bug"]);
+    }
+
+    #[test]
+    fn test_desired_assignee_follows_ticket_then_config() {
+        use crate::github::issues::IssueAssignee;
+
+        let dir = TicketDir::new();
+        let parse = |id: &str, extra: &str| dir.ticket(id, &format!("status: open\n{}", extra));
+        let config = Config::parse(
+            "[github]\nrepo = \"o/r\"\nassignee = \"lead\"\n\n[mapping.mentions]\ndrew = \"drew-myers\"\n",
+        )
+        .unwrap();
+
+        let mapped = parse("t-1", "assignee: \"@drew\"\n");
+        let login = parse("t-2", "assignee: octocat\n");
+        let name = parse("t-3", "assignee: Jane Doe\n");
+        let plain = parse("t-4", "");
+        assert_eq!(desired_assignee(&mapped, &config).as_deref(), Some("drew-myers"));
+        assert_eq!(desired_assignee(&login, &config).as_deref(), Some("octocat"));
+        assert_eq!(desired_assignee(&name, &config), None);
+        assert_eq!(desired_assignee(&plain, &config).as_deref(), Some("lead"));
+
+        let user = |login: &str| IssueAssignee { id: format!("U_{}", login), login: login.to_string() };
+        let mut issue = ExistingIssue {
+            id: "I_1".to_string(),
+            number: 1,
+            title: "Title".to_string(),
+            body: String::new(),
+            state: "OPEN".to_string(),
+            url: String::new(),
+            updated_at: String::new(),
+            created_at: String::new(),
+            closed_at: None,
+            assignees: vec![user("Drew-Myers")],
+            labels: Vec::new(),
+        };
+        assert!(!assignees_differ(&mapped, &config, &issue));
+        assert!(assignees_differ(&plain, &config, &issue));
+        // Names that can't be logins leave the issue alone
+        assert!(!assignees_differ(&name, &config, &issue));
+        issue.assignees.push(user("lead"));
+        assert!(assignees_differ(&mapped, &config, &issue));
+    }
+
+    #[test]
+    fn test_is_managed_requires_configured_label() {
+        let mut config = Config::parse("[github]\nrepo = \"o/r\"\n").unwrap();
+        let mut issue = ExistingIssue {
+            id: "I_1".to_string(),
+            number: 1,
+            title: "Title".to_string(),
+            body: String::new(),
+            state: "OPEN".to_string(),
+            url: String::new(),
+            updated_at: String::new(),
+            created_at: String::new(),
+            closed_at: None,
+            assignees: Vec::new(),
+            labels: vec!["bug".to_string()],
+        };
+        assert!(is_managed(&issue, &config));
+
+        config.github.managed_label = Some("ttr".to_string());
+        assert!(!is_managed(&issue, &config));
+        issue.labels.push("TTR".to_string());
+        assert!(is_managed(&issue, &config));
+    }
+
+    #[test]
+    fn test_type_field_maps_ticket_type() {
+        use crate::github::projects::FieldOption;
+
+        let dir = TicketDir::new();
+        let ticket = Ticket::parse(&dir.write("t-1.md", "---\nid: t-1\nstatus: open\ntype: bug\n---\n# Crash\n")).unwrap();
+
+        let option = |id: &str, name: &str| FieldOption { id: id.to_string(), name: name.to_string() };
+        let fields = vec![ProjectFieldInfo {
+            id: "F_type".to_string(),
+            name: "type".to_string(),
+            field_type: ProjectFieldType::SingleSelect {
+                options: vec![option("O_bug", "Defect"), option("O_task", "Task")],
+            },
+        }];
+        let mapping = MappingConfig {
+            type_map: HashMap::from([("bug".to_string(), "defect".to_string())]),
+            ..Default::default()
+        };
+
+        let field = SyncEngine::setup_type_field(&fields, &mapping, &[]).unwrap();
+        assert_eq!(field.raw_value(&ticket, &HashMap::new()).as_deref(), Some("defect"));
+        assert!(matches!(field.value_for(&ticket, &HashMap::new()), Some(ProjectFieldValue::SingleSelect(id)) if id == "O_bug"));
+
+        // A custom field mapped to the same project field wins
+        assert!(SyncEngine::setup_type_field(&fields, &mapping, &[field]).is_none());
+    }
+
+    #[test]
+    fn test_tag_field_strips_prefix() {
+        use crate::github::projects::FieldOption;
+
+        let dir = TicketDir::new();
+        let ticket = Ticket::parse(&dir.write("t-1.md", "---\nid: t-1\nstatus: open\ntags: [ui, area:Backend]\n---\n# Crash\n")).unwrap();
+
+        let fields = vec![ProjectFieldInfo {
+            id: "F_component".to_string(),
+            name: "Component".to_string(),
+            field_type: ProjectFieldType::SingleSelect {
+                options: vec![FieldOption { id: "O_backend".to_string(), name: "backend".to_string() }],
+            },
+        }];
+        let config = ProjectFieldsConfig {
+            tag_fields: HashMap::from([("area:*".to_string(), "component".to_string())]),
+            ..Default::default()
+        };
+
+        let tag_fields = SyncEngine::setup_tag_fields(&fields, &config, &[]);
+        assert_eq!(tag_fields.len(), 1);
+        assert_eq!(tag_fields[0].raw_value(&ticket, &HashMap::new()).as_deref(), Some("Backend"));
+        assert!(matches!(tag_fields[0].value_for(&ticket, &HashMap::new()), Some(ProjectFieldValue::SingleSelect(id)) if id == "O_backend"));
+
+        // The type field doesn't take over a field a tag prefix already sets
+        let mapping = MappingConfig {
+            type_field: "Component".to_string(),
+            ..Default::default()
+        };
+        assert!(SyncEngine::setup_type_field(&fields, &mapping, &tag_fields).is_none());
+    }
+
+    #[test]
+    fn test_spent_hours_roll_up_to_epic() {
+        let dir = TicketDir::new();
+        dir.write("epic.md", "---\nid: epic\ntype: epic\nspent_hours: 1\nestimate_hours: 20\n---\n# Epic\n");
+        dir.write("a.md", "---\nid: a\nparent: epic\nspent-hours: 2.5\n---\n# A\n");
+        dir.write("a1.md", "---\nid: a1\nparent: a\nspent_hours: 4\n---\n# A1\n");
+        dir.write("b.md", "---\nid: b\nparent: epic\n---\n# B\n");
+        let tickets = dir.load();
+
+        let spent = spent_totals(&tickets);
+        assert_eq!(spent.get("epic"), Some(&7.5));
+        assert_eq!(spent.get("a"), Some(&6.5));
+        assert_eq!(spent.get("a1"), Some(&4.0));
+        assert_eq!(spent.get("b"), None);
+
+        let fields = vec![
+            ProjectFieldInfo { id: "F_est".to_string(), name: "Estimate".to_string(), field_type: ProjectFieldType::Number },
+            ProjectFieldInfo { id: "F_spent".to_string(), name: "Spent".to_string(), field_type: ProjectFieldType::Text },
+        ];
+        let config = ProjectFieldsConfig {
+            estimate_field: Some("estimate".to_string()),
+            spent_field: Some("Spent".to_string()),
+            ..Default::default()
+        };
+        // Only number fields are used
+        let hours_fields = SyncEngine::setup_hours_fields(&fields, &config, &[]);
+        assert_eq!(hours_fields.len(), 1);
+        let epic = tickets.iter().find(|t| t.id == "epic").unwrap();
+        assert!(matches!(hours_fields[0].value_for(epic, &spent), Some(ProjectFieldValue::Number(h)) if h == 20.0));
+    }
+
+    // Engine tests against a mock GitHub
+
+    fn graphql(data: serde_json::Value) -> wiremock::ResponseTemplate {
+        wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": data }))
+    }
+
+    /// Engine for `config` against a mock GitHub
+    ///
+    /// Labels and issue types come from a seeded cache; other startup
+    /// lookups get the repo ID unless a test mounts a better match.
+    async fn mock_engine(server: &wiremock::MockServer, dir: &Path, config: &str, options: SyncOptions) -> SyncEngine {
+        try_mock_engine(server, dir, config, options).await.unwrap()
+    }
+
+    async fn try_mock_engine(
+        server: &wiremock::MockServer,
+        dir: &Path,
+        config: &str,
+        options: SyncOptions,
+    ) -> Result<SyncEngine> {
+        use wiremock::{matchers::method, Mock};
+
+        let labels = HashMap::from([("bug".to_string(), "LA_bug".to_string())]);
+        RepoMetadata::new("owner/repo", labels, HashMap::new()).save(dir).unwrap();
+        Mock::given(method("POST"))
+            .respond_with(graphql(serde_json::json!({ "repository": { "id": "R_1" } })))
+            .with_priority(u8::MAX)
+            .mount(server)
+            .await;
+
+        let config = Config::parse(&format!("[github]\nrepo = \"owner/repo\"\n{}", config)).unwrap();
+        let client = GitHubClient::with_base_url("token".to_string(), server.uri()).unwrap();
+        SyncEngine::with_options(client, config, dir, options).await
+    }
+
+    /// Mount the repo's projects, as (id, title, number)
+    async fn mock_projects(server: &wiremock::MockServer, projects: &[(&str, &str, u64)]) {
+        use wiremock::{matchers::{body_string_contains, method}, Mock};
+
+        let nodes: Vec<serde_json::Value> = projects
+            .iter()
+            .map(|(id, title, number)| serde_json::json!({ "id": id, "title": title, "number": number }))
+            .collect();
+        Mock::given(method("POST"))
+            .and(body_string_contains("projectsV2(first: 50)"))
+            .respond_with(graphql(serde_json::json!({ "repository": { "projectsV2": { "nodes": nodes } } })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_routes_to_one_project_share_it() {
+        use wiremock::{matchers::{body_string_contains, method}, Mock};
+
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        mock_projects(&server, &[("PVT_1", "Board", 1)]).await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("fields(first: 50)"))
+            .respond_with(graphql(serde_json::json!({ "node": { "fields": { "nodes": [] } } })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // By title for bugs, and by number as the catch-all
+        let config = "project = \"1\"\n\n[[projects]]\nname = \"Board\"\ntypes = [\"bug\"]\n";
+        let engine = mock_engine(&server, dir.path(), config, SyncOptions::default()).await;
+
+        assert_eq!(engine.projects.len(), 1);
+        let routes: Vec<usize> = engine.routes.iter().map(|r| r.project).collect();
+        assert_eq!(routes, vec![0, 0]);
+        let ticket = dir.ticket("t-1", "type: feature\n");
+        assert_eq!(engine.project_for(&ticket).map(|p| p.info.id.as_str()), Some("PVT_1"));
+        for route in &engine.routes {
+            engine.project_fields(&engine.projects[route.project]).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_to_one_project_merge_fields() {
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        mock_projects(&server, &[("PVT_1", "Board", 1)]).await;
+
+        let config = "project = \"1\"\n\n[project.status]\nclosed = \"Done\"\n\n\
+                      [[projects]]\nname = \"Board\"\ntypes = [\"bug\"]\niteration = \"@current\"\n\n\
+                      [projects.status]\nopen = \"Todo\"\n";
+        let engine = mock_engine(&server, dir.path(), config, SyncOptions::default()).await;
+        let fields = &engine.projects[0].fields_config;
+        assert_eq!(fields.status.get("open"), Some(&"Todo".to_string()));
+        assert_eq!(fields.status.get("closed"), Some(&"Done".to_string()));
+        assert_eq!(fields.iteration.as_deref(), Some("@current"));
+
+        // The catch-all can't map a status the bug route maps differently
+        let config = "project = \"1\"\n\n[project.status]\nopen = \"Backlog\"\n\n\
+                      [[projects]]\nname = \"Board\"\ntypes = [\"bug\"]\n\n[projects.status]\nopen = \"Todo\"\n";
+        let err = try_mock_engine(&server, dir.path(), config, SyncOptions::default()).await.err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Route '1' to project 'Board' has conflicting `status.open`: 'Todo' and 'Backlog' in sync.toml"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_project_fields_fetched_on_first_use() {
+        use wiremock::{matchers::{body_string_contains, method}, Mock};
+
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        mock_projects(&server, &[("PVT_1", "Board", 1)]).await;
+        let fields = Mock::given(method("POST"))
+            .and(body_string_contains("fields(first: 50)"))
+            .respond_with(graphql(serde_json::json!({ "node": { "fields": { "nodes": [] } } })))
+            .expect(1)
+            .mount_as_scoped(&server)
+            .await;
+
+        let engine = mock_engine(&server, dir.path(), "project = \"1\"\n", SyncOptions::default()).await;
+        assert!(fields.received_requests().await.is_empty());
+
+        // No mappings configured, so nothing to cache, but it's asked once
+        assert!(engine.project_fields(&engine.projects[0]).await.is_none());
+        assert!(engine.project_fields(&engine.projects[0]).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_converted_draft_gets_labels_assignee_and_type() {
+        use wiremock::{matchers::{body_string_contains, method}, Mock};
+
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        mock_projects(&server, &[("PVT_1", "Board", 1)]).await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("fields(first: 50)"))
+            .respond_with(graphql(serde_json::json!({ "node": { "fields": { "nodes": [] } } })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("convertProjectV2DraftIssueItemToIssue"))
+            .respond_with(graphql(serde_json::json!({
+                "convertProjectV2DraftIssueItemToIssue": {
+                    "item": { "content": { "id": "I_7", "number": 7, "url": "https://github.com/owner/repo/issues/7" } }
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("updateIssue"))
+            .and(body_string_contains("IT_feature"))
+            .respond_with(graphql(serde_json::json!({
+                "update_0": { "issue": { "id": "I_7", "number": 7, "url": "https://github.com/owner/repo/issues/7" } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("addLabelsToLabelable"))
+            .and(body_string_contains("LA_bug"))
+            .respond_with(graphql(serde_json::json!({ "label_0": { "clientMutationId": null } })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("addAssigneesToAssignable"))
+            .and(body_string_contains("U_alice"))
+            .respond_with(graphql(serde_json::json!({ "add_0": { "clientMutationId": null } })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = "project = \"1\"\n\n[project]\ndraft_types = [\"idea\"]\n\n[mapping.type]\nfeature = \"Feature\"\n";
+        let mut engine = mock_engine(&server, dir.path(), config, SyncOptions::default()).await;
+        engine.issue_type_cache.insert("feature".to_string(), "IT_feature".to_string());
+        engine.assignees = vec![("alice".to_string(), "U_alice".to_string())];
+
+        // Was an idea, now a feature
+        let tickets = vec![dir.ticket("t-1", "type: feature\ntags: [bug]\nexternal-ref: draft-PVTI_1\n")];
+        let results = engine.sync_drafts(&tickets, &[0]).await;
+
+        assert!(matches!(results[0], SyncResult::Created { issue_number: 7, .. }), "{:?}", results[0]);
+    }
+
+    // Plan tests
+
+    #[tokio::test]
+    async fn test_plan_without_project_fails() {
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        let engine = mock_engine(&server, dir.path(), "", SyncOptions::default()).await;
+
+        let tickets = vec![dir.ticket("t-1", "")];
+        let err = engine.plan(&tickets, None, Some("Todo")).await.unwrap_err();
+        assert!(err.to_string().starts_with("No project configured"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_plan_groups_tickets_by_project() {
+        use wiremock::{matchers::{body_string_contains, method}, Mock};
+
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        mock_projects(&server, &[("PVT_bugs", "Bugs", 1), ("PVT_docs", "Docs", 2), ("PVT_ideas", "Ideas", 3)]).await;
+        // Each project with routed tickets is looked at once; Ideas has none
+        for (project, calls) in [("PVT_bugs", 1), ("PVT_docs", 1), ("PVT_ideas", 0)] {
+            Mock::given(method("POST"))
+                .and(body_string_contains("fields(first: 50)"))
+                .and(body_string_contains(project))
+                .respond_with(graphql(serde_json::json!({ "node": { "fields": { "nodes": [] } } })))
+                .expect(calls)
+                .mount(&server)
+                .await;
+        }
+
+        let config = "\n[[projects]]\nname = \"Bugs\"\ntypes = [\"bug\"]\n\n[[projects]]\nname = \"Docs\"\ntags = [\"docs\"]\n\n[[projects]]\nname = \"Ideas\"\ntypes = [\"idea\"]\n";
+        let engine = mock_engine(&server, dir.path(), config, SyncOptions::default()).await;
+
+        let tickets = vec![
+            dir.ticket("t-1", "type: bug\n"),
+            dir.ticket("t-2", "type: bug\n"),
+            dir.ticket("t-3", "type: task\ntags: [docs]\n"),
+            dir.ticket("t-4", "type: task\n"),
+        ];
+        let summary = engine.plan(&tickets, None, None).await.unwrap();
+
+        // t-4 has no route; the rest reach their project but aren't synced
+        assert_eq!(summary.skipped, 4);
+        assert_eq!(summary.planned, 0);
+    }
+
+    #[tokio::test]
+    async fn test_plan_maps_ticket_status_to_project_option() {
+        use wiremock::{matchers::{body_string_contains, method}, Mock};
+
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        mock_projects(&server, &[("PVT_1", "Board", 1)]).await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("fields(first: 50)"))
+            .respond_with(graphql(serde_json::json!({ "node": { "fields": { "nodes": [{
+                "id": "F_status",
+                "name": "Status",
+                "__typename": "ProjectV2SingleSelectField",
+                "options": [{ "id": "OPT_todo", "name": "Todo" }, { "id": "OPT_doing", "name": "In Progress" }]
+            }] } } })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("issue_1: issue(number: 1)"))
+            .respond_with(graphql(serde_json::json!({ "repository": { "issue_1": {
+                "id": "I_1", "number": 1, "title": "t-1", "body": "", "state": "OPEN",
+                "url": "https://github.com/owner/repo/issues/1"
+            } } })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("projectItems(first: 10)"))
+            .respond_with(graphql(serde_json::json!({
+                "issue_0": { "projectItems": { "nodes": [{ "id": "PVTI_1", "project": { "id": "PVT_1" } }] } }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("updateProjectV2ItemFieldValue"))
+            .and(body_string_contains("OPT_doing"))
+            .respond_with(graphql(serde_json::json!({ "update_0": { "projectV2Item": { "id": "PVTI_1" } } })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = "project = \"1\"\n\n[project.status]\nin_progress = \"In Progress\"\n";
+        let engine = mock_engine(&server, dir.path(), config, SyncOptions::default()).await;
+
+        // #2 doesn't exist, so t-2 fails and is left out of the planned IDs
+        let tickets = vec![dir.ticket("t-1", "external-ref: gh-1\n"), dir.ticket("t-2", "external-ref: gh-2\n")];
+        let summary = engine.plan(&tickets, None, Some("in_progress")).await.unwrap();
+        assert_eq!((summary.planned, summary.failed), (1, 1));
+        assert_eq!(summary.planned_ids, vec!["t-1"]);
+
+        // Neither a mapped ticket status nor an option name
+        let err = engine.plan(&tickets, None, Some("blocked")).await.unwrap_err();
+        assert!(err.to_string().contains("Project status option 'blocked' not found"), "{}", err);
+    }
+
+    // Epic children tests
+
+    #[tokio::test]
+    async fn test_epic_body_lists_children() {
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        let mut engine = mock_engine(&server, dir.path(), "", SyncOptions::default()).await;
+
+        let children = vec!["t-1".to_string(), "t-2".to_string(), "t-3".to_string()];
+        engine.children = HashMap::from([("e-1".to_string(), children.clone()), ("t-9".to_string(), children)]);
+        engine.ticket_to_issue = HashMap::from([("t-1".to_string(), 5), ("t-2".to_string(), 6)]);
+        engine.closed_tickets = HashSet::from(["t-1".to_string()]);
+
+        let epic = dir.ticket("e-1", "type: epic\n");
+        let body = engine.format_issue_body(&epic);
+        assert!(body.contains("**Children:**\n- [x] #5\n- [ ] #6\n- [ ] `t-3` (not synced)"), "{}", body);
+
+        // Only epics roll up, and only when something names them as parent
+        let task = dir.ticket("t-9", "type: task\n");
+        assert!(!engine.format_issue_body(&task).contains("**Children:**"));
+        let empty = dir.ticket("e-2", "type: epic\n");
+        assert!(!engine.format_issue_body(&empty).contains("**Children:**"));
+    }
+
+    // Create label tests
+
+    #[tokio::test]
+    async fn test_type_label_used_while_issue_types_are_missing() {
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        let config = "\n[labels]\ntype_label = \"type:{type}\"\n";
+        let mut engine = mock_engine(&server, dir.path(), config, SyncOptions::default()).await;
+        let ticket = dir.ticket("t-1", "type: feature\n");
+
+        assert!(engine.issue_type_cache.is_empty());
+        assert_eq!(engine.create_labels(&ticket), vec!["type:feature"]);
+
+        engine.issue_type_cache.insert("feature".to_string(), "IT_feature".to_string());
+        assert!(engine.create_labels(&ticket).is_empty());
+    }
+
+    // Dependent check tests
+
+    #[tokio::test]
+    async fn test_closing_with_open_dependents_warns_or_fails() {
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        let mut engine = mock_engine(&server, dir.path(), "", SyncOptions::default()).await;
+        let tickets = vec![
+            dir.ticket("t-1", "status: closed\nexternal-ref: gh-5\n"),
+            dir.ticket("t-2", "deps: [t-1]\n"),
+            dir.ticket("t-3", "status: closed\ndeps: [t-1]\n"),
+        ];
+        engine.closed_tickets = HashSet::from(["t-1".to_string(), "t-3".to_string()]);
+
+        assert_eq!(
+            engine.check_open_dependents(&tickets[0], 5, &tickets),
+            Ok(Some("t-1 closing while open tickets depend on it: t-2".to_string()))
+        );
+        assert_eq!(engine.check_open_dependents(&tickets[1], 6, &tickets), Ok(None));
+
+        engine.options.strict_deps = true;
+        assert_eq!(
+            engine.check_open_dependents(&tickets[0], 5, &tickets),
+            Err("not closing #5: open tickets depend on it: t-2".to_string())
+        );
+
+        // Nothing open depends on it once t-2 closes too
+        engine.closed_tickets.insert("t-2".to_string());
+        assert_eq!(engine.check_open_dependents(&tickets[0], 5, &tickets), Ok(None));
+    }
+
+    // Phase flag tests
+
+    /// Request bodies a push sends: t-1 is a sub-issue of t-2, and t-3 is
+    /// new and tagged
+    async fn push_requests(options: SyncOptions) -> Vec<String> {
+        use wiremock::{matchers::{body_string_contains, method}, Mock};
+
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        mock_projects(&server, &[("PVT_1", "Board", 1)]).await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("AddSubIssueInput\\\") { name }"))
+            .respond_with(graphql(serde_json::json!({ "subIssues": { "name": "AddSubIssueInput" }, "issueTypes": null })))
+            .mount(&server)
+            .await;
+        let issue = |n: u64| {
+            serde_json::json!({
+                "id": format!("I_{}", n), "number": n, "title": format!("t-{}", n), "body": "", "state": "OPEN",
+                "url": format!("https://github.com/owner/repo/issues/{}", n)
+            })
+        };
+        Mock::given(method("POST"))
+            .and(body_string_contains("issue_1: issue(number: 1)"))
+            .respond_with(graphql(serde_json::json!({ "repository": { "issue_1": issue(1), "issue_2": issue(2) } })))
+            .mount(&server)
+            .await;
+
+        let config = "project = \"1\"\n\n[project.status]\nopen = \"Todo\"\n";
+        let mut engine = mock_engine(&server, dir.path(), config, options).await;
+        let mut tickets = vec![
+            dir.ticket("t-1", "external-ref: gh-1\nparent: t-2\n"),
+            dir.ticket("t-2", "external-ref: gh-2\n"),
+            dir.ticket("t-3", "tags: [ui]\n"),
+        ];
+        let all_tickets = tickets.clone();
+        engine.sync(&mut tickets, &all_tickets).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        requests.iter().map(|r| String::from_utf8_lossy(&r.body).into_owned()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_push_flags_skip_their_phase() {
+        let phases = [
+            ("projectsV2(first: 50)", SyncOptions { projects: false, ..Default::default() }),
+            ("addSubIssue(", SyncOptions { sub_issues: false, ..Default::default() }),
+            ("createLabel(", SyncOptions { labels: false, ..Default::default() }),
+            ("fields(first: 50)", SyncOptions { status: false, ..Default::default() }),
+        ];
+
+        let all = push_requests(SyncOptions::default()).await;
+        for (request, _) in &phases {
+            assert!(all.iter().any(|body| body.contains(request)), "no {} by default", request);
+        }
+
+        for (request, options) in &phases {
+            let sent = push_requests(*options).await;
+            assert!(!sent.iter().any(|body| body.contains(request)), "{} sent with its flag off", request);
+            let others = phases.iter().map(|(r, _)| r).filter(|r| *r != request && sent.iter().any(|body| body.contains(*r)));
+            // Without projects there are no fields to sync either
+            let expected = if *request == "projectsV2(first: 50)" { 2 } else { 3 };
+            assert_eq!(others.count(), expected, "{} off skipped other phases", request);
+        }
+    }
+
+    // Create order tests
+
+    #[tokio::test]
+    async fn test_creates_ordered_by_priority_then_id_up_to_top() {
+        let server = wiremock::MockServer::start().await;
+        let dir = TicketDir::new();
+        let mut engine = mock_engine(&server, dir.path(), "", SyncOptions::default()).await;
+        let tickets = vec![
+            dir.ticket("t-4", "priority: 2\n"),
+            dir.ticket("t-3", "priority: 0\n"),
+            dir.ticket("t-1", "priority: 2\n"),
+            dir.ticket("t-2", "priority: 1\n"),
+        ];
+        let pending = || -> Vec<PendingCreate> {
+            (0..tickets.len())
+                .map(|ticket_idx| PendingCreate {
+                    ticket_idx,
+                    title: String::new(),
+                    body: String::new(),
+                    labels: Vec::new(),
+                    issue_type_id: None,
+                    assignee_id: None,
+                })
+                .collect()
+        };
+        let ids = |pending: &[PendingCreate]| -> Vec<&str> {
+            pending.iter().map(|p| tickets[p.ticket_idx].id.as_str()).collect()
+        };
+
+        let mut all = pending();
+        assert!(engine.order_creates(&mut all, &tickets).is_empty());
+        assert_eq!(ids(&all), vec!["t-3", "t-2", "t-1", "t-4"]);
+
+        engine.options.top = Some(2);
+        let mut top = pending();
+        let skipped = engine.order_creates(&mut top, &tickets);
+        assert_eq!(ids(&top), vec!["t-3", "t-2"]);
+        let skipped: Vec<(&str, &str)> = skipped
+            .iter()
+            .map(|(i, result)| match result {
+                SyncResult::Skipped { reason } => (tickets[*i].id.as_str(), reason.as_str()),
+                other => panic!("{:?}", other),
+            })
+            .collect();
+        assert_eq!(skipped, vec![("t-1", "past --top 2"), ("t-4", "past --top 2")]);
+    }
 }
@@ -0,0 +1,216 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use yaml_rust2::YamlLoader;
+
+/// Shown under template sections the ticket has nothing for, as GitHub
+/// does for empty issue form fields
+pub const NO_RESPONSE: &str = "_No response_";
+
+/// Section the ticket's description goes in, when the template has one
+const DESCRIPTION: &str = "Description";
+
+/// The section headings of a repo's issue template, in order
+///
+/// Issue bodies are laid out under these headings so issues ttr creates
+/// look like those filed through the template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueTemplate {
+    /// Heading lines as they appear in filed issues, e.g. "### What happened?"
+    pub headings: Vec<String>,
+}
+
+impl IssueTemplate {
+    /// Load a Markdown template (`.md`) or an issue form (`.yml`/`.yaml`)
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path).with_context(|| format!("Failed to read issue template {}", path.display()))?;
+        let is_form = path.extension().is_some_and(|ext| ext == "yml" || ext == "yaml");
+        let template = if is_form { Self::parse_form(&raw) } else { Ok(Self::parse_markdown(&raw)) }
+            .with_context(|| format!("Invalid issue template {}", path.display()))?;
+        if template.headings.is_empty() {
+            anyhow::bail!("Issue template {} has no sections", path.display());
+        }
+        Ok(template)
+    }
+
+    /// Headings of a Markdown template, skipping its frontmatter and any
+    /// headings in comments or code
+    pub fn parse_markdown(raw: &str) -> Self {
+        let mut lines = raw.lines().peekable();
+        if lines.peek().is_some_and(|l| l.trim_end() == "---") {
+            lines.next();
+            for line in lines.by_ref() {
+                if line.trim_end() == "---" {
+                    break;
+                }
+            }
+        }
+
+        let mut headings = Vec::new();
+        let mut in_fence = false;
+        let mut in_comment = false;
+        for line in lines {
+            let trimmed = line.trim();
+            if !in_comment && (trimmed.starts_with("```") || trimmed.starts_with("~~~")) {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+            // Comments are assumed to open and close at line boundaries
+            if in_comment || trimmed.starts_with("<!--") {
+                in_comment = !trimmed.contains("-->");
+                continue;
+            }
+            if heading_name(trimmed).is_some() {
+                headings.push(trimmed.to_string());
+            }
+        }
+        Self { headings }
+    }
+
+    /// Headings of an issue form: GitHub files each input under
+    /// `### {label}`
+    pub fn parse_form(raw: &str) -> Result<Self> {
+        let docs = YamlLoader::load_from_str(raw).map_err(|e| anyhow::anyhow!("{}", e))?;
+        let Some(body) = docs.first().and_then(|doc| doc["body"].as_vec()) else {
+            anyhow::bail!("Issue form has no body");
+        };
+        let headings = body
+            .iter()
+            .filter(|item| item["type"].as_str() != Some("markdown"))
+            .filter_map(|item| item["attributes"]["label"].as_str())
+            .map(|label| format!("### {}", label.trim()))
+            .collect();
+        Ok(Self { headings })
+    }
+
+    /// Lay a ticket's public body out under the template's headings
+    ///
+    /// Ticket sections go under the template heading with the same name
+    /// (ignoring case), and the description before them under
+    /// "Description" or else the first heading left over. Headings the
+    /// ticket has nothing for read `_No response_`; ticket sections the
+    /// template has no heading for follow at the end as written.
+    pub fn fill(&self, body: &str) -> String {
+        let (description, sections) = split_sections(body);
+        let names: Vec<String> = self.headings.iter().filter_map(|h| heading_name(h)).map(normalize).collect();
+
+        let mut slots: Vec<Option<String>> = vec![None; self.headings.len()];
+        let mut extra = Vec::new();
+        for (heading, content) in sections {
+            let name = heading_name(&heading).map(normalize).unwrap_or_default();
+            match names.iter().position(|n| *n == name).filter(|&i| slots[i].is_none()) {
+                Some(i) => slots[i] = Some(content),
+                None if content.is_empty() => extra.push(heading),
+                None => extra.push(format!("{}\n\n{}", heading, content)),
+            }
+        }
+        if !description.is_empty() {
+            let free = |i: &usize| slots[*i].is_none();
+            let slot = names
+                .iter()
+                .position(|n| *n == normalize(DESCRIPTION))
+                .filter(free)
+                .or_else(|| (0..slots.len()).find(free));
+            match slot {
+                Some(i) => slots[i] = Some(description),
+                None => extra.insert(0, description),
+            }
+        }
+
+        let mut parts: Vec<String> = self
+            .headings
+            .iter()
+            .zip(slots)
+            .map(|(heading, content)| {
+                let content = content.filter(|c| !c.is_empty()).unwrap_or_else(|| NO_RESPONSE.to_string());
+                format!("{}\n\n{}", heading, content)
+            })
+            .collect();
+        parts.extend(extra);
+        parts.join("\n\n")
+    }
+}
+
+/// The text of a Markdown heading line, without its `#`s
+fn heading_name(line: &str) -> Option<&str> {
+    let hashes = line.len() - line.trim_start_matches('#').len();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    line[hashes..].strip_prefix(' ').map(str::trim)
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().trim_end_matches(':').trim().to_lowercase()
+}
+
+/// A ticket body's description and its `## ` sections as (heading line,
+/// content), outside code fences
+fn split_sections(body: &str) -> (String, Vec<(String, String)>) {
+    let mut description = Vec::new();
+    let mut sections: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && line.starts_with("## ") {
+            sections.push((line.trim_end().to_string(), Vec::new()));
+            continue;
+        }
+        match sections.last_mut() {
+            Some((_, lines)) => lines.push(line),
+            None => description.push(line),
+        }
+    }
+    let sections = sections
+        .into_iter()
+        .map(|(heading, lines)| (heading, lines.join("\n").trim().to_string()))
+        .collect();
+    (description.join("\n").trim().to_string(), sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_template_headings() {
+        let raw = "---\nname: Task\nabout: Planned work\n---\n\n<!--\n## Not a section\n-->\n## Summary\n\nWhat and why\n\n```\n# not a heading\n```\n\n## Acceptance criteria\n\n- [ ] ...\n";
+        assert_eq!(IssueTemplate::parse_markdown(raw).headings, vec!["## Summary", "## Acceptance criteria"]);
+    }
+
+    #[test]
+    fn test_issue_form_headings() {
+        let raw = "name: Bug\nbody:\n  - type: markdown\n    attributes:\n      value: Thanks!\n  - type: textarea\n    attributes:\n      label: What happened?\n  - type: input\n    attributes:\n      label: Version\n";
+        let template = IssueTemplate::parse_form(raw).unwrap();
+        assert_eq!(template.headings, vec!["### What happened?", "### Version"]);
+    }
+
+    #[test]
+    fn test_fill_slots_ticket_sections_into_template() {
+        let template = IssueTemplate {
+            headings: vec![
+                "### Environment".to_string(),
+                "### Description".to_string(),
+                "### Acceptance Criteria".to_string(),
+            ],
+        };
+        let body = "Retry failed requests.\n\n## Acceptance criteria:\n\n- [ ] Retries 3 times\n\n## Design\n\nBackoff";
+        assert_eq!(
+            template.fill(body),
+            "### Environment\n\n_No response_\n\n\
+             ### Description\n\nRetry failed requests.\n\n\
+             ### Acceptance Criteria\n\n- [ ] Retries 3 times\n\n\
+             ## Design\n\nBackoff"
+        );
+
+        // Without a Description heading the description takes the first free one
+        let template = IssueTemplate { headings: vec!["## Summary".to_string(), "## Design".to_string()] };
+        assert_eq!(template.fill("Intro\n\n## Design\n\nBackoff"), "## Summary\n\nIntro\n\n## Design\n\nBackoff");
+    }
+}
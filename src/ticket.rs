@@ -1,8 +1,13 @@
 use anyhow::{Context, Result};
 use gray_matter::{engine::YAML, Matter};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::frontmatter;
+use crate::worklog::WORKLOG_SECTION;
 
 /// Represents a parsed ticket from .tickets/*.md
 #[derive(Debug, Clone)]
@@ -19,22 +24,59 @@ pub struct Ticket {
     pub links: Vec<String>,
     /// Creation timestamp
     pub created: Option<String>,
+    /// Timestamp the ticket was closed
+    pub closed: Option<String>,
     /// Type: bug, feature, task, epic, chore
     pub ticket_type: String,
     /// Priority 0-4 (0 = highest)
     pub priority: u8,
     /// Assignee name
     pub assignee: Option<String>,
-    /// External reference (e.g., "gh-123")
+    /// External reference managed by ttr (e.g., "gh-123" or "draft-...")
     pub external_ref: Option<String>,
+    /// All external references as written, including other trackers'
+    pub external_refs: Vec<String>,
+    /// Web URL of the synced issue
+    pub url: Option<String>,
     /// Parent ticket ID
     pub parent: Option<String>,
+    /// Subdirectory of the tickets directory the file sits in, named after
+    /// its epic (`[sync] parent_dirs`)
+    pub subdir: Option<String>,
+    /// IDs this ticket had before being renamed
+    pub previous_ids: Vec<String>,
+    /// Other names the ticket can be referred to by
+    pub aliases: Vec<String>,
     /// Tags for labeling
     pub tags: Vec<String>,
+    /// Due date (YYYY-MM-DD), used to pick a project iteration
+    pub due: Option<String>,
+    /// Project iteration name override
+    pub iteration: Option<String>,
+    /// Git branch created for this ticket
+    pub branch: Option<String>,
+    /// Repository the issue was transferred to, when not the configured one
+    pub repo: Option<String>,
+    /// Whether the issue is pinned to the repository
+    pub pinned: bool,
+    /// Whether the ticket is archived, locking its issue's conversation
+    pub archived: bool,
+    /// Whether pushes update the issue body, overriding `[sync] manage_body`
+    pub manage_body: Option<bool>,
+    /// ID of the ticket this one duplicates; its issue is closed as a duplicate
+    pub duplicate_of: Option<String>,
+    /// Estimated effort in hours
+    pub estimate_hours: Option<f64>,
+    /// Hours spent on the ticket itself, not counting its children
+    pub spent_hours: Option<f64>,
+    /// Frontmatter keys ttr doesn't know about, for config-driven mappings
+    pub custom: HashMap<String, serde_json::Value>,
     /// Ticket title (from markdown heading)
     pub title: String,
     /// Full body content (excluding Notes section)
     pub body: String,
+    /// Markdown after the frontmatter, including private sections
+    pub markdown: String,
 }
 
 /// YAML frontmatter structure
@@ -48,18 +90,132 @@ struct Frontmatter {
     #[serde(default)]
     links: Vec<String>,
     created: Option<String>,
+    closed: Option<String>,
     #[serde(rename = "type", default = "default_type")]
     ticket_type: String,
     #[serde(default = "default_priority")]
     priority: u8,
     assignee: Option<String>,
     #[serde(rename = "external-ref")]
-    external_ref: Option<String>,
+    external_ref: Option<ExternalRefs>,
+    url: Option<String>,
     parent: Option<String>,
+    #[serde(rename = "previous-ids", default)]
+    previous_ids: Vec<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
     #[serde(default)]
     tags: Vec<String>,
+    due: Option<String>,
+    iteration: Option<String>,
+    branch: Option<String>,
+    repo: Option<String>,
+    #[serde(default)]
+    pinned: bool,
+    #[serde(default)]
+    archived: bool,
+    #[serde(rename = "manage-body")]
+    manage_body: Option<bool>,
+    #[serde(rename = "duplicate-of", alias = "duplicate_of")]
+    duplicate_of: Option<String>,
+    #[serde(rename = "estimate-hours", alias = "estimate_hours")]
+    estimate_hours: Option<f64>,
+    #[serde(rename = "spent-hours", alias = "spent_hours")]
+    spent_hours: Option<f64>,
+    #[serde(flatten)]
+    custom: HashMap<String, serde_json::Value>,
+}
+
+/// Sections kept out of issue bodies unless `[sync] private_sections` is set
+pub const DEFAULT_PRIVATE_SECTIONS: &[&str] = &["Notes"];
+
+/// Section that discussion comments are pulled into; never pushed
+pub const DISCUSSION_SECTION: &str = "Discussion";
+
+/// Below this many files per thread, tickets are parsed on one thread
+const FILES_PER_THREAD: usize = 64;
+
+/// How a tickets directory is loaded
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoadOptions {
+    /// Keep the first of several tickets sharing an ID instead of failing
+    /// (`--skip-duplicates`)
+    pub skip_duplicates: bool,
+    /// Fail when any ticket file can't be loaded (`--strict`, `[sync] strict`)
+    pub strict: bool,
+    /// Also load `.tickets/<epic-id>/`, with the epic as the tickets' parent
+    /// (`[sync] parent_dirs`)
+    pub parent_dirs: bool,
+}
+
+/// Whether a directory in the tickets directory can hold an epic's tickets
+///
+/// Hidden directories hold ttr's own files and never do.
+pub fn is_epic_dir(dir: &Path) -> bool {
+    visible_dir(dir)
+}
+
+fn visible_dir(dir: &Path) -> bool {
+    dir.is_dir() && dir.file_name().and_then(|n| n.to_str()).is_some_and(|n| !n.starts_with('.'))
 }
 
+/// `external-ref` as a single reference or a list for several trackers
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ExternalRefs {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Whether an external reference is one ttr writes (an issue or draft item)
+fn is_managed_ref(external_ref: &str) -> bool {
+    external_ref.starts_with("gh-") || external_ref.starts_with("ghd-") || external_ref.starts_with("draft-")
+}
+
+/// Expected shape of a known frontmatter field
+#[derive(Clone, Copy)]
+enum FieldKind {
+    Text,
+    List,
+    TextOrList,
+    Priority,
+    Bool,
+    /// A number of hours, 0 or more
+    Hours,
+}
+
+/// Known frontmatter fields; anything else is kept as a custom field
+const KNOWN_FIELDS: &[(&str, FieldKind)] = &[
+    ("id", FieldKind::Text),
+    ("status", FieldKind::Text),
+    ("deps", FieldKind::List),
+    ("links", FieldKind::List),
+    ("created", FieldKind::Text),
+    ("closed", FieldKind::Text),
+    ("type", FieldKind::Text),
+    ("priority", FieldKind::Priority),
+    ("assignee", FieldKind::Text),
+    ("external-ref", FieldKind::TextOrList),
+    ("url", FieldKind::Text),
+    ("parent", FieldKind::Text),
+    ("previous-ids", FieldKind::List),
+    ("aliases", FieldKind::List),
+    ("tags", FieldKind::List),
+    ("due", FieldKind::Text),
+    ("iteration", FieldKind::Text),
+    ("branch", FieldKind::Text),
+    ("repo", FieldKind::Text),
+    ("pinned", FieldKind::Bool),
+    ("archived", FieldKind::Bool),
+    ("manage-body", FieldKind::Bool),
+    ("duplicate-of", FieldKind::Text),
+    ("duplicate_of", FieldKind::Text),
+    ("estimate-hours", FieldKind::Hours),
+    ("estimate_hours", FieldKind::Hours),
+    ("spent-hours", FieldKind::Hours),
+    ("spent_hours", FieldKind::Hours),
+];
+
 fn default_status() -> String {
     "open".to_string()
 }
@@ -78,6 +234,8 @@ impl Ticket {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read ticket: {}", path.display()))?;
 
+        validate_frontmatter(path, &content)?;
+
         let matter = Matter::<YAML>::new();
         let parsed = matter.parse(&content);
 
@@ -98,6 +256,13 @@ impl Ticket {
 
         // Get body without the title line, and filter out Notes section
         let body = extract_body(body_content);
+        let markdown = body_content.to_string();
+
+        let external_refs = match frontmatter.external_ref {
+            Some(ExternalRefs::One(r)) => vec![r],
+            Some(ExternalRefs::Many(refs)) => refs,
+            None => Vec::new(),
+        };
 
         Ok(Ticket {
             path: path.to_path_buf(),
@@ -106,137 +271,356 @@ impl Ticket {
             deps: frontmatter.deps,
             links: frontmatter.links,
             created: frontmatter.created,
+            closed: frontmatter.closed,
             ticket_type: frontmatter.ticket_type,
             priority: frontmatter.priority,
             assignee: frontmatter.assignee,
-            external_ref: frontmatter.external_ref,
+            external_ref: external_refs.iter().find(|r| is_managed_ref(r)).cloned(),
+            external_refs,
+            url: frontmatter.url,
             parent: frontmatter.parent,
+            subdir: None,
+            previous_ids: frontmatter.previous_ids,
+            aliases: frontmatter.aliases,
             tags: frontmatter.tags,
+            due: frontmatter.due,
+            iteration: frontmatter.iteration,
+            branch: frontmatter.branch,
+            repo: frontmatter.repo,
+            pinned: frontmatter.pinned,
+            archived: frontmatter.archived,
+            manage_body: frontmatter.manage_body,
+            duplicate_of: frontmatter.duplicate_of,
+            estimate_hours: frontmatter.estimate_hours,
+            spent_hours: frontmatter.spent_hours,
+            custom: frontmatter.custom,
             title,
             body,
+            markdown,
         })
     }
 
     /// Load all tickets from a directory
-    pub fn load_all(tickets_dir: &Path) -> Result<Vec<Self>> {
-        let mut tickets = Vec::new();
+    ///
+    /// Tickets that fail to parse are skipped and reported as they're found.
+    pub fn load_all(tickets_dir: &Path, options: LoadOptions) -> Result<Vec<Self>> {
+        Ok(Self::load_all_counted(tickets_dir, options)?.0)
+    }
 
-        for entry in fs::read_dir(tickets_dir)
-            .with_context(|| format!("Failed to read directory: {}", tickets_dir.display()))?
-        {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.extension().is_some_and(|ext| ext == "md") {
-                // Skip sync.toml and other non-ticket files
-                if let Some(name) = path.file_stem() {
-                    if name == "sync" {
-                        continue;
-                    }
-                }
+    /// Load all tickets, also returning how many files were skipped
+    ///
+    /// In strict mode, any skipped file fails the load instead.
+    pub fn load_all_counted(tickets_dir: &Path, options: LoadOptions) -> Result<(Vec<Self>, usize)> {
+        let report = |e: &anyhow::Error| eprintln!("Warning: {:#}", e);
+        let (tickets, errors) = Self::load_dir(tickets_dir, options, &report)?;
+        Self::check_strict(&errors, options.strict)?;
+        Ok((tickets, errors.len()))
+    }
+
+    fn check_strict(errors: &[anyhow::Error], strict: bool) -> Result<()> {
+        if strict && !errors.is_empty() {
+            anyhow::bail!(
+                "{} ticket file(s) could not be loaded (strict mode is on)\n\
+                 Fix the files warned about above, or unset [sync] strict",
+                errors.len()
+            );
+        }
+        Ok(())
+    }
 
-                match Self::parse(&path) {
-                    Ok(ticket) => tickets.push(ticket),
-                    Err(e) => {
-                        eprintln!("Warning: Failed to parse {}: {}", path.display(), e);
+    /// Load all tickets from a directory, returning parse errors alongside
+    ///
+    /// Fails if two files share a ticket ID, unless duplicates are being
+    /// skipped, in which case the later files are reported as errors.
+    pub fn load_all_with_errors(tickets_dir: &Path, options: LoadOptions) -> Result<(Vec<Self>, Vec<anyhow::Error>)> {
+        Self::load_dir(tickets_dir, options, &|_| {})
+    }
+
+    /// Load tickets, parsing files on several threads for large directories
+    ///
+    /// `on_error` sees each error as it happens; errors are also returned in
+    /// file order. With `options.parent_dirs`, epic directories are loaded too.
+    fn load_dir(
+        tickets_dir: &Path,
+        options: LoadOptions,
+        on_error: &(dyn Fn(&anyhow::Error) + Sync),
+    ) -> Result<(Vec<Self>, Vec<anyhow::Error>)> {
+        let mut tickets = Vec::new();
+        let mut errors = Vec::new();
+
+        let mut paths = Vec::new();
+        let mut dirs = vec![tickets_dir.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read directory: {}", dir.display()))? {
+                let path = entry?.path();
+
+                // Epic directories are one level deep
+                if options.parent_dirs && dir == tickets_dir && visible_dir(&path) {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "md") {
+                    // Skip sync.toml and other non-ticket files
+                    if let Some(name) = path.file_stem() {
+                        if name == "sync" {
+                            continue;
+                        }
                     }
+                    paths.push(path);
+                }
+            }
+        }
+        // Report errors in a stable order
+        paths.sort();
+
+        let parse = |paths: &[PathBuf]| -> Vec<Result<Self>> {
+            paths
+                .iter()
+                .map(|path| Self::parse(path).inspect_err(|e| on_error(e)))
+                .collect()
+        };
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(paths.len().div_ceil(FILES_PER_THREAD));
+        let results = if threads <= 1 {
+            parse(&paths)
+        } else {
+            // Chunks are joined in order, so results stay in path order
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = paths
+                    .chunks(paths.len().div_ceil(threads))
+                    .map(|chunk| scope.spawn(move || parse(chunk)))
+                    .collect();
+                handles
+                    .into_iter()
+                    .flat_map(|h| h.join().expect("ticket parser thread panicked"))
+                    .collect()
+            })
+        };
+        for result in results {
+            match result {
+                Ok(mut ticket) => {
+                    ticket.infer_parent(tickets_dir);
+                    tickets.push(ticket)
                 }
+                Err(e) => errors.push(e),
             }
         }
 
+        let mut first_path: HashMap<String, PathBuf> = HashMap::new();
+        let mut duplicates: Vec<(String, PathBuf, PathBuf)> = Vec::new();
+        tickets.retain(|ticket| match first_path.get(&ticket.id) {
+            Some(first) => {
+                duplicates.push((ticket.id.clone(), first.clone(), ticket.path.clone()));
+                false
+            }
+            None => {
+                first_path.insert(ticket.id.clone(), ticket.path.clone());
+                true
+            }
+        });
+
+        if !duplicates.is_empty() {
+            if !options.skip_duplicates {
+                let lines: Vec<String> = duplicates
+                    .iter()
+                    .map(|(id, first, other)| format!("  {}: {} and {}", id, first.display(), other.display()))
+                    .collect();
+                anyhow::bail!(
+                    "Duplicate ticket IDs:\n{}\nGive each ticket a unique `id`, or rerun with --skip-duplicates",
+                    lines.join("\n")
+                );
+            }
+            for (id, first, other) in duplicates {
+                let error = anyhow::anyhow!(
+                    "{}: duplicate ID `{}` (already used by {}), skipped",
+                    other.display(),
+                    id,
+                    first.display()
+                );
+                on_error(&error);
+                errors.push(error);
+            }
+        }
+
+        resolve_aliases(&mut tickets)?;
+
         // Sort by ID for consistent ordering
         tickets.sort_by(|a, b| a.id.cmp(&b.id));
 
-        Ok(tickets)
+        Ok((tickets, errors))
     }
 
-    /// Write or update the external-ref field in the ticket file
-    pub fn write_external_ref(&mut self, external_ref: &str) -> Result<()> {
-        let content = fs::read_to_string(&self.path)
-            .with_context(|| format!("Failed to read ticket: {}", self.path.display()))?;
+    /// Note the epic directory the ticket was loaded from, making the epic
+    /// its parent unless it names one (or is the epic itself)
+    fn infer_parent(&mut self, tickets_dir: &Path) {
+        let Some(dir) = self.path.parent().filter(|d| *d != tickets_dir) else {
+            return;
+        };
+        let Some(name) = dir.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            return;
+        };
+        if self.parent.is_none() && name != self.id {
+            self.parent = Some(name.clone());
+        }
+        self.subdir = Some(name);
+    }
 
-        // Check if external-ref exists in frontmatter (not in body)
-        let has_external_ref_in_frontmatter = {
-            let mut in_frontmatter = false;
-            let mut found = false;
-            for line in content.lines() {
-                if line == "---" {
-                    if in_frontmatter {
-                        break; // End of frontmatter
-                    } else {
-                        in_frontmatter = true;
-                        continue;
-                    }
-                }
-                if in_frontmatter && line.starts_with("external-ref:") {
-                    found = true;
-                    break;
-                }
+    /// Directories from the repo root to the ticket's file, assuming the
+    /// tickets directory sits at the repo root
+    pub fn repo_dirs(&self) -> Vec<String> {
+        let depth = if self.subdir.is_some() { 2 } else { 1 };
+        let mut dirs: Vec<String> = self
+            .path
+            .ancestors()
+            .skip(1)
+            .take(depth)
+            .filter_map(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .collect();
+        dirs.reverse();
+        dirs
+    }
+
+    /// A custom frontmatter field as text
+    ///
+    /// Lists of scalars are joined with ", "; nulls and maps give None.
+    pub fn custom_value(&self, key: &str) -> Option<String> {
+        fn scalar(value: &serde_json::Value) -> Option<String> {
+            match value {
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Number(n) => Some(n.to_string()),
+                serde_json::Value::Bool(b) => Some(b.to_string()),
+                _ => None,
             }
-            found
-        };
+        }
 
-        let new_content = if has_external_ref_in_frontmatter {
-            // Update existing external-ref in frontmatter only
-            let mut in_frontmatter = false;
-            let mut passed_frontmatter = false;
-            content
-                .lines()
-                .map(|line| {
-                    if line == "---" {
-                        if in_frontmatter {
-                            passed_frontmatter = true;
-                        }
-                        in_frontmatter = !in_frontmatter;
-                        return line.to_string();
-                    }
-                    if in_frontmatter && !passed_frontmatter && line.starts_with("external-ref:") {
-                        format!("external-ref: {}", external_ref)
-                    } else {
-                        line.to_string()
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join("\n")
-        } else {
-            // Add external-ref before closing --- of frontmatter
-            let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-            let mut insert_idx = None;
-
-            let mut in_frontmatter = false;
-            for (i, line) in lines.iter().enumerate() {
-                if line == "---" {
-                    if in_frontmatter {
-                        // End of frontmatter
-                        insert_idx = Some(i);
-                        break;
-                    } else {
-                        in_frontmatter = true;
-                    }
-                }
+        match self.custom.get(key)? {
+            serde_json::Value::Array(items) => {
+                let values: Vec<String> = items.iter().filter_map(scalar).collect();
+                (!values.is_empty()).then(|| values.join(", "))
             }
+            value => scalar(value),
+        }
+    }
+
+    /// Write the ttr-managed external-ref, keeping other trackers' references
+    pub fn write_external_ref(&mut self, external_ref: &str) -> Result<()> {
+        let mut refs = self.external_refs.clone();
+        match refs.iter().position(|r| is_managed_ref(r)) {
+            Some(i) => refs[i] = external_ref.to_string(),
+            None => refs.push(external_ref.to_string()),
+        }
+        self.write_external_refs(refs)
+    }
 
-            if let Some(idx) = insert_idx {
-                lines.insert(idx, format!("external-ref: {}", external_ref));
+    /// Restore the external-ref field, removing it if `external_ref` is None
+    pub fn restore_external_ref(&mut self, external_ref: Option<&str>) -> Result<()> {
+        match external_ref {
+            Some(external_ref) => self.write_external_ref(external_ref),
+            None => {
+                let refs = self.external_refs.iter().filter(|r| !is_managed_ref(r)).cloned().collect();
+                self.write_external_refs(refs)?;
+                // The URL points at the issue being unlinked
+                if self.url.take().is_some() {
+                    remove_frontmatter_field(&self.path, "url")?;
+                }
+                Ok(())
             }
+        }
+    }
 
-            lines.join("\n")
-        };
+    /// Point the ticket at its issue after a transfer
+    ///
+    /// `repo` is None when the issue is back in the configured repo. The URL
+    /// is only written if the ticket already records one.
+    pub fn write_transfer(&mut self, repo: Option<&str>, issue_number: u64, url: &str) -> Result<()> {
+        match repo {
+            Some(repo) => write_frontmatter_field(&self.path, "repo", repo)?,
+            None if self.repo.is_some() => remove_frontmatter_field(&self.path, "repo")?,
+            None => {}
+        }
+        self.repo = repo.map(str::to_string);
+        self.write_external_ref(&format!("gh-{}", issue_number))?;
+        if self.url.is_some() {
+            self.write_url(url)?;
+        }
+        Ok(())
+    }
 
-        // Ensure file ends with newline
-        let final_content = if new_content.ends_with('\n') {
-            new_content
-        } else {
-            format!("{}\n", new_content)
-        };
+    /// Write or update the url field in the ticket file
+    pub fn write_url(&mut self, url: &str) -> Result<()> {
+        write_frontmatter_field(&self.path, "url", url)?;
+        self.url = Some(url.to_string());
+        Ok(())
+    }
+
+    /// Write all external references, as a scalar when there's only one
+    fn write_external_refs(&mut self, refs: Vec<String>) -> Result<()> {
+        match refs.as_slice() {
+            [] => remove_frontmatter_field(&self.path, "external-ref")?,
+            [external_ref] => write_frontmatter_field(&self.path, "external-ref", external_ref)?,
+            _ => edit_frontmatter(&self.path, |content| frontmatter::set_list(content, "external-ref", &refs))?,
+        }
+        self.external_ref = refs.iter().find(|r| is_managed_ref(r)).cloned();
+        self.external_refs = refs;
+        Ok(())
+    }
+
+    pub fn write_status(&mut self, status: &str) -> Result<()> {
+        write_frontmatter_field(&self.path, "status", status)?;
+        self.status = status.to_string();
+        Ok(())
+    }
+
+    /// Set status to closed and record when it happened
+    ///
+    /// `at` is when it closed (ISO 8601), such as the issue's close time;
+    /// defaults to now.
+    pub fn write_closed(&mut self, at: Option<&str>) -> Result<()> {
+        let at = at.map_or_else(now_timestamp, str::to_string);
+        self.write_status("closed")?;
+        write_frontmatter_field(&self.path, "closed", &at)?;
+        self.closed = Some(at);
+        Ok(())
+    }
+
+    /// Write the created timestamp (ISO 8601) into the ticket file
+    pub fn write_created(&mut self, at: &str) -> Result<()> {
+        write_frontmatter_field(&self.path, "created", at)?;
+        self.created = Some(at.to_string());
+        Ok(())
+    }
+
+    /// Write or update the branch field in the ticket file
+    pub fn write_branch(&mut self, branch: &str) -> Result<()> {
+        write_frontmatter_field(&self.path, "branch", branch)?;
+        self.branch = Some(branch.to_string());
+        Ok(())
+    }
+
+    /// Replace the title heading in the ticket file
+    pub fn write_title(&mut self, title: &str) -> Result<()> {
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read ticket: {}", self.path.display()))?;
+
+        let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+        let body_start = frontmatter_end(&lines).map(|i| i + 1).unwrap_or(0);
 
-        fs::write(&self.path, final_content)
-            .with_context(|| format!("Failed to write ticket: {}", self.path.display()))?;
+        match lines[body_start..].iter().position(|l| l.starts_with("# ")) {
+            Some(i) => lines[body_start + i] = format!("# {}", title),
+            None => lines.insert(body_start, format!("# {}", title)),
+        }
+
+        write_lines(&self.path, lines.join("\n"))?;
 
-        self.external_ref = Some(external_ref.to_string());
+        self.title = title.to_string();
         Ok(())
     }
 
+    /// Whether the ticket's issue should be closed: it's closed or a duplicate
+    pub fn closes_issue(&self) -> bool {
+        self.status == "closed" || self.duplicate_of.is_some()
+    }
+
     /// Check if this ticket has been synced to GitHub
     pub fn is_synced(&self) -> bool {
         self.external_ref
@@ -244,19 +628,322 @@ impl Ticket {
             .is_some_and(|r| r.starts_with("gh-"))
     }
 
-    /// Get the GitHub issue number if synced
+    /// Get the GitHub issue number if synced to the configured repo
     pub fn github_issue_number(&self) -> Option<u64> {
+        if self.repo.is_some() {
+            return None;
+        }
         self.external_ref.as_ref().and_then(|r| {
             r.strip_prefix("gh-")
                 .and_then(|num| num.parse::<u64>().ok())
         })
     }
+
+    /// Repository and number of an issue transferred out of the configured repo
+    pub fn issue_elsewhere(&self) -> Option<(&str, u64)> {
+        let repo = self.repo.as_deref()?;
+        let number = self.external_ref.as_deref()?.strip_prefix("gh-")?.parse().ok()?;
+        Some((repo, number))
+    }
+
+    /// Get the GitHub Discussion number if synced as a discussion
+    pub fn discussion_number(&self) -> Option<u64> {
+        self.external_ref.as_deref()?.strip_prefix("ghd-")?.parse().ok()
+    }
+
+    /// Replace the content of the `## heading` section, adding the section
+    /// at the end if there isn't one
+    ///
+    /// Returns whether the file changed.
+    pub fn write_section(&mut self, heading: &str, content: &str) -> Result<bool> {
+        let file = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read ticket: {}", self.path.display()))?;
+        let updated = replace_section(&file, heading, content);
+        if updated.trim_end() == file.trim_end() {
+            return Ok(false);
+        }
+        write_lines(&self.path, updated)?;
+        *self = Self::parse(&self.path)?;
+        Ok(true)
+    }
+
+    /// Checked and total checkboxes under `## Acceptance Criteria`, if any
+    pub fn acceptance_criteria(&self) -> Option<(usize, usize)> {
+        let mut in_section = false;
+        let (mut done, mut total) = (0, 0);
+        for line in self.markdown.lines() {
+            if let Some(heading) = line.strip_prefix("## ") {
+                in_section = heading.trim().eq_ignore_ascii_case("Acceptance Criteria");
+                continue;
+            }
+            if !in_section {
+                continue;
+            }
+            let item = line.trim_start().trim_start_matches(['-', '*', '+']).trim_start();
+            if item.starts_with("[ ]") {
+                total += 1;
+            } else if item.starts_with("[x]") || item.starts_with("[X]") {
+                done += 1;
+                total += 1;
+            }
+        }
+        (total > 0).then_some((done, total))
+    }
+
+    /// Body for the issue, leaving out the given private sections and the
+    /// worklog, whose entries are commented instead
+    pub fn public_body<S: AsRef<str>>(&self, private_sections: &[S]) -> String {
+        let mut private: Vec<&str> = private_sections.iter().map(AsRef::as_ref).collect();
+        private.push(WORKLOG_SECTION);
+        strip_sections(&self.markdown, &private)
+    }
+
+    /// Get the project item ID if synced as a draft item
+    pub fn draft_item_id(&self) -> Option<&str> {
+        self.external_ref
+            .as_deref()
+            .and_then(|r| r.strip_prefix("draft-"))
+    }
+}
+
+/// Index of the closing `---` line of the frontmatter, if any
+fn frontmatter_end(lines: &[String]) -> Option<usize> {
+    if lines.first().map(|l| l.as_str()) != Some("---") {
+        return None;
+    }
+    lines.iter().skip(1).position(|l| l == "---").map(|i| i + 1)
+}
+
+/// Check frontmatter syntax and the types of known fields
+///
+/// Errors are prefixed with `path:line:col` of the offending value.
+fn validate_frontmatter(path: &Path, content: &str) -> Result<()> {
+    let Some(source) = frontmatter::yaml_source(content) else {
+        anyhow::bail!("No frontmatter found in {}", path.display());
+    };
+
+    // The frontmatter starts on the second line of the file
+    let docs = YamlLoader::load_from_str(&source).map_err(|e| {
+        let marker = e.marker();
+        anyhow::anyhow!(
+            "{}:{}:{}: invalid frontmatter: {}",
+            path.display(),
+            marker.line() + 1,
+            marker.col() + 1,
+            e.info()
+        )
+    })?;
+
+    let fields = match docs.into_iter().next() {
+        Some(Yaml::Hash(fields)) => fields,
+        Some(Yaml::Null) | None => Default::default(),
+        Some(_) => anyhow::bail!("{}:2:1: frontmatter must be a set of `key: value` fields", path.display()),
+    };
+
+    let located = |key: &str, message: String| {
+        let (line, col) = frontmatter::value_position(content, key).unwrap_or((2, 1));
+        anyhow::anyhow!("{}:{}:{}: {}", path.display(), line, col, message)
+    };
+
+    for &(key, kind) in KNOWN_FIELDS {
+        let value = fields.get(&Yaml::String(key.to_string())).unwrap_or(&Yaml::Null);
+        let valid = match (kind, value) {
+            (_, Yaml::Null) => key != "id",
+            (FieldKind::Text, Yaml::String(_)) => true,
+            (FieldKind::List | FieldKind::TextOrList, Yaml::Array(items)) => {
+                items.iter().all(|i| matches!(i, Yaml::String(_)))
+            }
+            (FieldKind::TextOrList, Yaml::String(_)) => true,
+            (FieldKind::Priority, Yaml::Integer(p)) => (0..=4).contains(p),
+            (FieldKind::Bool, Yaml::Boolean(_)) => true,
+            (FieldKind::Hours, Yaml::Integer(h)) => *h >= 0,
+            (FieldKind::Hours, Yaml::Real(h)) => h.parse::<f64>().is_ok_and(|h| h >= 0.0),
+            _ => false,
+        };
+        if valid {
+            continue;
+        }
+
+        let expected = match kind {
+            _ if value.is_null() => "is required".to_string(),
+            FieldKind::Text => "must be a string".to_string(),
+            FieldKind::List => "must be a list of strings".to_string(),
+            FieldKind::TextOrList => "must be a string or a list of strings".to_string(),
+            FieldKind::Priority => "must be an integer 0–4".to_string(),
+            FieldKind::Bool => "must be true or false".to_string(),
+            FieldKind::Hours => "must be a number of hours, 0 or more".to_string(),
+        };
+        let message = match describe(value) {
+            Some(found) => format!("`{}` {} (found {})", key, expected, found),
+            None => format!("`{}` {}", key, expected),
+        };
+        return Err(located(key, message));
+    }
+
+    Ok(())
+}
+
+/// Short description of a YAML value for error messages
+fn describe(value: &Yaml) -> Option<String> {
+    match value {
+        Yaml::String(s) => Some(format!("\"{}\"", s)),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Real(r) => Some(r.clone()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        Yaml::Array(_) => Some("a list".to_string()),
+        Yaml::Hash(_) => Some("a mapping".to_string()),
+        _ => None,
+    }
+}
+
+/// Write or update a top-level field in a ticket's frontmatter
+///
+/// Only lines inside the frontmatter are considered, so examples of the same
+/// field in the markdown body are left untouched. Formatting and comments of
+/// other fields are preserved.
+fn write_frontmatter_field(path: &Path, key: &str, value: &str) -> Result<()> {
+    edit_frontmatter(path, |content| frontmatter::set_field(content, key, value))
+}
+
+/// Remove a frontmatter field, if present
+fn remove_frontmatter_field(path: &Path, key: &str) -> Result<()> {
+    edit_frontmatter(path, |content| frontmatter::remove_field(content, key))
+}
+
+fn edit_frontmatter(path: &Path, edit: impl FnOnce(&str) -> Option<String>) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read ticket: {}", path.display()))?;
+
+    let Some(updated) = edit(&content) else {
+        anyhow::bail!("No frontmatter found in {}", path.display());
+    };
+
+    if updated != content {
+        fs::write(path, updated)
+            .with_context(|| format!("Failed to write ticket: {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// The current time as a frontmatter timestamp
+pub fn now_timestamp() -> String {
+    chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// Point `deps`, `links`, and `parent` given by alias at the real ticket IDs
+///
+/// Fails if an alias is another ticket's ID or is claimed by two tickets.
+fn resolve_aliases(tickets: &mut [Ticket]) -> Result<()> {
+    let ids: HashMap<&str, &Path> = tickets.iter().map(|t| (t.id.as_str(), t.path.as_path())).collect();
+    let mut owners: HashMap<String, String> = HashMap::new();
+    let mut collisions = Vec::new();
+    for ticket in tickets.iter() {
+        for alias in ticket.aliases.iter().filter(|a| **a != ticket.id) {
+            if let Some(path) = ids.get(alias.as_str()) {
+                collisions.push(format!("  {}: alias of {} is the ID of {}", alias, ticket.id, path.display()));
+            } else if let Some(owner) = owners.get(alias) {
+                if *owner != ticket.id {
+                    collisions.push(format!("  {}: alias of both {} and {}", alias, owner, ticket.id));
+                }
+            } else {
+                owners.insert(alias.clone(), ticket.id.clone());
+            }
+        }
+    }
+    if !collisions.is_empty() {
+        anyhow::bail!(
+            "Ticket alias collisions:\n{}\nEach alias must name exactly one ticket",
+            collisions.join("\n")
+        );
+    }
+    if owners.is_empty() {
+        return Ok(());
+    }
+
+    let resolve = |id: &mut String| {
+        if let Some(real) = owners.get(id.as_str()) {
+            *id = real.clone();
+        }
+    };
+    for ticket in tickets.iter_mut() {
+        ticket.deps.iter_mut().for_each(resolve);
+        ticket.links.iter_mut().for_each(resolve);
+        ticket.parent.iter_mut().for_each(resolve);
+    }
+    Ok(())
+}
+
+/// Tickets an ID argument could mean, best match first
+///
+/// Stops at the first rule that matches anything: the exact ID or an alias,
+/// then the same number after the prefix (`1` for `ttr-0001`), then a prefix
+/// of the ID or of the part after its prefix, then anywhere in the ID.
+pub fn match_id<'a>(tickets: &'a [Ticket], arg: &str) -> Vec<&'a Ticket> {
+    let suffix = |t: &'a Ticket| t.id.split_once('-').map_or(t.id.as_str(), |(_, rest)| rest);
+    let number = |s: &str| s.parse::<u64>().ok().filter(|_| s.bytes().all(|b| b.is_ascii_digit()));
+    let rules: [&dyn Fn(&'a Ticket) -> bool; 4] = [
+        &|t| t.id == arg || t.aliases.iter().any(|a| a == arg),
+        &|t| number(arg).is_some() && number(suffix(t)) == number(arg),
+        &|t| t.id.starts_with(arg) || suffix(t).starts_with(arg),
+        &|t| t.id.contains(arg),
+    ];
+    rules
+        .iter()
+        .map(|rule| tickets.iter().filter(|t| rule(t)).collect::<Vec<_>>())
+        .find(|matches| !matches.is_empty())
+        .unwrap_or_default()
+}
+
+/// Write ticket content, ensuring the file ends with a newline
+fn write_lines(path: &Path, content: String) -> Result<()> {
+    let final_content = if content.ends_with('\n') {
+        content
+    } else {
+        format!("{}\n", content)
+    };
+
+    fs::write(path, final_content)
+        .with_context(|| format!("Failed to write ticket: {}", path.display()))
+}
+
+/// Swap a `## heading` section's content, or append the section
+fn replace_section(file: &str, heading: &str, content: &str) -> String {
+    let is_heading = |line: &str, name: Option<&str>| {
+        line.strip_prefix("## ")
+            .is_some_and(|h| name.is_none_or(|name| h.trim().trim_end_matches(':').eq_ignore_ascii_case(name)))
+    };
+    let lines: Vec<&str> = file.lines().collect();
+    let section = format!("## {}\n\n{}", heading, content.trim_end());
+
+    let Some(start) = lines.iter().position(|l| is_heading(l, Some(heading))) else {
+        return format!("{}\n\n{}\n", file.trim_end(), section);
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| is_heading(l, None))
+        .map_or(lines.len(), |i| start + 1 + i);
+
+    let mut out: Vec<String> = lines[..start].iter().map(|l| l.to_string()).collect();
+    out.push(section);
+    if end < lines.len() {
+        out.push(String::new());
+        out.extend(lines[end..].iter().map(|l| l.to_string()));
+    }
+    out.join("\n") + "\n"
 }
 
 /// Extract body content, filtering out the Notes section
 fn extract_body(content: &str) -> String {
+    strip_sections(content, DEFAULT_PRIVATE_SECTIONS)
+}
+
+/// Extract body content without the title line or the named `## ` sections
+///
+/// Section names match case-insensitively; a private section runs until the
+/// next `## ` heading.
+pub fn strip_sections<S: AsRef<str>>(content: &str, sections: &[S]) -> String {
     let mut result = Vec::new();
-    let mut in_notes = false;
+    let mut in_private = false;
 
     for line in content.lines() {
         // Skip the title line
@@ -264,18 +951,15 @@ fn extract_body(content: &str) -> String {
             continue;
         }
 
-        // Check for Notes section
-        if line.starts_with("## Notes") {
-            in_notes = true;
-            continue;
-        }
-
-        // Check if we've hit a new section after Notes
-        if in_notes && line.starts_with("## ") {
-            in_notes = false;
+        if let Some(heading) = line.strip_prefix("## ") {
+            let heading = heading.trim().trim_end_matches(':');
+            in_private = sections.iter().any(|s| s.as_ref().eq_ignore_ascii_case(heading));
+            if in_private {
+                continue;
+            }
         }
 
-        if !in_notes {
+        if !in_private {
             result.push(line);
         }
     }
@@ -285,6 +969,40 @@ fn extract_body(content: &str) -> String {
     body.trim().to_string()
 }
 
+/// Ticket files in a temporary directory, for tests across the crate
+#[cfg(test)]
+pub(crate) struct TicketDir(tempfile::TempDir);
+
+#[cfg(test)]
+impl TicketDir {
+    pub(crate) fn new() -> Self {
+        Self(tempfile::TempDir::new().unwrap())
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        self.0.path()
+    }
+
+    /// Write a file under the directory, creating its parents
+    pub(crate) fn write(&self, name: &str, content: &str) -> PathBuf {
+        let path = self.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// Write and parse `<id>.md` with extra frontmatter lines, titled by its ID
+    pub(crate) fn ticket(&self, id: &str, frontmatter: &str) -> Ticket {
+        let path = self.write(&format!("{}.md", id), &format!("---\nid: {}\n{}---\n# {}\n", id, frontmatter, id));
+        Ticket::parse(&path).unwrap()
+    }
+
+    /// Every ticket in the directory, loaded with default options
+    pub(crate) fn load(&self) -> Vec<Ticket> {
+        Ticket::load_all(self.path(), LoadOptions::default()).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,6 +1049,9 @@ assignee: acmyers
 external-ref: gh-123
 parent: parent-001
 tags: [setup, core]
+due: 2026-02-14
+iteration: Sprint 3
+branch: epic/ttr-0001-full-test-ticket
 ---
 # Full Test Ticket
 
@@ -362,6 +1083,9 @@ This note should not appear in body.
         assert_eq!(ticket.external_ref, Some("gh-123".to_string()));
         assert_eq!(ticket.parent, Some("parent-001".to_string()));
         assert_eq!(ticket.tags, vec!["setup", "core"]);
+        assert_eq!(ticket.due, Some("2026-02-14".to_string()));
+        assert_eq!(ticket.iteration, Some("Sprint 3".to_string()));
+        assert_eq!(ticket.branch, Some("epic/ttr-0001-full-test-ticket".to_string()));
         assert_eq!(ticket.title, "Full Test Ticket");
         assert!(ticket.body.contains("Description here"));
         assert!(ticket.body.contains("Design notes"));
@@ -369,6 +1093,156 @@ This note should not appear in body.
         assert!(!ticket.body.contains("This note should not appear"));
     }
 
+    #[test]
+    fn test_parse_custom_fields() {
+        let content = r#"---
+id: ttr-0001
+estimate: 3
+component: auth
+reviewers: [alice, bob]
+extra:
+  nested: true
+---
+# Custom
+"#;
+        let file = create_test_ticket(content);
+        let ticket = Ticket::parse(file.path()).unwrap();
+
+        assert_eq!(ticket.custom.len(), 4);
+        assert!(!ticket.custom.contains_key("id"));
+        assert_eq!(ticket.custom_value("estimate"), Some("3".to_string()));
+        assert_eq!(ticket.custom_value("component"), Some("auth".to_string()));
+        assert_eq!(ticket.custom_value("reviewers"), Some("alice, bob".to_string()));
+        assert_eq!(ticket.custom_value("extra"), None);
+        assert_eq!(ticket.custom_value("missing"), None);
+    }
+
+    #[test]
+    fn test_parse_error_names_field_and_position() {
+        let file = create_test_ticket("---\nid: ttr-0001\npriority: high\n---\n# Bad\n");
+        let err = Ticket::parse(file.path()).unwrap_err().to_string();
+        let expected = format!(
+            "{}:3:11: `priority` must be an integer 0–4 (found \"high\")",
+            file.path().display()
+        );
+        assert_eq!(err, expected);
+
+        let file = create_test_ticket("---\nstatus: open\n---\n# No id\n");
+        let err = Ticket::parse(file.path()).unwrap_err().to_string();
+        assert!(err.ends_with(":2:1: `id` is required"), "{}", err);
+
+        let file = create_test_ticket("---\nid: ttr-0001\ntags: [a, [b]]\n---\n");
+        let err = Ticket::parse(file.path()).unwrap_err().to_string();
+        assert!(err.ends_with(":3:7: `tags` must be a list of strings (found a list)"), "{}", err);
+
+        let file = create_test_ticket("---\nid: ttr-0001\narchived: yes please\n---\n");
+        let err = Ticket::parse(file.path()).unwrap_err().to_string();
+        assert!(err.contains(":3:11: `archived` must be true or false"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_error_reports_yaml_syntax_position() {
+        let file = create_test_ticket("---\nid: ttr-0001\nstatus: \"open\n---\n# Bad\n");
+        let err = Ticket::parse(file.path()).unwrap_err().to_string();
+        let prefix = format!("{}:3:", file.path().display());
+        assert!(err.starts_with(&prefix), "{}", err);
+        assert!(err.contains("invalid frontmatter"), "{}", err);
+    }
+
+    #[test]
+    fn test_load_all_collects_parse_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "---\nid: a\n---\n# A\n").unwrap();
+        fs::write(dir.path().join("b.md"), "---\nid: b\npriority: 9\n---\n# B\n").unwrap();
+        fs::write(dir.path().join("c.md"), "---\nid: [c]\n---\n# C\n").unwrap();
+
+        let (tickets, errors) = Ticket::load_all_with_errors(dir.path(), LoadOptions::default()).unwrap();
+
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].to_string().contains("b.md:3:11: `priority` must be an integer 0–4 (found 9)"));
+        assert!(errors[1].to_string().contains("c.md:2:5: `id` must be a string (found a list)"));
+    }
+
+    #[test]
+    fn test_load_large_directory_in_parallel() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let count = FILES_PER_THREAD * 4;
+        for i in 0..count {
+            fs::write(dir.path().join(format!("t-{:04}.md", i)), format!("---\nid: t-{:04}\n---\n# T\n", i)).unwrap();
+        }
+        fs::write(dir.path().join("t-0100x.md"), "---\nid: t-0100\n---\n# Dup\n").unwrap();
+        fs::write(dir.path().join("t-bad.md"), "---\nid: [bad]\n---\n").unwrap();
+
+        let seen = std::sync::Mutex::new(0);
+        let options = LoadOptions { skip_duplicates: true, ..Default::default() };
+        let (tickets, errors) = Ticket::load_dir(dir.path(), options, &|_| *seen.lock().unwrap() += 1).unwrap();
+
+        assert_eq!(tickets.len(), count);
+        assert!(tickets.windows(2).all(|w| w[0].id < w[1].id));
+        // The first file by path keeps the ID, however the work was split
+        assert_eq!(tickets[100].path, dir.path().join("t-0100.md"));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(*seen.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_strict_mode_fails_on_unloadable_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "---\nid: a\n---\n# A\n").unwrap();
+        fs::write(dir.path().join("b.md"), "---\nid: [bad]\n---\n").unwrap();
+        let (tickets, errors) = Ticket::load_dir(dir.path(), LoadOptions::default(), &|_| {}).unwrap();
+        assert_eq!(tickets.len(), 1);
+
+        assert!(Ticket::check_strict(&errors, false).is_ok());
+        let err = Ticket::check_strict(&errors, true).unwrap_err().to_string();
+        assert!(err.contains("1 ticket file(s) could not be loaded"), "{}", err);
+        assert!(Ticket::check_strict(&[], true).is_ok());
+    }
+
+    #[test]
+    fn test_load_all_rejects_duplicate_ids() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("a.md"), "---\nid: t-1\n---\n# A\n").unwrap();
+        fs::write(dir.path().join("b.md"), "---\nid: t-1\n---\n# B\n").unwrap();
+
+        let err = Ticket::load_dir(dir.path(), LoadOptions::default(), &|_| {}).unwrap_err().to_string();
+        assert!(err.contains("t-1:"), "{}", err);
+        assert!(err.contains("a.md and "), "{}", err);
+        assert!(err.contains("b.md"), "{}", err);
+        assert!(err.contains("--skip-duplicates"), "{}", err);
+
+        let options = LoadOptions { skip_duplicates: true, ..Default::default() };
+        let (tickets, errors) = Ticket::load_dir(dir.path(), options, &|_| {}).unwrap();
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].title, "A");
+        assert!(errors[0].to_string().contains("duplicate ID `t-1`"));
+    }
+
+    #[test]
+    fn test_parent_dirs_set_parent_from_epic_directory() {
+        let dir = TicketDir::new();
+        dir.write("epic-1.md", "---\nid: epic-1\ntype: epic\n---\n# Epic\n");
+        dir.write("epic-1/a.md", "---\nid: a\n---\n# A\n");
+        dir.write("epic-1/b.md", "---\nid: b\nparent: other\n---\n# B\n");
+        dir.write(".conflicts/c.remote.md", "Edited on GitHub\n");
+
+        let (tickets, _) = Ticket::load_dir(dir.path(), LoadOptions::default(), &|_| {}).unwrap();
+        assert_eq!(tickets.len(), 1);
+
+        let (tickets, errors) = Ticket::load_dir(dir.path(), LoadOptions { parent_dirs: true, ..Default::default() }, &|_| {}).unwrap();
+        assert!(errors.is_empty());
+        let ids: Vec<&str> = tickets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "epic-1"]);
+        assert_eq!(tickets[0].parent.as_deref(), Some("epic-1"));
+        assert_eq!(tickets[1].parent.as_deref(), Some("other"));
+        assert_eq!(tickets[2].parent, None);
+
+        let tickets_dir = dir.path().file_name().unwrap().to_string_lossy().to_string();
+        assert_eq!(tickets[0].repo_dirs(), vec![tickets_dir.clone(), "epic-1".to_string()]);
+        assert_eq!(tickets[2].repo_dirs(), vec![tickets_dir]);
+    }
+
     #[test]
     fn test_is_synced() {
         let content = r#"---
@@ -417,6 +1291,28 @@ tags: []
         assert_eq!(updated.external_ref, Some("gh-789".to_string()));
     }
 
+    #[test]
+    fn test_write_status_and_title() {
+        let content = r#"---
+id: test-001
+status: open
+---
+# Old title
+
+Body mentions status: open here.
+"#;
+        let file = create_test_ticket(content);
+        let mut ticket = Ticket::parse(file.path()).unwrap();
+
+        ticket.write_status("in_progress").unwrap();
+        ticket.write_title("New title").unwrap();
+
+        let updated = Ticket::parse(file.path()).unwrap();
+        assert_eq!(updated.status, "in_progress");
+        assert_eq!(updated.title, "New title");
+        assert!(updated.body.contains("status: open here"));
+    }
+
     #[test]
     fn test_write_external_ref_update() {
         let content = r#"---
@@ -434,6 +1330,25 @@ external-ref: gh-123
         assert_eq!(updated.external_ref, Some("gh-456".to_string()));
     }
 
+    #[test]
+    fn test_restore_external_ref_removes_field() {
+        let content = r#"---
+id: test-001
+external-ref: gh-123
+status: open
+---
+# Test
+"#;
+        let file = create_test_ticket(content);
+        let mut ticket = Ticket::parse(file.path()).unwrap();
+
+        ticket.restore_external_ref(None).unwrap();
+
+        let updated = Ticket::parse(file.path()).unwrap();
+        assert_eq!(updated.external_ref, None);
+        assert_eq!(updated.status, "open");
+    }
+
     #[test]
     fn test_write_external_ref_with_code_block_example() {
         // This tests the bug where external-ref in a code block example
@@ -542,6 +1457,28 @@ This should be included.
         assert!(body.contains("This should be included"));
     }
 
+    #[test]
+    fn test_public_body_strips_configured_sections() {
+        let content = "---\nid: t-1\n---\n# Title\n\nIntro.\n\n## Scratch:\n\nTodo.\n\n## Notes\n\nNote.\n\n## Design\n\nPlan.\n";
+        let file = create_test_ticket(content);
+        let ticket = Ticket::parse(file.path()).unwrap();
+
+        assert_eq!(ticket.public_body(DEFAULT_PRIVATE_SECTIONS), ticket.body);
+        assert_eq!(ticket.public_body(&["scratch"]), "Intro.\n\n## Notes\n\nNote.\n\n## Design\n\nPlan.");
+        assert_eq!(ticket.public_body(&["Notes", "Scratch"]), "Intro.\n\n## Design\n\nPlan.");
+    }
+
+    #[test]
+    fn test_acceptance_criteria() {
+        let content = "---\nid: t-1\n---\n# T\n\n- [ ] not a criterion\n\n## Acceptance Criteria\n\n- [x] One\n- [ ] Two\n* [X] Three\n\n## Notes\n\n- [ ] later\n";
+        let file = create_test_ticket(content);
+        let ticket = Ticket::parse(file.path()).unwrap();
+        assert_eq!(ticket.acceptance_criteria(), Some((2, 3)));
+
+        let file = create_test_ticket("---\nid: t-2\n---\n# T\n");
+        assert_eq!(Ticket::parse(file.path()).unwrap().acceptance_criteria(), None);
+    }
+
     #[test]
     fn test_github_issue_number_parsing() {
         let content = r#"---
@@ -569,4 +1506,123 @@ external-ref: jira-123
         assert!(!ticket.is_synced());
         assert_eq!(ticket.github_issue_number(), None);
     }
+
+    #[test]
+    fn test_external_ref_list() {
+        let content = "---\nid: test-001\nexternal-ref: [jira-ABC-7, gh-42]\n---\n# Test\n";
+        let file = create_test_ticket(content);
+        let mut ticket = Ticket::parse(file.path()).unwrap();
+        assert!(ticket.is_synced());
+        assert_eq!(ticket.github_issue_number(), Some(42));
+        assert_eq!(ticket.external_refs, vec!["jira-ABC-7", "gh-42"]);
+
+        ticket.write_external_ref("gh-43").unwrap();
+        let written = fs::read_to_string(file.path()).unwrap();
+        assert!(written.contains("external-ref: [jira-ABC-7, gh-43]\n"));
+
+        ticket.restore_external_ref(None).unwrap();
+        let written = fs::read_to_string(file.path()).unwrap();
+        assert!(written.contains("external-ref: jira-ABC-7\n"));
+        assert!(!ticket.is_synced());
+    }
+
+    #[test]
+    fn test_url_removed_with_external_ref() {
+        let content = "---\nid: test-001\n---\n# Test\n";
+        let file = create_test_ticket(content);
+        let mut ticket = Ticket::parse(file.path()).unwrap();
+
+        ticket.write_external_ref("gh-42").unwrap();
+        ticket.write_url("https://github.com/o/r/issues/42").unwrap();
+        let reparsed = Ticket::parse(file.path()).unwrap();
+        assert_eq!(reparsed.url.as_deref(), Some("https://github.com/o/r/issues/42"));
+        assert!(reparsed.custom.is_empty());
+
+        ticket.restore_external_ref(None).unwrap();
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), content);
+    }
+
+    #[test]
+    fn test_write_external_ref_keeps_other_tracker() {
+        let content = "---\nid: test-001\nexternal-ref: jira-123\n---\n# Test\n";
+        let file = create_test_ticket(content);
+        let mut ticket = Ticket::parse(file.path()).unwrap();
+        assert_eq!(ticket.external_ref, None);
+
+        ticket.write_external_ref("gh-9").unwrap();
+
+        let reparsed = Ticket::parse(file.path()).unwrap();
+        assert_eq!(reparsed.external_refs, vec!["jira-123", "gh-9"]);
+        assert_eq!(reparsed.github_issue_number(), Some(9));
+    }
+
+    #[test]
+    fn test_discussion_ref_and_section() {
+        let file = create_test_ticket("---\nid: t-1\nexternal-ref: ghd-12\n---\n# Idea\n\nPitch\n\n## Notes\n\nmine\n");
+        let mut ticket = Ticket::parse(file.path()).unwrap();
+        assert_eq!(ticket.discussion_number(), Some(12));
+        assert_eq!(ticket.github_issue_number(), None);
+        assert!(!ticket.is_synced());
+
+        assert!(ticket.write_section(DISCUSSION_SECTION, "> first").unwrap());
+        assert!(ticket.write_section("Notes", "still mine").unwrap());
+        assert!(ticket.write_section(DISCUSSION_SECTION, "> first\n\n> second").unwrap());
+        assert!(!ticket.write_section(DISCUSSION_SECTION, "> first\n\n> second").unwrap());
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "---\nid: t-1\nexternal-ref: ghd-12\n---\n# Idea\n\nPitch\n\n## Notes\n\nstill mine\n\n\
+             ## Discussion\n\n> first\n\n> second\n"
+        );
+    }
+
+    #[test]
+    fn test_draft_item_id() {
+        let content = r#"---
+id: test-001
+type: idea
+external-ref: draft-PVTI_lADOabc
+---
+# Test
+"#;
+        let file = create_test_ticket(content);
+        let ticket = Ticket::parse(file.path()).unwrap();
+        // Draft items aren't repo issues
+        assert!(!ticket.is_synced());
+        assert_eq!(ticket.draft_item_id(), Some("PVTI_lADOabc"));
+    }
+
+    #[test]
+    fn test_match_id_prefers_closest_match() {
+        let dir = tempfile::TempDir::new().unwrap();
+        for id in ["ttr-0001", "ttr-0010", "ttr-0100", "ttr-1000", "web-0012"] {
+            std::fs::write(dir.path().join(format!("{}.md", id)), format!("---\nid: {}\n---\n# T\n", id)).unwrap();
+        }
+        let tickets = Ticket::load_all(dir.path(), LoadOptions::default()).unwrap();
+        let ids = |arg: &str| match_id(&tickets, arg).iter().map(|t| t.id.as_str()).collect::<Vec<_>>();
+
+        assert_eq!(ids("ttr-0010"), vec!["ttr-0010"]);
+        assert_eq!(ids("1"), vec!["ttr-0001"]);
+        assert_eq!(ids("12"), vec!["web-0012"]);
+        assert_eq!(ids("ttr-00"), vec!["ttr-0001", "ttr-0010"]);
+        assert_eq!(ids("00"), vec!["ttr-0001", "ttr-0010", "web-0012"]);
+        assert_eq!(ids("r-1"), vec!["ttr-1000"]);
+        assert!(ids("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_aliases_resolve_references_and_collide() {
+        let dir = TicketDir::new();
+        dir.write("t-1.md", "---\nid: t-1\naliases: [auth-bug]\n---\n# Auth\n");
+        dir.write("t-2.md", "---\nid: t-2\ndeps: [auth-bug]\nparent: auth-bug\n---\n# Login\n");
+
+        let tickets = dir.load();
+        assert_eq!(tickets[1].deps, vec!["t-1"]);
+        assert_eq!(tickets[1].parent.as_deref(), Some("t-1"));
+        assert_eq!(match_id(&tickets, "auth-bug")[0].id, "t-1");
+
+        dir.write("t-3.md", "---\nid: t-3\naliases: [auth-bug, t-2]\n---\n# Other\n");
+        let err = Ticket::load_all(dir.path(), LoadOptions::default()).unwrap_err().to_string();
+        assert!(err.contains("auth-bug: alias of both t-1 and t-3"), "{}", err);
+        assert!(err.contains("t-2: alias of t-3 is the ID of"), "{}", err);
+    }
 }
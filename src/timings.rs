@@ -0,0 +1,152 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Time spent per phase and the latency of each GitHub request
+#[derive(Debug, Default)]
+pub struct Timings {
+    /// Phase name -> when it first started and its total time
+    phases: Vec<(&'static str, Instant, Duration)>,
+    requests: Vec<Duration>,
+}
+
+impl Timings {
+    fn add_phase(&mut self, name: &'static str, started: Instant, elapsed: Duration) {
+        match self.phases.iter_mut().find(|(n, _, _)| *n == name) {
+            Some((_, first, total)) => {
+                *first = (*first).min(started);
+                *total += elapsed;
+            }
+            None => self.phases.push((name, started, elapsed)),
+        }
+    }
+
+    /// Latency at percentile `p` (0-100), by nearest rank
+    fn percentile(&self, p: usize) -> Duration {
+        let mut sorted = self.requests.clone();
+        sorted.sort();
+        match sorted.len() {
+            0 => Duration::ZERO,
+            n => sorted[((p * n).div_ceil(100)).clamp(1, n) - 1],
+        }
+    }
+
+    fn report(&self) -> String {
+        let mut out = String::from("Timings:\n");
+        let mut phases: Vec<_> = self.phases.iter().collect();
+        phases.sort_by_key(|(_, started, _)| *started);
+        for (name, _, elapsed) in phases {
+            out.push_str(&format!("  {:<16}{:>9}\n", name, ms(*elapsed)));
+        }
+        if self.requests.is_empty() {
+            out.push_str("  No GitHub requests\n");
+        } else {
+            out.push_str(&format!(
+                "  {} request(s): p50 {}  p90 {}  p99 {}  max {}\n",
+                self.requests.len(),
+                ms(self.percentile(50)),
+                ms(self.percentile(90)),
+                ms(self.percentile(99)),
+                ms(self.percentile(100)),
+            ));
+        }
+        out
+    }
+}
+
+fn ms(d: Duration) -> String {
+    format!("{}ms", d.as_millis())
+}
+
+/// Collects the timings of a push (`ttr push --timings`), shared by the
+/// sync engine and its GitHub client
+#[derive(Debug, Clone, Default)]
+pub struct Recorder(Arc<Mutex<Timings>>);
+
+impl Recorder {
+    /// Record a phase timed before the recorder existed
+    pub fn add(&self, span: Span) {
+        self.0.lock().unwrap().add_phase(span.name, span.started, span.elapsed);
+    }
+
+    /// Record the latency of one GitHub request
+    pub fn request(&self, elapsed: Duration) {
+        self.0.lock().unwrap().requests.push(elapsed);
+    }
+
+    /// The timing report for everything recorded so far, phases in the
+    /// order they first ran
+    pub fn report(&self) -> String {
+        self.0.lock().unwrap().report()
+    }
+}
+
+/// One run of a phase
+#[derive(Debug, Clone, Copy)]
+pub struct Span {
+    name: &'static str,
+    started: Instant,
+    elapsed: Duration,
+}
+
+impl Span {
+    /// Run `f`, timing it as phase `name`
+    pub fn time<T>(name: &'static str, f: impl FnOnce() -> T) -> (T, Self) {
+        let started = Instant::now();
+        let result = f();
+        (result, Self { name, started, elapsed: started.elapsed() })
+    }
+}
+
+/// Times a phase until dropped, if there's a recorder
+pub struct Phase {
+    recorder: Option<Recorder>,
+    name: &'static str,
+    started: Instant,
+}
+
+impl Phase {
+    pub fn start(recorder: Option<&Recorder>, name: &'static str) -> Self {
+        Self {
+            recorder: recorder.cloned(),
+            name,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Phase {
+    fn drop(&mut self) {
+        if let Some(recorder) = &self.recorder {
+            recorder.add(Span {
+                name: self.name,
+                started: self.started,
+                elapsed: self.started.elapsed(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_and_percentiles() {
+        let start = Instant::now();
+        let mut timings = Timings::default();
+        timings.add_phase("create", start + Duration::from_millis(1), Duration::from_millis(30));
+        timings.add_phase("update", start + Duration::from_millis(40), Duration::from_millis(5));
+        timings.add_phase("create", start + Duration::from_millis(50), Duration::from_millis(12));
+        // Recorded last, but ran first
+        timings.add_phase("parse", start, Duration::from_millis(2));
+        timings.requests = (1..=10).map(Duration::from_millis).collect();
+
+        assert_eq!(timings.percentile(50), Duration::from_millis(5));
+        assert_eq!(timings.percentile(90), Duration::from_millis(9));
+        assert_eq!(timings.percentile(100), Duration::from_millis(10));
+
+        let report = timings.report();
+        assert!(report.starts_with("Timings:\n  parse                 2ms\n  create               42ms\n  update"), "{}", report);
+        assert!(report.contains("10 request(s): p50 5ms  p90 9ms  p99 10ms  max 10ms"));
+    }
+}
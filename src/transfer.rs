@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use std::fs;
+
+use crate::ticket::Ticket;
+
+/// An issue's move from one repository to another
+#[derive(Debug, Clone, Copy)]
+pub struct Move<'a> {
+    /// The configured repository, where bare `#N` references point
+    pub home: &'a str,
+    pub from: &'a str,
+    pub old: u64,
+    pub to: &'a str,
+    pub new: u64,
+}
+
+impl Move<'_> {
+    /// How tickets in the home repository should refer to the moved issue
+    fn new_ref(&self) -> String {
+        if self.to == self.home {
+            format!("#{}", self.new)
+        } else {
+            format!("{}#{}", self.to, self.new)
+        }
+    }
+}
+
+/// Point references to a moved issue at its new home
+///
+/// Rewrites issue URLs, `owner/name#N` references, and, when the issue left
+/// the home repository, bare `#N` references. Fenced code blocks are left
+/// alone.
+pub fn rewrite_issue_refs(text: &str, moved: &Move) -> String {
+    let old_url = format!("https://github.com/{}/issues/{}", moved.from, moved.old);
+    let new_url = format!("https://github.com/{}/issues/{}", moved.to, moved.new);
+    let qualified = format!("{}#{}", moved.from, moved.old);
+    let bare = format!("#{}", moved.old);
+    let new_ref = moved.new_ref();
+
+    let mut out = String::with_capacity(text.len());
+    let mut in_fence = false;
+    for line in text.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if in_fence || line.trim_start().starts_with("```") {
+            out.push_str(line);
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(c) = rest.chars().next() {
+            let prev = out.chars().next_back();
+            let at_word_start = !prev.is_some_and(|p| p.is_ascii_alphanumeric() || "/-._&#".contains(p));
+            let matched = [
+                (old_url.as_str(), new_url.as_str(), true),
+                (qualified.as_str(), new_ref.as_str(), at_word_start),
+                (bare.as_str(), new_ref.as_str(), at_word_start && moved.from == moved.home),
+            ]
+            .into_iter()
+            .find(|(pattern, _, allowed)| {
+                *allowed
+                    && rest.starts_with(pattern)
+                    && !rest[pattern.len()..].starts_with(|c: char| c.is_ascii_digit())
+            });
+            match matched {
+                Some((pattern, replacement, _)) => {
+                    out.push_str(replacement);
+                    rest = &rest[pattern.len()..];
+                }
+                None => {
+                    out.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Rewrite references to a moved issue in every other ticket's body
+///
+/// Returns the IDs of the tickets that changed. Tickets that depend on or
+/// link to the moved one by ID need no edit; their issues pick up the new
+/// reference on the next push.
+pub fn fix_references(tickets: &[Ticket], moved_id: &str, moved: &Move) -> Result<Vec<String>> {
+    let mut updated = Vec::new();
+    for ticket in tickets.iter().filter(|t| t.id != moved_id) {
+        let content = fs::read_to_string(&ticket.path)
+            .with_context(|| format!("Failed to read ticket: {}", ticket.path.display()))?;
+        let (frontmatter, body) = content.split_at(body_start(&content));
+        let rewritten = rewrite_issue_refs(body, moved);
+        if rewritten == body {
+            continue;
+        }
+        fs::write(&ticket.path, format!("{}{}", frontmatter, rewritten))
+            .with_context(|| format!("Failed to write ticket: {}", ticket.path.display()))?;
+        updated.push(ticket.id.clone());
+    }
+    Ok(updated)
+}
+
+/// Byte offset where the markdown after the frontmatter starts
+fn body_start(content: &str) -> usize {
+    let Some(after_open) = content.strip_prefix("---\n") else {
+        return 0;
+    };
+    match after_open.find("\n---") {
+        Some(end) => {
+            let close = 4 + end + 4;
+            close + content[close..].find('\n').map_or(content.len() - close, |n| n + 1)
+        }
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::TicketDir;
+
+    const MOVED_OUT: Move = Move {
+        home: "owner/repo",
+        from: "owner/repo",
+        old: 5,
+        to: "owner/other",
+        new: 12,
+    };
+
+    #[test]
+    fn test_rewrite_issue_refs() {
+        let text = "Blocked by #5 and owner/repo#5, not #50 or x#5.\n\
+                    See https://github.com/owner/repo/issues/5 and owner/repo#51\n\
+                    ```\n#5 in code\n```\n";
+        assert_eq!(
+            rewrite_issue_refs(text, &MOVED_OUT),
+            "Blocked by owner/other#12 and owner/other#12, not #50 or x#5.\n\
+             See https://github.com/owner/other/issues/12 and owner/repo#51\n\
+             ```\n#5 in code\n```\n"
+        );
+
+        // Moving back home turns the qualified reference bare again; bare
+        // references to the home repo's own #12 are someone else's issue
+        let back = Move {
+            home: "owner/repo",
+            from: "owner/other",
+            old: 12,
+            to: "owner/repo",
+            new: 9,
+        };
+        assert_eq!(rewrite_issue_refs("owner/other#12, #12", &back), "#9, #12");
+    }
+
+    #[test]
+    fn test_fix_references_rewrites_bodies_only() {
+        let dir = TicketDir::new();
+        dir.write("t-1.md", "---\nid: t-1\nexternal-ref: gh-5\n---\n# One\n\nSee #5\n");
+        dir.write("t-2.md", "---\nid: t-2\ndeps: [t-1]\n---\n# Two\n\nNeeds #5 first\n");
+        dir.write("t-3.md", "---\nid: t-3\nexternal-ref: gh-6\n---\n# Three\n\nSee #6\n");
+        let tickets = dir.load();
+
+        let updated = fix_references(&tickets, "t-1", &MOVED_OUT).unwrap();
+
+        assert_eq!(updated, vec!["t-2"]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("t-2.md")).unwrap(),
+            "---\nid: t-2\ndeps: [t-1]\n---\n# Two\n\nNeeds owner/other#12 first\n"
+        );
+        assert!(fs::read_to_string(dir.path().join("t-1.md")).unwrap().ends_with("See #5\n"));
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::board::sync_indicator;
+use crate::ticket::Ticket;
+
+/// The parent/child hierarchy as indented lines, for `ttr tree`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TreeView {
+    /// Tickets without a parent that have children, with their descendants
+    pub lines: Vec<String>,
+    /// Tickets whose parent isn't a known ticket, with their descendants
+    pub orphans: Vec<String>,
+    /// Tickets with neither parent nor children, left out
+    pub standalone: usize,
+}
+
+/// Status glyph: ○ open, ◐ in progress, ✓ closed
+pub fn glyph(status: &str) -> &'static str {
+    match status {
+        "closed" => "✓",
+        "in_progress" => "◐",
+        _ => "○",
+    }
+}
+
+/// Lay out the hierarchy, or only the subtree under `root`
+///
+/// Children are listed by ID. Tickets caught in a `parent` cycle are shown
+/// as roots, marked as such.
+pub fn render(tickets: &[Ticket], root: Option<&str>) -> TreeView {
+    let by_id: HashMap<&str, &Ticket> = tickets.iter().map(|t| (t.id.as_str(), t)).collect();
+    let mut children: HashMap<&str, Vec<&Ticket>> = HashMap::new();
+    for ticket in tickets {
+        if let Some(parent) = &ticket.parent {
+            children.entry(parent.as_str()).or_default().push(ticket);
+        }
+    }
+
+    let mut view = TreeView::default();
+    let mut seen = HashSet::new();
+    if let Some(root) = root.and_then(|id| by_id.get(id)) {
+        add_subtree(&mut view.lines, root, None, &children, &mut seen);
+        return view;
+    }
+
+    for ticket in tickets {
+        match &ticket.parent {
+            None if children.contains_key(ticket.id.as_str()) => {
+                add_subtree(&mut view.lines, ticket, None, &children, &mut seen)
+            }
+            None => view.standalone += 1,
+            Some(parent) if !by_id.contains_key(parent.as_str()) => {
+                let note = format!("(parent {} not found)", parent);
+                add_subtree(&mut view.orphans, ticket, Some(&note), &children, &mut seen);
+            }
+            Some(_) => {}
+        }
+    }
+    for ticket in tickets {
+        if ticket.parent.is_some() && !seen.contains(ticket.id.as_str()) {
+            add_subtree(&mut view.lines, ticket, Some("(parent cycle)"), &children, &mut seen);
+        }
+    }
+    view
+}
+
+/// Add a ticket's line, then its children's indented below it
+fn add_subtree<'a>(
+    lines: &mut Vec<String>,
+    ticket: &'a Ticket,
+    note: Option<&str>,
+    children: &HashMap<&str, Vec<&'a Ticket>>,
+    seen: &mut HashSet<&'a str>,
+) {
+    seen.insert(ticket.id.as_str());
+    let mut line = format!("{} {}  {}  {}", glyph(&ticket.status), ticket.id, sync_indicator(ticket), ticket.title);
+    if let Some(note) = note {
+        line.push_str("  ");
+        line.push_str(note);
+    }
+    lines.push(line);
+
+    let kids: Vec<&Ticket> = children
+        .get(ticket.id.as_str())
+        .map(|kids| kids.iter().copied().filter(|k| !seen.contains(k.id.as_str())).collect())
+        .unwrap_or_default();
+    for (i, kid) in kids.iter().enumerate() {
+        let last = i + 1 == kids.len();
+        let start = lines.len();
+        add_subtree(lines, kid, None, children, seen);
+        // Prefix the child's lines: its own with a branch, its descendants'
+        // with the rail continuing past it
+        for (j, line) in lines[start..].iter_mut().enumerate() {
+            let branch = match (j, last) {
+                (0, false) => "├── ",
+                (0, true) => "└── ",
+                (_, false) => "│   ",
+                (_, true) => "    ",
+            };
+            *line = format!("{}{}", branch, line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ticket::TicketDir;
+
+    #[test]
+    fn test_render_tree_with_orphans() {
+        let dir = TicketDir::new();
+        dir.write("epic.md", "---\nid: epic\ntype: epic\nexternal-ref: gh-10\n---\n# Epic\n");
+        dir.write("a.md", "---\nid: a\nparent: epic\nstatus: closed\n---\n# A\n");
+        dir.write("a1.md", "---\nid: a1\nparent: a\nstatus: in_progress\n---\n# A1\n");
+        dir.write("b.md", "---\nid: b\nparent: epic\n---\n# B\n");
+        dir.write("lost.md", "---\nid: lost\nparent: gone\n---\n# Lost\n");
+        dir.write("solo.md", "---\nid: solo\n---\n# Solo\n");
+        let tickets = dir.load();
+
+        let view = render(&tickets, None);
+        assert_eq!(
+            view.lines,
+            vec![
+                "○ epic  #10  Epic",
+                "├── ✓ a  local  A",
+                "│   └── ◐ a1  local  A1",
+                "└── ○ b  local  B",
+            ]
+        );
+        assert_eq!(view.orphans, vec!["○ lost  local  Lost  (parent gone not found)"]);
+        assert_eq!(view.standalone, 1);
+
+        let view = render(&tickets, Some("a"));
+        assert_eq!(view.lines, vec!["✓ a  local  A", "└── ◐ a1  local  A1"]);
+        assert!(view.orphans.is_empty());
+    }
+}
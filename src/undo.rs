@@ -0,0 +1,378 @@
+use crate::audit::{AuditAction, AuditEntry, AuditLog};
+use crate::github::client::GitHubClient;
+use crate::github::issues::IssueUpdate;
+
+/// A remote change that reverts one audit entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum UndoStep {
+    /// Close an issue (created or reopened by the run)
+    Close { ticket: String, issue: u64, node: String },
+    /// Reopen an issue closed by the run
+    Reopen { ticket: String, issue: u64, node: String },
+    /// Restore an issue's title and/or body (None leaves the field as is)
+    Restore {
+        ticket: String,
+        issue: u64,
+        node: String,
+        title: Option<String>,
+        body: Option<String>,
+    },
+    /// Remove an item (issue or draft) added to a project by the run
+    RemoveItem { ticket: String, project_id: String, item_id: String },
+}
+
+/// How to revert one run from the audit log
+#[derive(Debug, Default)]
+pub struct UndoPlan {
+    pub steps: Vec<UndoStep>,
+    /// Local external-ref writes to revert: (ticket ID, previous value)
+    pub external_refs: Vec<(String, Option<String>)>,
+    /// Changes that can't be reverted: (ticket ID, description)
+    pub skipped: Vec<(String, String)>,
+}
+
+impl UndoPlan {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty() && self.external_refs.is_empty()
+    }
+}
+
+/// Most recent run that made changes and hasn't been undone
+pub fn last_run(entries: &[AuditEntry]) -> Option<&str> {
+    entries
+        .iter()
+        .rev()
+        .filter(|e| e.undoes.is_none())
+        .map(|e| e.run.as_str())
+        .find(|run| !entries.iter().any(|e| e.undoes.as_deref() == Some(*run)))
+}
+
+/// Plan the reversal of a run's entries, newest change first
+pub fn plan(entries: &[AuditEntry], run: &str) -> UndoPlan {
+    let mut plan = UndoPlan::default();
+
+    for entry in entries.iter().rev().filter(|e| e.run == run && e.undoes.is_none()) {
+        let ticket = entry.ticket.clone();
+        let issue = entry.issue.unwrap_or_default();
+        let node = entry.node.clone().unwrap_or_default();
+        let external_ref = entry.changed("external-ref");
+
+        match entry.action {
+            AuditAction::Create => {
+                // Draft conversions can't be turned back into drafts
+                if let Some(old) = external_ref.and_then(|c| c.old.as_ref()) {
+                    plan.skipped.push((ticket, format!("{} converted to issue #{}", old, issue)));
+                    continue;
+                }
+                if !node.is_empty() {
+                    plan.steps.push(UndoStep::Close { ticket: ticket.clone(), issue, node });
+                }
+                if external_ref.is_some() {
+                    plan.external_refs.push((ticket, None));
+                }
+            }
+            AuditAction::Draft => match entry.changed("project").and_then(|c| c.new.clone()) {
+                Some(project_id) => {
+                    plan.steps.push(UndoStep::RemoveItem {
+                        ticket: ticket.clone(),
+                        project_id,
+                        item_id: node,
+                    });
+                    if let Some(change) = external_ref {
+                        plan.external_refs.push((ticket, change.old.clone()));
+                    }
+                }
+                None => plan.skipped.push((ticket, "draft content update".to_string())),
+            },
+            AuditAction::Update => plan.steps.push(UndoStep::Restore {
+                ticket,
+                issue,
+                node,
+                title: entry.changed("title").and_then(|c| c.old.clone()),
+                body: entry.changed("body").and_then(|c| c.old.clone()),
+            }),
+            AuditAction::Close => plan.steps.push(UndoStep::Reopen { ticket, issue, node }),
+            AuditAction::Reopen => plan.steps.push(UndoStep::Close { ticket, issue, node }),
+            AuditAction::ProjectAdd => {
+                let project_id = entry.changed("project").and_then(|c| c.new.clone());
+                match project_id {
+                    Some(project_id) if !node.is_empty() => plan.steps.push(UndoStep::RemoveItem {
+                        ticket,
+                        project_id,
+                        item_id: node,
+                    }),
+                    _ => plan.skipped.push((ticket, "project add (item already existed)".to_string())),
+                }
+            }
+            AuditAction::Link => plan.skipped.push((ticket, "link".to_string())),
+            AuditAction::ProjectField => {
+                let fields: Vec<&str> = entry.changes.iter().map(|c| c.field.as_str()).collect();
+                plan.skipped.push((ticket, format!("project {}", fields.join(", "))));
+            }
+            AuditAction::ProjectRemove => {}
+        }
+    }
+
+    plan
+}
+
+/// Counts of remote changes reverted by `apply`
+#[derive(Debug, Default)]
+pub struct UndoSummary {
+    pub reverted: u32,
+    pub failed: u32,
+}
+
+/// Apply a plan's remote steps, recording each reversal against `run`
+pub async fn apply(
+    client: &GitHubClient,
+    owner: &str,
+    repo_name: &str,
+    plan: &UndoPlan,
+    audit: &AuditLog,
+    run: &str,
+) -> UndoSummary {
+    let mut summary = UndoSummary::default();
+
+    // Restore titles/bodies; fields the run didn't change keep their current value
+    let restores: Vec<&UndoStep> = plan
+        .steps
+        .iter()
+        .filter(|s| matches!(s, UndoStep::Restore { .. }))
+        .collect();
+    if !restores.is_empty() {
+        let numbers: Vec<u64> = restores
+            .iter()
+            .filter_map(|s| match s {
+                UndoStep::Restore { issue, .. } => Some(*issue),
+                _ => None,
+            })
+            .collect();
+        let current = client
+            .get_issues_batch(owner, repo_name, &numbers)
+            .await
+            .unwrap_or_default();
+
+        let mut updates = Vec::new();
+        let mut pending = Vec::new();
+        for step in restores {
+            let UndoStep::Restore { ticket, issue, node, title, body } = step else {
+                continue;
+            };
+            let issue = *issue;
+            let Some(existing) = current.get(&issue) else {
+                println!("FAIL    {}  Issue #{} not found", ticket, issue);
+                summary.failed += 1;
+                continue;
+            };
+            updates.push(IssueUpdate {
+                issue_id: node.to_string(),
+                title: title.clone().unwrap_or_else(|| existing.title.clone()),
+                body: body.clone().unwrap_or_else(|| existing.body.clone()),
+                issue_type_id: None,
+            });
+            pending.push((ticket, issue, node, existing));
+        }
+
+        match client.update_issues_batch(&updates).await {
+            Ok(results) => {
+                for ((ticket, issue, node, existing), update) in pending.iter().zip(&updates) {
+                    match results.get(node.as_str()) {
+                        Some(Ok(_)) => {
+                            println!("RESTORE {} → #{}  {}", ticket, issue, update.title);
+                            let mut entry = AuditEntry::new(AuditAction::Update, ticket)
+                                .issue(*issue)
+                                .node(node)
+                                .undoes(run);
+                            if existing.title != update.title {
+                                entry = entry.change("title", Some(&existing.title), Some(&update.title));
+                            }
+                            if existing.body != update.body {
+                                entry = entry.change("body", Some(&existing.body), Some(&update.body));
+                            }
+                            audit.record(entry);
+                            summary.reverted += 1;
+                        }
+                        Some(Err(e)) => {
+                            println!("FAIL    {}  {}", ticket, e);
+                            summary.failed += 1;
+                        }
+                        None => {
+                            println!("FAIL    {}  no response for #{}", ticket, issue);
+                            summary.failed += 1;
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                println!("FAIL    restoring {} issue(s): {}", pending.len(), e);
+                summary.failed += pending.len() as u32;
+            }
+        }
+    }
+
+    // Close and reopen issues
+    for closing in [true, false] {
+        let targets: Vec<(&str, u64, &str)> = plan
+            .steps
+            .iter()
+            .filter_map(|s| match s {
+                UndoStep::Close { ticket, issue, node } if closing => {
+                    Some((ticket.as_str(), *issue, node.as_str()))
+                }
+                UndoStep::Reopen { ticket, issue, node } if !closing => {
+                    Some((ticket.as_str(), *issue, node.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        if targets.is_empty() {
+            continue;
+        }
+
+        let ids: Vec<String> = targets.iter().map(|(_, _, node)| node.to_string()).collect();
+        let result = if closing {
+            client.close_issues_batch(&ids).await
+        } else {
+            client.reopen_issues_batch(&ids).await
+        };
+
+        let (tag, verb, action, old, new) = if closing {
+            ("CLOSE   ", "closing", AuditAction::Close, "OPEN", "CLOSED")
+        } else {
+            ("REOPEN  ", "reopening", AuditAction::Reopen, "CLOSED", "OPEN")
+        };
+        match result {
+            Ok(()) => {
+                for (ticket, issue, node) in &targets {
+                    println!("{}{} → #{}", tag, ticket, issue);
+                    audit.record(
+                        AuditEntry::new(action, ticket)
+                            .issue(*issue)
+                            .node(node)
+                            .change("state", Some(old), Some(new))
+                            .undoes(run),
+                    );
+                    summary.reverted += 1;
+                }
+            }
+            Err(e) => {
+                println!("FAIL    {} {} issue(s): {}", verb, targets.len(), e);
+                summary.failed += targets.len() as u32;
+            }
+        }
+    }
+
+    // Remove project items, batched per project
+    let mut by_project: Vec<(&str, Vec<(&str, &str)>)> = Vec::new();
+    for step in &plan.steps {
+        if let UndoStep::RemoveItem { ticket, project_id, item_id } = step {
+            let item = (ticket.as_str(), item_id.as_str());
+            match by_project.iter_mut().find(|(id, _)| id == project_id) {
+                Some((_, items)) => items.push(item),
+                None => by_project.push((project_id, vec![item])),
+            }
+        }
+    }
+    for (project_id, items) in by_project {
+        let ids: Vec<String> = items.iter().map(|(_, id)| id.to_string()).collect();
+        match client.delete_project_items_batch(project_id, &ids).await {
+            Ok(results) => {
+                for ((ticket, item_id), result) in items.iter().zip(results) {
+                    match result {
+                        Ok(()) => {
+                            println!("REMOVE  {}  project item {}", ticket, item_id);
+                            audit.record(
+                                AuditEntry::new(AuditAction::ProjectRemove, ticket)
+                                    .node(item_id)
+                                    .change("project", Some(project_id), None)
+                                    .undoes(run),
+                            );
+                            summary.reverted += 1;
+                        }
+                        Err(e) => {
+                            println!("FAIL    {}  {}", ticket, e);
+                            summary.failed += 1;
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                println!("FAIL    removing {} project item(s): {}", items.len(), e);
+                summary.failed += items.len() as u32;
+            }
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(run: &str, action: AuditAction, ticket: &str) -> AuditEntry {
+        let mut e = AuditEntry::new(action, ticket).issue(7).node("I_7");
+        e.run = run.to_string();
+        e
+    }
+
+    #[test]
+    fn test_last_run_skips_undone_runs() {
+        let mut undo = entry("r3", AuditAction::Reopen, "a");
+        undo = undo.undoes("r2");
+        let entries = vec![
+            entry("r1", AuditAction::Update, "a"),
+            entry("r2", AuditAction::Close, "a"),
+            undo,
+        ];
+        assert_eq!(last_run(&entries), Some("r1"));
+        assert_eq!(last_run(&entries[..2]), Some("r2"));
+        assert_eq!(last_run(&[]), None);
+    }
+
+    #[test]
+    fn test_plan_reverses_run() {
+        let entries = vec![
+            entry("r0", AuditAction::Close, "old"),
+            entry("r1", AuditAction::Create, "new").change("external-ref", None, Some("gh-7")),
+            entry("r1", AuditAction::Update, "edit").change("title", Some("Before"), Some("After")),
+            entry("r1", AuditAction::Close, "done"),
+            entry("r1", AuditAction::ProjectAdd, "new")
+                .node("PVTI_1")
+                .change("project", None, Some("PVT_1")),
+            entry("r1", AuditAction::Link, "new").change("parent", None, Some("epic")),
+        ];
+
+        let plan = plan(&entries, "r1");
+
+        assert_eq!(plan.steps.len(), 4);
+        assert_eq!(
+            plan.steps[0],
+            UndoStep::RemoveItem {
+                ticket: "new".to_string(),
+                project_id: "PVT_1".to_string(),
+                item_id: "PVTI_1".to_string(),
+            }
+        );
+        assert!(matches!(&plan.steps[1], UndoStep::Reopen { ticket, .. } if ticket == "done"));
+        assert!(matches!(
+            &plan.steps[2],
+            UndoStep::Restore { title: Some(t), body: None, .. } if t == "Before"
+        ));
+        assert!(matches!(&plan.steps[3], UndoStep::Close { ticket, .. } if ticket == "new"));
+        assert_eq!(plan.external_refs, vec![("new".to_string(), None)]);
+        assert_eq!(plan.skipped, vec![("new".to_string(), "link".to_string())]);
+    }
+
+    #[test]
+    fn test_plan_skips_draft_conversion() {
+        let entries = vec![entry("r1", AuditAction::Create, "t")
+            .change("external-ref", Some("draft-PVTI_1"), Some("gh-7"))];
+
+        let plan = plan(&entries, "r1");
+
+        assert!(plan.is_empty());
+        assert_eq!(plan.skipped.len(), 1);
+    }
+}
@@ -0,0 +1,286 @@
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::time::{Duration, SystemTime};
+
+use crate::ticket;
+
+/// Watches the tickets directory and yields debounced batches of changed files
+pub struct TicketWatcher {
+    // Dropping the watcher stops event delivery
+    _watcher: Option<RecommendedWatcher>,
+    rx: Receiver<notify::Result<Event>>,
+    debounce: Duration,
+    tickets_dir: PathBuf,
+    parent_dirs: bool,
+    // Modification times of files ttr wrote itself
+    written: HashMap<PathBuf, SystemTime>,
+}
+
+impl TicketWatcher {
+    /// Start watching a tickets directory, and its epic directories with
+    /// `[sync] parent_dirs`
+    pub fn new(tickets_dir: &Path, debounce: Duration, parent_dirs: bool) -> Result<Self> {
+        let (tx, rx) = channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        let mode = if parent_dirs { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+        watcher.watch(tickets_dir, mode)?;
+
+        let mut ticket_watcher = TicketWatcher::from_receiver(rx, tickets_dir, debounce, parent_dirs);
+        ticket_watcher._watcher = Some(watcher);
+        Ok(ticket_watcher)
+    }
+
+    /// Batch events from any source, e.g. ones sent by hand
+    fn from_receiver(
+        rx: Receiver<notify::Result<Event>>,
+        tickets_dir: &Path,
+        debounce: Duration,
+        parent_dirs: bool,
+    ) -> Self {
+        TicketWatcher {
+            _watcher: None,
+            rx,
+            debounce,
+            tickets_dir: tickets_dir.to_path_buf(),
+            parent_dirs,
+            written: HashMap::new(),
+        }
+    }
+
+    /// Whether a path is in the tickets directory or an epic directory
+    fn watches(&self, path: &Path) -> bool {
+        let tickets_dir = self.tickets_dir.as_path();
+        path.parent().is_some_and(|dir| {
+            dir == tickets_dir || (self.parent_dirs && dir.parent() == Some(tickets_dir) && ticket::is_epic_dir(dir))
+        })
+    }
+
+    /// Paths of a change that are tickets, not ttr's own hidden ones, and
+    /// not files left as ttr last wrote them
+    fn changed_paths(&self, event: notify::Result<Event>) -> Vec<PathBuf> {
+        changed_paths(event)
+            .into_iter()
+            .filter(|p| self.watches(p))
+            .filter(|p| self.written.get(p).is_none_or(|&mtime| modified(p) != Some(mtime)))
+            .collect()
+    }
+
+    /// Block until ticket files change, then wait for edits to settle
+    ///
+    /// Returns the changed ticket files, or `None` if the watcher stopped.
+    pub fn next_batch(&self) -> Option<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+
+        // Wait for the first relevant change
+        loop {
+            match self.rx.recv() {
+                Ok(event) => {
+                    paths.extend(self.changed_paths(event));
+                    if !ticket_paths(&paths).is_empty() {
+                        break;
+                    }
+                }
+                Err(_) => return None,
+            }
+        }
+
+        // Keep collecting until no events arrive within the debounce window
+        loop {
+            match self.rx.recv_timeout(self.debounce) {
+                Ok(event) => paths.extend(self.changed_paths(event)),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        Some(ticket_paths(&paths))
+    }
+
+    /// Discard queued events, e.g. edits made while a prompt was open
+    pub fn drain(&self) {
+        while self.rx.try_recv().is_ok() {}
+    }
+
+    /// Ignore changes to ticket files as they are now, e.g. after a push
+    /// wrote external refs back
+    ///
+    /// Events for these files can arrive late, so they're matched by
+    /// modification time rather than drained; a later edit still counts.
+    pub fn ignore_own_writes(&mut self) {
+        let mut dirs = vec![self.tickets_dir.clone()];
+        let mut files = Vec::new();
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else { continue };
+            for path in entries.flatten().map(|e| e.path()) {
+                if path.is_dir() {
+                    if self.parent_dirs && dir == self.tickets_dir {
+                        dirs.push(path);
+                    }
+                } else if self.watches(&path) {
+                    files.push(path);
+                }
+            }
+        }
+
+        self.written = ticket_paths(&files)
+            .into_iter()
+            .filter_map(|p| modified(&p).map(|mtime| (p, mtime)))
+            .collect();
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Paths touched by a create or modify event
+fn changed_paths(event: notify::Result<Event>) -> Vec<PathBuf> {
+    match event {
+        Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+            event.paths
+        }
+        Ok(_) => Vec::new(),
+        Err(e) => {
+            eprintln!("Warning: watch error: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Filter to existing ticket markdown files, sorted and deduplicated
+///
+/// Hidden files and non-markdown files (editor swap/backup files) are skipped.
+pub fn ticket_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut result: Vec<PathBuf> = paths
+        .iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| !n.starts_with('.') && n != "sync.md")
+        })
+        .filter(|p| p.is_file())
+        .cloned()
+        .collect();
+
+    result.sort();
+    result.dedup();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ticket_paths_filters_and_dedupes() {
+        let dir = TempDir::new().unwrap();
+        let ticket = dir.path().join("t-1.md");
+        let swap = dir.path().join(".t-1.md");
+        let other = dir.path().join("notes.txt");
+        for path in [&ticket, &swap, &other] {
+            fs::write(path, "x").unwrap();
+        }
+        let removed = dir.path().join("t-2.md");
+
+        let paths = vec![ticket.clone(), swap, other, removed, ticket.clone()];
+        assert_eq!(ticket_paths(&paths), vec![ticket]);
+    }
+
+    // Batching tests, with events sent by hand
+
+    fn modify(path: &Path) -> notify::Result<Event> {
+        Ok(Event::new(EventKind::Modify(notify::event::ModifyKind::Any)).add_path(path.to_path_buf()))
+    }
+
+    fn names(batch: Option<Vec<PathBuf>>) -> Vec<String> {
+        batch
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_watcher_batches_changes() {
+        let dir = TempDir::new().unwrap();
+        let (tx, rx) = channel();
+        let watcher = TicketWatcher::from_receiver(rx, dir.path(), Duration::from_millis(20), false);
+        let t1 = dir.path().join("t-1.md");
+        let t2 = dir.path().join("t-2.md");
+        let notes = dir.path().join("notes.txt");
+        for path in [&t1, &t2, &notes] {
+            fs::write(path, "x").unwrap();
+        }
+
+        // Changes that aren't tickets don't start a batch, but are
+        // collected with the ones that follow
+        tx.send(modify(&notes)).unwrap();
+        tx.send(Err(notify::Error::generic("lost"))).unwrap();
+        tx.send(Ok(Event::new(EventKind::Remove(notify::event::RemoveKind::Any)).add_path(t1.clone()))).unwrap();
+        tx.send(modify(&t2)).unwrap();
+        tx.send(modify(&t1)).unwrap();
+        tx.send(modify(&t2)).unwrap();
+        assert_eq!(names(watcher.next_batch()), vec!["t-1.md", "t-2.md"]);
+
+        // Once the debounce window passes quietly, later events start a new batch
+        tx.send(modify(&t1)).unwrap();
+        assert_eq!(names(watcher.next_batch()), vec!["t-1.md"]);
+
+        drop(tx);
+        assert!(watcher.next_batch().is_none());
+    }
+
+    #[test]
+    fn test_watcher_skips_hidden_and_unwatched_directories() {
+        let dir = TempDir::new().unwrap();
+        let epic = dir.path().join("e-1");
+        let other = dir.path().join(".cache");
+        fs::create_dir(&epic).unwrap();
+        fs::write(epic.join("e-1.md"), "x").unwrap();
+        fs::create_dir(&other).unwrap();
+        let child = epic.join("t-1.md");
+        let doc = other.join("t-2.md");
+        fs::write(&child, "x").unwrap();
+        fs::write(&doc, "x").unwrap();
+
+        for parent_dirs in [false, true] {
+            let (tx, rx) = channel();
+            let watcher = TicketWatcher::from_receiver(rx, dir.path(), Duration::from_millis(20), parent_dirs);
+            tx.send(modify(&doc)).unwrap();
+            tx.send(modify(&child)).unwrap();
+            drop(tx);
+
+            let expected = if parent_dirs { Some(vec![child.clone()]) } else { None };
+            assert_eq!(watcher.next_batch(), expected);
+        }
+    }
+
+    #[test]
+    fn test_watcher_ignores_own_writes_until_edited() {
+        let dir = TempDir::new().unwrap();
+        let (tx, rx) = channel();
+        let mut watcher = TicketWatcher::from_receiver(rx, dir.path(), Duration::from_millis(20), false);
+        let t1 = dir.path().join("t-1.md");
+        let t2 = dir.path().join("t-2.md");
+        fs::write(&t1, "pushed").unwrap();
+        fs::write(&t2, "pushed").unwrap();
+
+        watcher.ignore_own_writes();
+        // An event for our own write arrives after the push finished
+        tx.send(modify(&t1)).unwrap();
+
+        // Editing the file again gives it a new modification time
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options().write(true).open(&t2).unwrap().set_modified(later).unwrap();
+        tx.send(modify(&t2)).unwrap();
+
+        assert_eq!(names(watcher.next_batch()), vec!["t-2.md"]);
+    }
+}
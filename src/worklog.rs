@@ -0,0 +1,144 @@
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::sync::content_hash;
+
+/// Section time entries are logged under; kept out of issue bodies
+pub const WORKLOG_SECTION: &str = "Worklog";
+
+/// Start of the hidden marker on worklog comments
+const MARKER_PREFIX: &str = "<!-- ttr:worklog";
+
+/// One line of a ticket's `## Worklog`, e.g.
+/// `- 2026-10-17 14:05 1h30m Wired up retries`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Date, with the time when one was given, as written
+    pub at: String,
+    pub minutes: u32,
+    pub note: String,
+}
+
+impl Entry {
+    /// Parse a list item: date, optional `HH:MM`, duration, then a note
+    pub fn parse(line: &str) -> Option<Self> {
+        let item = line.trim_start().strip_prefix(['-', '*', '+'])?.trim();
+        let mut tokens = item.split_whitespace().peekable();
+        let date = tokens.next()?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+        let mut at = date.to_string();
+        if let Some(time) = tokens.next_if(|t| NaiveTime::parse_from_str(t, "%H:%M").is_ok()) {
+            at = format!("{} {}", at, time);
+        }
+        let minutes = parse_duration(tokens.next()?.trim_end_matches(':'))?;
+        let note = tokens.collect::<Vec<_>>().join(" ");
+        let note = note.trim_start_matches([':', '-', '—']).trim().to_string();
+        Some(Self { at, minutes, note })
+    }
+
+    /// Stable ID for the entry, so each is commented once
+    pub fn key(&self) -> String {
+        content_hash(&format!("{} {} {}", self.at, self.minutes, self.note))
+    }
+
+    /// Issue comment for the entry, marked so reruns find it
+    pub fn comment(&self, ticket_id: &str) -> String {
+        let mut comment = format!(
+            "{} ticket:{} entry:{} -->\n**Logged {}** on {}",
+            MARKER_PREFIX,
+            ticket_id,
+            self.key(),
+            format_duration(self.minutes),
+            self.at
+        );
+        if !self.note.is_empty() {
+            comment.push_str("\n\n");
+            comment.push_str(&self.note);
+        }
+        comment
+    }
+}
+
+/// Entries under a ticket's `## Worklog`, skipping lines that aren't one
+pub fn entries(markdown: &str) -> Vec<Entry> {
+    let mut in_section = false;
+    let mut entries = Vec::new();
+    for line in markdown.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            in_section = heading.trim().trim_end_matches(':').eq_ignore_ascii_case(WORKLOG_SECTION);
+            continue;
+        }
+        if in_section {
+            entries.extend(Entry::parse(line));
+        }
+    }
+    entries
+}
+
+/// Whether a comment posted `entry`
+pub fn is_comment_for(body: &str, entry: &Entry) -> bool {
+    body.starts_with(MARKER_PREFIX) && body.lines().next().is_some_and(|l| l.contains(&format!("entry:{} ", entry.key())))
+}
+
+/// Totals table for the issue body, if anything is logged
+pub fn totals_table(entries: &[Entry]) -> Option<String> {
+    let latest = entries.iter().map(|e| e.at.as_str()).max()?;
+    let minutes: u32 = entries.iter().map(|e| e.minutes).sum();
+    Some(format!(
+        "| Time logged | Entries | Latest |\n|---|---|---|\n| {} | {} | {} |",
+        format_duration(minutes),
+        entries.len(),
+        latest
+    ))
+}
+
+/// Minutes in a duration like `1h30m`, `45m`, `2h` or `1.5h`
+pub fn parse_duration(s: &str) -> Option<u32> {
+    let (hours, rest) = match s.split_once('h') {
+        Some((hours, rest)) => (hours.parse::<f64>().ok().filter(|h| h.is_finite() && *h >= 0.0)?, rest),
+        None => (0.0, s),
+    };
+    let minutes = match rest.strip_suffix('m') {
+        Some(minutes) => minutes.parse::<u32>().ok()?,
+        None if rest.is_empty() => 0,
+        None => return None,
+    };
+    let total = (hours * 60.0).round() as u32 + minutes;
+    (total > 0).then_some(total)
+}
+
+/// `1h 30m`, `45m` or `2h`
+pub fn format_duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_worklog_section() {
+        let markdown = "# Retry\n\nBody\n\n## Worklog\n\n- 2026-10-17 14:05 1h30m: Wired up retries\n- 2026-10-18 45m\n- sometime 2h\n* 2026-10-16 1.5h — Spike\n\n## Notes\n\n- 2026-10-19 9h not logged\n";
+        let entries = entries(markdown);
+        assert_eq!(
+            entries,
+            vec![
+                Entry { at: "2026-10-17 14:05".to_string(), minutes: 90, note: "Wired up retries".to_string() },
+                Entry { at: "2026-10-18".to_string(), minutes: 45, note: String::new() },
+                Entry { at: "2026-10-16".to_string(), minutes: 90, note: "Spike".to_string() },
+            ]
+        );
+        assert_eq!(
+            totals_table(&entries).unwrap(),
+            "| Time logged | Entries | Latest |\n|---|---|---|\n| 3h 45m | 3 | 2026-10-18 |"
+        );
+
+        let comment = entries[0].comment("t-1");
+        assert!(comment.ends_with("-->\n**Logged 1h 30m** on 2026-10-17 14:05\n\nWired up retries"));
+        assert!(is_comment_for(&comment, &entries[0]));
+        assert!(!is_comment_for(&comment, &entries[1]));
+    }
+}
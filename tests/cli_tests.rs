@@ -0,0 +1,47 @@
+//! Tests running the `ttr` binary against a temporary tickets directory
+
+use std::fs;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn tickets_dir() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("sync.toml"), "[github]\nrepo = \"owner/repo\"\n").unwrap();
+    for (id, status) in [("t-1", "open"), ("t-2", "closed")] {
+        let ticket = format!("---\nid: {}\nstatus: {}\ntype: task\npriority: 2\n---\n# Ticket {}\n", id, status, id);
+        fs::write(dir.path().join(format!("{}.md", id)), ticket).unwrap();
+    }
+    dir
+}
+
+fn ttr(dir: &TempDir, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ttr"))
+        .args(args)
+        .env("TICKETS_DIR", dir.path())
+        .current_dir(dir.path())
+        .output()
+        .unwrap()
+}
+
+// Ticket selection tests
+
+#[test]
+fn test_lint_without_ids_checks_every_ticket() {
+    let dir = tickets_dir();
+    let output = ttr(&dir, &["lint"]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Checked 2 ticket(s)"), "{}", stdout);
+}
+
+#[test]
+fn test_plan_without_ids_considers_open_tickets() {
+    let dir = tickets_dir();
+    // No open ticket is P1, so the plan stops before contacting GitHub
+    let output = ttr(&dir, &["plan", "--status", "Todo", "--priority", "1"]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(stdout.contains("No tickets matched the plan filter"), "{}", stdout);
+}
@@ -4,8 +4,10 @@
 //! including request formatting, response parsing, and error handling.
 
 use serde_json::json;
+use ticket_to_ride::config::NetworkConfig;
 use ticket_to_ride::github::client::GitHubClient;
-use wiremock::matchers::{header, method, path};
+use ticket_to_ride::github::projects::{ProjectFieldType, ProjectFieldValue};
+use wiremock::matchers::{body_string_contains, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Helper to create a client pointing to the mock server
@@ -196,6 +198,25 @@ async fn test_server_error() {
     assert!(err.contains("500"), "Error was: {}", err);
 }
 
+#[tokio::test]
+async fn test_proxy_rejects_connect() {
+    // Unmatched requests, including the CONNECT, get a 404 from the mock
+    let proxy = MockServer::start().await;
+    let network = NetworkConfig {
+        proxy: Some(proxy.uri()),
+        ..Default::default()
+    };
+    let client = GitHubClient::with_network("test_token".to_string(), &network).unwrap();
+
+    #[derive(serde::Deserialize, Debug)]
+    struct Empty {}
+
+    let result: Result<Empty, _> = client.query("query { viewer { id } }", None).await;
+
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("Proxy refused to connect"), "Error was: {}", err);
+}
+
 // =============================================================================
 // Repository ID Tests
 // =============================================================================
@@ -231,6 +252,22 @@ async fn test_get_repository_id_not_found() {
     assert!(result.unwrap_err().to_string().contains("not found"));
 }
 
+#[tokio::test]
+async fn test_get_default_branch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(json!({
+            "repository": { "defaultBranchRef": { "name": "trunk" } }
+        })))
+        .mount(&server)
+        .await;
+
+    let branch = client.get_default_branch("owner", "repo").await.unwrap();
+    assert_eq!(branch.as_deref(), Some("trunk"));
+}
+
 // =============================================================================
 // Issue Creation Tests
 // =============================================================================
@@ -260,6 +297,7 @@ async fn test_create_issue() {
         body: "Test body".to_string(),
         label_ids: vec![],
         issue_type_id: None,
+        assignee_ids: vec![],
     }];
 
     let results = client
@@ -310,12 +348,14 @@ async fn test_batch_create_multiple_issues() {
             body: "Body 1".to_string(),
             label_ids: vec![],
             issue_type_id: None,
+            assignee_ids: vec![],
         },
         IssueCreate {
             title: "Issue 2".to_string(),
             body: "Body 2".to_string(),
             label_ids: vec![],
             issue_type_id: None,
+            assignee_ids: vec![],
         },
     ];
 
@@ -329,6 +369,94 @@ async fn test_batch_create_multiple_issues() {
     assert_eq!(results[1].as_ref().unwrap().number, 2);
 }
 
+#[tokio::test]
+async fn test_batch_create_reports_each_items_error() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    // One alias fails; GitHub nulls it and points the error at it
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "create_0": {
+                    "issue": { "id": "I_1", "number": 1, "url": "https://github.com/owner/repo/issues/1" }
+                },
+                "create_1": null
+            },
+            "errors": [{
+                "message": "Could not resolve to a node with the global id of 'LA_gone'",
+                "path": ["create_1"],
+                "locations": []
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    use ticket_to_ride::github::issues::IssueCreate;
+    let create = |title: &str| IssueCreate {
+        title: title.to_string(),
+        body: String::new(),
+        label_ids: vec![],
+        issue_type_id: None,
+        assignee_ids: vec![],
+    };
+    let results = client
+        .create_issues_batch("R_123", &[create("Issue 1"), create("Issue 2")], None)
+        .await
+        .unwrap();
+
+    assert_eq!(results[0].as_ref().unwrap().number, 1);
+    assert_eq!(
+        results[1].as_ref().unwrap_err(),
+        "Failed to create issue: Could not resolve to a node with the global id of 'LA_gone'"
+    );
+}
+
+#[tokio::test]
+async fn test_batch_error_without_item_path_fails_whole_batch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "add_0": null },
+            "errors": [{ "message": "Resource not accessible by integration", "path": [], "locations": [] }]
+        })))
+        .mount(&server)
+        .await;
+
+    let err = client
+        .add_issues_to_project_batch("PVT_1", &["I_1".to_string()])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Resource not accessible by integration"));
+}
+
+#[tokio::test]
+async fn test_large_batch_is_split_across_requests() {
+    use ticket_to_ride::github::batch::MAX_BATCH_FIELDS;
+
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    // Every alias in one response; each request picks out its own
+    let count = MAX_BATCH_FIELDS + 2;
+    let data: serde_json::Map<String, serde_json::Value> = (0..count)
+        .map(|i| (format!("add_{}", i), json!({ "item": { "id": format!("PVTI_{}", i) } })))
+        .collect();
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(data))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let issue_ids: Vec<String> = (0..count).map(|i| format!("I_{}", i)).collect();
+    let results = client.add_issues_to_project_batch("PVT_1", &issue_ids).await.unwrap();
+
+    assert_eq!(results.len(), count);
+    assert_eq!(results[count - 1].as_ref().unwrap().item_id, format!("PVTI_{}", count - 1));
+}
+
 #[tokio::test]
 async fn test_batch_update_issues() {
     let server = MockServer::start().await;
@@ -378,10 +506,156 @@ async fn test_batch_update_issues() {
     assert!(results.get("I_2").unwrap().is_ok());
 }
 
+#[tokio::test]
+async fn test_issue_cache_refetches_only_changed_issues() {
+    use ticket_to_ride::cache::IssueCache;
+
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+    let dir = tempfile::TempDir::new().unwrap();
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("{ updatedAt }"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "issue_1": { "updatedAt": "2026-01-01T00:00:00Z" },
+                "issue_2": null
+            }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("body state url updatedAt"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "issue_1": {
+                    "id": "I_1",
+                    "number": 1,
+                    "title": "One",
+                    "body": "Body",
+                    "state": "OPEN",
+                    "url": "https://github.com/owner/repo/issues/1",
+                    "updatedAt": "2026-01-01T00:00:00Z"
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut cache = IssueCache::load(dir.path());
+    let issues = cache.get_issues(&client, "owner", "repo", &[1, 2]).await.unwrap();
+    assert_eq!(issues.len(), 1);
+    cache.save(dir.path()).unwrap();
+
+    // Second run: the timestamp is unchanged, so the body comes from disk
+    let mut cache = IssueCache::load(dir.path());
+    let issues = cache.get_issues(&client, "owner", "repo", &[1, 2]).await.unwrap();
+    assert_eq!(issues[&1].title, "One");
+    assert!(!issues.contains_key(&2));
+}
+
 // =============================================================================
 // Label Tests
 // =============================================================================
 
+#[tokio::test]
+async fn test_paced_client_sends_mutations_one_at_a_time() {
+    let server = MockServer::start().await;
+    // 6000 a minute: one every 10ms
+    let client = create_test_client(&server).with_pace(6000);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("closeIssue"))
+        .respond_with(graphql_response(json!({})))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let started = std::time::Instant::now();
+    let ids = vec!["I_1".to_string(), "I_2".to_string(), "I_3".to_string()];
+    client.close_issues_batch(&ids).await.unwrap();
+    assert!(started.elapsed() >= std::time::Duration::from_millis(20));
+}
+
+#[tokio::test]
+async fn test_update_assignees_batch() {
+    use ticket_to_ride::github::issues::AssigneeChange;
+
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("addAssigneesToAssignable"))
+        .respond_with(graphql_response(json!({
+            "add_0": { "clientMutationId": null },
+            "add_1": { "clientMutationId": null }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("removeAssigneesFromAssignable"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "remove_0": null },
+            "errors": [{ "message": "Could not resolve to a node", "path": ["remove_0"], "locations": [] }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let changes = vec![
+        AssigneeChange {
+            issue_id: "I_1".to_string(),
+            add: vec!["U_new".to_string()],
+            remove: vec!["U_old".to_string()],
+        },
+        AssigneeChange {
+            issue_id: "I_2".to_string(),
+            add: vec!["U_new".to_string()],
+            remove: Vec::new(),
+        },
+    ];
+    let results = client.update_assignees_batch(&changes).await.unwrap();
+
+    assert_eq!(
+        results["I_1"],
+        Err("Failed to remove assignees on issue I_1: Could not resolve to a node".to_string())
+    );
+    assert_eq!(results["I_2"], Ok(()));
+}
+
+#[tokio::test]
+async fn test_add_labels_batch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("addLabelsToLabelable"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "label_0": { "clientMutationId": null }, "label_1": null },
+            "errors": [{ "message": "Could not resolve to a node", "path": ["label_1"], "locations": [] }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let additions = vec![
+        ("I_1".to_string(), vec!["L_ttr".to_string()]),
+        ("I_2".to_string(), vec!["L_ttr".to_string()]),
+    ];
+    let results = client.add_labels_batch(&additions).await.unwrap();
+
+    assert_eq!(results["I_1"], Ok(()));
+    assert_eq!(
+        results["I_2"],
+        Err("Failed to add labels to issue I_2: Could not resolve to a node".to_string())
+    );
+}
+
 #[tokio::test]
 async fn test_get_labels() {
     let server = MockServer::start().await;
@@ -435,6 +709,35 @@ async fn test_create_label() {
     assert_eq!(label.name, "new-label");
 }
 
+#[tokio::test]
+async fn test_create_labels_batch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    // All labels go out in one request
+    Mock::given(method("POST"))
+        .and(body_string_contains("label_1: createLabel"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "label_0": { "label": { "id": "LA_1", "name": "area:ui" } },
+                "label_1": null
+            },
+            "errors": [{ "message": "Name has already been taken", "path": ["label_1"], "locations": [] }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let names = vec!["area:ui".to_string(), "bug".to_string()];
+    let results = client.create_labels_batch("R_123", &names).await.unwrap();
+
+    assert_eq!(results[0].as_ref().unwrap().id, "LA_1");
+    assert_eq!(
+        results[1].as_ref().unwrap_err(),
+        "Failed to create label 'bug': Name has already been taken"
+    );
+}
+
 // =============================================================================
 // Project Tests
 // =============================================================================
@@ -494,6 +797,55 @@ async fn test_find_project_by_name() {
     assert_eq!(p.number, 1);
 }
 
+#[tokio::test]
+async fn test_list_projects_dedupes() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    // Repo-level, then owner type, then org-level (which repeats PVT_1)
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "projectsV2": {
+                    "nodes": [{ "id": "PVT_1", "title": "Roadmap", "number": 1 }]
+                }
+            }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "owner": { "__typename": "Organization" }
+            }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(json!({
+            "organization": {
+                "projectsV2": {
+                    "nodes": [
+                        { "id": "PVT_1", "title": "Roadmap", "number": 1 },
+                        { "id": "PVT_2", "title": "Triage", "number": 2 }
+                    ]
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let projects = client.list_projects("org", "repo").await.unwrap();
+
+    assert_eq!(projects.len(), 2);
+    assert_eq!(projects[0].title, "Roadmap");
+    assert_eq!(projects[1].title, "Triage");
+}
+
 #[tokio::test]
 async fn test_add_issue_to_project() {
     let server = MockServer::start().await;
@@ -516,33 +868,718 @@ async fn test_add_issue_to_project() {
     assert_eq!(result.item_id, "PVTI_item123");
 }
 
-// =============================================================================
-// Sub-Issue Tests
-// =============================================================================
-
 #[tokio::test]
-async fn test_add_sub_issues_batch() {
+async fn test_add_to_project_adopts_existing_items() {
     let server = MockServer::start().await;
     let client = create_test_client(&server);
 
-    // sub-issues batch uses link_0 and expects subIssue in response
+    // add_1 is already on the board; its item is looked up afterwards
+    Mock::given(method("POST"))
+        .and(body_string_contains("addProjectV2ItemById"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "add_0": { "item": { "id": "PVTI_new" } },
+                "add_1": null
+            },
+            "errors": [{ "message": "Content already in the project", "path": ["add_1"], "locations": [] }]
+        })))
+        .mount(&server)
+        .await;
     Mock::given(method("POST"))
+        .and(body_string_contains("projectItems"))
         .respond_with(graphql_response(json!({
-            "link_0": {
-                "subIssue": { "id": "I_child" }
+            "issue_0": {
+                "projectItems": { "nodes": [
+                    { "id": "PVTI_other", "project": { "id": "PVT_other" } },
+                    { "id": "PVTI_old", "project": { "id": "PVT_1" } }
+                ] }
             }
         })))
+        .expect(1)
         .mount(&server)
         .await;
 
-    use ticket_to_ride::github::subissues::SubIssueLink;
-    let links = vec![SubIssueLink {
-        parent_issue_id: "I_parent".to_string(),
-        child_issue_id: "I_child".to_string(),
-    }];
+    let results = client
+        .add_issues_to_project_batch("PVT_1", &["I_1".to_string(), "I_2".to_string()])
+        .await
+        .unwrap();
 
-    let results = client.add_sub_issues_batch(&links).await.unwrap();
+    let added = results[0].as_ref().unwrap();
+    assert_eq!((added.item_id.as_str(), added.existing), ("PVTI_new", false));
+    let adopted = results[1].as_ref().unwrap();
+    assert_eq!((adopted.item_id.as_str(), adopted.existing), ("PVTI_old", true));
+}
 
-    assert_eq!(results.len(), 1);
-    assert!(results[0].is_ok());
+#[tokio::test]
+async fn test_add_draft_issues_batch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    // Drafts use draft_0, draft_1 aliases; draft_1 failed (null)
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(json!({
+            "draft_0": {
+                "projectItem": { "id": "PVTI_draft1" }
+            },
+            "draft_1": null
+        })))
+        .mount(&server)
+        .await;
+
+    let drafts = vec![
+        ("Idea one".to_string(), "Body".to_string()),
+        ("Idea two".to_string(), "Body".to_string()),
+    ];
+    let results = client
+        .add_draft_issues_batch("PVT_project", &drafts)
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap().item_id, "PVTI_draft1");
+    assert!(results[1].is_err());
+}
+
+#[tokio::test]
+async fn test_get_project_fields_plain_types() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(json!({
+            "node": {
+                "fields": {
+                    "nodes": [
+                        { "id": "F_1", "name": "Story Points", "__typename": "ProjectV2Field", "dataType": "NUMBER" },
+                        { "id": "F_2", "name": "Notes", "__typename": "ProjectV2Field", "dataType": "TEXT" },
+                        { "id": "F_3", "name": "Title", "__typename": "ProjectV2Field", "dataType": "TITLE" }
+                    ]
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let fields = client.get_project_fields("PVT_project").await.unwrap();
+
+    assert_eq!(fields.len(), 3);
+    assert!(matches!(fields[0].field_type, ProjectFieldType::Number));
+    assert!(matches!(fields[1].field_type, ProjectFieldType::Text));
+    assert!(matches!(fields[2].field_type, ProjectFieldType::Other));
+}
+
+#[tokio::test]
+async fn test_set_project_item_field_values_batch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("\"number\":3.0"))
+        .respond_with(graphql_response(json!({
+            "update_0": { "projectV2Item": { "id": "PVTI_1" } },
+            "update_1": null
+        })))
+        .mount(&server)
+        .await;
+
+    let updates = vec![
+        ("PVTI_1".to_string(), "F_1".to_string(), ProjectFieldValue::Number(3.0)),
+        ("PVTI_2".to_string(), "F_2".to_string(), ProjectFieldValue::Text("hi".to_string())),
+    ];
+    let results = client
+        .set_project_item_field_values_batch("PVT_project", &updates)
+        .await
+        .unwrap();
+
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+}
+
+#[tokio::test]
+async fn test_delete_project_items_batch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(json!({
+            "delete_0": { "deletedItemId": "PVTI_1" },
+            "delete_1": null
+        })))
+        .mount(&server)
+        .await;
+
+    let ids = vec!["PVTI_1".to_string(), "PVTI_2".to_string()];
+    let results = client
+        .delete_project_items_batch("PVT_project", &ids)
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+}
+
+#[tokio::test]
+async fn test_get_project_item_statuses_batch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("fieldValueByName(name: $field)"))
+        .respond_with(graphql_response(json!({
+            "issue_0": { "projectItems": { "nodes": [
+                { "project": { "id": "PVT_other" }, "fieldValueByName": { "name": "Done" } },
+                { "project": { "id": "PVT_project" }, "fieldValueByName": { "name": "In Progress" } }
+            ] } },
+            "issue_1": { "projectItems": { "nodes": [
+                { "project": { "id": "PVT_project" }, "fieldValueByName": null }
+            ] } }
+        })))
+        .mount(&server)
+        .await;
+
+    let ids = vec!["I_1".to_string(), "I_2".to_string()];
+    let statuses = client
+        .get_project_item_statuses_batch("PVT_project", &ids, "Status")
+        .await
+        .unwrap();
+
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses["I_1"], "In Progress");
+}
+
+// =============================================================================
+// Sub-Issue Tests
+// =============================================================================
+
+#[tokio::test]
+async fn test_add_sub_issues_batch() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    // sub-issues batch uses link_0 and expects subIssue in response
+    Mock::given(method("POST"))
+        .respond_with(graphql_response(json!({
+            "link_0": {
+                "subIssue": { "id": "I_child" }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    use ticket_to_ride::github::subissues::SubIssueLink;
+    let links = vec![SubIssueLink {
+        parent_issue_id: "I_parent".to_string(),
+        child_issue_id: "I_child".to_string(),
+    }];
+
+    let results = client.add_sub_issues_batch(&links).await.unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_ok());
+}
+
+#[tokio::test]
+async fn test_list_pull_requests() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "pullRequests": {
+                    "nodes": [{
+                        "id": "PR_1",
+                        "number": 7,
+                        "title": "Add login",
+                        "body": null,
+                        "state": "OPEN",
+                        "url": "https://github.com/owner/repo/pull/7",
+                        "headRefName": "ttr-0042-login"
+                    }]
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let prs = client.list_pull_requests("owner", "repo").await.unwrap();
+
+    assert_eq!(prs.len(), 1);
+    assert_eq!(prs[0].number, 7);
+    assert_eq!(prs[0].head_ref, "ttr-0042-login");
+    assert_eq!(prs[0].body, "");
+}
+
+#[tokio::test]
+async fn test_get_project_iteration_items_paginates() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(graphql_response(json!({
+            "node": {
+                "items": {
+                    "pageInfo": { "hasNextPage": true, "endCursor": "c1" },
+                    "nodes": [{
+                        "id": "PVTI_1",
+                        "fieldValueByName": { "iterationId": "it1" },
+                        "content": { "number": 5, "closedAt": "2026-03-05T10:00:00Z" }
+                    }]
+                }
+            }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(graphql_response(json!({
+            "node": {
+                "items": {
+                    "pageInfo": { "hasNextPage": false, "endCursor": null },
+                    "nodes": [{
+                        "id": "PVTI_2",
+                        "fieldValueByName": null,
+                        "content": {}
+                    }]
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let items = client
+        .get_project_iteration_items("PVT_1", "Iteration")
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].iteration_id.as_deref(), Some("it1"));
+    assert_eq!(items[0].issue_number, Some(5));
+    assert!(items[0].closed_at.is_some());
+    assert_eq!(items[1].iteration_id, None);
+    assert_eq!(items[1].issue_number, None);
+}
+
+#[tokio::test]
+async fn test_list_open_issues_paginates_with_label() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    let issue = |number: u64, body: &str| {
+        json!({
+            "id": format!("I_{}", number),
+            "number": number,
+            "title": format!("Issue {}", number),
+            "body": body,
+            "state": "OPEN",
+            "url": format!("https://github.com/owner/repo/issues/{}", number),
+            "updatedAt": "2026-01-01T00:00:00Z"
+        })
+    };
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("\"labels\":[\"bug\"]"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "issues": {
+                    "pageInfo": { "hasNextPage": true, "endCursor": "c1" },
+                    "nodes": [issue(3, "Reported on GitHub")]
+                }
+            }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("\"after\":\"c1\""))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "issues": {
+                    "pageInfo": { "hasNextPage": false, "endCursor": null },
+                    "nodes": [issue(4, "<!-- ttr:v2 ticket:t-1 hash:abc -->\nBody")]
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let issues = client.list_open_issues("owner", "repo", Some("bug")).await.unwrap();
+
+    let numbers: Vec<u64> = issues.iter().map(|i| i.number).collect();
+    assert_eq!(numbers, vec![3, 4]);
+    assert_eq!(issues[0].body, "Reported on GitHub");
+}
+
+#[tokio::test]
+async fn test_list_issues_created_since_finds_issue_search_misses() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    // The search index hasn't caught up with the new issue yet
+    Mock::given(method("POST"))
+        .and(body_string_contains("search("))
+        .respond_with(graphql_response(json!({ "search": { "nodes": [] } })))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("orderBy: {field: CREATED_AT, direction: DESC}"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "issues": {
+                    "pageInfo": { "hasNextPage": true, "endCursor": "c1" },
+                    "nodes": [
+                        {
+                            "id": "I_6",
+                            "number": 6,
+                            "title": "Two",
+                            "body": "Filed by hand",
+                            "state": "OPEN",
+                            "url": "https://github.com/owner/repo/issues/6",
+                            "createdAt": "2026-01-01T00:02:00Z"
+                        },
+                        {
+                            "id": "I_5",
+                            "number": 5,
+                            "title": "One",
+                            "body": "<!-- ttr:v2 ticket:t-1 hash:abc -->\nBody",
+                            "state": "OPEN",
+                            "url": "https://github.com/owner/repo/issues/5",
+                            "createdAt": "2026-01-01T00:01:00Z",
+                            "labels": { "nodes": [{ "name": "ticket" }] }
+                        },
+                        {
+                            "id": "I_4",
+                            "number": 4,
+                            "title": "Older",
+                            "body": "",
+                            "state": "CLOSED",
+                            "url": "https://github.com/owner/repo/issues/4",
+                            "createdAt": "2025-12-31T23:00:00Z"
+                        }
+                    ]
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let found = client.list_issues_created_since("owner", "repo", "2026-01-01T00:00:00Z").await.unwrap();
+
+    let numbers: Vec<u64> = found.iter().map(|i| i.number).collect();
+    assert_eq!(numbers, vec![6, 5]);
+    assert!(found[1].body.contains("ticket:t-1"));
+    assert_eq!(found[1].labels, vec!["ticket".to_string()]);
+}
+
+#[tokio::test]
+async fn test_get_thumbs_up_counts() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("reactions(content: THUMBS_UP)"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "issue_3": { "reactions": { "totalCount": 12 } },
+                "issue_4": { "reactions": { "totalCount": 0 } },
+                "issue_9": null
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let counts = client.get_thumbs_up_counts("owner", "repo", &[3, 4, 9]).await.unwrap();
+
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[&3], 12);
+    assert_eq!(counts[&4], 0);
+}
+
+#[tokio::test]
+async fn test_assignee_load_and_per_issue_assignees() {
+    use ticket_to_ride::github::issues::IssueCreate;
+
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("repo:owner/repo is:issue is:open assignee:alice"))
+        .respond_with(graphql_response(json!({
+            "count_0": { "issueCount": 4 },
+            "count_1": { "issueCount": 1 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("\"assigneeIds\":[\"U_bob\"]"))
+        .and(body_string_contains("\"assigneeIds\":[\"U_default\"]"))
+        .respond_with(graphql_response(json!({
+            "create_0": { "issue": { "id": "I_1", "number": 1, "url": "https://github.com/owner/repo/issues/1" } },
+            "create_1": { "issue": { "id": "I_2", "number": 2, "url": "https://github.com/owner/repo/issues/2" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let logins = vec!["alice".to_string(), "bob".to_string()];
+    let counts = client.count_open_issues_assigned("owner", "repo", &logins).await.unwrap();
+    assert_eq!(counts, vec![4, 1]);
+
+    let create = |assignee_ids: Vec<String>| IssueCreate {
+        title: "Title".to_string(),
+        body: "Body".to_string(),
+        label_ids: vec![],
+        issue_type_id: None,
+        assignee_ids,
+    };
+    let creates = vec![create(vec!["U_bob".to_string()]), create(vec![])];
+    let default = vec!["U_default".to_string()];
+    let created = client.create_issues_batch("R_1", &creates, Some(&default)).await.unwrap();
+    assert_eq!(created.len(), 2);
+}
+
+#[tokio::test]
+async fn test_transfer_issue() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("transferIssue"))
+        .and(body_string_contains("\"repositoryId\":\"R_other\""))
+        .respond_with(graphql_response(json!({
+            "transferIssue": {
+                "issue": { "id": "I_moved", "number": 7, "url": "https://github.com/owner/other/issues/7" }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let moved = client.transfer_issue("I_1", "R_other").await.unwrap();
+    assert_eq!(moved.number, 7);
+    assert_eq!(moved.url, "https://github.com/owner/other/issues/7");
+}
+
+#[tokio::test]
+async fn test_pinned_issues() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("pinnedIssues(first: 3)"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "pinnedIssues": {
+                    "nodes": [
+                        { "issue": { "id": "I_1", "number": 1, "url": "https://github.com/owner/repo/issues/1" } }
+                    ]
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("pinIssue"))
+        .and(body_string_contains("\"issueId\":\"I_2\""))
+        .respond_with(graphql_response(json!({ "pinIssue": { "issue": { "id": "I_2" } } })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let pinned = client.get_pinned_issues("owner", "repo").await.unwrap();
+    assert_eq!(pinned.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1]);
+    client.pin_issue("I_2").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_lock_and_unlock_issue() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("lockLockable"))
+        .and(body_string_contains("lockReason: RESOLVED"))
+        .respond_with(graphql_response(json!({ "lockLockable": { "lockedRecord": { "locked": true } } })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("unlockLockable"))
+        .respond_with(graphql_response(json!({ "unlockLockable": { "unlockedRecord": { "locked": false } } })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client.lock_issue("I_1").await.unwrap();
+    client.unlock_issue("I_1").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_discussions() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("discussion_4: discussion(number: 4)"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "discussion_4": {
+                    "id": "D_4", "number": 4, "title": "Idea", "body": "Pitch",
+                    "url": "https://github.com/owner/repo/discussions/4"
+                },
+                "discussion_5": null
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("createDiscussion"))
+        .and(body_string_contains("\"categoryId\":\"DIC_ideas\""))
+        .respond_with(graphql_response(json!({
+            "createDiscussion": {
+                "discussion": { "id": "D_6", "number": 6, "url": "https://github.com/owner/repo/discussions/6" }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("comments(first: 100)"))
+        .respond_with(graphql_response(json!({
+            "repository": {
+                "discussion": {
+                    "comments": {
+                        "nodes": [
+                            { "author": { "login": "alice" }, "body": "+1", "createdAt": "2026-03-04T10:00:00Z" },
+                            { "author": null, "body": "old", "createdAt": "2026-03-05T10:00:00Z" }
+                        ]
+                    }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let existing = client.get_discussions_batch("owner", "repo", &[4, 5]).await.unwrap();
+    assert_eq!(existing.len(), 1);
+    assert_eq!(existing[&4].id, "D_4");
+
+    let created = client.create_discussion("R_1", "DIC_ideas", "Title", "Body").await.unwrap();
+    assert_eq!(created.number, 6);
+
+    let comments = client.get_discussion_comments("owner", "repo", 4).await.unwrap();
+    let authors: Vec<&str> = comments.iter().map(|c| c.author.as_str()).collect();
+    assert_eq!(authors, vec!["alice", "ghost"]);
+}
+
+#[tokio::test]
+async fn test_rest_transport_issue_operations() {
+    use ticket_to_ride::github::issues::{IssueCreate, IssueUpdate};
+
+    let server = MockServer::start().await;
+    let client = create_test_client(&server).with_rest("owner", "repo");
+    let issue = |state: &str| {
+        json!({
+            "node_id": "I_5",
+            "number": 5,
+            "title": "Title",
+            "body": "Body",
+            "state": state,
+            "html_url": "https://github.com/owner/repo/issues/5",
+            "updated_at": "2026-01-01T00:00:00Z"
+        })
+    };
+
+    Mock::given(method("POST"))
+        .and(path("/repos/owner/repo/issues"))
+        .and(body_string_contains("\"labels\":[\"bug\"]"))
+        .and(body_string_contains("\"assignees\":[\"octocat\"]"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(issue("open")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/repos/owner/repo/issues/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issue("closed")))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/owner/repo/issues/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issue("closed")))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/owner/repo/issues/6"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "message": "Not Found" })))
+        .mount(&server)
+        .await;
+
+    let creates = vec![IssueCreate {
+        title: "Title".to_string(),
+        body: "Body".to_string(),
+        label_ids: vec!["bug".to_string()],
+        issue_type_id: None,
+        assignee_ids: vec![],
+    }];
+    let assignees = vec!["octocat".to_string()];
+    let created = client.create_issues_batch("R_1", &creates, Some(&assignees)).await.unwrap();
+    let created = created[0].as_ref().unwrap();
+    assert_eq!((created.id.as_str(), created.number), ("I_5", 5));
+
+    // Node IDs from earlier responses map back to issue numbers
+    let updates = vec![IssueUpdate {
+        issue_id: "I_5".to_string(),
+        title: "New".to_string(),
+        body: "Body".to_string(),
+        issue_type_id: None,
+    }];
+    let updated = client.update_issues_batch(&updates).await.unwrap();
+    assert!(updated["I_5"].is_ok());
+    client.close_issues_batch(&["I_5".to_string()]).await.unwrap();
+
+    let issues = client.get_issues_batch("owner", "repo", &[5, 6]).await.unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[&5].state, "CLOSED");
+}
+
+#[tokio::test]
+async fn test_capabilities_probe() {
+    let server = MockServer::start().await;
+    let client = create_test_client(&server);
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("__type(name: \\\"AddSubIssueInput\\\")"))
+        .respond_with(graphql_response(json!({
+            "subIssues": null,
+            "issueTypes": { "name": "IssueType" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("issueTypes(first: 50)"))
+        .respond_with(graphql_error_response("Field 'issueTypes' doesn't exist on type 'Repository'"))
+        .mount(&server)
+        .await;
+
+    let capabilities = client.get_capabilities().await;
+    assert!(!capabilities.sub_issues);
+    assert!(capabilities.issue_types);
+
+    // Schema errors mean the feature is missing, not that the push failed
+    assert!(client.get_issue_types("owner", "repo").await.unwrap().is_empty());
 }